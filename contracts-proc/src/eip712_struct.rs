@@ -0,0 +1,396 @@
+//! Defines the `#[derive(Eip712Struct)]` procedural macro.
+
+use convert_case::{Case, Casing};
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    ext::IdentExt, parse_macro_input, Expr, ExprLit, Fields, GenericArgument,
+    ItemStruct, Lit, Path, PathArguments, Result, Type,
+};
+
+/// Implements [EIP-712] typed structured data hashing for a struct.
+///
+/// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+pub(crate) fn eip712_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ItemStruct);
+
+    if !input.generics.params.is_empty() {
+        error!(input.generics, "generic structs are not supported");
+    }
+
+    let Fields::Named(fields) = &input.fields else {
+        error!(input.fields, "only structs with named fields are supported");
+    };
+
+    let mut members = Vec::new();
+    let mut encoded_fields = Vec::new();
+    let mut dependencies: Vec<(String, Path)> = Vec::new();
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("fields should be named");
+        let ty = match Eip712Type::parse(&field.ty) {
+            Ok(ty) => ty,
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        let member = ident.unraw().to_string().to_case(Case::Camel);
+        members.push(format!("{} {member}", ty.name()));
+        encoded_fields.push(ty.encode(&quote!(&self.#ident)));
+
+        if let Some((path, name)) = ty.struct_path() {
+            if !dependencies.iter().any(|(dependency, _)| dependency == name) {
+                dependencies.push((name.to_string(), path.clone()));
+            }
+        }
+    }
+    let dependencies: Vec<_> =
+        dependencies.into_iter().map(|(_, path)| path).collect();
+
+    let name = &input.ident;
+    let primary = format!("{}({})", name.unraw(), members.join(","));
+    let encode_type = if dependencies.is_empty() {
+        quote! { #primary }
+    } else {
+        encode_type_with_dependencies(&primary, &dependencies)
+    };
+
+    quote! {
+        impl #name {
+            /// Hash of the type of the struct, as defined in EIP-712.
+            pub const TYPE_HASH: stylus_sdk::alloy_primitives::B256 =
+                stylus_sdk::alloy_primitives::B256::new(
+                    stylus_sdk::keccak_const::Keccak256::new()
+                        .update(Self::encode_type().as_bytes())
+                        .finalize(),
+                );
+
+            /// Returns the type of the struct, followed by the types of the
+            /// structs it references sorted by name, as defined in EIP-712.
+            #[must_use]
+            pub const fn encode_type() -> &'static str {
+                #encode_type
+            }
+
+            /// Returns the hash of the struct, as defined in EIP-712.
+            #[must_use]
+            pub fn struct_hash(&self) -> stylus_sdk::alloy_primitives::B256 {
+                let mut encoded = alloc::vec::Vec::new();
+                encoded.extend_from_slice(Self::TYPE_HASH.as_slice());
+                #(
+                    encoded.extend_from_slice(#encoded_fields.as_slice());
+                )*
+                stylus_sdk::alloy_primitives::keccak256(encoded)
+            }
+        }
+    }
+    .into()
+}
+
+/// Builds the type of a struct referencing other structs at compile time.
+///
+/// The types of the referenced structs are split into components, i.e.
+/// `Name(...)`, which are deduplicated and sorted after the `primary` type.
+/// Sorting the components sorts them by name, since `(` sorts before any
+/// character of a name.
+fn encode_type_with_dependencies(
+    primary: &str,
+    dependencies: &[Path],
+) -> TokenStream2 {
+    quote! {
+        /// Writes the `primary` type followed by the sorted components of
+        /// the other `sources` into a buffer of `N` bytes, and returns the
+        /// buffer and the length of the type.
+        const fn build<const N: usize>(sources: &[&str]) -> ([u8; N], usize) {
+            /// Compares the components `a[a0..a1]` and `b[b0..b1]`.
+            const fn cmp(a: &[u8], a0: usize, a1: usize, b: &[u8], b0: usize, b1: usize) -> i8 {
+                let mut i = 0;
+                while a0 + i < a1 && b0 + i < b1 {
+                    if a[a0 + i] != b[b0 + i] {
+                        return if a[a0 + i] < b[b0 + i] { -1 } else { 1 };
+                    }
+                    i += 1;
+                }
+                if a1 - a0 == b1 - b0 {
+                    0
+                } else if a1 - a0 < b1 - b0 {
+                    -1
+                } else {
+                    1
+                }
+            }
+
+            /// Returns the end of the component of `source` starting at `start`.
+            const fn end(source: &[u8], start: usize) -> usize {
+                let mut i = start;
+                while source[i] != b')' {
+                    i += 1;
+                }
+                i + 1
+            }
+
+            let mut buffer = [0u8; N];
+            let mut len = 0;
+            let primary = sources[0].as_bytes();
+            let mut i = 0;
+            while i < primary.len() {
+                if len < N {
+                    buffer[len] = primary[i];
+                }
+                len += 1;
+                i += 1;
+            }
+
+            // Append the smallest component greater than the last appended
+            // one, until there is none.
+            let mut last: Option<(usize, usize, usize)> = None;
+            loop {
+                let mut next: Option<(usize, usize, usize)> = None;
+                let mut s = 1;
+                while s < sources.len() {
+                    let source = sources[s].as_bytes();
+                    let mut start = 0;
+                    while start < source.len() {
+                        let stop = end(source, start);
+                        let is_primary = cmp(source, start, stop, primary, 0, primary.len()) == 0;
+                        let after_last = match last {
+                            Some((l, l0, l1)) => cmp(source, start, stop, sources[l].as_bytes(), l0, l1) > 0,
+                            None => true,
+                        };
+                        let before_next = match next {
+                            Some((n, n0, n1)) => cmp(source, start, stop, sources[n].as_bytes(), n0, n1) < 0,
+                            None => true,
+                        };
+                        if !is_primary && after_last && before_next {
+                            next = Some((s, start, stop));
+                        }
+                        start = stop;
+                    }
+                    s += 1;
+                }
+
+                let Some((n, n0, n1)) = next else {
+                    break;
+                };
+                let source = sources[n].as_bytes();
+                let mut i = n0;
+                while i < n1 {
+                    if len < N {
+                        buffer[len] = source[i];
+                    }
+                    len += 1;
+                    i += 1;
+                }
+                last = next;
+            }
+
+            (buffer, len)
+        }
+
+        const SOURCES: &[&str] = &[#primary, #(#dependencies::encode_type()),*];
+        const LEN: usize = build::<0>(SOURCES).1;
+        const TYPE: [u8; LEN] = build::<LEN>(SOURCES).0;
+        // SAFETY: `TYPE` is made of whole components of valid UTF-8 strings.
+        unsafe { core::str::from_utf8_unchecked(&TYPE) }
+    }
+}
+
+/// EIP-712 type of a struct field.
+enum Eip712Type {
+    /// `address`.
+    Address,
+    /// `bool`.
+    Bool,
+    /// `uintN`, for Rust unsigned integers and `alloy_primitives::Uint`.
+    Uint(usize),
+    /// `intN`, for Rust signed integers.
+    Int(usize),
+    /// `intN`, for `alloy_primitives::Signed`.
+    Signed(usize),
+    /// `bytesN`.
+    FixedBytes(usize),
+    /// `bytes`.
+    Bytes,
+    /// `string`.
+    String,
+    /// `T[]` or `T[N]`.
+    Array(Box<Eip712Type>, Option<usize>),
+    /// A struct deriving `Eip712Struct`, and its name.
+    Struct(Path, String),
+}
+
+impl Eip712Type {
+    /// Parses the EIP-712 type of a Rust type.
+    fn parse(ty: &Type) -> Result<Self> {
+        match ty {
+            Type::Array(array) => {
+                let len = parse_usize(&array.len)?;
+                let element = Self::parse(&array.elem)?;
+                Ok(Self::Array(Box::new(element), Some(len)))
+            }
+            Type::Path(type_path) if type_path.qself.is_none() => {
+                Self::parse_path(&type_path.path)
+            }
+            _ => error!(@ty, "unsupported type"),
+        }
+    }
+
+    /// Parses the EIP-712 type of a path, e.g. `Address` or `Vec<U256>`.
+    fn parse_path(path: &Path) -> Result<Self> {
+        let segment = path.segments.last().expect("path should not be empty");
+        let ident = segment.ident.to_string();
+
+        if let PathArguments::AngleBracketed(args) = &segment.arguments {
+            let args: Vec<_> = args.args.iter().collect();
+            return match (ident.as_str(), args.as_slice()) {
+                ("Vec", [GenericArgument::Type(element)]) => {
+                    Ok(Self::Array(Box::new(Self::parse(element)?), None))
+                }
+                ("Uint", [GenericArgument::Const(bits), _]) => {
+                    Ok(Self::Uint(parse_bits(bits)?))
+                }
+                ("Signed", [GenericArgument::Const(bits), _]) => {
+                    Ok(Self::Signed(parse_bits(bits)?))
+                }
+                ("FixedBytes", [GenericArgument::Const(len)]) => {
+                    let len = parse_usize(len)?;
+                    if !(1..=32).contains(&len) {
+                        error!(@len, "expected a length between 1 and 32");
+                    }
+                    Ok(Self::FixedBytes(len))
+                }
+                _ => error!(@segment, "unsupported type"),
+            };
+        }
+
+        let ty = match ident.as_str() {
+            "Address" => Self::Address,
+            "bool" => Self::Bool,
+            "String" => Self::String,
+            "Bytes" => Self::Bytes,
+            "u8" | "u16" | "u32" | "u64" | "u128" => {
+                Self::Uint(ident[1..].parse().expect("should be a number"))
+            }
+            "i8" | "i16" | "i32" | "i64" | "i128" => {
+                Self::Int(ident[1..].parse().expect("should be a number"))
+            }
+            _ => match alias_bits(&ident) {
+                Some(('U', bits)) => Self::Uint(bits),
+                Some(('I', bits)) => Self::Signed(bits),
+                Some(('B', bits)) => Self::FixedBytes(bits / 8),
+                _ => Self::Struct(path.clone(), ident),
+            },
+        };
+
+        Ok(ty)
+    }
+
+    /// Returns the name of the type, e.g. `uint256` or `Person[]`.
+    fn name(&self) -> String {
+        match self {
+            Self::Address => "address".to_string(),
+            Self::Bool => "bool".to_string(),
+            Self::Uint(bits) => format!("uint{bits}"),
+            Self::Int(bits) | Self::Signed(bits) => format!("int{bits}"),
+            Self::FixedBytes(len) => format!("bytes{len}"),
+            Self::Bytes => "bytes".to_string(),
+            Self::String => "string".to_string(),
+            Self::Array(element, Some(len)) => {
+                format!("{}[{len}]", element.name())
+            }
+            Self::Array(element, None) => format!("{}[]", element.name()),
+            Self::Struct(_, name) => name.clone(),
+        }
+    }
+
+    /// Returns the path and the name of the struct referenced by the type,
+    /// if any.
+    fn struct_path(&self) -> Option<(&Path, &str)> {
+        match self {
+            Self::Array(element, _) => element.struct_path(),
+            Self::Struct(path, name) => Some((path, name)),
+            _ => None,
+        }
+    }
+
+    /// Returns an expression encoding the reference `value` into a `B256`
+    /// word, as defined by `encodeData` in EIP-712.
+    fn encode(&self, value: &TokenStream2) -> TokenStream2 {
+        match self {
+            Self::Address => quote! {
+                stylus_sdk::alloy_primitives::B256::left_padding_from((#value).as_slice())
+            },
+            Self::Bool => quote! {
+                stylus_sdk::alloy_primitives::B256::with_last_byte(u8::from(*(#value)))
+            },
+            Self::Uint(_) => quote! {
+                stylus_sdk::alloy_primitives::B256::new(
+                    stylus_sdk::alloy_primitives::U256::from(*(#value)).to_be_bytes::<32>(),
+                )
+            },
+            Self::Int(_) => quote! {
+                stylus_sdk::alloy_primitives::B256::new(
+                    stylus_sdk::alloy_primitives::I256::unchecked_from(*(#value))
+                        .into_raw()
+                        .to_be_bytes::<32>(),
+                )
+            },
+            Self::Signed(bits) => quote! {{
+                let value = *(#value);
+                let mut raw = stylus_sdk::alloy_primitives::U256::from(value.into_raw());
+                // Extend the sign to 256 bits.
+                if value.is_negative() && #bits < 256 {
+                    raw |= stylus_sdk::alloy_primitives::U256::MAX << #bits;
+                }
+                stylus_sdk::alloy_primitives::B256::new(raw.to_be_bytes::<32>())
+            }},
+            Self::FixedBytes(_) => quote! {
+                stylus_sdk::alloy_primitives::B256::right_padding_from((#value).as_slice())
+            },
+            Self::Bytes => quote! {
+                stylus_sdk::alloy_primitives::keccak256(AsRef::<[u8]>::as_ref(#value))
+            },
+            Self::String => quote! {
+                stylus_sdk::alloy_primitives::keccak256((#value).as_bytes())
+            },
+            Self::Array(element, _) => {
+                let element = element.encode(&quote!(item));
+                quote! {{
+                    let mut encoded = alloc::vec::Vec::new();
+                    for item in (#value).iter() {
+                        encoded.extend_from_slice(#element.as_slice());
+                    }
+                    stylus_sdk::alloy_primitives::keccak256(encoded)
+                }}
+            }
+            Self::Struct(..) => quote! {
+                (#value).struct_hash()
+            },
+        }
+    }
+}
+
+/// Returns the kind and the number of bits of an `alloy_primitives` alias,
+/// e.g. `('U', 256)` for `U256`.
+fn alias_bits(ident: &str) -> Option<(char, usize)> {
+    let mut chars = ident.chars();
+    let kind = chars.next().filter(|kind| matches!(kind, 'U' | 'I' | 'B'))?;
+    let bits: usize = chars.as_str().parse().ok()?;
+    (bits % 8 == 0 && (8..=256).contains(&bits)).then_some((kind, bits))
+}
+
+/// Parses the number of bits of an integer type, between 8 and 256.
+fn parse_bits(expr: &Expr) -> Result<usize> {
+    let bits = parse_usize(expr)?;
+    if bits % 8 != 0 || !(8..=256).contains(&bits) {
+        error!(@expr, "expected a multiple of 8 between 8 and 256");
+    }
+    Ok(bits)
+}
+
+/// Parses an integer literal.
+fn parse_usize(expr: &Expr) -> Result<usize> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(int), .. }) => int.base10_parse(),
+        _ => error!(@expr, "expected an integer literal"),
+    }
+}
@@ -16,7 +16,9 @@ macro_rules! error {
     }};
 }
 
+mod eip712_struct;
 mod interface_id;
+mod storage_location;
 
 /// Computes the interface id as an associated constant `INTERFACE_ID` for the
 /// trait that describes contract's abi.
@@ -61,3 +63,80 @@ mod interface_id;
 pub fn interface_id(attr: TokenStream, input: TokenStream) -> TokenStream {
     interface_id::interface_id(&attr, input)
 }
+
+/// Pins a storage struct to the [ERC-7201] slot computed from a namespace id,
+/// instead of laying it out sequentially in the storage of its parent.
+///
+/// The struct takes up no space in the layout of the parent struct, so adding,
+/// removing or reordering namespaced components doesn't shift the slots of
+/// the other fields. Its own fields are laid out from the namespace slot, the
+/// same way `#[storage]` lays them out from slot zero.
+///
+/// The attribute replaces `#[storage]`, which is removed if present. Fields
+/// marked with `#[borrow]` are supported.
+///
+/// It also generates the associated constants `STORAGE_LOCATION`, which holds
+/// the value of the `@custom:storage-location` annotation, and
+/// `STORAGE_SLOT`.
+///
+/// CAUTION: Two structs must never use the same namespace id.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[storage_location(erc7201 = "openzeppelin.storage.Initializable")]
+/// pub struct Initializable {
+///     initialized: StorageU64,
+///     initializing: StorageBool,
+/// }
+///
+/// assert_eq!(
+///     Initializable::STORAGE_LOCATION,
+///     "erc7201:openzeppelin.storage.Initializable"
+/// );
+/// ```
+///
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+#[proc_macro_attribute]
+pub fn storage_location(attr: TokenStream, input: TokenStream) -> TokenStream {
+    storage_location::storage_location(attr, input)
+}
+
+/// Implements [EIP-712] typed structured data hashing for a struct.
+///
+/// Generates the associated constant `TYPE_HASH`, and the functions
+/// `encode_type`, which returns the type of the struct followed by the types
+/// of the structs it references sorted by name, and `struct_hash`.
+///
+/// Fields are mapped to Solidity types from their Rust type: `Address`,
+/// `bool`, Rust and `alloy_primitives` integers, `FixedBytes<N>` and its
+/// aliases, `Bytes`, `String`, `Vec<T>`, `[T; N]`, and other structs deriving
+/// `Eip712Struct`. Field names are converted to camel case.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[derive(Eip712Struct)]
+/// struct Person {
+///     name: String,
+///     wallet: Address,
+/// }
+///
+/// #[derive(Eip712Struct)]
+/// struct Mail {
+///     from: Person,
+///     to: Person,
+///     contents: String,
+/// }
+///
+/// assert_eq!(
+///     Mail::encode_type(),
+///     "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+/// );
+/// ```
+///
+/// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+#[proc_macro_derive(Eip712Struct)]
+pub fn eip712_struct(input: TokenStream) -> TokenStream {
+    eip712_struct::eip712_struct(input)
+}
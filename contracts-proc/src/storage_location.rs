@@ -0,0 +1,228 @@
+//! Defines the `#[storage_location]` procedural macro.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Fields, Generics, ItemStruct, LitStr, Result, Token,
+    Type,
+};
+use tiny_keccak::{Hasher, Keccak};
+
+/// Pins a storage struct to the [ERC-7201] slot of a namespace.
+///
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+pub(crate) fn storage_location(
+    attr: TokenStream,
+    input: TokenStream,
+) -> TokenStream {
+    let args = parse_macro_input!(attr as StorageLocationArgs);
+    let mut input = parse_macro_input!(input as ItemStruct);
+
+    // This macro lays out the struct on its own, so `#[storage]` must not
+    // run on it.
+    input.attrs.retain(|attr| !attr.path().is_ident("storage"));
+
+    let Fields::Named(fields) = &mut input.fields else {
+        error!(input.fields, "only structs with named fields are supported");
+    };
+
+    let mut names = Vec::new();
+    let mut types = Vec::new();
+    let mut borrows = Vec::new();
+    for field in &mut fields.named {
+        let name = field.ident.clone().expect("fields should be named");
+        let len = field.attrs.len();
+        field.attrs.retain(|attr| !attr.path().is_ident("borrow"));
+        if field.attrs.len() != len {
+            borrows.push((name.clone(), field.ty.clone()));
+        }
+        names.push(name);
+        types.push(field.ty.clone());
+    }
+
+    let location = format!("erc7201:{}", args.id);
+    let slot = erc7201_slot(&args.id);
+    let host = Ident::new("__stylus_host", Span::call_site());
+    let carrier = format_ident!("__{}StorageHost", input.ident);
+
+    let attrs = &input.attrs;
+    let vis = &input.vis;
+    let name = &input.ident;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fields = fields.named.iter();
+
+    let storage_type =
+        impl_storage_type(name, generics, &carrier, &host, &names, &types);
+    let borrow_impls = borrows.iter().map(|(field, ty)| {
+        quote! {
+            impl #impl_generics core::borrow::Borrow<#ty> for #name #ty_generics #where_clause {
+                fn borrow(&self) -> &#ty {
+                    &self.#field
+                }
+            }
+
+            impl #impl_generics core::borrow::BorrowMut<#ty> for #name #ty_generics #where_clause {
+                fn borrow_mut(&mut self) -> &mut #ty {
+                    &mut self.#field
+                }
+            }
+        }
+    });
+
+    quote! {
+        #(#attrs)*
+        #vis struct #name #generics #where_clause {
+            #host: #carrier,
+            #(#fields),*
+        }
+
+        /// Gives access to the host for the namespaced storage struct.
+        #[doc(hidden)]
+        #[stylus_sdk::stylus_proc::storage]
+        #vis struct #carrier {}
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #[doc = concat!("Storage location of ", stringify!(#name), ", as in the `@custom:storage-location` annotation.")]
+            pub const STORAGE_LOCATION: &'static str = #location;
+
+            #[doc = concat!("Storage slot where the fields of ", stringify!(#name), " are laid out.")]
+            #[doc = "Computed as `keccak256(abi.encode(uint256(keccak256(id)) - 1)) & ~bytes32(uint256(0xff))`."]
+            pub const STORAGE_SLOT: stylus_sdk::alloy_primitives::B256 =
+                stylus_sdk::alloy_primitives::B256::new([#(#slot),*]);
+        }
+
+        #storage_type
+
+        impl #impl_generics stylus_sdk::stylus_core::HostAccess for #name #ty_generics #where_clause {
+            fn vm(&self) -> &dyn stylus_sdk::stylus_core::Host {
+                stylus_sdk::stylus_core::HostAccess::vm(&self.#host)
+            }
+        }
+
+        impl #impl_generics stylus_sdk::stylus_core::ValueDenier for #name #ty_generics #where_clause {
+            fn deny_value(&self, method_name: &str) -> Result<(), alloc::vec::Vec<u8>> {
+                stylus_sdk::stylus_core::ValueDenier::deny_value(&self.#host, method_name)
+            }
+        }
+
+        #(#borrow_impls)*
+    }
+    .into()
+}
+
+/// Implements `StorageType` for the struct, so that its fields are laid out
+/// from the namespace slot.
+fn impl_storage_type(
+    name: &Ident,
+    generics: &Generics,
+    carrier: &Ident,
+    host: &Ident,
+    names: &[Ident],
+    types: &[Type],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics stylus_sdk::storage::StorageType for #name #ty_generics #where_clause {
+            type Wraps<'a> = stylus_sdk::storage::StorageGuard<'a, Self> where Self: 'a;
+            type WrapsMut<'a> = stylus_sdk::storage::StorageGuardMut<'a, Self> where Self: 'a;
+
+            // Take up no space in the layout of the parent struct.
+            const SLOT_BYTES: usize = 0;
+            const REQUIRED_SLOTS: usize = 0;
+
+            unsafe fn new(
+                _slot: stylus_sdk::alloy_primitives::U256,
+                _offset: u8,
+                host: stylus_sdk::host::VM,
+            ) -> Self {
+                use stylus_sdk::{alloy_primitives::U256, storage::StorageType};
+
+                let root = U256::from_be_bytes(Self::STORAGE_SLOT.0);
+                let mut space: usize = 32;
+                let mut slot: usize = 0;
+                Self {
+                    #host: <#carrier as StorageType>::new(root, 0, host.clone()),
+                    #(
+                        #names: {
+                            let bytes = <#types as StorageType>::SLOT_BYTES;
+                            let words = <#types as StorageType>::REQUIRED_SLOTS;
+                            if space < bytes {
+                                space = 32;
+                                slot += 1;
+                            }
+                            space -= bytes;
+
+                            let root = root + U256::from(slot);
+                            let field = <#types as StorageType>::new(root, space as u8, host.clone());
+                            if words > 0 {
+                                slot += words;
+                                space = 32;
+                            }
+                            field
+                        },
+                    )*
+                }
+            }
+
+            fn load<'s>(self) -> Self::Wraps<'s> {
+                stylus_sdk::storage::StorageGuard::new(self)
+            }
+
+            fn load_mut<'s>(self) -> Self::WrapsMut<'s> {
+                stylus_sdk::storage::StorageGuardMut::new(self)
+            }
+        }
+    }
+}
+
+/// Computes the [ERC-7201] storage slot of the namespace `id`.
+///
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+fn erc7201_slot(id: &str) -> [u8; 32] {
+    let mut location = keccak256(id.as_bytes());
+
+    // Subtract 1 from the hash, as a big-endian integer.
+    for byte in location.iter_mut().rev() {
+        let (value, borrow) = byte.overflowing_sub(1);
+        *byte = value;
+        if !borrow {
+            break;
+        }
+    }
+
+    let mut slot = keccak256(&location);
+    slot[31] = 0;
+    slot
+}
+
+/// Computes the keccak-256 hash of `input`.
+fn keccak256(input: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    hasher.update(input);
+    let mut output = [0u8; 32];
+    hasher.finalize(&mut output);
+    output
+}
+
+/// Contains arguments of the `#[storage_location(..)]` attribute.
+struct StorageLocationArgs {
+    id: String,
+}
+
+impl Parse for StorageLocationArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let formula: Ident = input.parse()?;
+
+        if formula == "erc7201" {
+            let _: Token![=] = input.parse()?;
+            let lit: LitStr = input.parse()?;
+            Ok(StorageLocationArgs { id: lit.value() })
+        } else {
+            error!(@formula, "expected formula 'erc7201'")
+        }
+    }
+}
@@ -0,0 +1,1352 @@
+//! Extension of [`AccessControl`] that allows specifying special rules to
+//! manage the [`AccessControl::DEFAULT_ADMIN_ROLE`] holder.
+//!
+//! The default admin is a sensitive role with special permissions over other
+//! roles that may potentially have privileged rights in the system.
+//!
+//! If a specific role doesn't have an admin role assigned, the holder of the
+//! [`AccessControl::DEFAULT_ADMIN_ROLE`] will have the ability to grant it and
+//! revoke it.
+//!
+//! This contract implements the following risk mitigations on top of
+//! [`AccessControl`]:
+//!
+//! * Only one account holds the [`AccessControl::DEFAULT_ADMIN_ROLE`] since
+//!   deployment until it's potentially renounced.
+//! * Enforces a 2-step process to transfer the
+//!   [`AccessControl::DEFAULT_ADMIN_ROLE`] to another account.
+//! * Enforces a configurable delay between the two steps, with the ability to
+//!   cancel before the transfer is accepted.
+//! * The delay can be changed by scheduling, see
+//!   [`IAccessControlDefaultAdminRules::change_default_admin_delay`].
+//! * It is not possible to use another role to manage the
+//!   [`AccessControl::DEFAULT_ADMIN_ROLE`].
+//!
+//! The initial admin and delay are expected to be set at construction, e.g.
+//! with [`AccessControlDefaultAdminRules::_grant_role`].
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{aliases::U48, Address, FixedBytes, B256};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    block,
+    call::MethodError,
+    evm,
+    prelude::*,
+    storage::{StorageAddress, StorageUint},
+};
+
+use crate::{
+    access::control::{
+        self, AccessControl, AccessControlBadConfirmation, IAccessControl,
+    },
+    utils::{
+        context,
+        introspection::erc165::{Erc165, IErc165},
+    },
+};
+
+/// Default wait for an increase of the admin delay, 5 days.
+const DEFAULT_ADMIN_DELAY_INCREASE_WAIT: u64 = 5 * 24 * 60 * 60;
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when a [`AccessControl::DEFAULT_ADMIN_ROLE`] transfer is
+        /// started, setting `new_admin` as the next address to become the
+        /// default admin by calling `accept_default_admin_transfer` only
+        /// after `accept_schedule` passes.
+        ///
+        /// * `new_admin` - The account that will become the default admin.
+        /// * `accept_schedule` - Timestamp after which the transfer can be
+        ///   accepted.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event DefaultAdminTransferScheduled(
+            address indexed new_admin,
+            uint48 accept_schedule
+        );
+
+        /// Emitted when a pending default admin is reset if it was never
+        /// accepted, regardless of its schedule.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event DefaultAdminTransferCanceled();
+
+        /// Emitted when a default admin delay change is started, setting
+        /// `new_delay` as the next delay to be applied between default admin
+        /// transfers after `effect_schedule` has passed.
+        ///
+        /// * `new_delay` - The delay that will be applied.
+        /// * `effect_schedule` - Timestamp after which the delay applies.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event DefaultAdminDelayChangeScheduled(
+            uint48 new_delay,
+            uint48 effect_schedule
+        );
+
+        /// Emitted when a pending default admin delay is reset if its
+        /// schedule didn't pass.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event DefaultAdminDelayChangeCanceled();
+    }
+
+    sol! {
+        /// The new default admin is not a valid default admin.
+        ///
+        /// * `default_admin` - The invalid default admin.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessControlInvalidDefaultAdmin(address default_admin);
+
+        /// At least one of the following rules was violated:
+        ///
+        /// - The `DEFAULT_ADMIN_ROLE` must only be managed by itself.
+        /// - The `DEFAULT_ADMIN_ROLE` must only be held by one account at the
+        ///   time.
+        /// - Any `DEFAULT_ADMIN_ROLE` transfer must be in two delayed steps.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessControlEnforcedDefaultAdminRules();
+
+        /// The delay for transferring the default admin is not over yet.
+        ///
+        /// * `schedule` - Timestamp after which the transfer can be
+        ///   completed.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessControlEnforcedDefaultAdminDelay(uint48 schedule);
+    }
+}
+
+/// An error that occurred in the implementation of an
+/// [`AccessControlDefaultAdminRules`] contract.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// The new default admin is not a valid default admin.
+    InvalidDefaultAdmin(AccessControlInvalidDefaultAdmin),
+    /// At least one of the default admin rules was violated.
+    EnforcedDefaultAdminRules(AccessControlEnforcedDefaultAdminRules),
+    /// The delay for transferring the default admin is not over yet.
+    EnforcedDefaultAdminDelay(AccessControlEnforcedDefaultAdminDelay),
+    /// Error type from [`AccessControl`] contract [`control::Error`].
+    AccessControl(control::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`AccessControlDefaultAdminRules`] contract.
+#[storage]
+pub struct AccessControlDefaultAdminRules {
+    /// [`AccessControl`] contract.
+    // We leave the parent [`AccessControl`] contract instance public, so that
+    // inheritting contract have access to its internal functions.
+    pub access_control: AccessControl,
+    /// Account that can accept the default admin transfer.
+    pub(crate) pending_default_admin: StorageAddress,
+    /// Timestamp after which the pending default admin can accept the
+    /// transfer.
+    pub(crate) pending_default_admin_schedule: StorageUint<48, 1>,
+    /// Current delay between default admin transfer steps.
+    pub(crate) current_delay: StorageUint<48, 1>,
+    /// Current holder of the [`AccessControl::DEFAULT_ADMIN_ROLE`].
+    pub(crate) current_default_admin: StorageAddress,
+    /// Delay that will replace [`Self::current_delay`].
+    pub(crate) pending_delay: StorageUint<48, 1>,
+    /// Timestamp after which [`Self::pending_delay`] takes effect.
+    pub(crate) pending_delay_schedule: StorageUint<48, 1>,
+}
+
+impl Deref for AccessControlDefaultAdminRules {
+    type Target = AccessControl;
+
+    fn deref(&self) -> &Self::Target {
+        &self.access_control
+    }
+}
+
+impl DerefMut for AccessControlDefaultAdminRules {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.access_control
+    }
+}
+
+/// Interface for an [`AccessControlDefaultAdminRules`] contract.
+#[interface_id]
+pub trait IAccessControlDefaultAdminRules {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Returns `true` if `account` has been granted `role`.
+    ///
+    /// Re-export of [`AccessControl::has_role`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account to check for membership.
+    fn has_role(&self, role: B256, account: Address) -> bool;
+
+    /// Returns the admin role that controls `role`.
+    ///
+    /// Re-export of [`AccessControl::get_role_admin`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role` - The role identifier.
+    fn get_role_admin(&self, role: B256) -> B256;
+
+    /// Grants `role` to `account`. The caller must have `role`'s admin role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account which will be granted the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::EnforcedDefaultAdminRules`] - If `role` is
+    ///   [`AccessControl::DEFAULT_ADMIN_ROLE`].
+    /// * [`Error::AccessControl`] - If [`context::msg_sender`] has not been
+    ///   granted `role`'s admin role.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleGranted`].
+    fn grant_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Revokes `role` from `account`. The caller must have `role`'s admin
+    /// role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account which will be revoked the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::EnforcedDefaultAdminRules`] - If `role` is
+    ///   [`AccessControl::DEFAULT_ADMIN_ROLE`].
+    /// * [`Error::AccessControl`] - If [`context::msg_sender`] has not been
+    ///   granted `role`'s admin role.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleRevoked`].
+    fn revoke_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Revokes `role` from the calling account.
+    ///
+    /// For the [`AccessControl::DEFAULT_ADMIN_ROLE`], it only allows
+    /// renouncing in two delayed steps by first calling
+    /// [`Self::begin_default_admin_transfer`] to `Address::ZERO`, so it's
+    /// required that the schedule returned by [`Self::pending_default_admin`]
+    /// has also passed when calling this function.
+    ///
+    /// NOTE: Renouncing the [`AccessControl::DEFAULT_ADMIN_ROLE`] will leave
+    /// the contract without a default admin, thereby disabling any
+    /// functionality that is only available for it, and the possibility of
+    /// reassigning a non-administrated role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `confirmation` - The account which will be revoked the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`context::msg_sender`] is not
+    ///   `confirmation`.
+    /// * [`Error::EnforcedDefaultAdminDelay`] - If renouncing the
+    ///   [`AccessControl::DEFAULT_ADMIN_ROLE`] without a passed transfer to
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleRevoked`].
+    fn renounce_role(
+        &mut self,
+        role: B256,
+        confirmation: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Returns the address of the current
+    /// [`AccessControl::DEFAULT_ADMIN_ROLE`] holder, for compatibility with
+    /// tooling that expects an owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn owner(&self) -> Address;
+
+    /// Returns the address of the current
+    /// [`AccessControl::DEFAULT_ADMIN_ROLE`] holder.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn default_admin(&self) -> Address;
+
+    /// Returns a tuple of a `new_admin` and an accept schedule.
+    ///
+    /// After the `schedule` passes, the `new_admin` will be able to accept the
+    /// [`AccessControl::DEFAULT_ADMIN_ROLE`] by calling
+    /// [`Self::accept_default_admin_transfer`], completing the role transfer.
+    ///
+    /// A zero value only in `schedule` indicates no pending admin transfer.
+    ///
+    /// NOTE: A zero address `new_admin` means that the default admin is being
+    /// renounced.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn pending_default_admin(&self) -> (Address, U48);
+
+    /// Returns the delay required to schedule the acceptance of a
+    /// [`AccessControl::DEFAULT_ADMIN_ROLE`] transfer started.
+    ///
+    /// This delay will be added to the current timestamp when calling
+    /// [`Self::begin_default_admin_transfer`] to set the acceptance schedule.
+    ///
+    /// NOTE: If a delay change has been scheduled, it will take effect as soon
+    /// as the schedule passes, making this function return the new delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn default_admin_delay(&self) -> U48;
+
+    /// Returns a tuple of `new_delay` and an effect schedule.
+    ///
+    /// After the `schedule` passes, the `new_delay` will get into effect
+    /// immediately for every new [`AccessControl::DEFAULT_ADMIN_ROLE`]
+    /// transfer started with [`Self::begin_default_admin_transfer`].
+    ///
+    /// A zero value only in `schedule` indicates no pending delay change.
+    ///
+    /// NOTE: A zero value only for `new_delay` means that the next
+    /// [`Self::default_admin_delay`] will be zero after the effect schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn pending_default_admin_delay(&self) -> (U48, U48);
+
+    /// Maximum time in seconds for an increase to
+    /// [`Self::default_admin_delay`] (that is scheduled using
+    /// [`Self::change_default_admin_delay`]) to take effect. Defaults to 5
+    /// days.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn default_admin_delay_increase_wait(&self) -> U48;
+
+    /// Starts a [`AccessControl::DEFAULT_ADMIN_ROLE`] transfer by setting a
+    /// [`Self::pending_default_admin`] scheduled for acceptance after the
+    /// current timestamp plus a [`Self::default_admin_delay`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_admin` - The account that will become the default admin.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`context::msg_sender`] is not the
+    ///   default admin.
+    ///
+    /// # Events
+    ///
+    /// * [`DefaultAdminTransferScheduled`].
+    /// * [`DefaultAdminTransferCanceled`] - If a pending transfer was replaced.
+    fn begin_default_admin_transfer(
+        &mut self,
+        new_admin: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Cancels a [`AccessControl::DEFAULT_ADMIN_ROLE`] transfer previously
+    /// started with [`Self::begin_default_admin_transfer`].
+    ///
+    /// A [`Self::pending_default_admin`] not yet accepted can also be
+    /// cancelled with this function.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`context::msg_sender`] is not the
+    ///   default admin.
+    ///
+    /// # Events
+    ///
+    /// * [`DefaultAdminTransferCanceled`] - If there was a pending transfer.
+    fn cancel_default_admin_transfer(&mut self) -> Result<(), Self::Error>;
+
+    /// Completes a [`AccessControl::DEFAULT_ADMIN_ROLE`] transfer previously
+    /// started with [`Self::begin_default_admin_transfer`].
+    ///
+    /// After calling the function:
+    ///
+    /// - [`AccessControl::DEFAULT_ADMIN_ROLE`] should be granted to the caller.
+    /// - [`AccessControl::DEFAULT_ADMIN_ROLE`] should be revoked from the
+    ///   previous holder.
+    /// - [`Self::pending_default_admin`] should be reset to zero values.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidDefaultAdmin`] - If [`context::msg_sender`] is not the
+    ///   pending default admin.
+    /// * [`Error::EnforcedDefaultAdminDelay`] - If the accept schedule has not
+    ///   passed yet.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleRevoked`].
+    /// * [`control::RoleGranted`].
+    fn accept_default_admin_transfer(&mut self) -> Result<(), Self::Error>;
+
+    /// Initiates a [`Self::default_admin_delay`] update by setting a
+    /// [`Self::pending_default_admin_delay`] scheduled for getting into effect
+    /// after the current timestamp plus a wait.
+    ///
+    /// The wait is the difference between the current and the new delay when
+    /// decreasing it, so that a transfer started right before the change
+    /// can't be accepted earlier than expected. When increasing the delay, the
+    /// wait is the new delay capped by
+    /// [`Self::default_admin_delay_increase_wait`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_delay` - The new delay between default admin transfer steps.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`context::msg_sender`] is not the
+    ///   default admin.
+    ///
+    /// # Events
+    ///
+    /// * [`DefaultAdminDelayChangeScheduled`].
+    /// * [`DefaultAdminDelayChangeCanceled`] - If a pending delay change was
+    ///   replaced.
+    fn change_default_admin_delay(
+        &mut self,
+        new_delay: U48,
+    ) -> Result<(), Self::Error>;
+
+    /// Cancels a scheduled [`Self::default_admin_delay`] change.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`context::msg_sender`] is not the
+    ///   default admin.
+    ///
+    /// # Events
+    ///
+    /// * [`DefaultAdminDelayChangeCanceled`] - If there was a pending delay
+    ///   change.
+    fn rollback_default_admin_delay(&mut self) -> Result<(), Self::Error>;
+}
+
+#[public]
+impl IAccessControlDefaultAdminRules for AccessControlDefaultAdminRules {
+    type Error = Error;
+
+    fn has_role(&self, role: B256, account: Address) -> bool {
+        self.access_control.has_role(role, account)
+    }
+
+    fn get_role_admin(&self, role: B256) -> B256 {
+        self.access_control.get_role_admin(role)
+    }
+
+    fn grant_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error> {
+        Self::_check_not_default_admin_role(role)?;
+        Ok(self.access_control.grant_role(role, account)?)
+    }
+
+    fn revoke_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error> {
+        Self::_check_not_default_admin_role(role)?;
+        Ok(self.access_control.revoke_role(role, account)?)
+    }
+
+    fn renounce_role(
+        &mut self,
+        role: B256,
+        confirmation: Address,
+    ) -> Result<(), Self::Error> {
+        if context::msg_sender() != confirmation {
+            return Err(control::Error::BadConfirmation(
+                AccessControlBadConfirmation {},
+            )
+            .into());
+        }
+
+        if role == AccessControl::DEFAULT_ADMIN_ROLE
+            && confirmation == self.default_admin()
+        {
+            let (new_default_admin, schedule) = self.pending_default_admin();
+            if !new_default_admin.is_zero()
+                || !Self::_is_schedule_set(schedule)
+                || !Self::_has_schedule_passed(schedule)
+            {
+                return Err(Error::EnforcedDefaultAdminDelay(
+                    AccessControlEnforcedDefaultAdminDelay { schedule },
+                ));
+            }
+            self.pending_default_admin_schedule.set(U48::ZERO);
+        }
+
+        self._revoke_role(role, confirmation);
+        Ok(())
+    }
+
+    fn owner(&self) -> Address {
+        self.default_admin()
+    }
+
+    fn default_admin(&self) -> Address {
+        self.current_default_admin.get()
+    }
+
+    fn pending_default_admin(&self) -> (Address, U48) {
+        (
+            self.pending_default_admin.get(),
+            self.pending_default_admin_schedule.get(),
+        )
+    }
+
+    fn default_admin_delay(&self) -> U48 {
+        let schedule = self.pending_delay_schedule.get();
+        if Self::_is_schedule_set(schedule)
+            && Self::_has_schedule_passed(schedule)
+        {
+            self.pending_delay.get()
+        } else {
+            self.current_delay.get()
+        }
+    }
+
+    fn pending_default_admin_delay(&self) -> (U48, U48) {
+        let schedule = self.pending_delay_schedule.get();
+        if Self::_is_schedule_set(schedule)
+            && !Self::_has_schedule_passed(schedule)
+        {
+            (self.pending_delay.get(), schedule)
+        } else {
+            (U48::ZERO, U48::ZERO)
+        }
+    }
+
+    fn default_admin_delay_increase_wait(&self) -> U48 {
+        U48::from(DEFAULT_ADMIN_DELAY_INCREASE_WAIT)
+    }
+
+    fn begin_default_admin_transfer(
+        &mut self,
+        new_admin: Address,
+    ) -> Result<(), Self::Error> {
+        self.access_control
+            .only_role(AccessControl::DEFAULT_ADMIN_ROLE.into())?;
+        self._begin_default_admin_transfer(new_admin);
+        Ok(())
+    }
+
+    fn cancel_default_admin_transfer(&mut self) -> Result<(), Self::Error> {
+        self.access_control
+            .only_role(AccessControl::DEFAULT_ADMIN_ROLE.into())?;
+        self._set_pending_default_admin(Address::ZERO, U48::ZERO);
+        Ok(())
+    }
+
+    fn accept_default_admin_transfer(&mut self) -> Result<(), Self::Error> {
+        let (new_default_admin, _) = self.pending_default_admin();
+        let sender = context::msg_sender();
+        if sender != new_default_admin {
+            // Enforce `new_default_admin` explicit acceptance.
+            return Err(Error::InvalidDefaultAdmin(
+                AccessControlInvalidDefaultAdmin { default_admin: sender },
+            ));
+        }
+        self._accept_default_admin_transfer()
+    }
+
+    fn change_default_admin_delay(
+        &mut self,
+        new_delay: U48,
+    ) -> Result<(), Self::Error> {
+        self.access_control
+            .only_role(AccessControl::DEFAULT_ADMIN_ROLE.into())?;
+        self._change_default_admin_delay(new_delay);
+        Ok(())
+    }
+
+    fn rollback_default_admin_delay(&mut self) -> Result<(), Self::Error> {
+        self.access_control
+            .only_role(AccessControl::DEFAULT_ADMIN_ROLE.into())?;
+        self._set_pending_delay(U48::ZERO, U48::ZERO);
+        Ok(())
+    }
+}
+
+impl AccessControlDefaultAdminRules {
+    /// Attempts to grant `role` to `account` and returns a boolean indicating
+    /// if `role` was granted.
+    ///
+    /// For the [`AccessControl::DEFAULT_ADMIN_ROLE`], it only allows granting
+    /// if there isn't already a default admin, or if the role has been
+    /// previously renounced. Can be used to set the initial default admin.
+    ///
+    /// Internal function without access restriction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account which will be granted the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::EnforcedDefaultAdminRules`] - If `role` is
+    ///   [`AccessControl::DEFAULT_ADMIN_ROLE`] and there is already a default
+    ///   admin.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleGranted`].
+    pub fn _grant_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<bool, Error> {
+        if role == AccessControl::DEFAULT_ADMIN_ROLE {
+            if !self.default_admin().is_zero() {
+                return Err(Error::EnforcedDefaultAdminRules(
+                    AccessControlEnforcedDefaultAdminRules {},
+                ));
+            }
+            self.current_default_admin.set(account);
+        }
+        Ok(self.access_control._grant_role(role, account))
+    }
+
+    /// Attempts to revoke `role` from `account` and returns a boolean
+    /// indicating if `role` was revoked.
+    ///
+    /// Clears the default admin when revoking the
+    /// [`AccessControl::DEFAULT_ADMIN_ROLE`] from it.
+    ///
+    /// Internal function without access restriction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account which will be revoked the role.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleRevoked`].
+    pub fn _revoke_role(&mut self, role: B256, account: Address) -> bool {
+        if role == AccessControl::DEFAULT_ADMIN_ROLE
+            && account == self.default_admin()
+        {
+            self.current_default_admin.set(Address::ZERO);
+        }
+        self.access_control._revoke_role(role, account)
+    }
+
+    /// Sets `new_admin_role` as `role`'s admin role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The identifier of the role we are changing the admin to.
+    /// * `new_admin_role` - The new admin role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::EnforcedDefaultAdminRules`] - If `role` is
+    ///   [`AccessControl::DEFAULT_ADMIN_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleAdminChanged`].
+    pub fn _set_role_admin(
+        &mut self,
+        role: B256,
+        new_admin_role: B256,
+    ) -> Result<(), Error> {
+        Self::_check_not_default_admin_role(role)?;
+        self.access_control._set_role_admin(role, new_admin_role);
+        Ok(())
+    }
+
+    /// Sets the delay between default admin transfer steps, without waiting.
+    /// Intended to be used at construction, in the same way as
+    /// [`Self::_grant_role`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `delay` - The delay between default admin transfer steps.
+    pub fn _set_default_admin_delay(&mut self, delay: U48) {
+        self.current_delay.set(delay);
+    }
+
+    /// Starts a default admin transfer, see
+    /// [`IAccessControlDefaultAdminRules::begin_default_admin_transfer`].
+    ///
+    /// Internal function without access restriction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_admin` - The account that will become the default admin.
+    ///
+    /// # Events
+    ///
+    /// * [`DefaultAdminTransferScheduled`].
+    /// * [`DefaultAdminTransferCanceled`] - If a pending transfer was replaced.
+    pub fn _begin_default_admin_transfer(&mut self, new_admin: Address) {
+        let new_schedule = Self::_now() + self.default_admin_delay();
+        self._set_pending_default_admin(new_admin, new_schedule);
+        evm::log(DefaultAdminTransferScheduled {
+            new_admin,
+            accept_schedule: new_schedule,
+        });
+    }
+
+    /// Completes a default admin transfer, see
+    /// [`IAccessControlDefaultAdminRules::accept_default_admin_transfer`].
+    ///
+    /// Internal function without access restriction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::EnforcedDefaultAdminDelay`] - If the accept schedule has not
+    ///   passed yet.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleRevoked`].
+    /// * [`control::RoleGranted`].
+    pub fn _accept_default_admin_transfer(&mut self) -> Result<(), Error> {
+        let (new_admin, schedule) = self.pending_default_admin();
+        if !Self::_is_schedule_set(schedule)
+            || !Self::_has_schedule_passed(schedule)
+        {
+            return Err(Error::EnforcedDefaultAdminDelay(
+                AccessControlEnforcedDefaultAdminDelay { schedule },
+            ));
+        }
+        self._revoke_role(
+            AccessControl::DEFAULT_ADMIN_ROLE.into(),
+            self.default_admin(),
+        );
+        self._grant_role(AccessControl::DEFAULT_ADMIN_ROLE.into(), new_admin)?;
+        self.pending_default_admin.set(Address::ZERO);
+        self.pending_default_admin_schedule.set(U48::ZERO);
+        Ok(())
+    }
+
+    /// Schedules a default admin delay change, see
+    /// [`IAccessControlDefaultAdminRules::change_default_admin_delay`].
+    ///
+    /// Internal function without access restriction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_delay` - The new delay between default admin transfer steps.
+    ///
+    /// # Events
+    ///
+    /// * [`DefaultAdminDelayChangeScheduled`].
+    /// * [`DefaultAdminDelayChangeCanceled`] - If a pending delay change was
+    ///   replaced.
+    pub fn _change_default_admin_delay(&mut self, new_delay: U48) {
+        let new_schedule = Self::_now() + self._delay_change_wait(new_delay);
+        self._set_pending_delay(new_delay, new_schedule);
+        evm::log(DefaultAdminDelayChangeScheduled {
+            new_delay,
+            effect_schedule: new_schedule,
+        });
+    }
+
+    /// Returns the amount of seconds to wait after the `new_delay` will
+    /// become the new [`IAccessControlDefaultAdminRules::default_admin_delay`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `new_delay` - The new delay between default admin transfer steps.
+    fn _delay_change_wait(&self, new_delay: U48) -> U48 {
+        let current_delay = self.default_admin_delay();
+
+        // When increasing the delay, we schedule the delay change to occur
+        // after a period of "new delay" has passed, up to a maximum given by
+        // `default_admin_delay_increase_wait`. This allows the admin to fix
+        // an error in the delay change by rolling it back before it applies.
+        //
+        // When decreasing the delay, we wait the difference between "current
+        // delay" and "new delay". This guarantees that an admin transfer
+        // cannot be made faster than "current delay" at the time the delay
+        // change is scheduled.
+        if new_delay > current_delay {
+            new_delay.min(self.default_admin_delay_increase_wait())
+        } else {
+            current_delay - new_delay
+        }
+    }
+
+    /// Setter of the tuple for pending admin and its schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_admin` - The pending default admin.
+    /// * `new_schedule` - Timestamp after which the transfer can be accepted.
+    ///
+    /// # Events
+    ///
+    /// * [`DefaultAdminTransferCanceled`] - If a pending transfer was replaced.
+    fn _set_pending_default_admin(
+        &mut self,
+        new_admin: Address,
+        new_schedule: U48,
+    ) {
+        let (_, old_schedule) = self.pending_default_admin();
+
+        self.pending_default_admin.set(new_admin);
+        self.pending_default_admin_schedule.set(new_schedule);
+
+        // An `old_schedule` from `pending_default_admin()` is only set if it
+        // hasn't been accepted.
+        if Self::_is_schedule_set(old_schedule) {
+            // Emit for implicit cancellations when another default admin was
+            // scheduled.
+            evm::log(DefaultAdminTransferCanceled {});
+        }
+    }
+
+    /// Setter of the tuple for pending delay and its schedule.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_delay` - The pending delay.
+    /// * `new_schedule` - Timestamp after which the delay takes effect.
+    ///
+    /// # Events
+    ///
+    /// * [`DefaultAdminDelayChangeCanceled`] - If a pending delay change was
+    ///   replaced.
+    fn _set_pending_delay(&mut self, new_delay: U48, new_schedule: U48) {
+        let old_schedule = self.pending_delay_schedule.get();
+
+        if Self::_is_schedule_set(old_schedule) {
+            if Self::_has_schedule_passed(old_schedule) {
+                // Materialize a virtual delay.
+                self.current_delay.set(self.pending_delay.get());
+            } else {
+                // Emit for implicit cancellations when another delay was
+                // scheduled.
+                evm::log(DefaultAdminDelayChangeCanceled {});
+            }
+        }
+
+        self.pending_delay.set(new_delay);
+        self.pending_delay_schedule.set(new_schedule);
+    }
+
+    /// Checks that `role` is not the [`AccessControl::DEFAULT_ADMIN_ROLE`].
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role identifier.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::EnforcedDefaultAdminRules`] - If `role` is
+    ///   [`AccessControl::DEFAULT_ADMIN_ROLE`].
+    fn _check_not_default_admin_role(role: B256) -> Result<(), Error> {
+        if role == AccessControl::DEFAULT_ADMIN_ROLE {
+            return Err(Error::EnforcedDefaultAdminRules(
+                AccessControlEnforcedDefaultAdminRules {},
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the current block timestamp.
+    fn _now() -> U48 {
+        U48::from(block::timestamp())
+    }
+
+    /// Defines if a `schedule` is considered set.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - Timestamp to check.
+    fn _is_schedule_set(schedule: U48) -> bool {
+        !schedule.is_zero()
+    }
+
+    /// Defines if a `schedule` is considered passed.
+    ///
+    /// # Arguments
+    ///
+    /// * `schedule` - Timestamp to check.
+    fn _has_schedule_passed(schedule: U48) -> bool {
+        schedule < Self::_now()
+    }
+}
+
+impl IErc165 for AccessControlDefaultAdminRules {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IAccessControlDefaultAdminRules>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || <AccessControl as IAccessControl>::INTERFACE_ID
+                == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{aliases::U48, Address};
+    use motsu::prelude::*;
+    use stylus_sdk::{block, prelude::TopLevelStorage};
+
+    use super::*;
+
+    const ROLE: [u8; 32] = alloy_primitives::hex!(
+        "ed9ea7bc2a13bc59432ab07436e7f7f5450f82d4b48c401bed177bfaf36b1873"
+    );
+    const DEFAULT_ADMIN_ROLE: [u8; 32] = AccessControl::DEFAULT_ADMIN_ROLE;
+    const DELAY: u64 = 3 * 24 * 60 * 60;
+
+    unsafe impl TopLevelStorage for AccessControlDefaultAdminRules {}
+
+    fn now() -> U48 {
+        U48::from(block::timestamp())
+    }
+
+    fn init(
+        contract: &Contract<AccessControlDefaultAdminRules>,
+        admin: Address,
+    ) {
+        contract.init(admin, |contract| {
+            contract
+                ._grant_role(DEFAULT_ADMIN_ROLE.into(), admin)
+                .expect("should grant the initial default admin");
+            contract._set_default_admin_delay(U48::from(DELAY));
+        });
+    }
+
+    #[motsu::test]
+    fn initial_default_admin_is_set(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+    ) {
+        init(&contract, alice);
+
+        assert_eq!(contract.sender(alice).default_admin(), alice);
+        assert_eq!(contract.sender(alice).owner(), alice);
+        assert!(contract
+            .sender(alice)
+            .has_role(DEFAULT_ADMIN_ROLE.into(), alice));
+        assert_eq!(
+            contract.sender(alice).default_admin_delay(),
+            U48::from(DELAY)
+        );
+    }
+
+    #[motsu::test]
+    fn second_default_admin_cannot_be_granted_internally(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&contract, alice);
+
+        let err = contract
+            .sender(alice)
+            ._grant_role(DEFAULT_ADMIN_ROLE.into(), bob)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::EnforcedDefaultAdminRules(_)));
+    }
+
+    #[motsu::test]
+    fn grant_and_revoke_default_admin_role_are_blocked(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&contract, alice);
+
+        let err = contract
+            .sender(alice)
+            .grant_role(DEFAULT_ADMIN_ROLE.into(), bob)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::EnforcedDefaultAdminRules(_)));
+
+        let err = contract
+            .sender(alice)
+            .revoke_role(DEFAULT_ADMIN_ROLE.into(), alice)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::EnforcedDefaultAdminRules(_)));
+
+        let err = contract
+            .sender(alice)
+            ._set_role_admin(DEFAULT_ADMIN_ROLE.into(), ROLE.into())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::EnforcedDefaultAdminRules(_)));
+    }
+
+    #[motsu::test]
+    fn admin_can_grant_and_revoke_other_roles(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&contract, alice);
+
+        contract.sender(alice).grant_role(ROLE.into(), bob).motsu_unwrap();
+        assert!(contract.sender(alice).has_role(ROLE.into(), bob));
+
+        contract.sender(alice).revoke_role(ROLE.into(), bob).motsu_unwrap();
+        assert!(!contract.sender(alice).has_role(ROLE.into(), bob));
+    }
+
+    #[motsu::test]
+    fn begins_default_admin_transfer(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&contract, alice);
+
+        contract.sender(alice).begin_default_admin_transfer(bob).motsu_unwrap();
+
+        let accept_schedule = now() + U48::from(DELAY);
+        contract.assert_emitted(&DefaultAdminTransferScheduled {
+            new_admin: bob,
+            accept_schedule,
+        });
+        assert_eq!(
+            contract.sender(alice).pending_default_admin(),
+            (bob, accept_schedule)
+        );
+    }
+
+    #[motsu::test]
+    fn begin_default_admin_transfer_reverts_when_not_admin(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&contract, alice);
+
+        let err = contract
+            .sender(bob)
+            .begin_default_admin_transfer(bob)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::AccessControl(_)));
+    }
+
+    #[motsu::test]
+    fn accept_reverts_before_schedule_passes(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&contract, alice);
+        contract.sender(alice).begin_default_admin_transfer(bob).motsu_unwrap();
+
+        let err = contract
+            .sender(bob)
+            .accept_default_admin_transfer()
+            .motsu_unwrap_err();
+        assert!(matches!(
+            err,
+            Error::EnforcedDefaultAdminDelay(
+                AccessControlEnforcedDefaultAdminDelay { schedule }
+            ) if schedule == now() + U48::from(DELAY)
+        ));
+    }
+
+    #[motsu::test]
+    fn accept_reverts_when_not_pending_admin(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+        bob: Address,
+        charlie: Address,
+    ) {
+        init(&contract, alice);
+        contract.sender(alice).begin_default_admin_transfer(bob).motsu_unwrap();
+
+        let err = contract
+            .sender(charlie)
+            .accept_default_admin_transfer()
+            .motsu_unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InvalidDefaultAdmin(AccessControlInvalidDefaultAdmin {
+                default_admin
+            }) if default_admin == charlie
+        ));
+    }
+
+    #[motsu::test]
+    fn accepts_default_admin_transfer_after_schedule(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&contract, alice);
+        contract.sender(alice).begin_default_admin_transfer(bob).motsu_unwrap();
+        // Move the schedule into the past.
+        contract
+            .sender(alice)
+            .pending_default_admin_schedule
+            .set(now() - U48::from(1));
+
+        contract.sender(bob).accept_default_admin_transfer().motsu_unwrap();
+
+        assert_eq!(contract.sender(bob).default_admin(), bob);
+        assert!(contract.sender(bob).has_role(DEFAULT_ADMIN_ROLE.into(), bob));
+        assert!(!contract
+            .sender(bob)
+            .has_role(DEFAULT_ADMIN_ROLE.into(), alice));
+        assert_eq!(
+            contract.sender(bob).pending_default_admin(),
+            (Address::ZERO, U48::ZERO)
+        );
+    }
+
+    #[motsu::test]
+    fn cancels_default_admin_transfer(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&contract, alice);
+        contract.sender(alice).begin_default_admin_transfer(bob).motsu_unwrap();
+
+        contract.sender(alice).cancel_default_admin_transfer().motsu_unwrap();
+
+        contract.assert_emitted(&DefaultAdminTransferCanceled {});
+        assert_eq!(
+            contract.sender(alice).pending_default_admin(),
+            (Address::ZERO, U48::ZERO)
+        );
+    }
+
+    #[motsu::test]
+    fn renounce_default_admin_requires_scheduled_transfer(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+    ) {
+        init(&contract, alice);
+
+        let err = contract
+            .sender(alice)
+            .renounce_role(DEFAULT_ADMIN_ROLE.into(), alice)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::EnforcedDefaultAdminDelay(_)));
+
+        contract
+            .sender(alice)
+            .begin_default_admin_transfer(Address::ZERO)
+            .motsu_unwrap();
+        contract
+            .sender(alice)
+            .pending_default_admin_schedule
+            .set(now() - U48::from(1));
+
+        contract
+            .sender(alice)
+            .renounce_role(DEFAULT_ADMIN_ROLE.into(), alice)
+            .motsu_unwrap();
+
+        assert_eq!(contract.sender(alice).default_admin(), Address::ZERO);
+        assert!(!contract
+            .sender(alice)
+            .has_role(DEFAULT_ADMIN_ROLE.into(), alice));
+    }
+
+    #[motsu::test]
+    fn increasing_delay_waits_for_new_delay_capped(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+    ) {
+        init(&contract, alice);
+
+        let new_delay = U48::from(DELAY + 1);
+        contract
+            .sender(alice)
+            .change_default_admin_delay(new_delay)
+            .motsu_unwrap();
+
+        let effect_schedule = now() + new_delay;
+        contract.assert_emitted(&DefaultAdminDelayChangeScheduled {
+            new_delay,
+            effect_schedule,
+        });
+        assert_eq!(
+            contract.sender(alice).pending_default_admin_delay(),
+            (new_delay, effect_schedule)
+        );
+        // The current delay applies until the schedule passes.
+        assert_eq!(
+            contract.sender(alice).default_admin_delay(),
+            U48::from(DELAY)
+        );
+
+        let large_delay = U48::from(DELAY * 10);
+        contract
+            .sender(alice)
+            .change_default_admin_delay(large_delay)
+            .motsu_unwrap();
+        contract.assert_emitted(&DefaultAdminDelayChangeCanceled {});
+
+        let wait = contract.sender(alice).default_admin_delay_increase_wait();
+        assert_eq!(
+            contract.sender(alice).pending_default_admin_delay(),
+            (large_delay, now() + wait)
+        );
+    }
+
+    #[motsu::test]
+    fn decreasing_delay_waits_for_difference(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+    ) {
+        init(&contract, alice);
+
+        let new_delay = U48::from(DELAY / 3);
+        contract
+            .sender(alice)
+            .change_default_admin_delay(new_delay)
+            .motsu_unwrap();
+
+        assert_eq!(
+            contract.sender(alice).pending_default_admin_delay(),
+            (new_delay, now() + U48::from(DELAY) - new_delay)
+        );
+    }
+
+    #[motsu::test]
+    fn delay_applies_after_schedule_passes(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+    ) {
+        init(&contract, alice);
+
+        let new_delay = U48::from(DELAY / 3);
+        contract
+            .sender(alice)
+            .change_default_admin_delay(new_delay)
+            .motsu_unwrap();
+        contract.sender(alice).pending_delay_schedule.set(now() - U48::from(1));
+
+        assert_eq!(contract.sender(alice).default_admin_delay(), new_delay);
+        assert_eq!(
+            contract.sender(alice).pending_default_admin_delay(),
+            (U48::ZERO, U48::ZERO)
+        );
+    }
+
+    #[motsu::test]
+    fn rolls_back_default_admin_delay(
+        contract: Contract<AccessControlDefaultAdminRules>,
+        alice: Address,
+    ) {
+        init(&contract, alice);
+
+        contract
+            .sender(alice)
+            .change_default_admin_delay(U48::from(DELAY * 2))
+            .motsu_unwrap();
+        contract.sender(alice).rollback_default_admin_delay().motsu_unwrap();
+
+        contract.assert_emitted(&DefaultAdminDelayChangeCanceled {});
+        assert_eq!(
+            contract.sender(alice).pending_default_admin_delay(),
+            (U48::ZERO, U48::ZERO)
+        );
+        assert_eq!(
+            contract.sender(alice).default_admin_delay(),
+            U48::from(DELAY)
+        );
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(AccessControlDefaultAdminRules::supports_interface(
+            <AccessControlDefaultAdminRules as IAccessControlDefaultAdminRules>::INTERFACE_ID
+                .into()
+        ));
+        assert!(AccessControlDefaultAdminRules::supports_interface(
+            <AccessControl as IAccessControl>::INTERFACE_ID.into()
+        ));
+        assert!(AccessControlDefaultAdminRules::supports_interface(
+            <AccessControlDefaultAdminRules as IErc165>::INTERFACE_ID.into()
+        ));
+
+        let fake_interface_id = 0x12345678u32;
+        assert!(!AccessControlDefaultAdminRules::supports_interface(
+            fake_interface_id.into()
+        ));
+    }
+}
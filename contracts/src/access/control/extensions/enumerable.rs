@@ -0,0 +1,622 @@
+//! Extension of [`AccessControl`] that allows enumerating the members of each
+//! role.
+//!
+//! Role membership is tracked in an [`EnumerableSet`], kept in sync with the
+//! wrapped [`AccessControl`] by the role management functions of
+//! [`AccessControlEnumerable`]. Roles granted or revoked internally should go
+//! through [`AccessControlEnumerable::_grant_role`] and
+//! [`AccessControlEnumerable::_revoke_role`], rather than through the wrapped
+//! [`AccessControl`].
+use alloc::{vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{Address, FixedBytes, B256, U256};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{call::MethodError, prelude::*, storage::StorageMap};
+
+use crate::{
+    access::control::{
+        self, AccessControl, AccessControlBadConfirmation, IAccessControl,
+    },
+    utils::{
+        context,
+        introspection::erc165::{Erc165, IErc165},
+        structs::enumerable_set::EnumerableSet,
+    },
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Indicates an error when a `role`'s member query was out of bounds
+        /// for `index`.
+        ///
+        /// * `role` - The role identifier.
+        /// * `index` - Index of the member in the role's member list.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessControlEnumerableOutOfBoundsIndex(bytes32 role, uint256 index);
+    }
+}
+
+/// An [`AccessControlEnumerable`] extension error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Indicates an error when a `role`'s member query was out of bounds for
+    /// `index`.
+    OutOfBoundsIndex(AccessControlEnumerableOutOfBoundsIndex),
+    /// Error type from [`AccessControl`] contract [`control::Error`].
+    AccessControl(control::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`AccessControlEnumerable`] contract.
+#[storage]
+pub struct AccessControlEnumerable {
+    /// [`AccessControl`] contract.
+    // We leave the parent [`AccessControl`] contract instance public, so that
+    // inheritting contract have access to its internal functions.
+    pub access_control: AccessControl,
+    /// Role identifier -> Set of accounts that have been granted the role.
+    pub(crate) role_members: StorageMap<B256, EnumerableSet<Address>>,
+}
+
+impl Deref for AccessControlEnumerable {
+    type Target = AccessControl;
+
+    fn deref(&self) -> &Self::Target {
+        &self.access_control
+    }
+}
+
+impl DerefMut for AccessControlEnumerable {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.access_control
+    }
+}
+
+/// Interface for an [`AccessControlEnumerable`] contract.
+#[interface_id]
+pub trait IAccessControlEnumerable {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Returns `true` if `account` has been granted `role`.
+    ///
+    /// Re-export of [`AccessControl::has_role`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account to check for membership.
+    fn has_role(&self, role: B256, account: Address) -> bool;
+
+    /// Returns the admin role that controls `role`.
+    ///
+    /// Re-export of [`AccessControl::get_role_admin`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role` - The role identifier.
+    fn get_role_admin(&self, role: B256) -> B256;
+
+    /// Grants `role` to `account`, tracking it as a member of `role`. The
+    /// caller must have `role`'s admin role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account which will be granted the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`context::msg_sender`] has not been
+    ///   granted `role`'s admin role.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleGranted`].
+    fn grant_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Revokes `role` from `account`, removing it from the members of `role`.
+    /// The caller must have `role`'s admin role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account which will be revoked the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`context::msg_sender`] has not been
+    ///   granted `role`'s admin role.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleRevoked`].
+    fn revoke_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Revokes `role` from the calling account, removing it from the members
+    /// of `role`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `confirmation` - The account which will be revoked the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`context::msg_sender`] is not
+    ///   `confirmation`.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleRevoked`] - If the calling account has its `role`
+    ///   revoked.
+    fn renounce_role(
+        &mut self,
+        role: B256,
+        confirmation: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Returns one of the accounts that have `role`. `index` must be a value
+    /// between 0 and [`Self::get_role_member_count`], non-inclusive.
+    ///
+    /// Role bearers are not sorted in any particular way, and their ordering
+    /// may change at any point.
+    ///
+    /// WARNING: When using [`Self::get_role_member`] and
+    /// [`Self::get_role_member_count`], make sure you perform all queries on
+    /// the same block.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `index` - Index of the member in the role's member list.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::OutOfBoundsIndex`] - If `index` is greater than or equal to
+    ///   the number of members of `role`.
+    fn get_role_member(
+        &self,
+        role: B256,
+        index: U256,
+    ) -> Result<Address, Self::Error>;
+
+    /// Returns the number of accounts that have `role`. Can be used together
+    /// with [`Self::get_role_member`] to enumerate all bearers of a role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role` - The role identifier.
+    fn get_role_member_count(&self, role: B256) -> U256;
+
+    /// Returns all the accounts that have `role`.
+    ///
+    /// WARNING: This operation will copy the entire storage to memory, which
+    /// can be quite expensive. It is designed to be used by view accessors
+    /// that are queried without any gas fees.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role` - The role identifier.
+    fn get_role_members(&self, role: B256) -> Vec<Address>;
+}
+
+#[public]
+impl IAccessControlEnumerable for AccessControlEnumerable {
+    type Error = Error;
+
+    fn has_role(&self, role: B256, account: Address) -> bool {
+        self.access_control.has_role(role, account)
+    }
+
+    fn get_role_admin(&self, role: B256) -> B256 {
+        self.access_control.get_role_admin(role)
+    }
+
+    fn grant_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error> {
+        let admin_role = self.access_control.get_role_admin(role);
+        self.access_control.only_role(admin_role)?;
+        self._grant_role(role, account);
+        Ok(())
+    }
+
+    fn revoke_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error> {
+        let admin_role = self.access_control.get_role_admin(role);
+        self.access_control.only_role(admin_role)?;
+        self._revoke_role(role, account);
+        Ok(())
+    }
+
+    fn renounce_role(
+        &mut self,
+        role: B256,
+        confirmation: Address,
+    ) -> Result<(), Self::Error> {
+        if context::msg_sender() != confirmation {
+            return Err(control::Error::BadConfirmation(
+                AccessControlBadConfirmation {},
+            )
+            .into());
+        }
+
+        self._revoke_role(role, confirmation);
+        Ok(())
+    }
+
+    fn get_role_member(
+        &self,
+        role: B256,
+        index: U256,
+    ) -> Result<Address, Self::Error> {
+        self.role_members.getter(role).at(index).ok_or(Error::OutOfBoundsIndex(
+            AccessControlEnumerableOutOfBoundsIndex { role, index },
+        ))
+    }
+
+    fn get_role_member_count(&self, role: B256) -> U256 {
+        self.role_members.getter(role).length()
+    }
+
+    fn get_role_members(&self, role: B256) -> Vec<Address> {
+        self.role_members.getter(role).values()
+    }
+}
+
+impl AccessControlEnumerable {
+    /// Attempts to grant `role` to `account` and returns a boolean indicating
+    /// if `role` was granted. Tracks `account` as a member of `role` when it
+    /// was.
+    ///
+    /// Internal function without access restriction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account which will be granted the role.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleGranted`].
+    pub fn _grant_role(&mut self, role: B256, account: Address) -> bool {
+        let granted = self.access_control._grant_role(role, account);
+        if granted {
+            self.role_members.setter(role).add(account);
+        }
+        granted
+    }
+
+    /// Attempts to revoke `role` from `account` and returns a boolean
+    /// indicating if `role` was revoked. Stops tracking `account` as a member
+    /// of `role` when it was.
+    ///
+    /// Internal function without access restriction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account which will be revoked the role.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleRevoked`].
+    pub fn _revoke_role(&mut self, role: B256, account: Address) -> bool {
+        let revoked = self.access_control._revoke_role(role, account);
+        if revoked {
+            self.role_members.setter(role).remove(account);
+        }
+        revoked
+    }
+}
+
+impl IErc165 for AccessControlEnumerable {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IAccessControlEnumerable>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || <AccessControl as IAccessControl>::INTERFACE_ID
+                == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{Address, U256};
+    use alloy_sol_types::SolCall;
+    use motsu::prelude::*;
+    use stylus_sdk::{abi::Router, prelude::*};
+
+    use super::{
+        AccessControlEnumerable, AccessControlEnumerableOutOfBoundsIndex,
+        Error, IAccessControlEnumerable,
+    };
+    use crate::{
+        access::control::{self, AccessControl, IAccessControl},
+        utils::introspection::erc165::IErc165,
+    };
+
+    const ROLE: [u8; 32] = alloy_primitives::hex!(
+        "ed9ea7bc2a13bc59432ab07436e7f7f5450f82d4b48c401bed177bfaf36b1873"
+    );
+    const DEFAULT_ADMIN_ROLE: [u8; 32] = AccessControl::DEFAULT_ADMIN_ROLE;
+
+    unsafe impl TopLevelStorage for AccessControlEnumerable {}
+
+    /// Contract inheriting the extension's public functions.
+    #[storage]
+    struct AccessControlEnumerableMock {
+        #[borrow]
+        enumerable: AccessControlEnumerable,
+    }
+
+    unsafe impl TopLevelStorage for AccessControlEnumerableMock {}
+
+    #[public]
+    #[inherit(AccessControlEnumerable)]
+    impl AccessControlEnumerableMock {}
+
+    mod abi {
+        #![allow(missing_docs)]
+
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function grantRole(bytes32 role, address account) external;
+        }
+    }
+
+    #[motsu::test]
+    fn role_has_no_members_by_default(
+        contract: Contract<AccessControlEnumerable>,
+        alice: Address,
+    ) {
+        let contract = contract.sender(alice);
+        assert_eq!(contract.get_role_member_count(ROLE.into()), U256::ZERO);
+        assert!(contract.get_role_members(ROLE.into()).is_empty());
+    }
+
+    #[motsu::test]
+    fn grant_role_tracks_member(
+        contract: Contract<AccessControlEnumerable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        assert!(contract.sender(alice)._grant_role(ROLE.into(), alice));
+        assert!(contract.sender(alice)._grant_role(ROLE.into(), bob));
+        // Granting a role twice doesn't duplicate the member.
+        assert!(!contract.sender(alice)._grant_role(ROLE.into(), bob));
+
+        let contract = contract.sender(alice);
+        assert!(contract.has_role(ROLE.into(), bob));
+        assert_eq!(contract.get_role_member_count(ROLE.into()), U256::from(2));
+        assert_eq!(
+            contract
+                .get_role_member(ROLE.into(), U256::ZERO)
+                .expect("should return a member"),
+            alice
+        );
+        assert_eq!(
+            contract
+                .get_role_member(ROLE.into(), U256::from(1))
+                .expect("should return a member"),
+            bob
+        );
+        assert_eq!(contract.get_role_members(ROLE.into()), vec![alice, bob]);
+    }
+
+    #[motsu::test]
+    fn revoke_role_untracks_member(
+        contract: Contract<AccessControlEnumerable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._grant_role(ROLE.into(), alice);
+        contract.sender(alice)._grant_role(ROLE.into(), bob);
+
+        assert!(contract.sender(alice)._revoke_role(ROLE.into(), alice));
+        // Revoking a role twice is a no-op.
+        assert!(!contract.sender(alice)._revoke_role(ROLE.into(), alice));
+
+        let contract = contract.sender(alice);
+        assert!(!contract.has_role(ROLE.into(), alice));
+        assert_eq!(contract.get_role_member_count(ROLE.into()), U256::from(1));
+        assert_eq!(contract.get_role_members(ROLE.into()), vec![bob]);
+    }
+
+    #[motsu::test]
+    fn admin_grants_and_revokes_tracked_role(
+        contract: Contract<AccessControlEnumerable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._grant_role(DEFAULT_ADMIN_ROLE.into(), alice);
+
+        contract
+            .sender(alice)
+            .grant_role(ROLE.into(), bob)
+            .motsu_expect("should grant role");
+        assert_eq!(
+            contract.sender(alice).get_role_members(ROLE.into()),
+            vec![bob]
+        );
+
+        contract
+            .sender(alice)
+            .revoke_role(ROLE.into(), bob)
+            .motsu_expect("should revoke role");
+        assert!(!contract.sender(alice).has_role(ROLE.into(), bob));
+        assert!(contract
+            .sender(alice)
+            .get_role_members(ROLE.into())
+            .is_empty());
+    }
+
+    #[motsu::test]
+    fn public_grant_role_tracks_member(
+        contract: Contract<AccessControlEnumerableMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            .enumerable
+            ._grant_role(DEFAULT_ADMIN_ROLE.into(), alice);
+
+        let calldata =
+            abi::grantRoleCall { role: ROLE.into(), account: bob }.abi_encode();
+        contract.init(alice, |contract| {
+            let (selector, input) = calldata.split_at(4);
+            let selector = u32::from_be_bytes(
+                selector.try_into().expect("should be a selector"),
+            );
+            <AccessControlEnumerableMock as Router<_>>::route(
+                contract, selector, input,
+            )
+            .expect("should route grantRole")
+            .expect("should grant role");
+        });
+
+        let contract = contract.sender(alice);
+        assert!(contract.enumerable.has_role(ROLE.into(), bob));
+        assert_eq!(
+            contract.enumerable.get_role_members(ROLE.into()),
+            vec![bob]
+        );
+    }
+
+    #[motsu::test]
+    fn non_admin_cannot_grant_or_revoke_role(
+        contract: Contract<AccessControlEnumerable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let err = contract
+            .sender(bob)
+            .grant_role(ROLE.into(), bob)
+            .motsu_expect_err("should not grant role");
+        assert!(matches!(
+            err,
+            Error::AccessControl(control::Error::UnauthorizedAccount(_))
+        ));
+
+        contract.sender(alice)._grant_role(ROLE.into(), alice);
+        let err = contract
+            .sender(bob)
+            .revoke_role(ROLE.into(), alice)
+            .motsu_expect_err("should not revoke role");
+        assert!(matches!(
+            err,
+            Error::AccessControl(control::Error::UnauthorizedAccount(_))
+        ));
+        assert_eq!(
+            contract.sender(alice).get_role_members(ROLE.into()),
+            vec![alice]
+        );
+    }
+
+    #[motsu::test]
+    fn renounce_role_untracks_member(
+        contract: Contract<AccessControlEnumerable>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._grant_role(ROLE.into(), alice);
+        contract.sender(alice)._grant_role(ROLE.into(), bob);
+
+        let err = contract
+            .sender(bob)
+            .renounce_role(ROLE.into(), alice)
+            .motsu_expect_err("should not renounce role of another account");
+        assert!(matches!(
+            err,
+            Error::AccessControl(control::Error::BadConfirmation(_))
+        ));
+
+        contract
+            .sender(alice)
+            .renounce_role(ROLE.into(), alice)
+            .motsu_expect("should renounce role");
+        assert!(!contract.sender(alice).has_role(ROLE.into(), alice));
+        assert_eq!(
+            contract.sender(alice).get_role_members(ROLE.into()),
+            vec![bob]
+        );
+    }
+
+    #[motsu::test]
+    fn get_role_member_reverts_when_out_of_bounds(
+        contract: Contract<AccessControlEnumerable>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._grant_role(ROLE.into(), alice);
+
+        let index = U256::from(1);
+        let err = contract
+            .sender(alice)
+            .get_role_member(ROLE.into(), index)
+            .motsu_expect_err("should return Error::OutOfBoundsIndex");
+
+        assert!(matches!(
+            err,
+            Error::OutOfBoundsIndex(AccessControlEnumerableOutOfBoundsIndex {
+                role,
+                index: err_index,
+            }) if role == ROLE && err_index == index
+        ));
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(AccessControlEnumerable::supports_interface(
+            <AccessControlEnumerable as IAccessControlEnumerable>::INTERFACE_ID
+                .into()
+        ));
+        assert!(AccessControlEnumerable::supports_interface(
+            <AccessControl as IAccessControl>::INTERFACE_ID.into()
+        ));
+        assert!(AccessControlEnumerable::supports_interface(
+            <AccessControlEnumerable as IErc165>::INTERFACE_ID.into()
+        ));
+
+        let fake_interface_id = 0x12345678u32;
+        assert!(!AccessControlEnumerable::supports_interface(
+            fake_interface_id.into()
+        ));
+    }
+}
@@ -0,0 +1,8 @@
+//! Common extensions to the [`super::AccessControl`] contract.
+pub mod default_admin_rules;
+pub mod enumerable;
+
+pub use default_admin_rules::{
+    AccessControlDefaultAdminRules, IAccessControlDefaultAdminRules,
+};
+pub use enumerable::{AccessControlEnumerable, IAccessControlEnumerable};
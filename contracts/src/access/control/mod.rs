@@ -2,7 +2,8 @@
 //! mechanisms.
 //!
 //! This is a lightweight version that doesn't allow enumerating role members
-//! except through off-chain means by accessing the contract event logs.
+//! except through off-chain means by accessing the contract event logs. See
+//! [`extensions::AccessControlEnumerable`] for on-chain enumeration.
 //!
 //! Roles are referred to by their `bytes32` identifier. These should be exposed
 //! in the external API and be unique. The best way to achieve this is by using
@@ -46,12 +47,17 @@ use openzeppelin_stylus_proc::interface_id;
 pub use sol::*;
 use stylus_sdk::{
     call::MethodError,
-    evm, msg,
+    evm,
     prelude::*,
     storage::{StorageBool, StorageFixedBytes, StorageMap},
 };
 
-use crate::utils::introspection::erc165::{Erc165, IErc165};
+use crate::utils::{
+    context,
+    introspection::erc165::{Erc165, IErc165},
+};
+
+pub mod extensions;
 
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod sol {
@@ -150,7 +156,7 @@ pub trait IAccessControl {
     /// * `account` - The account to check for membership.
     fn has_role(&self, role: B256, account: Address) -> bool;
 
-    /// Checks if [`msg::sender`] has been granted `role`.
+    /// Checks if [`context::msg_sender`] has been granted `role`.
     ///
     /// # Arguments
     ///
@@ -159,8 +165,8 @@ pub trait IAccessControl {
     ///
     /// # Errors
     ///
-    /// * [`Error::UnauthorizedAccount`] - If [`msg::sender`] has not been
-    ///   granted `role`.
+    /// * [`Error::UnauthorizedAccount`] - If [`context::msg_sender`] has not
+    ///   been granted `role`.
     fn only_role(&self, role: B256) -> Result<(), Self::Error>;
 
     /// Returns the admin role that controls `role`. See
@@ -187,8 +193,8 @@ pub trait IAccessControl {
     ///
     /// # Errors
     ///
-    /// * [`Error::UnauthorizedAccount`] - If [`msg::sender`] has not been
-    ///   granted `role`.
+    /// * [`Error::UnauthorizedAccount`] - If [`context::msg_sender`] has not
+    ///   been granted `role`.
     ///
     /// # Events
     ///
@@ -211,8 +217,8 @@ pub trait IAccessControl {
     ///
     /// # Errors
     ///
-    /// * [`Error::UnauthorizedAccount`] - If [`msg::sender`] has not been
-    ///   granted `role`.
+    /// * [`Error::UnauthorizedAccount`] - If [`context::msg_sender`] has not
+    ///   been granted `role`.
     ///
     /// # Events
     ///
@@ -238,7 +244,7 @@ pub trait IAccessControl {
     ///
     /// # Errors
     ///
-    /// * [`Error::BadConfirmation`]  - If [`msg::sender`] is not the
+    /// * [`Error::BadConfirmation`]  - If [`context::msg_sender`] is not the
     ///   `confirmation` address.
     ///
     /// # Events
@@ -260,7 +266,7 @@ impl IAccessControl for AccessControl {
     }
 
     fn only_role(&self, role: B256) -> Result<(), Self::Error> {
-        self._check_role(role, msg::sender())
+        self._check_role(role, context::msg_sender())
     }
 
     fn get_role_admin(&self, role: B256) -> B256 {
@@ -294,7 +300,7 @@ impl IAccessControl for AccessControl {
         role: B256,
         confirmation: Address,
     ) -> Result<(), Self::Error> {
-        if msg::sender() != confirmation {
+        if context::msg_sender() != confirmation {
             return Err(Error::BadConfirmation(
                 AccessControlBadConfirmation {},
             ));
@@ -340,8 +346,8 @@ impl AccessControl {
     ///
     /// # Errors
     ///
-    /// * [`Error::UnauthorizedAccount`] - If [`msg::sender`] has not been
-    ///   granted `role`.
+    /// * [`Error::UnauthorizedAccount`] - If [`context::msg_sender`] has not
+    ///   been granted `role`.
     pub fn _check_role(
         &self,
         role: B256,
@@ -375,7 +381,11 @@ impl AccessControl {
             false
         } else {
             self.roles.setter(role).has_role.insert(account, true);
-            evm::log(RoleGranted { role, account, sender: msg::sender() });
+            evm::log(RoleGranted {
+                role,
+                account,
+                sender: context::msg_sender(),
+            });
             true
         }
     }
@@ -397,7 +407,11 @@ impl AccessControl {
     pub fn _revoke_role(&mut self, role: B256, account: Address) -> bool {
         if self.has_role(role, account) {
             self.roles.setter(role).has_role.insert(account, false);
-            evm::log(RoleRevoked { role, account, sender: msg::sender() });
+            evm::log(RoleRevoked {
+                role,
+                account,
+                sender: context::msg_sender(),
+            });
             true
         } else {
             false
@@ -0,0 +1,24 @@
+//! Solidity Interfaces of the [`super::AccessManager`] and
+//! [`super::AccessManaged`] contracts, used for cross-contract calls.
+pub use manager::*;
+
+mod manager {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::prelude::sol_interface;
+    sol_interface! {
+        interface AccessManagerInterface {
+            function canCall(address caller, address target, bytes4 selector) external view returns (bool allowed, uint32 delay);
+            function consumeScheduledOp(address caller, bytes calldata data) external;
+        }
+
+        interface AccessManagedInterface {
+            function authority() external view returns (address);
+            function setAuthority(address) external;
+            function isConsumingScheduledOp() external view returns (bytes4);
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Contract module that makes available a [`AccessManaged::restricted`] check
+//! to restrict access to functions of a contract.
+//!
+//! This contract module is connected to an [`super::AccessManager`] instance
+//! (the authority), which decides which accounts can call each restricted
+//! function, and with which delay. The authority is set at construction time
+//! and can later be updated by the authority itself through
+//! [`IAccessManaged::set_authority`].
+//!
+//! IMPORTANT: The [`AccessManaged::restricted`] check should never be used on
+//! internal functions, since the selector it is given should always
+//! correspond to a function of the external interface of the contract.
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{Address, FixedBytes};
+use alloy_sol_types::SolCall;
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    call::{self, Call, MethodError},
+    contract, evm, msg,
+    prelude::*,
+    storage::{StorageAddress, StorageBool},
+};
+
+use super::interface::AccessManagerInterface;
+use crate::utils::introspection::erc165::{Erc165, IErc165};
+
+/// Selector of [`IAccessManaged::is_consuming_scheduled_op`], returned by
+/// it while a scheduled operation is being consumed.
+pub const IS_CONSUMING_SCHEDULED_OP_SELECTOR: FixedBytes<4> =
+    FixedBytes::new(abi::isConsumingScheduledOpCall::SELECTOR);
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Authority that manages this contract was updated.
+        ///
+        /// * `authority` - The new authority.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event AuthorityUpdated(address authority);
+    }
+
+    sol! {
+        /// The caller is not allowed to call the function.
+        ///
+        /// * `caller` - Account that attempted the call.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagedUnauthorized(address caller);
+
+        /// The new authority is not a valid contract.
+        ///
+        /// * `authority` - The invalid authority.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagedInvalidAuthority(address authority);
+    }
+}
+
+/// Function signatures used to identify calls to an [`AccessManaged`]
+/// contract.
+mod abi {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloy_sol_macro::sol;
+
+    sol! {
+        function isConsumingScheduledOp();
+    }
+}
+
+/// An error that occurred in the implementation of an [`AccessManaged`]
+/// contract.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// The caller is not allowed to call the function.
+    Unauthorized(AccessManagedUnauthorized),
+    /// The new authority is not a valid contract.
+    InvalidAuthority(AccessManagedInvalidAuthority),
+    /// A call to the authority failed.
+    FailedCall(call::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`AccessManaged`] contract.
+#[storage]
+pub struct AccessManaged {
+    /// The authority that manages this contract.
+    pub(crate) authority: StorageAddress,
+    /// Whether a scheduled operation is being consumed.
+    pub(crate) consuming_schedule: StorageBool,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for AccessManaged {}
+
+/// Interface for an [`AccessManaged`] contract.
+#[interface_id]
+pub trait IAccessManaged {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Returns the current authority.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn authority(&self) -> Address;
+
+    /// Transfers control to a new authority. The caller must be the current
+    /// authority.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_authority` - The new authority.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is not the current authority.
+    /// * [`Error::InvalidAuthority`] - If `new_authority` has no code.
+    ///
+    /// # Events
+    ///
+    /// * [`AuthorityUpdated`].
+    fn set_authority(
+        &mut self,
+        new_authority: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Returns the selector of this function while a scheduled operation is
+    /// being consumed, or zero otherwise.
+    ///
+    /// This is used by the authority to check that a call to
+    /// [`super::IAccessManager::consume_scheduled_op`] comes from a restricted
+    /// function of this contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn is_consuming_scheduled_op(&self) -> FixedBytes<4>;
+}
+
+#[public]
+impl IAccessManaged for AccessManaged {
+    type Error = Error;
+
+    fn authority(&self) -> Address {
+        self.authority.get()
+    }
+
+    fn set_authority(
+        &mut self,
+        new_authority: Address,
+    ) -> Result<(), Self::Error> {
+        let caller = msg::sender();
+        if caller != self.authority() {
+            return Err(Error::Unauthorized(AccessManagedUnauthorized {
+                caller,
+            }));
+        }
+        if !new_authority.has_code() {
+            return Err(Error::InvalidAuthority(
+                AccessManagedInvalidAuthority { authority: new_authority },
+            ));
+        }
+        self._set_authority(new_authority);
+        Ok(())
+    }
+
+    fn is_consuming_scheduled_op(&self) -> FixedBytes<4> {
+        if self.consuming_schedule.get() {
+            IS_CONSUMING_SCHEDULED_OP_SELECTOR
+        } else {
+            FixedBytes::ZERO
+        }
+    }
+}
+
+impl AccessManaged {
+    /// Restricts access to a function as defined by the connected authority
+    /// for this contract and the function's selector.
+    ///
+    /// `data` must be the calldata of the function being restricted, that is
+    /// its selector followed by its ABI-encoded arguments, so that scheduled
+    /// operations can be matched against it.
+    ///
+    /// WARNING: Avoid passing the calldata of a different function than the
+    /// one being called, since it would make the function subject to the
+    /// permissions of the other one.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `data` - Calldata of the restricted function call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is not allowed to call the
+    ///   function.
+    /// * [`Error::FailedCall`] - If a call to the authority failed, including
+    ///   when a scheduled operation could not be consumed.
+    pub fn restricted(&mut self, data: &[u8]) -> Result<(), Error> {
+        self._check_can_call(msg::sender(), data)
+    }
+
+    /// Transfers control to a new authority. Internal function with no access
+    /// restriction. Allows bypassing the permissions set by the current
+    /// authority.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_authority` - The new authority.
+    ///
+    /// # Events
+    ///
+    /// * [`AuthorityUpdated`].
+    pub fn _set_authority(&mut self, new_authority: Address) {
+        self.authority.set(new_authority);
+        evm::log(AuthorityUpdated { authority: new_authority });
+    }
+
+    /// Reverts if the `caller` is not allowed to call the function identified
+    /// by the selector of `data`. If the call is delayed, consumes the
+    /// matching scheduled operation on the authority.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `caller` - Account calling the function.
+    /// * `data` - Calldata of the restricted function call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is not allowed to call the
+    ///   function.
+    /// * [`Error::FailedCall`] - If a call to the authority failed.
+    pub fn _check_can_call(
+        &mut self,
+        caller: Address,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        let mut selector = [0u8; 4];
+        let len = data.len().min(4);
+        selector[..len].copy_from_slice(&data[..len]);
+
+        let authority = AccessManagerInterface::new(self.authority());
+        let (immediate, delay) = authority.can_call(
+            Call::new_in(self),
+            caller,
+            contract::address(),
+            FixedBytes::new(selector),
+        )?;
+
+        if !immediate {
+            if delay > 0 {
+                self.consuming_schedule.set(true);
+                authority.consume_scheduled_op(
+                    Call::new_in(self),
+                    caller,
+                    alloy_primitives::Bytes::copy_from_slice(data),
+                )?;
+                self.consuming_schedule.set(false);
+            } else {
+                return Err(Error::Unauthorized(AccessManagedUnauthorized {
+                    caller,
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl IErc165 for AccessManaged {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IAccessManaged>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
@@ -0,0 +1,2773 @@
+//! Central contract to manage the permissions of a system.
+//!
+//! [`AccessManager`] is a central contract to store the permissions of a
+//! system.
+//!
+//! A smart contract under the control of an [`AccessManager`] instance is
+//! known as a target, and will compose the [`AccessManaged`] contract, be
+//! connected to this contract as its manager and implement the
+//! [`AccessManaged::restricted`] check on a set of functions selected to be
+//! permissioned. Note that any function without this check will not be
+//! managed by the [`AccessManager`].
+//!
+//! The restricted functions of the target can be called by accounts that have
+//! been granted the role that the function is assigned to. Roles are `u64`
+//! identifiers, with [`ADMIN_ROLE`] and [`PUBLIC_ROLE`] having special
+//! meaning:
+//!
+//! * [`ADMIN_ROLE`] - Members can manage roles and targets. It is granted to
+//!   the initial admin at deployment, and is its own admin.
+//! * [`PUBLIC_ROLE`] - All accounts are considered members.
+//!
+//! Each role has:
+//!
+//! * An admin role, whose members can grant and revoke the role.
+//! * A guardian role, whose members can cancel operations scheduled by the role
+//!   members.
+//! * A grant delay, which is the time between an account being granted the role
+//!   and the role becoming effective.
+//!
+//! Each member of a role can additionally have an execution delay, which
+//! forces them to [`IAccessManager::schedule`] their calls and wait before
+//! they can [`IAccessManager::execute`] them.
+//!
+//! NOTE: This contract is also restricted: its own functions can be called
+//! only by [`ADMIN_ROLE`] members (or the admin of the role they manage), and
+//! the admin delay configured for a target applies to the operations that
+//! change that target's configuration.
+use alloc::{string::String, vec, vec::Vec};
+
+use alloy_primitives::{
+    aliases::{U112, U48},
+    Address, FixedBytes, B256, U256, U32, U64,
+};
+use alloy_sol_types::{SolCall, SolType};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    call::{self, Call, MethodError, RawCall},
+    contract, evm, msg,
+    prelude::*,
+    storage::{
+        StorageBool, StorageFixedBytes, StorageMap, StorageU32, StorageU64,
+        StorageUint,
+    },
+};
+
+use crate::utils::{
+    introspection::erc165::{Erc165, IErc165},
+    reentrant_call_handler::ReentrantCallHandler,
+    types::time::{self, Delay},
+};
+
+pub mod interface;
+pub mod managed;
+
+pub use managed::{AccessManaged, IAccessManaged};
+
+use self::interface::AccessManagedInterface;
+
+/// The identifier of the admin role. Required to perform most configuration
+/// operations including other roles' management and target restrictions.
+pub const ADMIN_ROLE: u64 = u64::MIN;
+
+/// The identifier of the public role. Automatically granted to all
+/// addresses with no delay.
+pub const PUBLIC_ROLE: u64 = u64::MAX;
+
+/// Time after which a scheduled operation becomes invalid, one week.
+const EXPIRATION: u32 = 7 * 24 * 60 * 60;
+
+/// Minimum setback for all delay updates, with the exception of execution
+/// delays, five days.
+const MIN_SETBACK: u32 = 5 * 24 * 60 * 60;
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    pub(crate) type OperationTuple = sol! {
+        tuple(address, address, bytes)
+    };
+
+    pub(crate) type ExecutionIdTuple = sol! {
+        tuple(address, bytes4)
+    };
+
+    sol! {
+        /// A delayed operation was scheduled.
+        ///
+        /// * `operation_id` - Id of the operation.
+        /// * `nonce` - Nonce of the operation.
+        /// * `schedule` - Timepoint at which the operation can be executed.
+        /// * `caller` - Account that scheduled the operation.
+        /// * `target` - Target of the call.
+        /// * `data` - Calldata of the call.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event OperationScheduled(
+            bytes32 indexed operation_id,
+            uint32 indexed nonce,
+            uint48 schedule,
+            address caller,
+            address target,
+            bytes data
+        );
+
+        /// A scheduled operation was executed.
+        ///
+        /// * `operation_id` - Id of the operation.
+        /// * `nonce` - Nonce of the operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event OperationExecuted(bytes32 indexed operation_id, uint32 indexed nonce);
+
+        /// A scheduled operation was canceled.
+        ///
+        /// * `operation_id` - Id of the operation.
+        /// * `nonce` - Nonce of the operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event OperationCanceled(bytes32 indexed operation_id, uint32 indexed nonce);
+
+        /// Informational labelling for a role id.
+        ///
+        /// * `role_id` - The role identifier.
+        /// * `label` - The label of the role.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event RoleLabel(uint64 indexed role_id, string label);
+
+        /// Emitted when `account` is granted `role_id`.
+        ///
+        /// * `role_id` - The role identifier.
+        /// * `account` - The account granted the role.
+        /// * `delay` - Execution delay of the account.
+        /// * `since` - Timepoint at which the membership becomes effective,
+        ///   or the execution delay update takes effect for existing members.
+        /// * `new_member` - Whether `account` was not a member before.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event RoleGranted(
+            uint64 indexed role_id,
+            address indexed account,
+            uint32 delay,
+            uint48 since,
+            bool new_member
+        );
+
+        /// Emitted when `account` membership or `role_id` is revoked.
+        /// Unlike granting, revoking is instantaneous.
+        ///
+        /// * `role_id` - The role identifier.
+        /// * `account` - The account revoked the role.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event RoleRevoked(uint64 indexed role_id, address indexed account);
+
+        /// Role acting as admin over a given `role_id` is updated.
+        ///
+        /// * `role_id` - The role identifier.
+        /// * `admin` - The new admin role.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event RoleAdminChanged(uint64 indexed role_id, uint64 indexed admin);
+
+        /// Role acting as guardian over a given `role_id` is updated.
+        ///
+        /// * `role_id` - The role identifier.
+        /// * `guardian` - The new guardian role.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event RoleGuardianChanged(uint64 indexed role_id, uint64 indexed guardian);
+
+        /// Grant delay for a given `role_id` will be updated to `delay` when
+        /// `since` is reached.
+        ///
+        /// * `role_id` - The role identifier.
+        /// * `delay` - The new grant delay.
+        /// * `since` - Timepoint at which the new delay takes effect.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event RoleGrantDelayChanged(uint64 indexed role_id, uint32 delay, uint48 since);
+
+        /// Target mode is updated (true = closed, false = open).
+        ///
+        /// * `target` - The target contract.
+        /// * `closed` - Whether the target is closed.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event TargetClosed(address indexed target, bool closed);
+
+        /// Role required to invoke `selector` on `target` is updated to
+        /// `role_id`.
+        ///
+        /// * `target` - The target contract.
+        /// * `selector` - The function selector.
+        /// * `role_id` - The role required to call the function.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event TargetFunctionRoleUpdated(
+            address indexed target,
+            bytes4 selector,
+            uint64 indexed role_id
+        );
+
+        /// Admin delay for a given `target` will be updated to `delay` when
+        /// `since` is reached.
+        ///
+        /// * `target` - The target contract.
+        /// * `delay` - The new admin delay.
+        /// * `since` - Timepoint at which the new delay takes effect.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event TargetAdminDelayUpdated(address indexed target, uint32 delay, uint48 since);
+    }
+
+    sol! {
+        /// The operation is already scheduled.
+        ///
+        /// * `operation_id` - Id of the operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagerAlreadyScheduled(bytes32 operation_id);
+
+        /// The operation is not scheduled.
+        ///
+        /// * `operation_id` - Id of the operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagerNotScheduled(bytes32 operation_id);
+
+        /// The operation is not ready yet.
+        ///
+        /// * `operation_id` - Id of the operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagerNotReady(bytes32 operation_id);
+
+        /// The operation has expired.
+        ///
+        /// * `operation_id` - Id of the operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagerExpired(bytes32 operation_id);
+
+        /// The role can't be modified.
+        ///
+        /// * `role_id` - The role identifier.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagerLockedRole(uint64 role_id);
+
+        /// The caller of a function is not the expected one.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagerBadConfirmation();
+
+        /// The account is missing a role.
+        ///
+        /// * `msg_sender` - Account that was found to not be authorized.
+        /// * `role_id` - The missing role.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagerUnauthorizedAccount(address msg_sender, uint64 role_id);
+
+        /// The caller is not allowed to call `selector` on `target`.
+        ///
+        /// * `caller` - Account that attempted the call.
+        /// * `target` - Target of the call.
+        /// * `selector` - Function selector of the call.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagerUnauthorizedCall(address caller, address target, bytes4 selector);
+
+        /// The target is not consuming a scheduled operation.
+        ///
+        /// * `target` - Target that attempted the consumption.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagerUnauthorizedConsume(address target);
+
+        /// The sender is not allowed to cancel the operation.
+        ///
+        /// * `msg_sender` - Account that attempted the cancellation.
+        /// * `caller` - Account that scheduled the operation.
+        /// * `target` - Target of the operation.
+        /// * `selector` - Function selector of the operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccessManagerUnauthorizedCancel(
+            address msg_sender,
+            address caller,
+            address target,
+            bytes4 selector
+        );
+    }
+}
+
+/// Calldata of the restricted [`AccessManager`] functions, used to check the
+/// permissions of the caller.
+mod abi {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloy_sol_macro::sol;
+
+    sol! {
+        function labelRole(uint64 role_id, string label);
+        function grantRole(uint64 role_id, address account, uint32 execution_delay);
+        function revokeRole(uint64 role_id, address account);
+        function setRoleAdmin(uint64 role_id, uint64 admin);
+        function setRoleGuardian(uint64 role_id, uint64 guardian);
+        function setGrantDelay(uint64 role_id, uint32 new_delay);
+        function setTargetFunctionRole(address target, bytes4[] selectors, uint64 role_id);
+        function setTargetAdminDelay(address target, uint32 new_delay);
+        function setTargetClosed(address target, bool closed);
+        function updateAuthority(address target, address new_authority);
+    }
+}
+
+/// An error that occurred in the implementation of an [`AccessManager`]
+/// contract.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// The operation is already scheduled.
+    AlreadyScheduled(AccessManagerAlreadyScheduled),
+    /// The operation is not scheduled.
+    NotScheduled(AccessManagerNotScheduled),
+    /// The operation is not ready yet.
+    NotReady(AccessManagerNotReady),
+    /// The operation has expired.
+    Expired(AccessManagerExpired),
+    /// The role can't be modified.
+    LockedRole(AccessManagerLockedRole),
+    /// The caller of a function is not the expected one.
+    BadConfirmation(AccessManagerBadConfirmation),
+    /// The account is missing a role.
+    UnauthorizedAccount(AccessManagerUnauthorizedAccount),
+    /// The caller is not allowed to call a function on a target.
+    UnauthorizedCall(AccessManagerUnauthorizedCall),
+    /// The target is not consuming a scheduled operation.
+    UnauthorizedConsume(AccessManagerUnauthorizedConsume),
+    /// The sender is not allowed to cancel the operation.
+    UnauthorizedCancel(AccessManagerUnauthorizedCancel),
+    /// A call to a target failed.
+    FailedCall(call::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of a [`TargetConfig`] contract.
+///
+/// Stores the permissions of a target contract.
+#[storage]
+pub struct TargetConfig {
+    /// Function selector -> Role allowed to call it.
+    pub(crate) allowed_roles: StorageMap<FixedBytes<4>, StorageU64>,
+    /// Delay for the operations that change the target's configuration.
+    pub(crate) admin_delay: StorageUint<112, 2>,
+    /// Whether the target is closed.
+    pub(crate) closed: StorageBool,
+}
+
+/// State of an [`Access`] contract.
+///
+/// Stores the membership of an account to a role.
+#[storage]
+pub struct Access {
+    /// Timepoint at which the user gets the permission. If this is either
+    /// 0 or in the future, then the role permission is not available.
+    pub(crate) since: StorageUint<48, 1>,
+    /// Delay for execution. Only applies to restricted calls.
+    pub(crate) delay: StorageUint<112, 2>,
+}
+
+/// State of a [`Role`] contract.
+///
+/// Stores information about a specific role.
+#[storage]
+pub struct Role {
+    /// Members of the role.
+    pub(crate) members: StorageMap<Address, Access>,
+    /// Admin who can grant or revoke permissions.
+    pub(crate) admin: StorageU64,
+    /// Guardian who can cancel operations targeting functions that need this
+    /// role.
+    pub(crate) guardian: StorageU64,
+    /// Delay in which the role takes effect after being granted.
+    pub(crate) grant_delay: StorageUint<112, 2>,
+}
+
+/// State of a [`Schedule`] contract.
+///
+/// Stores the timepoint and nonce of a scheduled operation.
+#[storage]
+pub struct Schedule {
+    /// Moment at which the operation can be executed.
+    pub(crate) timepoint: StorageUint<48, 1>,
+    /// Operation nonce to allow third-party contracts to identify the
+    /// operation.
+    pub(crate) nonce: StorageU32,
+}
+
+/// State of an [`AccessManager`] contract.
+#[storage]
+pub struct AccessManager {
+    /// Target address -> Target configuration.
+    pub(crate) targets: StorageMap<Address, TargetConfig>,
+    /// Role identifier -> Role information.
+    pub(crate) roles: StorageMap<u64, Role>,
+    /// Operation id -> Schedule of the operation.
+    pub(crate) schedules: StorageMap<B256, Schedule>,
+    /// Identifier of the call being executed through
+    /// [`IAccessManager::execute`], used to authorize the call when it
+    /// reaches a target.
+    pub(crate) execution_id: StorageFixedBytes<32>,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for AccessManager {}
+
+/// Interface for an [`AccessManager`] contract.
+#[interface_id]
+pub trait IAccessManager {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Checks if an address (`caller`) is authorised to call a given function
+    /// on a given contract directly (with no restriction). Additionally, it
+    /// returns the delay needed to perform the call indirectly through the
+    /// [`Self::schedule`] & [`Self::execute`] workflow.
+    ///
+    /// This function is usually called by the targeted contract to control
+    /// immediate execution of restricted functions. Therefore we only return
+    /// true if the call can be performed without any delay. If the call is
+    /// subject to a previously set delay (not zero), then the function should
+    /// return false and the caller should schedule the operation for future
+    /// execution.
+    ///
+    /// If `allowed` is true, the delay can be disregarded and the operation
+    /// can be immediately executed, otherwise the operation can be executed
+    /// if and only if delay is greater than 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `caller` - Account calling the function.
+    /// * `target` - Target contract of the call.
+    /// * `selector` - Function selector of the call.
+    fn can_call(
+        &self,
+        caller: Address,
+        target: Address,
+        selector: FixedBytes<4>,
+    ) -> (bool, u32);
+
+    /// Expiration delay for scheduled proposals. Defaults to 1 week.
+    ///
+    /// IMPORTANT: Avoid overriding the expiration with 0. Otherwise every
+    /// contract proposal will be expired immediately, disabling any
+    /// scheduling usage.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn expiration(&self) -> u32;
+
+    /// Minimum setback for all delay updates, with the exception of execution
+    /// delays. It can be increased without setback (and reset via
+    /// [`Self::revoke_role`] in the case event of an accidental increase).
+    /// Defaults to 5 days.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn min_setback(&self) -> u32;
+
+    /// Get whether the contract is closed disabling any access. Otherwise
+    /// role permissions are applied.
+    ///
+    /// NOTE: When the manager itself is closed, admin functions are still
+    /// accessible to avoid locking the contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `target` - The target contract.
+    fn is_target_closed(&self, target: Address) -> bool;
+
+    /// Get the role required to call a function.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `target` - The target contract.
+    /// * `selector` - The function selector.
+    fn get_target_function_role(
+        &self,
+        target: Address,
+        selector: FixedBytes<4>,
+    ) -> u64;
+
+    /// Get the admin delay for a target contract. Changes to contract
+    /// configuration are subject to this delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `target` - The target contract.
+    fn get_target_admin_delay(&self, target: Address) -> u32;
+
+    /// Get the id of the role that acts as an admin for the given role.
+    ///
+    /// The admin permission is required to grant the role, revoke the role
+    /// and update the execution delay to execute an operation that is
+    /// restricted to this role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role_id` - The role identifier.
+    fn get_role_admin(&self, role_id: u64) -> u64;
+
+    /// Get the role that acts as a guardian for a given role.
+    ///
+    /// The guardian permission allows canceling operations that have been
+    /// scheduled under the role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role_id` - The role identifier.
+    fn get_role_guardian(&self, role_id: u64) -> u64;
+
+    /// Get the role current grant delay.
+    ///
+    /// Its value may change at any point without an event emitted following
+    /// a call to [`Self::set_grant_delay`]. Changes to this value, including
+    /// effect timepoint are notified in advance by the
+    /// [`RoleGrantDelayChanged`] event.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role_id` - The role identifier.
+    fn get_role_grant_delay(&self, role_id: u64) -> u32;
+
+    /// Get the access details for a given account for a given role. These
+    /// details include the timepoint at which membership becomes active, and
+    /// the delay applied to all operation by this user that requires this
+    /// permission level.
+    ///
+    /// Returns `(since, current_delay, pending_delay, effect)`:
+    ///
+    /// * `since` - Timepoint at which the account gained the role.
+    /// * `current_delay` - Execution delay for the account.
+    /// * `pending_delay` - Pending execution delay for the account.
+    /// * `effect` - Timepoint at which the pending execution delay will become
+    ///   active. 0 means no delay update is scheduled.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `account` - The account to query.
+    fn get_access(
+        &self,
+        role_id: u64,
+        account: Address,
+    ) -> (U48, u32, u32, U48);
+
+    /// Check if a given account currently has the permission level
+    /// corresponding to a given role. Note that this permission might be
+    /// associated with an execution delay. [`Self::get_access`] can provide
+    /// more details.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `account` - The account to query.
+    fn has_role(&self, role_id: u64, account: Address) -> (bool, u32);
+
+    /// Give a label to a role, for improved role discoverability by UIs.
+    ///
+    /// Requires the caller to be a global admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `label` - The label of the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not a global admin.
+    /// * [`Error::LockedRole`] - If `role_id` is [`ADMIN_ROLE`] or
+    ///   [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleLabel`].
+    fn label_role(
+        &mut self,
+        role_id: u64,
+        label: String,
+    ) -> Result<(), Self::Error>;
+
+    /// Add `account` to `role_id`, or change its execution delay.
+    ///
+    /// This gives the account the authorization to call any function that is
+    /// restricted to this role. An optional execution delay (in seconds) can
+    /// be set. If that delay is non 0, the user is required to schedule any
+    /// operation that is restricted to members of this role. The user will
+    /// only be able to execute the operation after the delay has passed,
+    /// before it has expired. During this period, admin and guardians can
+    /// cancel the operation (see [`Self::cancel`]).
+    ///
+    /// If the account has already been granted this role, the execution
+    /// delay will be updated. This update is not immediate and follows the
+    /// delay rules. For example, if a user currently has a delay of 3 hours,
+    /// and this is called to reduce that delay to 1 hour, the new delay will
+    /// take some time to take effect, enforcing that any operation executed
+    /// in the 3 hours that follows this update was indeed scheduled before
+    /// this update.
+    ///
+    /// Requires the caller to be an admin for the role (see
+    /// [`Self::get_role_admin`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `account` - The account which will be granted the role.
+    /// * `execution_delay` - Execution delay of the account.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not an admin of the
+    ///   role.
+    /// * [`Error::LockedRole`] - If `role_id` is [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleGranted`].
+    fn grant_role(
+        &mut self,
+        role_id: u64,
+        account: Address,
+        execution_delay: u32,
+    ) -> Result<(), Self::Error>;
+
+    /// Remove an account from a role, with immediate effect. If the account
+    /// does not have the role, this call has no effect.
+    ///
+    /// Requires the caller to be an admin for the role (see
+    /// [`Self::get_role_admin`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `account` - The account which will be revoked the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not an admin of the
+    ///   role.
+    /// * [`Error::LockedRole`] - If `role_id` is [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleRevoked`] - If the account had the role.
+    fn revoke_role(
+        &mut self,
+        role_id: u64,
+        account: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Renounce role permissions for the calling account with immediate
+    /// effect. If the sender is not in the role this call has no effect.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `caller_confirmation` - The account which will be revoked the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::BadConfirmation`] - If [`msg::sender`] is not
+    ///   `caller_confirmation`.
+    /// * [`Error::LockedRole`] - If `role_id` is [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleRevoked`] - If the account had the role.
+    fn renounce_role(
+        &mut self,
+        role_id: u64,
+        caller_confirmation: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Change admin role for a given role.
+    ///
+    /// Requires the caller to be a global admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `admin` - The new admin role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not a global admin.
+    /// * [`Error::LockedRole`] - If `role_id` is [`ADMIN_ROLE`] or
+    ///   [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleAdminChanged`].
+    fn set_role_admin(
+        &mut self,
+        role_id: u64,
+        admin: u64,
+    ) -> Result<(), Self::Error>;
+
+    /// Change guardian role for a given role.
+    ///
+    /// Requires the caller to be a global admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `guardian` - The new guardian role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not a global admin.
+    /// * [`Error::LockedRole`] - If `role_id` is [`ADMIN_ROLE`] or
+    ///   [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleGuardianChanged`].
+    fn set_role_guardian(
+        &mut self,
+        role_id: u64,
+        guardian: u64,
+    ) -> Result<(), Self::Error>;
+
+    /// Update the delay for granting a `role_id`.
+    ///
+    /// Requires the caller to be a global admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `new_delay` - The new grant delay.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not a global admin.
+    /// * [`Error::LockedRole`] - If `role_id` is [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleGrantDelayChanged`].
+    fn set_grant_delay(
+        &mut self,
+        role_id: u64,
+        new_delay: u32,
+    ) -> Result<(), Self::Error>;
+
+    /// Set the role required to call functions identified by the `selectors`
+    /// in the `target` contract.
+    ///
+    /// Requires the caller to be a global admin, and is subject to the
+    /// target's admin delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - The target contract.
+    /// * `selectors` - The function selectors.
+    /// * `role_id` - The role required to call the functions.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not a global admin.
+    ///
+    /// # Events
+    ///
+    /// * [`TargetFunctionRoleUpdated`] - For each selector.
+    fn set_target_function_role(
+        &mut self,
+        target: Address,
+        selectors: Vec<FixedBytes<4>>,
+        role_id: u64,
+    ) -> Result<(), Self::Error>;
+
+    /// Set the delay for changing the configuration of a given target
+    /// contract.
+    ///
+    /// Requires the caller to be a global admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - The target contract.
+    /// * `new_delay` - The new admin delay.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not a global admin.
+    ///
+    /// # Events
+    ///
+    /// * [`TargetAdminDelayUpdated`].
+    fn set_target_admin_delay(
+        &mut self,
+        target: Address,
+        new_delay: u32,
+    ) -> Result<(), Self::Error>;
+
+    /// Set the closed flag for a contract.
+    ///
+    /// Closing the manager itself won't disable access to admin methods to
+    /// avoid locking the contract.
+    ///
+    /// Requires the caller to be a global admin, and is subject to the
+    /// target's admin delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - The target contract.
+    /// * `closed` - Whether the target is closed.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not a global admin.
+    ///
+    /// # Events
+    ///
+    /// * [`TargetClosed`].
+    fn set_target_closed(
+        &mut self,
+        target: Address,
+        closed: bool,
+    ) -> Result<(), Self::Error>;
+
+    /// Return the timepoint at which a scheduled operation will be ready for
+    /// execution. This returns 0 if the operation is not yet scheduled, has
+    /// expired, was executed, or was canceled.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Id of the operation.
+    fn get_schedule(&self, id: B256) -> U48;
+
+    /// Return the nonce for the latest scheduled operation with a given id.
+    /// Returns 0 if the operation has never been scheduled.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Id of the operation.
+    fn get_nonce(&self, id: B256) -> u32;
+
+    /// Schedule a delayed operation for future execution, and return the
+    /// operation identifier. It is possible to choose the timestamp at which
+    /// the operation becomes executable as long as it satisfies the execution
+    /// delays required for the caller. The special value zero will
+    /// automatically set the earliest possible time.
+    ///
+    /// Returns the `operation_id` that was scheduled. Since this value is a
+    /// hash of the parameters, it can reoccur when the same parameters are
+    /// used; if this is relevant, the returned `nonce` can be used to
+    /// uniquely identify this scheduled operation from other occurrences of
+    /// the same `operation_id` in invocations of [`Self::execute`] and
+    /// [`Self::cancel`].
+    ///
+    /// NOTE: It is not possible to concurrently schedule more than one
+    /// operation with the same `target` and `data`. If this is necessary, a
+    /// random byte can be appended to `data` to act as a salt that will be
+    /// ignored by the target contract if it is using standard Solidity ABI
+    /// encoding.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - Target of the call.
+    /// * `data` - Calldata of the call.
+    /// * `when` - Timepoint at which the operation can be executed, or zero for
+    ///   the earliest possible time.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedCall`] - If the caller is not allowed to schedule
+    ///   the call, or `when` is before the earliest possible time.
+    /// * [`Error::AlreadyScheduled`] - If the operation is already scheduled.
+    ///
+    /// # Events
+    ///
+    /// * [`OperationScheduled`].
+    fn schedule(
+        &mut self,
+        target: Address,
+        data: Bytes,
+        when: U48,
+    ) -> Result<(B256, u32), Self::Error>;
+
+    /// Execute a function that is delay restricted, provided it was properly
+    /// scheduled beforehand, or the execution delay is 0.
+    ///
+    /// Returns the nonce that identifies the previously scheduled operation
+    /// that is executed, or 0 if the operation wasn't previously scheduled
+    /// (if the caller doesn't have an execution delay).
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - Target of the call.
+    /// * `data` - Calldata of the call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedCall`] - If the caller is not allowed to execute
+    ///   the call.
+    /// * [`Error::NotScheduled`], [`Error::NotReady`], [`Error::Expired`] - If
+    ///   the call has a delay and wasn't properly scheduled.
+    /// * [`Error::FailedCall`] - If the call to the target failed.
+    ///
+    /// # Events
+    ///
+    /// * [`OperationExecuted`] - If a scheduled operation was consumed.
+    fn execute(
+        &mut self,
+        target: Address,
+        data: Bytes,
+    ) -> Result<u32, Self::Error>;
+
+    /// Cancel a scheduled (delayed) operation. Returns the nonce that
+    /// identifies the previously scheduled operation that is cancelled.
+    ///
+    /// Requires the caller to be the proposer, a guardian of the targeted
+    /// function, or a global admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `caller` - Account that scheduled the operation.
+    /// * `target` - Target of the operation.
+    /// * `data` - Calldata of the operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::NotScheduled`] - If the operation is not scheduled.
+    /// * [`Error::UnauthorizedCancel`] - If the sender is not allowed to cancel
+    ///   the operation.
+    ///
+    /// # Events
+    ///
+    /// * [`OperationCanceled`].
+    fn cancel(
+        &mut self,
+        caller: Address,
+        target: Address,
+        data: Bytes,
+    ) -> Result<u32, Self::Error>;
+
+    /// Consume a scheduled operation targeting the caller. If such an
+    /// operation exists, mark it as consumed (emit an [`OperationExecuted`]
+    /// event and clean the state). Otherwise, throw an error.
+    ///
+    /// This is useful for contracts that want to enforce that calls targeting
+    /// them were scheduled on the manager, with all the verifications that
+    /// it implies.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `caller` - Account that scheduled the operation.
+    /// * `data` - Calldata of the operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedConsume`] - If the caller is not an
+    ///   [`AccessManaged`] contract consuming a scheduled operation.
+    /// * [`Error::NotScheduled`], [`Error::NotReady`], [`Error::Expired`] - If
+    ///   the operation wasn't properly scheduled.
+    ///
+    /// # Events
+    ///
+    /// * [`OperationExecuted`].
+    fn consume_scheduled_op(
+        &mut self,
+        caller: Address,
+        data: Bytes,
+    ) -> Result<(), Self::Error>;
+
+    /// Hashing function for delayed operations.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `caller` - Account that scheduled the operation.
+    /// * `target` - Target of the operation.
+    /// * `data` - Calldata of the operation.
+    fn hash_operation(
+        &self,
+        caller: Address,
+        target: Address,
+        data: Bytes,
+    ) -> B256;
+
+    /// Changes the authority of a target managed by this manager instance.
+    ///
+    /// Requires the caller to be a global admin, and is subject to the
+    /// target's admin delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - The target contract.
+    /// * `new_authority` - The new authority of the target.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not a global admin.
+    /// * [`Error::FailedCall`] - If the call to the target failed.
+    fn update_authority(
+        &mut self,
+        target: Address,
+        new_authority: Address,
+    ) -> Result<(), Self::Error>;
+}
+
+#[public]
+impl IAccessManager for AccessManager {
+    type Error = Error;
+
+    fn can_call(
+        &self,
+        caller: Address,
+        target: Address,
+        selector: FixedBytes<4>,
+    ) -> (bool, u32) {
+        if self.is_target_closed(target) {
+            (false, 0)
+        } else if caller == contract::address() {
+            // Caller is AccessManager, this means the call was sent through
+            // `execute` and it already checked permissions. We verify that
+            // the call "identifier", which is set during `execute`, is
+            // correct.
+            (self._is_executing(target, selector), 0)
+        } else {
+            let role_id = self.get_target_function_role(target, selector);
+            let (is_member, current_delay) = self.has_role(role_id, caller);
+            if is_member {
+                (current_delay == 0, current_delay)
+            } else {
+                (false, 0)
+            }
+        }
+    }
+
+    fn expiration(&self) -> u32 {
+        EXPIRATION
+    }
+
+    fn min_setback(&self) -> u32 {
+        MIN_SETBACK
+    }
+
+    fn is_target_closed(&self, target: Address) -> bool {
+        self.targets.getter(target).closed.get()
+    }
+
+    fn get_target_function_role(
+        &self,
+        target: Address,
+        selector: FixedBytes<4>,
+    ) -> u64 {
+        self.targets.getter(target).allowed_roles.get(selector).to()
+    }
+
+    fn get_target_admin_delay(&self, target: Address) -> u32 {
+        Delay::from(self.targets.getter(target).admin_delay.get()).get()
+    }
+
+    fn get_role_admin(&self, role_id: u64) -> u64 {
+        self.roles.getter(role_id).admin.get().to()
+    }
+
+    fn get_role_guardian(&self, role_id: u64) -> u64 {
+        self.roles.getter(role_id).guardian.get().to()
+    }
+
+    fn get_role_grant_delay(&self, role_id: u64) -> u32 {
+        Delay::from(self.roles.getter(role_id).grant_delay.get()).get()
+    }
+
+    fn get_access(
+        &self,
+        role_id: u64,
+        account: Address,
+    ) -> (U48, u32, u32, U48) {
+        let role = self.roles.getter(role_id);
+        let access = role.members.getter(account);
+        let since = access.since.get();
+        let (current_delay, pending_delay, effect) =
+            Delay::from(access.delay.get()).get_full();
+        (since, current_delay, pending_delay, effect)
+    }
+
+    fn has_role(&self, role_id: u64, account: Address) -> (bool, u32) {
+        if role_id == PUBLIC_ROLE {
+            return (true, 0);
+        }
+        let (has_role_since, current_delay, _, _) =
+            self.get_access(role_id, account);
+        (
+            !has_role_since.is_zero() && has_role_since <= time::timestamp(),
+            current_delay,
+        )
+    }
+
+    fn label_role(
+        &mut self,
+        role_id: u64,
+        label: String,
+    ) -> Result<(), Self::Error> {
+        self._check_authorized(
+            &abi::labelRoleCall { role_id, label: label.clone() }.abi_encode(),
+        )?;
+        if role_id == ADMIN_ROLE || role_id == PUBLIC_ROLE {
+            return Err(Error::LockedRole(AccessManagerLockedRole { role_id }));
+        }
+        evm::log(RoleLabel { role_id, label });
+        Ok(())
+    }
+
+    fn grant_role(
+        &mut self,
+        role_id: u64,
+        account: Address,
+        execution_delay: u32,
+    ) -> Result<(), Self::Error> {
+        self._check_authorized(
+            &abi::grantRoleCall { role_id, account, execution_delay }
+                .abi_encode(),
+        )?;
+        let grant_delay = self.get_role_grant_delay(role_id);
+        self._grant_role(role_id, account, grant_delay, execution_delay)?;
+        Ok(())
+    }
+
+    fn revoke_role(
+        &mut self,
+        role_id: u64,
+        account: Address,
+    ) -> Result<(), Self::Error> {
+        self._check_authorized(
+            &abi::revokeRoleCall { role_id, account }.abi_encode(),
+        )?;
+        self._revoke_role(role_id, account)?;
+        Ok(())
+    }
+
+    fn renounce_role(
+        &mut self,
+        role_id: u64,
+        caller_confirmation: Address,
+    ) -> Result<(), Self::Error> {
+        if caller_confirmation != msg::sender() {
+            return Err(Error::BadConfirmation(
+                AccessManagerBadConfirmation {},
+            ));
+        }
+        self._revoke_role(role_id, caller_confirmation)?;
+        Ok(())
+    }
+
+    fn set_role_admin(
+        &mut self,
+        role_id: u64,
+        admin: u64,
+    ) -> Result<(), Self::Error> {
+        self._check_authorized(
+            &abi::setRoleAdminCall { role_id, admin }.abi_encode(),
+        )?;
+        self._set_role_admin(role_id, admin)
+    }
+
+    fn set_role_guardian(
+        &mut self,
+        role_id: u64,
+        guardian: u64,
+    ) -> Result<(), Self::Error> {
+        self._check_authorized(
+            &abi::setRoleGuardianCall { role_id, guardian }.abi_encode(),
+        )?;
+        self._set_role_guardian(role_id, guardian)
+    }
+
+    fn set_grant_delay(
+        &mut self,
+        role_id: u64,
+        new_delay: u32,
+    ) -> Result<(), Self::Error> {
+        self._check_authorized(
+            &abi::setGrantDelayCall { role_id, new_delay }.abi_encode(),
+        )?;
+        self._set_grant_delay(role_id, new_delay)
+    }
+
+    fn set_target_function_role(
+        &mut self,
+        target: Address,
+        selectors: Vec<FixedBytes<4>>,
+        role_id: u64,
+    ) -> Result<(), Self::Error> {
+        self._check_authorized(
+            &abi::setTargetFunctionRoleCall {
+                target,
+                selectors: selectors.clone(),
+                role_id,
+            }
+            .abi_encode(),
+        )?;
+        for selector in selectors {
+            self._set_target_function_role(target, selector, role_id);
+        }
+        Ok(())
+    }
+
+    fn set_target_admin_delay(
+        &mut self,
+        target: Address,
+        new_delay: u32,
+    ) -> Result<(), Self::Error> {
+        self._check_authorized(
+            &abi::setTargetAdminDelayCall { target, new_delay }.abi_encode(),
+        )?;
+        self._set_target_admin_delay(target, new_delay);
+        Ok(())
+    }
+
+    fn set_target_closed(
+        &mut self,
+        target: Address,
+        closed: bool,
+    ) -> Result<(), Self::Error> {
+        self._check_authorized(
+            &abi::setTargetClosedCall { target, closed }.abi_encode(),
+        )?;
+        self._set_target_closed(target, closed);
+        Ok(())
+    }
+
+    fn get_schedule(&self, id: B256) -> U48 {
+        let timepoint = self.schedules.getter(id).timepoint.get();
+        if self._is_expired(timepoint) {
+            U48::ZERO
+        } else {
+            timepoint
+        }
+    }
+
+    fn get_nonce(&self, id: B256) -> u32 {
+        self.schedules.getter(id).nonce.get().to()
+    }
+
+    fn schedule(
+        &mut self,
+        target: Address,
+        data: Bytes,
+        when: U48,
+    ) -> Result<(B256, u32), Self::Error> {
+        let caller = msg::sender();
+
+        // Fetch restrictions that apply to the caller on the targeted
+        // function.
+        let (_, setback) = self._can_call_extended(caller, target, &data);
+
+        let min_when = time::timestamp() + U48::from(setback);
+
+        // If call with delay is not authorized, or if requested timing is too
+        // soon, revert.
+        if setback == 0 || (!when.is_zero() && when < min_when) {
+            return Err(Error::UnauthorizedCall(
+                AccessManagerUnauthorizedCall {
+                    caller,
+                    target,
+                    selector: Self::_check_selector(&data),
+                },
+            ));
+        }
+
+        let when = when.max(min_when);
+
+        // If caller is authorised, schedule operation.
+        let operation_id = self.hash_operation(caller, target, data.clone());
+
+        self._check_not_scheduled(operation_id)?;
+
+        let nonce = self.get_nonce(operation_id).wrapping_add(1);
+        let mut schedule = self.schedules.setter(operation_id);
+        schedule.timepoint.set(when);
+        schedule.nonce.set(U32::from(nonce));
+
+        evm::log(OperationScheduled {
+            operation_id,
+            nonce,
+            schedule: when,
+            caller,
+            target,
+            data: data.to_vec().into(),
+        });
+
+        Ok((operation_id, nonce))
+    }
+
+    #[payable]
+    fn execute(
+        &mut self,
+        target: Address,
+        data: Bytes,
+    ) -> Result<u32, Self::Error> {
+        let caller = msg::sender();
+
+        // Fetch restrictions that apply to the caller on the targeted
+        // function.
+        let (immediate, setback) =
+            self._can_call_extended(caller, target, &data);
+
+        // If call is not authorized, revert.
+        if !immediate && setback == 0 {
+            return Err(Error::UnauthorizedCall(
+                AccessManagerUnauthorizedCall {
+                    caller,
+                    target,
+                    selector: Self::_check_selector(&data),
+                },
+            ));
+        }
+
+        let operation_id = self.hash_operation(caller, target, data.clone());
+
+        // If caller is authorised, check operation was scheduled early
+        // enough. Consume an available schedule even if there is no
+        // currently enforced delay.
+        let nonce =
+            if setback != 0 || !self.get_schedule(operation_id).is_zero() {
+                self._consume_scheduled_op(operation_id)?
+            } else {
+                0
+            };
+
+        // Mark the target and selector as authorised.
+        let execution_id_before = self.execution_id.get();
+        self.execution_id.set(Self::_hash_execution_id(
+            target,
+            Self::_check_selector(&data),
+        ));
+
+        // Perform call.
+        RawCall::new_with_value(msg::value())
+            .call_with_reentrant_handling(target, &data)
+            .map_err(call::Error::Revert)?;
+
+        // Reset execute identifier.
+        self.execution_id.set(execution_id_before);
+
+        Ok(nonce)
+    }
+
+    fn cancel(
+        &mut self,
+        caller: Address,
+        target: Address,
+        data: Bytes,
+    ) -> Result<u32, Self::Error> {
+        let msg_sender = msg::sender();
+        let selector = Self::_check_selector(&data);
+
+        let operation_id = self.hash_operation(caller, target, data);
+        if self.schedules.getter(operation_id).timepoint.get().is_zero() {
+            return Err(Error::NotScheduled(AccessManagerNotScheduled {
+                operation_id,
+            }));
+        } else if caller != msg_sender {
+            // Calls can only be canceled by the account that scheduled them,
+            // a global admin, or by a guardian of the required role.
+            let (is_admin, _) = self.has_role(ADMIN_ROLE, msg_sender);
+            let (is_guardian, _) = self.has_role(
+                self.get_role_guardian(
+                    self.get_target_function_role(target, selector),
+                ),
+                msg_sender,
+            );
+            if !is_admin && !is_guardian {
+                return Err(Error::UnauthorizedCancel(
+                    AccessManagerUnauthorizedCancel {
+                        msg_sender,
+                        caller,
+                        target,
+                        selector,
+                    },
+                ));
+            }
+        }
+
+        // Reset the timepoint, keep the nonce.
+        self.schedules.setter(operation_id).timepoint.set(U48::ZERO);
+        let nonce = self.get_nonce(operation_id);
+        evm::log(OperationCanceled { operation_id, nonce });
+
+        Ok(nonce)
+    }
+
+    fn consume_scheduled_op(
+        &mut self,
+        caller: Address,
+        data: Bytes,
+    ) -> Result<(), Self::Error> {
+        let target = msg::sender();
+        let consuming = AccessManagedInterface::new(target)
+            .is_consuming_scheduled_op(Call::new_in(self))
+            .map_err(|_| {
+                Error::UnauthorizedConsume(AccessManagerUnauthorizedConsume {
+                    target,
+                })
+            })?;
+        if consuming != managed::IS_CONSUMING_SCHEDULED_OP_SELECTOR {
+            return Err(Error::UnauthorizedConsume(
+                AccessManagerUnauthorizedConsume { target },
+            ));
+        }
+        let operation_id = self.hash_operation(caller, target, data);
+        self._consume_scheduled_op(operation_id)?;
+        Ok(())
+    }
+
+    fn hash_operation(
+        &self,
+        caller: Address,
+        target: Address,
+        data: Bytes,
+    ) -> B256 {
+        Self::_hash_operation(caller, target, &data)
+    }
+
+    fn update_authority(
+        &mut self,
+        target: Address,
+        new_authority: Address,
+    ) -> Result<(), Self::Error> {
+        self._check_authorized(
+            &abi::updateAuthorityCall { target, new_authority }.abi_encode(),
+        )?;
+        AccessManagedInterface::new(target)
+            .set_authority(Call::new_in(self), new_authority)?;
+        Ok(())
+    }
+}
+
+impl AccessManager {
+    /// Internal version of [`IAccessManager::grant_role`] without access
+    /// control. Returns true if the role was newly granted.
+    ///
+    /// Can be used to grant the [`ADMIN_ROLE`] to the initial admin.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `account` - The account which will be granted the role.
+    /// * `grant_delay` - Delay before the membership becomes effective.
+    /// * `execution_delay` - Execution delay of the account.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::LockedRole`] - If `role_id` is [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleGranted`].
+    pub fn _grant_role(
+        &mut self,
+        role_id: u64,
+        account: Address,
+        grant_delay: u32,
+        execution_delay: u32,
+    ) -> Result<bool, Error> {
+        if role_id == PUBLIC_ROLE {
+            return Err(Error::LockedRole(AccessManagerLockedRole { role_id }));
+        }
+
+        let mut role = self.roles.setter(role_id);
+        let mut access = role.members.setter(account);
+        let new_member = access.since.get().is_zero();
+
+        let since = if new_member {
+            let since = time::timestamp() + U48::from(grant_delay);
+            access.since.set(since);
+            access.delay.set(Delay::new(execution_delay).into());
+            since
+        } else {
+            // No setback here. Value can be reset by doing revoke + grant,
+            // effectively allowing the admin to perform any change to the
+            // execution delay within the duration of the role admin delay.
+            let (delay, since) =
+                Delay::from(access.delay.get()).with_update(execution_delay, 0);
+            access.delay.set(delay.into());
+            since
+        };
+
+        evm::log(RoleGranted {
+            role_id,
+            account,
+            delay: execution_delay,
+            since,
+            new_member,
+        });
+        Ok(new_member)
+    }
+
+    /// Internal version of [`IAccessManager::revoke_role`] without access
+    /// control. Returns true if the role was previously granted.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `account` - The account which will be revoked the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::LockedRole`] - If `role_id` is [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleRevoked`] - If the account had the role.
+    pub fn _revoke_role(
+        &mut self,
+        role_id: u64,
+        account: Address,
+    ) -> Result<bool, Error> {
+        if role_id == PUBLIC_ROLE {
+            return Err(Error::LockedRole(AccessManagerLockedRole { role_id }));
+        }
+
+        let mut role = self.roles.setter(role_id);
+        let mut access = role.members.setter(account);
+        if access.since.get().is_zero() {
+            return Ok(false);
+        }
+
+        access.since.set(U48::ZERO);
+        access.delay.set(U112::ZERO);
+
+        evm::log(RoleRevoked { role_id, account });
+        Ok(true)
+    }
+
+    /// Internal version of [`IAccessManager::set_role_admin`] without access
+    /// control.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `admin` - The new admin role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::LockedRole`] - If `role_id` is [`ADMIN_ROLE`] or
+    ///   [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleAdminChanged`].
+    pub fn _set_role_admin(
+        &mut self,
+        role_id: u64,
+        admin: u64,
+    ) -> Result<(), Error> {
+        if role_id == ADMIN_ROLE || role_id == PUBLIC_ROLE {
+            return Err(Error::LockedRole(AccessManagerLockedRole { role_id }));
+        }
+
+        self.roles.setter(role_id).admin.set(U64::from(admin));
+
+        evm::log(RoleAdminChanged { role_id, admin });
+        Ok(())
+    }
+
+    /// Internal version of [`IAccessManager::set_role_guardian`] without
+    /// access control.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `guardian` - The new guardian role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::LockedRole`] - If `role_id` is [`ADMIN_ROLE`] or
+    ///   [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleGuardianChanged`].
+    pub fn _set_role_guardian(
+        &mut self,
+        role_id: u64,
+        guardian: u64,
+    ) -> Result<(), Error> {
+        if role_id == ADMIN_ROLE || role_id == PUBLIC_ROLE {
+            return Err(Error::LockedRole(AccessManagerLockedRole { role_id }));
+        }
+
+        self.roles.setter(role_id).guardian.set(U64::from(guardian));
+
+        evm::log(RoleGuardianChanged { role_id, guardian });
+        Ok(())
+    }
+
+    /// Internal version of [`IAccessManager::set_grant_delay`] without access
+    /// control.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role_id` - The role identifier.
+    /// * `new_delay` - The new grant delay.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::LockedRole`] - If `role_id` is [`PUBLIC_ROLE`].
+    ///
+    /// # Events
+    ///
+    /// * [`RoleGrantDelayChanged`].
+    pub fn _set_grant_delay(
+        &mut self,
+        role_id: u64,
+        new_delay: u32,
+    ) -> Result<(), Error> {
+        if role_id == PUBLIC_ROLE {
+            return Err(Error::LockedRole(AccessManagerLockedRole { role_id }));
+        }
+
+        let mut role = self.roles.setter(role_id);
+        let (delay, since) = Delay::from(role.grant_delay.get())
+            .with_update(new_delay, MIN_SETBACK);
+        role.grant_delay.set(delay.into());
+
+        evm::log(RoleGrantDelayChanged { role_id, delay: new_delay, since });
+        Ok(())
+    }
+
+    /// Internal version of [`IAccessManager::set_target_function_role`]
+    /// without access control, for a single selector.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - The target contract.
+    /// * `selector` - The function selector.
+    /// * `role_id` - The role required to call the function.
+    ///
+    /// # Events
+    ///
+    /// * [`TargetFunctionRoleUpdated`].
+    pub fn _set_target_function_role(
+        &mut self,
+        target: Address,
+        selector: FixedBytes<4>,
+        role_id: u64,
+    ) {
+        self.targets
+            .setter(target)
+            .allowed_roles
+            .setter(selector)
+            .set(U64::from(role_id));
+        evm::log(TargetFunctionRoleUpdated { target, selector, role_id });
+    }
+
+    /// Internal version of [`IAccessManager::set_target_admin_delay`] without
+    /// access control.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - The target contract.
+    /// * `new_delay` - The new admin delay.
+    ///
+    /// # Events
+    ///
+    /// * [`TargetAdminDelayUpdated`].
+    pub fn _set_target_admin_delay(&mut self, target: Address, new_delay: u32) {
+        let mut config = self.targets.setter(target);
+        let (delay, since) = Delay::from(config.admin_delay.get())
+            .with_update(new_delay, MIN_SETBACK);
+        config.admin_delay.set(delay.into());
+
+        evm::log(TargetAdminDelayUpdated { target, delay: new_delay, since });
+    }
+
+    /// Internal version of [`IAccessManager::set_target_closed`] without
+    /// access control.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - The target contract.
+    /// * `closed` - Whether the target is closed.
+    ///
+    /// # Events
+    ///
+    /// * [`TargetClosed`].
+    pub fn _set_target_closed(&mut self, target: Address, closed: bool) {
+        self.targets.setter(target).closed.set(closed);
+        evm::log(TargetClosed { target, closed });
+    }
+
+    /// Reverts if the operation is currently scheduled and has not expired.
+    ///
+    /// NOTE: This function was introduced due to stack too deep errors in
+    /// the Solidity implementation, and is kept for parity.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `operation_id` - Id of the operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AlreadyScheduled`] - If the operation is scheduled and has
+    ///   not expired.
+    fn _check_not_scheduled(&self, operation_id: B256) -> Result<(), Error> {
+        let prev_timepoint =
+            self.schedules.getter(operation_id).timepoint.get();
+        if !prev_timepoint.is_zero() && !self._is_expired(prev_timepoint) {
+            return Err(Error::AlreadyScheduled(
+                AccessManagerAlreadyScheduled { operation_id },
+            ));
+        }
+        Ok(())
+    }
+
+    /// Internal variant of [`IAccessManager::consume_scheduled_op`] that
+    /// operates on an operation id. Returns the nonce of the scheduled
+    /// operation that is consumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `operation_id` - Id of the operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::NotScheduled`] - If the operation is not scheduled.
+    /// * [`Error::NotReady`] - If the operation is not ready yet.
+    /// * [`Error::Expired`] - If the operation has expired.
+    ///
+    /// # Events
+    ///
+    /// * [`OperationExecuted`].
+    fn _consume_scheduled_op(
+        &mut self,
+        operation_id: B256,
+    ) -> Result<u32, Error> {
+        let timepoint = self.schedules.getter(operation_id).timepoint.get();
+        let nonce = self.get_nonce(operation_id);
+
+        if timepoint.is_zero() {
+            return Err(Error::NotScheduled(AccessManagerNotScheduled {
+                operation_id,
+            }));
+        } else if timepoint > time::timestamp() {
+            return Err(Error::NotReady(AccessManagerNotReady {
+                operation_id,
+            }));
+        } else if self._is_expired(timepoint) {
+            return Err(Error::Expired(AccessManagerExpired { operation_id }));
+        }
+
+        // Reset the timepoint, keep the nonce.
+        self.schedules.setter(operation_id).timepoint.set(U48::ZERO);
+        evm::log(OperationExecuted { operation_id, nonce });
+
+        Ok(nonce)
+    }
+
+    /// Check if the current call is authorized according to admin and roles
+    /// logic.
+    ///
+    /// WARNING: Carefully review the considerations of
+    /// [`AccessManaged::restricted`] since they apply to this function.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `data` - Calldata of the current call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedAccount`] - If the caller is not authorized.
+    /// * [`Error::NotScheduled`], [`Error::NotReady`], [`Error::Expired`] - If
+    ///   the call has a delay and wasn't properly scheduled.
+    fn _check_authorized(&mut self, data: &[u8]) -> Result<(), Error> {
+        let caller = msg::sender();
+        let (immediate, delay) = self._can_call_self(caller, data);
+        if !immediate {
+            if delay == 0 {
+                let (_, required_role, _) = self._get_admin_restrictions(data);
+                return Err(Error::UnauthorizedAccount(
+                    AccessManagerUnauthorizedAccount {
+                        msg_sender: caller,
+                        role_id: required_role,
+                    },
+                ));
+            }
+            let operation_id =
+                Self::_hash_operation(caller, contract::address(), data);
+            self._consume_scheduled_op(operation_id)?;
+        }
+        Ok(())
+    }
+
+    /// Get the admin restrictions of a given function call based on the
+    /// function and arguments involved.
+    ///
+    /// Returns:
+    ///
+    /// * `admin_restricted` - Whether the function is restricted to an admin
+    ///   role.
+    /// * `role_admin_id` - The role required to call the function.
+    /// * `execution_delay` - The admin delay that applies to the call.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `data` - Calldata of the call.
+    fn _get_admin_restrictions(&self, data: &[u8]) -> (bool, u64, u32) {
+        if data.len() < 4 {
+            return (false, 0, 0);
+        }
+
+        let selector = Self::_check_selector(data);
+
+        // Restricted to ADMIN with no delay beside any execution delay the
+        // caller may have.
+        if selector == abi::labelRoleCall::SELECTOR
+            || selector == abi::setRoleAdminCall::SELECTOR
+            || selector == abi::setRoleGuardianCall::SELECTOR
+            || selector == abi::setGrantDelayCall::SELECTOR
+            || selector == abi::setTargetAdminDelayCall::SELECTOR
+        {
+            return (true, ADMIN_ROLE, 0);
+        }
+
+        // Restricted to ADMIN with the admin delay corresponding to the
+        // target.
+        if selector == abi::updateAuthorityCall::SELECTOR
+            || selector == abi::setTargetClosedCall::SELECTOR
+            || selector == abi::setTargetFunctionRoleCall::SELECTOR
+        {
+            // First argument is a target.
+            let target = Address::from_word(Self::_first_argument(data));
+            let delay = self.get_target_admin_delay(target);
+            return (true, ADMIN_ROLE, delay);
+        }
+
+        // Restricted to that role's admin with no delay beside any execution
+        // delay the caller may have.
+        if selector == abi::grantRoleCall::SELECTOR
+            || selector == abi::revokeRoleCall::SELECTOR
+        {
+            // First argument is a role id.
+            let role_id: u64 =
+                U256::from_be_bytes(Self::_first_argument(data).0).to();
+            return (true, self.get_role_admin(role_id), 0);
+        }
+
+        (false, self.get_target_function_role(contract::address(), selector), 0)
+    }
+
+    /// An extended version of [`IAccessManager::can_call`] for internal usage
+    /// that checks [`Self::_can_call_self`] when the target is this contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `caller` - Account calling the function.
+    /// * `target` - Target contract of the call.
+    /// * `data` - Calldata of the call.
+    fn _can_call_extended(
+        &self,
+        caller: Address,
+        target: Address,
+        data: &[u8],
+    ) -> (bool, u32) {
+        if target == contract::address() {
+            self._can_call_self(caller, data)
+        } else if data.len() < 4 {
+            (false, 0)
+        } else {
+            self.can_call(caller, target, Self::_check_selector(data))
+        }
+    }
+
+    /// A version of [`IAccessManager::can_call`] that checks calls targeting
+    /// this contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `caller` - Account calling the function.
+    /// * `data` - Calldata of the call.
+    fn _can_call_self(&self, caller: Address, data: &[u8]) -> (bool, u32) {
+        if data.len() < 4 {
+            return (false, 0);
+        }
+
+        if caller == contract::address() {
+            // Caller is AccessManager, this means the call was sent through
+            // `execute` and it already checked permissions. We verify that
+            // the call "identifier", which is set during `execute`, is
+            // correct.
+            return (
+                self._is_executing(
+                    contract::address(),
+                    Self::_check_selector(data),
+                ),
+                0,
+            );
+        }
+
+        let (admin_restricted, role_id, operation_delay) =
+            self._get_admin_restrictions(data);
+
+        // `is_target_closed` applies to non-admin-restricted functions.
+        if !admin_restricted && self.is_target_closed(contract::address()) {
+            return (false, 0);
+        }
+
+        let (in_role, execution_delay) = self.has_role(role_id, caller);
+        if !in_role {
+            return (false, 0);
+        }
+
+        let delay = operation_delay.max(execution_delay);
+        (delay == 0, delay)
+    }
+
+    /// Returns true if a call with `target` and `selector` is being executed
+    /// via [`IAccessManager::execute`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `target` - Target of the call.
+    /// * `selector` - Function selector of the call.
+    fn _is_executing(&self, target: Address, selector: FixedBytes<4>) -> bool {
+        self.execution_id.get() == Self::_hash_execution_id(target, selector)
+    }
+
+    /// Returns true if a schedule timepoint is past its expiration deadline.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `timepoint` - Timepoint of the schedule.
+    fn _is_expired(&self, timepoint: U48) -> bool {
+        timepoint + U48::from(self.expiration()) <= time::timestamp()
+    }
+
+    /// Hashing function for delayed operations.
+    ///
+    /// # Arguments
+    ///
+    /// * `caller` - Account that scheduled the operation.
+    /// * `target` - Target of the operation.
+    /// * `data` - Calldata of the operation.
+    fn _hash_operation(caller: Address, target: Address, data: &[u8]) -> B256 {
+        let encoded = OperationTuple::abi_encode_params(&(
+            caller,
+            target,
+            alloy_primitives::Bytes::copy_from_slice(data),
+        ));
+        alloy_primitives::keccak256(encoded)
+    }
+
+    /// Hashing function for execute protection.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Target of the call.
+    /// * `selector` - Function selector of the call.
+    fn _hash_execution_id(target: Address, selector: FixedBytes<4>) -> B256 {
+        let encoded = ExecutionIdTuple::abi_encode_params(&(target, selector));
+        alloy_primitives::keccak256(encoded)
+    }
+
+    /// Extracts the selector from calldata, padding with zeros if `data` is
+    /// shorter than 4 bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Calldata of the call.
+    fn _check_selector(data: &[u8]) -> FixedBytes<4> {
+        let mut selector = [0u8; 4];
+        let len = data.len().min(4);
+        selector[..len].copy_from_slice(&data[..len]);
+        FixedBytes::from(selector)
+    }
+
+    /// Extracts the first ABI-encoded argument from calldata, as a 32-byte
+    /// word. Missing bytes are considered zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Calldata of the call.
+    fn _first_argument(data: &[u8]) -> B256 {
+        let mut word = [0u8; 32];
+        let argument = data.get(4..).unwrap_or_default();
+        let len = argument.len().min(32);
+        word[..len].copy_from_slice(&argument[..len]);
+        B256::from(word)
+    }
+}
+
+impl IErc165 for AccessManager {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IAccessManager>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{aliases::U48, Address, FixedBytes, B256};
+    use motsu::prelude::*;
+    use stylus_sdk::{
+        abi::Bytes, function_selector, prelude::*, storage::StorageBool,
+    };
+
+    use super::{
+        AccessManaged, AccessManager, Error, IAccessManaged, IAccessManager,
+        OperationCanceled, OperationExecuted, OperationScheduled, RoleGranted,
+        RoleLabel, RoleRevoked, TargetFunctionRoleUpdated, ADMIN_ROLE,
+        MIN_SETBACK, PUBLIC_ROLE,
+    };
+    use crate::utils::{
+        introspection::erc165::IErc165,
+        types::time::{self, Delay},
+    };
+
+    const ROLE: u64 = 1;
+    const GUARDIAN_ROLE: u64 = 2;
+    const DELAY: u32 = 60;
+
+    /// Target of the restricted calls.
+    #[storage]
+    struct ManagedTarget {
+        access_managed: AccessManaged,
+        called: StorageBool,
+    }
+
+    unsafe impl TopLevelStorage for ManagedTarget {}
+
+    #[public]
+    impl ManagedTarget {
+        fn restricted_function(&mut self) -> Result<(), Vec<u8>> {
+            self.access_managed
+                .restricted(&function_selector!("restrictedFunction"))?;
+            self.called.set(true);
+            Ok(())
+        }
+
+        fn is_consuming_scheduled_op(&self) -> FixedBytes<4> {
+            self.access_managed.is_consuming_scheduled_op()
+        }
+    }
+
+    fn init(manager: &Contract<AccessManager>, admin: Address) {
+        manager.init(admin, |manager| {
+            manager
+                ._grant_role(ADMIN_ROLE, admin, 0, 0)
+                .expect("should grant admin role");
+        });
+    }
+
+    fn init_target(
+        target: &Contract<ManagedTarget>,
+        manager: &Contract<AccessManager>,
+        admin: Address,
+    ) {
+        target.init(admin, |target| {
+            target.access_managed._set_authority(manager.address());
+        });
+    }
+
+    fn selector() -> FixedBytes<4> {
+        FixedBytes::new(function_selector!("restrictedFunction"))
+    }
+
+    fn restricted_call() -> Bytes {
+        selector().to_vec().into()
+    }
+
+    fn now() -> U48 {
+        time::timestamp()
+    }
+
+    #[motsu::test]
+    fn initial_admin_has_admin_role(
+        manager: Contract<AccessManager>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        assert_eq!(
+            manager.sender(alice).has_role(ADMIN_ROLE, alice),
+            (true, 0)
+        );
+        assert_eq!(manager.sender(alice).has_role(ADMIN_ROLE, bob), (false, 0));
+        assert_eq!(manager.sender(alice).has_role(PUBLIC_ROLE, bob), (true, 0));
+    }
+
+    #[motsu::test]
+    fn admin_grants_role(
+        manager: Contract<AccessManager>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        manager.sender(alice).grant_role(ROLE, bob, DELAY).motsu_unwrap();
+
+        assert_eq!(manager.sender(alice).has_role(ROLE, bob), (true, DELAY));
+        assert_eq!(
+            manager.sender(alice).get_access(ROLE, bob),
+            (now(), DELAY, 0, U48::ZERO)
+        );
+        manager.assert_emitted(&RoleGranted {
+            role_id: ROLE,
+            account: bob,
+            delay: DELAY,
+            since: now(),
+            new_member: true,
+        });
+    }
+
+    #[motsu::test]
+    fn grant_role_applies_grant_delay(
+        manager: Contract<AccessManager>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        manager
+            .sender(alice)
+            .roles
+            .setter(ROLE)
+            .grant_delay
+            .set(Delay::new(DELAY).into());
+
+        manager.sender(alice).grant_role(ROLE, bob, 0).motsu_unwrap();
+
+        assert_eq!(manager.sender(alice).has_role(ROLE, bob), (false, 0));
+        let (since, ..) = manager.sender(alice).get_access(ROLE, bob);
+        assert_eq!(since, now() + U48::from(DELAY));
+    }
+
+    #[motsu::test]
+    fn grant_role_updates_execution_delay(
+        manager: Contract<AccessManager>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        manager.sender(alice).grant_role(ROLE, bob, DELAY).motsu_unwrap();
+        manager.sender(alice).grant_role(ROLE, bob, 0).motsu_unwrap();
+
+        // Reducing the delay is subject to a setback equal to the difference.
+        let effect = now() + U48::from(DELAY);
+        assert_eq!(
+            manager.sender(alice).get_access(ROLE, bob),
+            (now(), DELAY, 0, effect)
+        );
+        manager.assert_emitted(&RoleGranted {
+            role_id: ROLE,
+            account: bob,
+            delay: 0,
+            since: effect,
+            new_member: false,
+        });
+    }
+
+    #[motsu::test]
+    fn grant_role_reverts_when_not_role_admin(
+        manager: Contract<AccessManager>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        let err =
+            manager.sender(bob).grant_role(ROLE, bob, 0).motsu_unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnauthorizedAccount(super::AccessManagerUnauthorizedAccount {
+                msg_sender,
+                role_id,
+            }) if msg_sender == bob && role_id == ADMIN_ROLE
+        ));
+    }
+
+    #[motsu::test]
+    fn grant_role_reverts_for_public_role(
+        manager: Contract<AccessManager>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        let err = manager
+            .sender(alice)
+            .grant_role(PUBLIC_ROLE, bob, 0)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::LockedRole(_)));
+    }
+
+    #[motsu::test]
+    fn role_admin_grants_role(
+        manager: Contract<AccessManager>,
+        alice: Address,
+        bob: Address,
+        charlie: Address,
+    ) {
+        init(&manager, alice);
+        manager
+            .sender(alice)
+            .set_role_admin(ROLE, GUARDIAN_ROLE)
+            .motsu_unwrap();
+        manager.sender(alice).grant_role(GUARDIAN_ROLE, bob, 0).motsu_unwrap();
+
+        assert_eq!(manager.sender(alice).get_role_admin(ROLE), GUARDIAN_ROLE);
+        manager.sender(bob).grant_role(ROLE, charlie, 0).motsu_unwrap();
+        assert_eq!(manager.sender(alice).has_role(ROLE, charlie), (true, 0));
+    }
+
+    #[motsu::test]
+    fn set_role_admin_reverts_for_locked_roles(
+        manager: Contract<AccessManager>,
+        alice: Address,
+    ) {
+        init(&manager, alice);
+        for role_id in [ADMIN_ROLE, PUBLIC_ROLE] {
+            let err = manager
+                .sender(alice)
+                .set_role_admin(role_id, ROLE)
+                .motsu_unwrap_err();
+            assert!(matches!(err, Error::LockedRole(_)));
+        }
+    }
+
+    #[motsu::test]
+    fn revokes_role(
+        manager: Contract<AccessManager>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        manager.sender(alice).grant_role(ROLE, bob, DELAY).motsu_unwrap();
+        manager.sender(alice).revoke_role(ROLE, bob).motsu_unwrap();
+
+        assert_eq!(manager.sender(alice).has_role(ROLE, bob), (false, 0));
+        assert_eq!(
+            manager.sender(alice).get_access(ROLE, bob),
+            (U48::ZERO, 0, 0, U48::ZERO)
+        );
+        manager.assert_emitted(&RoleRevoked { role_id: ROLE, account: bob });
+    }
+
+    #[motsu::test]
+    fn renounces_role(
+        manager: Contract<AccessManager>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        manager.sender(alice).grant_role(ROLE, bob, 0).motsu_unwrap();
+
+        let err =
+            manager.sender(alice).renounce_role(ROLE, bob).motsu_unwrap_err();
+        assert!(matches!(err, Error::BadConfirmation(_)));
+
+        manager.sender(bob).renounce_role(ROLE, bob).motsu_unwrap();
+        assert_eq!(manager.sender(alice).has_role(ROLE, bob), (false, 0));
+    }
+
+    #[motsu::test]
+    fn labels_role(manager: Contract<AccessManager>, alice: Address) {
+        init(&manager, alice);
+        let label = String::from("ROLE");
+        manager.sender(alice).label_role(ROLE, label.clone()).motsu_unwrap();
+        manager.assert_emitted(&RoleLabel { role_id: ROLE, label });
+
+        let err = manager
+            .sender(alice)
+            .label_role(ADMIN_ROLE, String::from("ADMIN"))
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::LockedRole(_)));
+    }
+
+    #[motsu::test]
+    fn grant_delay_increase_waits_for_min_setback(
+        manager: Contract<AccessManager>,
+        alice: Address,
+    ) {
+        init(&manager, alice);
+        manager.sender(alice).set_grant_delay(ROLE, DELAY).motsu_unwrap();
+
+        assert_eq!(manager.sender(alice).get_role_grant_delay(ROLE), 0);
+        let (_, pending, effect) = Delay::from(
+            manager.sender(alice).roles.getter(ROLE).grant_delay.get(),
+        )
+        .get_full();
+        assert_eq!(pending, DELAY);
+        assert_eq!(effect, now() + U48::from(MIN_SETBACK));
+    }
+
+    #[motsu::test]
+    fn can_call_follows_target_function_role(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        let target = target.address();
+        assert_eq!(
+            manager.sender(alice).can_call(bob, target, selector()),
+            (false, 0)
+        );
+
+        manager
+            .sender(alice)
+            .set_target_function_role(target, vec![selector()], ROLE)
+            .motsu_unwrap();
+        manager.assert_emitted(&TargetFunctionRoleUpdated {
+            target,
+            selector: selector(),
+            role_id: ROLE,
+        });
+        manager.sender(alice).grant_role(ROLE, bob, 0).motsu_unwrap();
+        assert_eq!(
+            manager.sender(alice).can_call(bob, target, selector()),
+            (true, 0)
+        );
+
+        manager.sender(alice).grant_role(ROLE, bob, DELAY).motsu_unwrap();
+        assert_eq!(
+            manager.sender(alice).can_call(bob, target, selector()),
+            (false, DELAY)
+        );
+
+        manager.sender(alice).set_target_closed(target, true).motsu_unwrap();
+        assert!(manager.sender(alice).is_target_closed(target));
+        assert_eq!(
+            manager.sender(alice).can_call(bob, target, selector()),
+            (false, 0)
+        );
+    }
+
+    #[motsu::test]
+    fn schedule_reverts_without_execution_delay(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        let target = target.address();
+        manager
+            .sender(alice)
+            .set_target_function_role(target, vec![selector()], ROLE)
+            .motsu_unwrap();
+        manager.sender(alice).grant_role(ROLE, bob, 0).motsu_unwrap();
+
+        let err = manager
+            .sender(bob)
+            .schedule(target, restricted_call(), U48::ZERO)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCall(_)));
+    }
+
+    #[motsu::test]
+    fn schedules_operation(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        let target = target.address();
+        manager
+            .sender(alice)
+            .set_target_function_role(target, vec![selector()], ROLE)
+            .motsu_unwrap();
+        manager.sender(alice).grant_role(ROLE, bob, DELAY).motsu_unwrap();
+
+        let too_soon = now() + U48::from(DELAY - 1);
+        let err = manager
+            .sender(bob)
+            .schedule(target, restricted_call(), too_soon)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCall(_)));
+
+        let (operation_id, nonce) = manager
+            .sender(bob)
+            .schedule(target, restricted_call(), U48::ZERO)
+            .motsu_unwrap();
+
+        let when = now() + U48::from(DELAY);
+        assert_eq!(nonce, 1);
+        assert_eq!(
+            operation_id,
+            manager.sender(bob).hash_operation(bob, target, restricted_call())
+        );
+        assert_eq!(manager.sender(bob).get_schedule(operation_id), when);
+        assert_eq!(manager.sender(bob).get_nonce(operation_id), 1);
+        manager.assert_emitted(&OperationScheduled {
+            operation_id,
+            nonce,
+            schedule: when,
+            caller: bob,
+            target,
+            data: restricted_call().to_vec().into(),
+        });
+
+        let err = manager
+            .sender(bob)
+            .schedule(target, restricted_call(), U48::ZERO)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::AlreadyScheduled(_)));
+    }
+
+    #[motsu::test]
+    fn expired_schedule_returns_zero(
+        manager: Contract<AccessManager>,
+        alice: Address,
+    ) {
+        init(&manager, alice);
+        let id = B256::repeat_byte(1);
+        manager
+            .sender(alice)
+            .schedules
+            .setter(id)
+            .timepoint
+            .set(now() - U48::from(manager.sender(alice).expiration()));
+        assert_eq!(manager.sender(alice).get_schedule(id), U48::ZERO);
+    }
+
+    #[motsu::test]
+    fn executes_immediate_call(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        init_target(&target, &manager, alice);
+        let target_address = target.address();
+        manager
+            .sender(alice)
+            .set_target_function_role(target_address, vec![selector()], ROLE)
+            .motsu_unwrap();
+        manager.sender(alice).grant_role(ROLE, bob, 0).motsu_unwrap();
+
+        let nonce = manager
+            .sender(bob)
+            .execute(target_address, restricted_call())
+            .motsu_unwrap();
+
+        assert_eq!(nonce, 0);
+        assert!(target.sender(alice).called.get());
+        assert_eq!(manager.sender(alice).execution_id.get(), B256::ZERO);
+    }
+
+    #[motsu::test]
+    fn execute_reverts_when_not_ready(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        init_target(&target, &manager, alice);
+        let target = target.address();
+        manager
+            .sender(alice)
+            .set_target_function_role(target, vec![selector()], ROLE)
+            .motsu_unwrap();
+        manager.sender(alice).grant_role(ROLE, bob, DELAY).motsu_unwrap();
+
+        let err = manager
+            .sender(bob)
+            .execute(target, restricted_call())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::NotScheduled(_)));
+
+        manager
+            .sender(bob)
+            .schedule(target, restricted_call(), U48::ZERO)
+            .motsu_unwrap();
+        let err = manager
+            .sender(bob)
+            .execute(target, restricted_call())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::NotReady(_)));
+    }
+
+    #[motsu::test]
+    fn executes_scheduled_call(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        init_target(&target, &manager, alice);
+        let target_address = target.address();
+        manager
+            .sender(alice)
+            .set_target_function_role(target_address, vec![selector()], ROLE)
+            .motsu_unwrap();
+        manager.sender(alice).grant_role(ROLE, bob, DELAY).motsu_unwrap();
+
+        let (operation_id, nonce) = manager
+            .sender(bob)
+            .schedule(target_address, restricted_call(), U48::ZERO)
+            .motsu_unwrap();
+        manager
+            .sender(alice)
+            .schedules
+            .setter(operation_id)
+            .timepoint
+            .set(now() - U48::from(1));
+
+        let executed = manager
+            .sender(bob)
+            .execute(target_address, restricted_call())
+            .motsu_unwrap();
+
+        assert_eq!(executed, nonce);
+        assert!(target.sender(alice).called.get());
+        assert_eq!(manager.sender(bob).get_schedule(operation_id), U48::ZERO);
+        manager.assert_emitted(&OperationExecuted { operation_id, nonce });
+    }
+
+    #[motsu::test]
+    fn execute_reverts_when_unauthorized(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        let target = target.address();
+        let err = manager
+            .sender(bob)
+            .execute(target, restricted_call())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCall(_)));
+    }
+
+    #[motsu::test]
+    fn cancels_operation(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+        charlie: Address,
+    ) {
+        init(&manager, alice);
+        let target = target.address();
+        manager
+            .sender(alice)
+            .set_target_function_role(target, vec![selector()], ROLE)
+            .motsu_unwrap();
+        manager
+            .sender(alice)
+            .set_role_guardian(ROLE, GUARDIAN_ROLE)
+            .motsu_unwrap();
+        manager.sender(alice).grant_role(ROLE, bob, DELAY).motsu_unwrap();
+
+        let (operation_id, nonce) = manager
+            .sender(bob)
+            .schedule(target, restricted_call(), U48::ZERO)
+            .motsu_unwrap();
+
+        let err = manager
+            .sender(charlie)
+            .cancel(bob, target, restricted_call())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCancel(_)));
+
+        manager
+            .sender(alice)
+            .grant_role(GUARDIAN_ROLE, charlie, 0)
+            .motsu_unwrap();
+        let canceled = manager
+            .sender(charlie)
+            .cancel(bob, target, restricted_call())
+            .motsu_unwrap();
+
+        assert_eq!(canceled, nonce);
+        assert_eq!(manager.sender(bob).get_schedule(operation_id), U48::ZERO);
+        manager.assert_emitted(&OperationCanceled { operation_id, nonce });
+
+        let err = manager
+            .sender(bob)
+            .cancel(bob, target, restricted_call())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::NotScheduled(_)));
+    }
+
+    #[motsu::test]
+    fn restricted_allows_authorized_caller(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        init_target(&target, &manager, alice);
+        manager
+            .sender(alice)
+            .set_target_function_role(target.address(), vec![selector()], ROLE)
+            .motsu_unwrap();
+
+        let err = target.sender(bob).restricted_function().motsu_unwrap_err();
+        assert_eq!(
+            err,
+            Vec::<u8>::from(super::managed::Error::Unauthorized(
+                super::managed::AccessManagedUnauthorized { caller: bob }
+            ))
+        );
+
+        manager.sender(alice).grant_role(ROLE, bob, 0).motsu_unwrap();
+        target.sender(bob).restricted_function().motsu_unwrap();
+        assert!(target.sender(alice).called.get());
+    }
+
+    #[motsu::test]
+    fn restricted_consumes_scheduled_operation(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        init_target(&target, &manager, alice);
+        let target_address = target.address();
+        manager
+            .sender(alice)
+            .set_target_function_role(target_address, vec![selector()], ROLE)
+            .motsu_unwrap();
+        manager.sender(alice).grant_role(ROLE, bob, DELAY).motsu_unwrap();
+
+        let (operation_id, nonce) = manager
+            .sender(bob)
+            .schedule(target_address, restricted_call(), U48::ZERO)
+            .motsu_unwrap();
+        manager
+            .sender(alice)
+            .schedules
+            .setter(operation_id)
+            .timepoint
+            .set(now() - U48::from(1));
+
+        target.sender(bob).restricted_function().motsu_unwrap();
+
+        assert!(target.sender(alice).called.get());
+        assert_eq!(
+            target.sender(alice).is_consuming_scheduled_op(),
+            FixedBytes::ZERO
+        );
+        manager.assert_emitted(&OperationExecuted { operation_id, nonce });
+    }
+
+    #[motsu::test]
+    fn consume_scheduled_op_reverts_when_not_consuming(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&manager, alice);
+        init_target(&target, &manager, alice);
+        let err = manager
+            .sender(target.address())
+            .consume_scheduled_op(bob, restricted_call())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedConsume(_)));
+    }
+
+    #[motsu::test]
+    fn set_authority_reverts_when_not_authority(
+        manager: Contract<AccessManager>,
+        target: Contract<ManagedTarget>,
+        alice: Address,
+    ) {
+        init(&manager, alice);
+        init_target(&target, &manager, alice);
+        let err = target
+            .sender(alice)
+            .access_managed
+            .set_authority(alice)
+            .motsu_unwrap_err();
+        assert!(matches!(err, super::managed::Error::Unauthorized(_)));
+        assert_eq!(
+            target.sender(alice).access_managed.authority(),
+            manager.address()
+        );
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(AccessManager::supports_interface(
+            <AccessManager as IAccessManager>::INTERFACE_ID.into()
+        ));
+        assert!(AccessManager::supports_interface(
+            <AccessManager as IErc165>::INTERFACE_ID.into()
+        ));
+        assert!(AccessManaged::supports_interface(
+            <AccessManaged as IAccessManaged>::INTERFACE_ID.into()
+        ));
+
+        let fake_interface_id = 0x12345678u32;
+        assert!(!AccessManager::supports_interface(fake_interface_id.into()));
+        assert!(!AccessManaged::supports_interface(fake_interface_id.into()));
+    }
+}
@@ -1,4 +1,5 @@
 //! Contracts implementing access control mechanisms.
 pub mod control;
+pub mod manager;
 pub mod ownable;
 pub mod ownable_two_step;
@@ -13,11 +13,12 @@ use alloc::{vec, vec::Vec};
 use alloy_primitives::{Address, FixedBytes};
 use openzeppelin_stylus_proc::interface_id;
 pub use sol::*;
-use stylus_sdk::{
-    call::MethodError, evm, msg, prelude::*, storage::StorageAddress,
-};
+use stylus_sdk::{call::MethodError, evm, prelude::*, storage::StorageAddress};
 
-use crate::utils::introspection::erc165::{Erc165, IErc165};
+use crate::utils::{
+    context,
+    introspection::erc165::{Erc165, IErc165},
+};
 
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod sol {
@@ -158,7 +159,7 @@ impl IOwnable for Ownable {
 }
 
 impl Ownable {
-    /// Checks if the [`msg::sender`] is set as the owner.
+    /// Checks if the [`context::msg_sender`] is set as the owner.
     ///
     /// # Arguments
     ///
@@ -169,7 +170,7 @@ impl Ownable {
     /// * [`Error::UnauthorizedAccount`] - If called by any account other than
     ///   the owner.
     pub fn only_owner(&self) -> Result<(), Error> {
-        let account = msg::sender();
+        let account = context::msg_sender();
         if self.owner() != account {
             return Err(Error::UnauthorizedAccount(
                 OwnableUnauthorizedAccount { account },
@@ -22,11 +22,14 @@ use core::ops::{Deref, DerefMut};
 use alloy_primitives::{Address, FixedBytes};
 use openzeppelin_stylus_proc::interface_id;
 pub use sol::*;
-use stylus_sdk::{evm, msg, prelude::*, storage::StorageAddress};
+use stylus_sdk::{evm, prelude::*, storage::StorageAddress};
 
 use crate::{
     access::ownable::{self, IOwnable, Ownable, OwnableUnauthorizedAccount},
-    utils::introspection::erc165::{Erc165, IErc165},
+    utils::{
+        context,
+        introspection::erc165::{Erc165, IErc165},
+    },
 };
 
 #[cfg_attr(coverage_nightly, coverage(off))]
@@ -185,7 +188,7 @@ impl IOwnable2Step for Ownable2Step {
     }
 
     fn accept_ownership(&mut self) -> Result<(), Self::Error> {
-        let sender = msg::sender();
+        let sender = context::msg_sender();
         let pending_owner = self.pending_owner();
         if sender != pending_owner {
             return Err(ownable::Error::UnauthorizedAccount(
@@ -0,0 +1,2009 @@
+//! Modular smart account, as defined in [ERC-7579].
+//!
+//! The account delegates its logic to modules, installed and uninstalled by
+//! the account itself:
+//!
+//! * Validators ([`MODULE_TYPE_VALIDATOR`]) validate user operations and
+//!   [ERC-1271] signatures. The signature starts with the address of the
+//!   validator to use, followed by the signature expected by that validator.
+//! * Executors ([`MODULE_TYPE_EXECUTOR`]) execute calls from the account with
+//!   [`IErc7579Execution::execute_from_executor`].
+//! * Fallback handlers ([`MODULE_TYPE_FALLBACK`]) handle calls to functions the
+//!   account doesn't implement. Their `init_data` starts with the selector of
+//!   the handled function. The calldata forwarded to the handler is suffixed
+//!   with the original caller, as in [ERC-2771].
+//! * A single hook ([`MODULE_TYPE_HOOK`]) is called before and after each
+//!   execution, module (un)installation and fallback call.
+//!
+//! The account must re-export the functions of [`AccountErc7579`] and
+//! [`AccountErc7579::_fallback`], and declare the supported interfaces:
+//!
+//! ```ignore
+//! #[entrypoint]
+//! #[storage]
+//! struct MyAccount {
+//!     #[borrow]
+//!     account: AccountErc7579,
+//! }
+//!
+//! #[public]
+//! #[inherit(AccountErc7579)]
+//! impl MyAccount {
+//!     #[fallback]
+//!     #[payable]
+//!     fn fallback(&mut self, calldata: &[u8]) -> ArbResult {
+//!         Ok(self.account._fallback(calldata)?)
+//!     }
+//!
+//!     #[receive]
+//!     fn receive(&mut self) -> Result<(), Vec<u8>> {
+//!         Ok(())
+//!     }
+//!
+//!     fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+//!         AccountErc7579::supports_interface(interface_id)
+//!     }
+//! }
+//! ```
+//!
+//! WARNING: An account without any validator installed can't validate user
+//! operations. Install a validator when the account is initialized.
+//!
+//! [ERC-7579]: https://eips.ethereum.org/EIPS/eip-7579
+//! [ERC-2771]: https://eips.ethereum.org/EIPS/eip-2771
+//! [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use alloy_primitives::{aliases::U192, Address, FixedBytes, B256, U256};
+use alloy_sol_types::SolCall;
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    call::{self, Call, MethodError, RawCall},
+    evm, msg,
+    prelude::*,
+    storage::{StorageAddress, StorageMap},
+};
+
+use crate::{
+    account::{
+        self,
+        utils::{
+            erc4337::{
+                PackedUserOperation, ENTRYPOINT_V07, SIG_VALIDATION_FAILED,
+            },
+            erc7579::{
+                self, decode_batch, decode_delegate, decode_mode,
+                decode_single, ERC7579TryExecuteFail,
+                ERC7579UnsupportedCallType, ERC7579UnsupportedExecType,
+                CALLTYPE_BATCH, CALLTYPE_DELEGATECALL, CALLTYPE_SINGLE,
+                EXECTYPE_DEFAULT, EXECTYPE_TRY, MODULE_TYPE_EXECUTOR,
+                MODULE_TYPE_FALLBACK, MODULE_TYPE_HOOK, MODULE_TYPE_VALIDATOR,
+            },
+        },
+        AccountUnauthorized,
+    },
+    utils::{
+        cryptography::signature_checker::IErc1271,
+        introspection::erc165::{Erc165, IErc165},
+        structs::enumerable_set::EnumerableSet,
+        ReentrantCallHandler,
+    },
+};
+
+/// Identifier of the [`AccountErc7579`] implementation, returned by
+/// [`IErc7579AccountConfig::account_id`].
+pub const ACCOUNT_ID: &str = "openzeppelin-stylus.AccountErc7579.v0.0.0";
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when a `module` of type `module_type_id` is installed.
+        ///
+        /// * `module_type_id` - Type of the module.
+        /// * `module` - Address of the module.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event ModuleInstalled(uint256 moduleTypeId, address module);
+
+        /// Emitted when a `module` of type `module_type_id` is uninstalled.
+        ///
+        /// * `module_type_id` - Type of the module.
+        /// * `module` - Address of the module.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event ModuleUninstalled(uint256 moduleTypeId, address module);
+    }
+
+    sol! {
+        /// The `module` doesn't declare itself of type `module_type_id`.
+        ///
+        /// * `module_type_id` - Expected type of the module.
+        /// * `module` - Address of the module.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7579MismatchedModuleTypeId(uint256 module_type_id, address module);
+
+        /// The `module` of type `module_type_id` is not installed.
+        ///
+        /// * `module_type_id` - Type of the module.
+        /// * `module` - Address of the module.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7579UninstalledModule(uint256 module_type_id, address module);
+
+        /// The `module` of type `module_type_id` is already installed.
+        ///
+        /// * `module_type_id` - Type of the module.
+        /// * `module` - Address of the module.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7579AlreadyInstalledModule(uint256 module_type_id, address module);
+
+        /// The module type `module_type_id` is not supported.
+        ///
+        /// * `module_type_id` - The unsupported module type.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7579UnsupportedModuleType(uint256 module_type_id);
+
+        /// No fallback handler is installed for `selector`.
+        ///
+        /// * `selector` - Selector of the called function.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7579MissingFallbackHandler(bytes4 selector);
+
+        /// The data of a fallback module doesn't start with a selector.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7579CannotDecodeFallbackData();
+    }
+}
+
+mod interface {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::prelude::sol_interface;
+    sol_interface! {
+        interface IErc7579Module {
+            function onInstall(bytes calldata data) external;
+            function onUninstall(bytes calldata data) external;
+            function isModuleType(uint256 module_type_id) external view returns (bool);
+        }
+
+        interface IErc7579Validator {
+            function isValidSignatureWithSender(address sender, bytes32 hash, bytes calldata signature) external view returns (bytes4 magic_value);
+        }
+
+        interface IErc7579Hook {
+            function preCheck(address msg_sender, uint256 value, bytes calldata msg_data) external returns (bytes memory hook_data);
+            function postCheck(bytes calldata hook_data) external;
+        }
+    }
+}
+
+/// Calls of the account and its modules, encoded with their Solidity
+/// signature.
+mod abi {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloy_sol_macro::sol;
+
+    sol! {
+        function execute(bytes32 mode, bytes executionCalldata);
+        function executeFromExecutor(bytes32 mode, bytes executionCalldata);
+        function installModule(uint256 moduleTypeId, address module, bytes initData);
+        function uninstallModule(uint256 moduleTypeId, address module, bytes deInitData);
+        function validateUserOp(
+            (address, uint256, bytes, bytes, bytes32, uint256, bytes32, bytes, bytes) userOp,
+            bytes32 userOpHash
+        ) returns (uint256 validationData);
+    }
+}
+
+/// An [`AccountErc7579`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Unauthorized call to the account.
+    Unauthorized(AccountUnauthorized),
+    /// The module doesn't declare itself of the expected type.
+    MismatchedModuleTypeId(ERC7579MismatchedModuleTypeId),
+    /// The module is not installed.
+    UninstalledModule(ERC7579UninstalledModule),
+    /// The module is already installed.
+    AlreadyInstalledModule(ERC7579AlreadyInstalledModule),
+    /// The module type is not supported.
+    UnsupportedModuleType(ERC7579UnsupportedModuleType),
+    /// No fallback handler is installed for the called function.
+    MissingFallbackHandler(ERC7579MissingFallbackHandler),
+    /// The data of a fallback module doesn't start with a selector.
+    CannotDecodeFallbackData(ERC7579CannotDecodeFallbackData),
+    /// Error decoding an execution mode or execution calldata.
+    Erc7579(erc7579::Error),
+    /// A call to an address target failed. Contains its revert data as is.
+    FailedCall(call::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`AccountErc7579`] contract.
+#[storage]
+pub struct AccountErc7579 {
+    /// Installed validator modules.
+    pub(crate) validators: EnumerableSet<Address>,
+    /// Installed executor modules.
+    pub(crate) executors: EnumerableSet<Address>,
+    /// Mapping from a function selector to its fallback handler.
+    pub(crate) fallbacks: StorageMap<FixedBytes<4>, StorageAddress>,
+    /// Installed hook module, or `Address::ZERO` if none.
+    pub(crate) hook: StorageAddress,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for AccountErc7579 {}
+
+/// Execution interface of an [ERC-7579] account.
+///
+/// [ERC-7579]: https://eips.ethereum.org/EIPS/eip-7579
+#[interface_id]
+pub trait IErc7579Execution {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Executes calls from the account, as described by `mode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `mode` - The execution mode, see [`erc7579`].
+    /// * `execution_calldata` - The calls, encoded according to `mode`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is neither the entry point nor
+    ///   the account itself.
+    /// * [`Error::Erc7579`] - If `mode` is not supported, or if
+    ///   `execution_calldata` can't be decoded.
+    /// * [`Error::FailedCall`] - If a call reverts with the
+    ///   [`EXECTYPE_DEFAULT`] execution type, or if the hook reverts.
+    ///
+    /// # Events
+    ///
+    /// * [`ERC7579TryExecuteFail`] - For each call reverting with the
+    ///   [`EXECTYPE_TRY`] execution type.
+    fn execute(
+        &mut self,
+        mode: B256,
+        execution_calldata: Bytes,
+    ) -> Result<(), Self::Error>;
+
+    /// Executes calls from the account on behalf of an executor module, and
+    /// returns the data returned by each call.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `mode` - The execution mode, see [`erc7579`].
+    /// * `execution_calldata` - The calls, encoded according to `mode`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UninstalledModule`] - If the caller is not an installed
+    ///   executor.
+    /// * [`Error::Erc7579`] - If `mode` is not supported, or if
+    ///   `execution_calldata` can't be decoded.
+    /// * [`Error::FailedCall`] - If a call reverts with the
+    ///   [`EXECTYPE_DEFAULT`] execution type, or if the hook reverts.
+    ///
+    /// # Events
+    ///
+    /// * [`ERC7579TryExecuteFail`] - For each call reverting with the
+    ///   [`EXECTYPE_TRY`] execution type.
+    fn execute_from_executor(
+        &mut self,
+        mode: B256,
+        execution_calldata: Bytes,
+    ) -> Result<Vec<Bytes>, Self::Error>;
+}
+
+/// Configuration interface of an [ERC-7579] account.
+///
+/// [ERC-7579]: https://eips.ethereum.org/EIPS/eip-7579
+#[interface_id]
+pub trait IErc7579AccountConfig {
+    /// Returns the identifier of the account implementation, formatted as
+    /// `vendorname.accountname.semver`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn account_id(&self) -> String;
+
+    /// Returns whether the account supports the execution `mode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `mode` - The execution mode, see [`erc7579`].
+    fn supports_execution_mode(&self, mode: B256) -> bool;
+
+    /// Returns whether the account supports modules of type
+    /// `module_type_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `module_type_id` - The module type.
+    fn supports_module(&self, module_type_id: U256) -> bool;
+}
+
+/// Module management interface of an [ERC-7579] account.
+///
+/// [ERC-7579]: https://eips.ethereum.org/EIPS/eip-7579
+#[interface_id]
+pub trait IErc7579ModuleConfig {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Installs a `module` of type `module_type_id`, and calls its
+    /// `onInstall` function with `init_data`.
+    ///
+    /// For fallback modules, `init_data` starts with the selector of the
+    /// handled function, and only the rest is passed to `onInstall`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `module_type_id` - Type of the module.
+    /// * `module` - Address of the module.
+    /// * `init_data` - Data passed to the module.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is neither the entry point nor
+    ///   the account itself.
+    /// * [`Error::UnsupportedModuleType`] - If `module_type_id` is not
+    ///   supported.
+    /// * [`Error::MismatchedModuleTypeId`] - If `module` is not of type
+    ///   `module_type_id`.
+    /// * [`Error::AlreadyInstalledModule`] - If `module` is already installed,
+    ///   if a handler is already installed for the selector, or if a hook is
+    ///   already installed.
+    /// * [`Error::CannotDecodeFallbackData`] - If the `init_data` of a fallback
+    ///   module is shorter than a selector.
+    /// * [`Error::FailedCall`] - If the call to the module or to the hook
+    ///   reverts.
+    ///
+    /// # Events
+    ///
+    /// * [`ModuleInstalled`].
+    fn install_module(
+        &mut self,
+        module_type_id: U256,
+        module: Address,
+        init_data: Bytes,
+    ) -> Result<(), Self::Error>;
+
+    /// Uninstalls a `module` of type `module_type_id`, and calls its
+    /// `onUninstall` function with `de_init_data`.
+    ///
+    /// For fallback modules, `de_init_data` starts with the selector of the
+    /// handled function, and only the rest is passed to `onUninstall`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `module_type_id` - Type of the module.
+    /// * `module` - Address of the module.
+    /// * `de_init_data` - Data passed to the module.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is neither the entry point nor
+    ///   the account itself.
+    /// * [`Error::UnsupportedModuleType`] - If `module_type_id` is not
+    ///   supported.
+    /// * [`Error::UninstalledModule`] - If `module` is not installed.
+    /// * [`Error::CannotDecodeFallbackData`] - If the `de_init_data` of a
+    ///   fallback module is shorter than a selector.
+    /// * [`Error::FailedCall`] - If the call to the module or to the hook
+    ///   reverts.
+    ///
+    /// # Events
+    ///
+    /// * [`ModuleUninstalled`].
+    fn uninstall_module(
+        &mut self,
+        module_type_id: U256,
+        module: Address,
+        de_init_data: Bytes,
+    ) -> Result<(), Self::Error>;
+
+    /// Returns whether a `module` of type `module_type_id` is installed.
+    ///
+    /// For fallback modules, `additional_context` starts with the selector of
+    /// the handled function.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `module_type_id` - Type of the module.
+    /// * `module` - Address of the module.
+    /// * `additional_context` - Additional data depending on the module type.
+    fn is_module_installed(
+        &self,
+        module_type_id: U256,
+        module: Address,
+        additional_context: Bytes,
+    ) -> bool;
+}
+
+#[public]
+impl AccountErc7579 {
+    /// Returns the address of the entry point trusted by the account.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn entry_point(&self) -> Address {
+        ENTRYPOINT_V07
+    }
+
+    /// Returns the next nonce of the account for the given `key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `key` - The 192-bit key of the nonce.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call to the entry point fails.
+    pub fn get_nonce(&mut self, key: U192) -> Result<U256, Error> {
+        Ok(account::get_nonce(self, key)?)
+    }
+
+    /// Validates a user operation with the validator module whose address
+    /// prefixes its signature, and pays the entry point the missing funds
+    /// for its execution.
+    ///
+    /// Returns the validation data returned by the validator, or
+    /// [`SIG_VALIDATION_FAILED`] if the validator is not installed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `user_op` - The user operation to validate.
+    /// * `user_op_hash` - Hash of the user operation, computed by the entry
+    ///   point.
+    /// * `missing_account_funds` - Amount the account must send to the entry
+    ///   point to pay for the operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is not the entry point.
+    /// * [`Error::FailedCall`] - If the call to the validator reverts.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn validate_user_op(
+        &mut self,
+        user_op: PackedUserOperation,
+        user_op_hash: B256,
+        missing_account_funds: U256,
+    ) -> Result<U256, Error> {
+        account::only_entry_point()?;
+        let validation_data = self._validate_user_op(&user_op, user_op_hash)?;
+        account::pay_prefund(missing_account_funds);
+        Ok(validation_data)
+    }
+
+    /// See [`IErc7579Execution::execute`].
+    #[allow(clippy::missing_errors_doc)]
+    #[payable]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn execute(
+        &mut self,
+        mode: B256,
+        execution_calldata: Bytes,
+    ) -> Result<(), Error> {
+        IErc7579Execution::execute(self, mode, execution_calldata)
+    }
+
+    /// See [`IErc7579Execution::execute_from_executor`].
+    #[allow(clippy::missing_errors_doc)]
+    #[payable]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn execute_from_executor(
+        &mut self,
+        mode: B256,
+        execution_calldata: Bytes,
+    ) -> Result<Vec<Bytes>, Error> {
+        IErc7579Execution::execute_from_executor(self, mode, execution_calldata)
+    }
+
+    /// Validates an [ERC-1271] signature with the validator module whose
+    /// address prefixes `signature`, by calling its
+    /// `isValidSignatureWithSender` function with the caller and the rest of
+    /// `signature`.
+    ///
+    /// Returns the value returned by the validator, or `0xffffffff` if
+    /// `signature` doesn't start with an installed validator or if the call
+    /// to the validator reverts.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `hash` - Hash of the signed data.
+    /// * `signature` - Address of the validator, followed by the signature
+    ///   expected by that validator.
+    ///
+    /// [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn is_valid_signature(
+        &self,
+        hash: B256,
+        signature: Bytes,
+    ) -> FixedBytes<4> {
+        IErc1271::is_valid_signature(self, hash, signature)
+    }
+
+    /// See [`IErc7579AccountConfig::account_id`].
+    #[must_use]
+    pub fn account_id(&self) -> String {
+        IErc7579AccountConfig::account_id(self)
+    }
+
+    /// See [`IErc7579AccountConfig::supports_execution_mode`].
+    #[must_use]
+    pub fn supports_execution_mode(&self, mode: B256) -> bool {
+        IErc7579AccountConfig::supports_execution_mode(self, mode)
+    }
+
+    /// See [`IErc7579AccountConfig::supports_module`].
+    #[must_use]
+    pub fn supports_module(&self, module_type_id: U256) -> bool {
+        IErc7579AccountConfig::supports_module(self, module_type_id)
+    }
+
+    /// See [`IErc7579ModuleConfig::install_module`].
+    #[allow(clippy::missing_errors_doc)]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn install_module(
+        &mut self,
+        module_type_id: U256,
+        module: Address,
+        init_data: Bytes,
+    ) -> Result<(), Error> {
+        IErc7579ModuleConfig::install_module(
+            self,
+            module_type_id,
+            module,
+            init_data,
+        )
+    }
+
+    /// See [`IErc7579ModuleConfig::uninstall_module`].
+    #[allow(clippy::missing_errors_doc)]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn uninstall_module(
+        &mut self,
+        module_type_id: U256,
+        module: Address,
+        de_init_data: Bytes,
+    ) -> Result<(), Error> {
+        IErc7579ModuleConfig::uninstall_module(
+            self,
+            module_type_id,
+            module,
+            de_init_data,
+        )
+    }
+
+    /// See [`IErc7579ModuleConfig::is_module_installed`].
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn is_module_installed(
+        &self,
+        module_type_id: U256,
+        module: Address,
+        additional_context: Bytes,
+    ) -> bool {
+        IErc7579ModuleConfig::is_module_installed(
+            self,
+            module_type_id,
+            module,
+            additional_context,
+        )
+    }
+}
+
+impl IErc7579Execution for AccountErc7579 {
+    type Error = Error;
+
+    fn execute(
+        &mut self,
+        mode: B256,
+        execution_calldata: Bytes,
+    ) -> Result<(), Self::Error> {
+        account::only_entry_point_or_self()?;
+
+        let msg_data = abi::executeCall {
+            mode,
+            executionCalldata: execution_calldata.to_vec().into(),
+        }
+        .abi_encode();
+        let (hook, hook_data) = self._pre_check(&msg_data)?;
+        self._execute(mode, &execution_calldata)?;
+        self._post_check(hook, hook_data)
+    }
+
+    fn execute_from_executor(
+        &mut self,
+        mode: B256,
+        execution_calldata: Bytes,
+    ) -> Result<Vec<Bytes>, Self::Error> {
+        let sender = msg::sender();
+        if !self.executors.contains(sender) {
+            return Err(ERC7579UninstalledModule {
+                module_type_id: MODULE_TYPE_EXECUTOR,
+                module: sender,
+            }
+            .into());
+        }
+
+        let msg_data = abi::executeFromExecutorCall {
+            mode,
+            executionCalldata: execution_calldata.to_vec().into(),
+        }
+        .abi_encode();
+        let (hook, hook_data) = self._pre_check(&msg_data)?;
+        let results = self._execute(mode, &execution_calldata)?;
+        self._post_check(hook, hook_data)?;
+        Ok(results)
+    }
+}
+
+impl IErc7579AccountConfig for AccountErc7579 {
+    fn account_id(&self) -> String {
+        ACCOUNT_ID.to_string()
+    }
+
+    fn supports_execution_mode(&self, mode: B256) -> bool {
+        let (call_type, exec_type, _, _) = decode_mode(mode);
+        [CALLTYPE_SINGLE, CALLTYPE_BATCH, CALLTYPE_DELEGATECALL]
+            .contains(&call_type)
+            && [EXECTYPE_DEFAULT, EXECTYPE_TRY].contains(&exec_type)
+    }
+
+    fn supports_module(&self, module_type_id: U256) -> bool {
+        [
+            MODULE_TYPE_VALIDATOR,
+            MODULE_TYPE_EXECUTOR,
+            MODULE_TYPE_FALLBACK,
+            MODULE_TYPE_HOOK,
+        ]
+        .contains(&module_type_id)
+    }
+}
+
+impl IErc7579ModuleConfig for AccountErc7579 {
+    type Error = Error;
+
+    fn install_module(
+        &mut self,
+        module_type_id: U256,
+        module: Address,
+        init_data: Bytes,
+    ) -> Result<(), Self::Error> {
+        account::only_entry_point_or_self()?;
+
+        let msg_data = abi::installModuleCall {
+            moduleTypeId: module_type_id,
+            module,
+            initData: init_data.to_vec().into(),
+        }
+        .abi_encode();
+        let (hook, hook_data) = self._pre_check(&msg_data)?;
+        self._install_module(module_type_id, module, &init_data)?;
+        self._post_check(hook, hook_data)
+    }
+
+    fn uninstall_module(
+        &mut self,
+        module_type_id: U256,
+        module: Address,
+        de_init_data: Bytes,
+    ) -> Result<(), Self::Error> {
+        account::only_entry_point_or_self()?;
+
+        let msg_data = abi::uninstallModuleCall {
+            moduleTypeId: module_type_id,
+            module,
+            deInitData: de_init_data.to_vec().into(),
+        }
+        .abi_encode();
+        let (hook, hook_data) = self._pre_check(&msg_data)?;
+        self._uninstall_module(module_type_id, module, &de_init_data)?;
+        self._post_check(hook, hook_data)
+    }
+
+    fn is_module_installed(
+        &self,
+        module_type_id: U256,
+        module: Address,
+        additional_context: Bytes,
+    ) -> bool {
+        if module_type_id == MODULE_TYPE_VALIDATOR {
+            self.validators.contains(module)
+        } else if module_type_id == MODULE_TYPE_EXECUTOR {
+            self.executors.contains(module)
+        } else if module_type_id == MODULE_TYPE_FALLBACK {
+            Self::decode_fallback_data(&additional_context).is_ok_and(
+                |(selector, _)| self.fallbacks.get(selector) == module,
+            )
+        } else if module_type_id == MODULE_TYPE_HOOK {
+            self.hook.get() == module
+        } else {
+            false
+        }
+    }
+}
+
+impl IErc1271 for AccountErc7579 {
+    fn is_valid_signature(
+        &self,
+        hash: B256,
+        signature: Bytes,
+    ) -> FixedBytes<4> {
+        let invalid = FixedBytes::repeat_byte(0xff);
+        if signature.len() < 20 {
+            return invalid;
+        }
+
+        let validator = Address::from_slice(&signature[..20]);
+        if !self.validators.contains(validator) {
+            return invalid;
+        }
+
+        interface::IErc7579Validator::new(validator)
+            .is_valid_signature_with_sender(
+                self,
+                msg::sender(),
+                hash,
+                signature[20..].to_vec().into(),
+            )
+            .unwrap_or(invalid)
+    }
+}
+
+impl IErc165 for AccountErc7579 {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        let interface_id = u32::from_be_bytes(*interface_id);
+        <Self as IErc7579Execution>::INTERFACE_ID == interface_id
+            || <Self as IErc7579AccountConfig>::INTERFACE_ID == interface_id
+            || <Self as IErc7579ModuleConfig>::INTERFACE_ID == interface_id
+            || <Self as IErc1271>::INTERFACE_ID == interface_id
+            || Erc165::supports_interface(interface_id.into())
+    }
+}
+
+impl AccountErc7579 {
+    /// Returns the address of the installed hook, or `Address::ZERO` if none.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn hook(&self) -> Address {
+        self.hook.get()
+    }
+
+    /// Forwards a call to a function the account doesn't implement to its
+    /// fallback handler, and returns the data returned by the handler.
+    ///
+    /// The original caller is appended to the forwarded calldata.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `calldata` - Calldata of the call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::MissingFallbackHandler`] - If no handler is installed for the
+    ///   selector of the call.
+    /// * [`Error::FailedCall`] - If the handler or the hook reverts.
+    pub fn _fallback(&mut self, calldata: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut selector = FixedBytes::<4>::ZERO;
+        let len = calldata.len().min(4);
+        selector[..len].copy_from_slice(&calldata[..len]);
+
+        let handler = self.fallbacks.get(selector);
+        if handler.is_zero() {
+            return Err(ERC7579MissingFallbackHandler { selector }.into());
+        }
+
+        let (hook, hook_data) = self._pre_check(calldata)?;
+        let data = [calldata, msg::sender().as_slice()].concat();
+        let result = RawCall::new_with_value(msg::value())
+            .call_with_reentrant_handling(handler, &data)
+            .map_err(call::Error::Revert)?;
+        self._post_check(hook, hook_data)?;
+
+        Ok(result)
+    }
+
+    /// Validates a user operation with the validator module whose address
+    /// prefixes its signature.
+    ///
+    /// Returns [`SIG_VALIDATION_FAILED`] if the signature doesn't start with
+    /// an installed validator.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `user_op` - The user operation to validate.
+    /// * `user_op_hash` - Hash of the user operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call to the validator reverts.
+    pub fn _validate_user_op(
+        &mut self,
+        user_op: &PackedUserOperation,
+        user_op_hash: B256,
+    ) -> Result<U256, Error> {
+        let signature = &user_op.8;
+        if signature.len() < 20 {
+            return Ok(SIG_VALIDATION_FAILED);
+        }
+
+        let validator = Address::from_slice(&signature[..20]);
+        if !self.validators.contains(validator) {
+            return Ok(SIG_VALIDATION_FAILED);
+        }
+
+        let (
+            sender,
+            nonce,
+            init_code,
+            call_data,
+            account_gas_limits,
+            pre_verification_gas,
+            gas_fees,
+            paymaster_and_data,
+            signature,
+        ) = user_op;
+        let call = abi::validateUserOpCall {
+            userOp: (
+                *sender,
+                *nonce,
+                init_code.to_vec().into(),
+                call_data.to_vec().into(),
+                *account_gas_limits,
+                *pre_verification_gas,
+                *gas_fees,
+                paymaster_and_data.to_vec().into(),
+                signature.to_vec().into(),
+            ),
+            userOpHash: user_op_hash,
+        };
+
+        let result = RawCall::new()
+            .call_with_reentrant_handling(validator, &call.abi_encode())
+            .map_err(call::Error::Revert)?;
+
+        // A validator returning malformed data is treated as a failure.
+        Ok(abi::validateUserOpCall::abi_decode_returns(&result, true)
+            .map_or(SIG_VALIDATION_FAILED, |ret| ret.validationData))
+    }
+
+    /// Executes the calls in `execution_calldata`, as described by `mode`,
+    /// and returns the data returned by each call.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `mode` - The execution mode.
+    /// * `execution_calldata` - The calls, encoded according to `mode`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Erc7579`] - If `mode` is not supported, or if
+    ///   `execution_calldata` can't be decoded.
+    /// * [`Error::FailedCall`] - If a call reverts with the
+    ///   [`EXECTYPE_DEFAULT`] execution type.
+    ///
+    /// # Events
+    ///
+    /// * [`ERC7579TryExecuteFail`] - For each call reverting with the
+    ///   [`EXECTYPE_TRY`] execution type.
+    pub fn _execute(
+        &mut self,
+        mode: B256,
+        execution_calldata: &[u8],
+    ) -> Result<Vec<Bytes>, Error> {
+        let (call_type, exec_type, _, _) = decode_mode(mode);
+        if exec_type != EXECTYPE_DEFAULT && exec_type != EXECTYPE_TRY {
+            return Err(erc7579::Error::from(ERC7579UnsupportedExecType {
+                exec_type,
+            })
+            .into());
+        }
+
+        if call_type == CALLTYPE_SINGLE {
+            let (target, value, data) = decode_single(execution_calldata)?;
+            let call = RawCall::new_with_value(value);
+            Ok(vec![Self::_call(0, exec_type, call, target, data)?])
+        } else if call_type == CALLTYPE_BATCH {
+            decode_batch(execution_calldata)?
+                .into_iter()
+                .enumerate()
+                .map(|(index, (target, value, data))| {
+                    let call = RawCall::new_with_value(value);
+                    Self::_call(index, exec_type, call, target, &data)
+                })
+                .collect()
+        } else if call_type == CALLTYPE_DELEGATECALL {
+            let (target, data) = decode_delegate(execution_calldata)?;
+            let call = RawCall::new_delegate();
+            Ok(vec![Self::_call(0, exec_type, call, target, data)?])
+        } else {
+            Err(erc7579::Error::from(ERC7579UnsupportedCallType { call_type })
+                .into())
+        }
+    }
+
+    /// Installs a `module` of type `module_type_id`, and calls its
+    /// `onInstall` function with `init_data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `module_type_id` - Type of the module.
+    /// * `module` - Address of the module.
+    /// * `init_data` - Data passed to the module.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnsupportedModuleType`] - If `module_type_id` is not
+    ///   supported.
+    /// * [`Error::MismatchedModuleTypeId`] - If `module` is not of type
+    ///   `module_type_id`.
+    /// * [`Error::AlreadyInstalledModule`] - If `module` is already installed,
+    ///   if a handler is already installed for the selector, or if a hook is
+    ///   already installed.
+    /// * [`Error::CannotDecodeFallbackData`] - If the `init_data` of a fallback
+    ///   module is shorter than a selector.
+    /// * [`Error::FailedCall`] - If the call to the module reverts.
+    ///
+    /// # Events
+    ///
+    /// * [`ModuleInstalled`].
+    pub fn _install_module(
+        &mut self,
+        module_type_id: U256,
+        module: Address,
+        init_data: &[u8],
+    ) -> Result<(), Error> {
+        if !IErc7579AccountConfig::supports_module(self, module_type_id) {
+            return Err(ERC7579UnsupportedModuleType { module_type_id }.into());
+        }
+
+        let is_module_type = interface::IErc7579Module::new(module)
+            .is_module_type(Call::new_in(self), module_type_id)?;
+        if !is_module_type {
+            return Err(ERC7579MismatchedModuleTypeId {
+                module_type_id,
+                module,
+            }
+            .into());
+        }
+
+        let already_installed = |module| {
+            Error::from(ERC7579AlreadyInstalledModule {
+                module_type_id,
+                module,
+            })
+        };
+
+        let mut data = init_data;
+        if module_type_id == MODULE_TYPE_VALIDATOR {
+            if !self.validators.add(module) {
+                return Err(already_installed(module));
+            }
+        } else if module_type_id == MODULE_TYPE_EXECUTOR {
+            if !self.executors.add(module) {
+                return Err(already_installed(module));
+            }
+        } else if module_type_id == MODULE_TYPE_FALLBACK {
+            let (selector, inner_data) = Self::decode_fallback_data(init_data)?;
+            if !self.fallbacks.get(selector).is_zero() {
+                return Err(already_installed(module));
+            }
+            self.fallbacks.setter(selector).set(module);
+            data = inner_data;
+        } else {
+            let hook = self.hook.get();
+            if !hook.is_zero() {
+                return Err(already_installed(hook));
+            }
+            self.hook.set(module);
+        }
+
+        interface::IErc7579Module::new(module)
+            .on_install(Call::new_in(self), data.to_vec().into())?;
+
+        evm::log(ModuleInstalled { moduleTypeId: module_type_id, module });
+
+        Ok(())
+    }
+
+    /// Uninstalls a `module` of type `module_type_id`, and calls its
+    /// `onUninstall` function with `de_init_data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `module_type_id` - Type of the module.
+    /// * `module` - Address of the module.
+    /// * `de_init_data` - Data passed to the module.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnsupportedModuleType`] - If `module_type_id` is not
+    ///   supported.
+    /// * [`Error::UninstalledModule`] - If `module` is not installed.
+    /// * [`Error::CannotDecodeFallbackData`] - If the `de_init_data` of a
+    ///   fallback module is shorter than a selector.
+    /// * [`Error::FailedCall`] - If the call to the module reverts.
+    ///
+    /// # Events
+    ///
+    /// * [`ModuleUninstalled`].
+    pub fn _uninstall_module(
+        &mut self,
+        module_type_id: U256,
+        module: Address,
+        de_init_data: &[u8],
+    ) -> Result<(), Error> {
+        if !IErc7579AccountConfig::supports_module(self, module_type_id) {
+            return Err(ERC7579UnsupportedModuleType { module_type_id }.into());
+        }
+
+        let uninstalled =
+            Error::from(ERC7579UninstalledModule { module_type_id, module });
+
+        let mut data = de_init_data;
+        if module_type_id == MODULE_TYPE_VALIDATOR {
+            if !self.validators.remove(module) {
+                return Err(uninstalled);
+            }
+        } else if module_type_id == MODULE_TYPE_EXECUTOR {
+            if !self.executors.remove(module) {
+                return Err(uninstalled);
+            }
+        } else if module_type_id == MODULE_TYPE_FALLBACK {
+            let (selector, inner_data) =
+                Self::decode_fallback_data(de_init_data)?;
+            if self.fallbacks.get(selector) != module {
+                return Err(uninstalled);
+            }
+            self.fallbacks.delete(selector);
+            data = inner_data;
+        } else {
+            if self.hook.get() != module {
+                return Err(uninstalled);
+            }
+            self.hook.set(Address::ZERO);
+        }
+
+        interface::IErc7579Module::new(module)
+            .on_uninstall(Call::new_in(self), data.to_vec().into())?;
+
+        evm::log(ModuleUninstalled { moduleTypeId: module_type_id, module });
+
+        Ok(())
+    }
+
+    /// Calls the `preCheck` function of the installed hook, if any, with the
+    /// calldata of the current call.
+    ///
+    /// Returns the address of the hook and the data returned by it, to be
+    /// passed to [`Self::_post_check`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `msg_data` - Calldata of the current call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the hook reverts.
+    fn _pre_check(
+        &mut self,
+        msg_data: &[u8],
+    ) -> Result<(Address, alloy_primitives::Bytes), Error> {
+        let hook = self.hook.get();
+        if hook.is_zero() {
+            return Ok((hook, alloy_primitives::Bytes::new()));
+        }
+
+        let hook_data = interface::IErc7579Hook::new(hook).pre_check(
+            Call::new_in(self),
+            msg::sender(),
+            msg::value(),
+            msg_data.to_vec().into(),
+        )?;
+        Ok((hook, hook_data))
+    }
+
+    /// Calls the `postCheck` function of `hook` with the data returned by
+    /// [`Self::_pre_check`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `hook` - Address of the hook, or `Address::ZERO` if none.
+    /// * `hook_data` - Data returned by the `preCheck` function of the hook.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the hook reverts.
+    fn _post_check(
+        &mut self,
+        hook: Address,
+        hook_data: alloy_primitives::Bytes,
+    ) -> Result<(), Error> {
+        if hook.is_zero() {
+            return Ok(());
+        }
+
+        interface::IErc7579Hook::new(hook)
+            .post_check(Call::new_in(self), hook_data)?;
+        Ok(())
+    }
+
+    /// Performs a single call of an execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the call in the batch.
+    /// * `exec_type` - The execution type.
+    /// * `call` - The configured raw call.
+    /// * `target` - Address of the called contract.
+    /// * `data` - Calldata of the call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call reverts with the
+    ///   [`EXECTYPE_DEFAULT`] execution type.
+    ///
+    /// # Events
+    ///
+    /// * [`ERC7579TryExecuteFail`] - If the call reverts with the
+    ///   [`EXECTYPE_TRY`] execution type.
+    fn _call(
+        index: usize,
+        exec_type: FixedBytes<1>,
+        call: RawCall,
+        target: Address,
+        data: &[u8],
+    ) -> Result<Bytes, Error> {
+        match call.call_with_reentrant_handling(target, data) {
+            Ok(returndata) => Ok(returndata.into()),
+            Err(returndata) if exec_type == EXECTYPE_TRY => {
+                evm::log(ERC7579TryExecuteFail {
+                    batchExecutionIndex: U256::from(index),
+                    returndata: returndata.clone().into(),
+                });
+                Ok(returndata.into())
+            }
+            Err(returndata) => Err(call::Error::Revert(returndata).into()),
+        }
+    }
+
+    /// Splits the data of a fallback module into the handled selector and
+    /// the data passed to the module.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Data of the fallback module.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::CannotDecodeFallbackData`] - If `data` is shorter than a
+    ///   selector.
+    fn decode_fallback_data(
+        data: &[u8],
+    ) -> Result<(FixedBytes<4>, &[u8]), Error> {
+        if data.len() < 4 {
+            return Err(ERC7579CannotDecodeFallbackData {}.into());
+        }
+        Ok((FixedBytes::from_slice(&data[..4]), &data[4..]))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{
+        fixed_bytes, keccak256, Address, FixedBytes, B256, U256,
+    };
+    use alloy_sol_types::SolCall;
+    use motsu::prelude::*;
+    use stylus_sdk::{abi::Bytes, call, prelude::*, storage::StorageU256};
+
+    use super::*;
+    use crate::{
+        account::utils::{
+            erc4337::SIG_VALIDATION_SUCCESS,
+            erc7579::{encode_batch, encode_mode, encode_single},
+        },
+        utils::cryptography::signature_checker::ERC1271_MAGIC_VALUE,
+    };
+
+    const HOOK_DATA: [u8; 1] = [0x42];
+    /// Selector handled by `ModuleMock` when installed as a fallback module.
+    const SELECTOR: FixedBytes<4> = fixed_bytes!("12345678");
+
+    mod target_abi {
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function setValue(uint256 value);
+            function fail();
+        }
+    }
+
+    #[storage]
+    struct ModuleMock {
+        module_type: StorageU256,
+        installs: StorageU256,
+        uninstalls: StorageU256,
+        pre_checks: StorageU256,
+        post_checks: StorageU256,
+    }
+
+    unsafe impl TopLevelStorage for ModuleMock {}
+
+    #[public]
+    impl ModuleMock {
+        fn is_module_type(&self, module_type_id: U256) -> bool {
+            self.module_type.get() == module_type_id
+        }
+
+        fn on_install(&mut self, _data: Bytes) {
+            let installs = self.installs.get();
+            self.installs.set(installs + U256::from(1));
+        }
+
+        fn on_uninstall(&mut self, _data: Bytes) {
+            let uninstalls = self.uninstalls.get();
+            self.uninstalls.set(uninstalls + U256::from(1));
+        }
+
+        /// Accepts signatures made of the module address and the hash.
+        #[allow(clippy::needless_pass_by_value, clippy::unused_self)]
+        fn validate_user_op(
+            &self,
+            user_op: PackedUserOperation,
+            user_op_hash: B256,
+        ) -> U256 {
+            if user_op.8[20..] == user_op_hash[..] {
+                SIG_VALIDATION_SUCCESS
+            } else {
+                SIG_VALIDATION_FAILED
+            }
+        }
+
+        /// Accepts signatures made of the hash.
+        #[allow(clippy::needless_pass_by_value, clippy::unused_self)]
+        fn is_valid_signature_with_sender(
+            &self,
+            _sender: Address,
+            hash: B256,
+            signature: Bytes,
+        ) -> FixedBytes<4> {
+            if signature[..] == hash[..] {
+                ERC1271_MAGIC_VALUE
+            } else {
+                FixedBytes::ZERO
+            }
+        }
+
+        fn pre_check(
+            &mut self,
+            _msg_sender: Address,
+            _value: U256,
+            _msg_data: Bytes,
+        ) -> Bytes {
+            let pre_checks = self.pre_checks.get();
+            self.pre_checks.set(pre_checks + U256::from(1));
+            HOOK_DATA.to_vec().into()
+        }
+
+        fn post_check(&mut self, hook_data: Bytes) -> Result<(), Vec<u8>> {
+            if hook_data.as_slice() != HOOK_DATA {
+                return Err(b"invalid hook data".to_vec());
+            }
+            let post_checks = self.post_checks.get();
+            self.post_checks.set(post_checks + U256::from(1));
+            Ok(())
+        }
+    }
+
+    #[storage]
+    struct TargetMock {
+        value: StorageU256,
+    }
+
+    unsafe impl TopLevelStorage for TargetMock {}
+
+    #[public]
+    impl TargetMock {
+        fn set_value(&mut self, value: U256) {
+            self.value.set(value);
+        }
+
+        #[allow(clippy::unused_self)]
+        fn fail(&mut self) -> Result<(), Vec<u8>> {
+            Err(b"target failed".to_vec())
+        }
+    }
+
+    fn mode(call_type: FixedBytes<1>, exec_type: FixedBytes<1>) -> B256 {
+        encode_mode(call_type, exec_type, FixedBytes::ZERO, FixedBytes::ZERO)
+    }
+
+    fn set_value_call(value: u64) -> Vec<u8> {
+        target_abi::setValueCall { value: U256::from(value) }.abi_encode()
+    }
+
+    fn selector(signature: &str) -> u32 {
+        u32::from_be_bytes(
+            keccak256(signature)[..4].try_into().expect("should be 4 bytes"),
+        )
+    }
+
+    fn user_op(signature: &[u8]) -> PackedUserOperation {
+        (
+            Address::ZERO,
+            U256::ZERO,
+            Bytes::from(vec![]),
+            Bytes::from(vec![]),
+            B256::ZERO,
+            U256::ZERO,
+            B256::ZERO,
+            Bytes::from(vec![]),
+            Bytes::from(signature.to_vec()),
+        )
+    }
+
+    fn install(
+        account: &Contract<AccountErc7579>,
+        module: &Contract<ModuleMock>,
+        module_type_id: U256,
+        init_data: &[u8],
+    ) {
+        module.sender(Address::ZERO).module_type.set(module_type_id);
+        account
+            .sender(account.address())
+            .install_module(
+                module_type_id,
+                module.address(),
+                init_data.to_vec().into(),
+            )
+            .motsu_expect("should install module");
+    }
+
+    #[motsu::test]
+    fn interface_ids() {
+        let execution = selector("execute(bytes32,bytes)")
+            ^ selector("executeFromExecutor(bytes32,bytes)");
+        assert_eq!(
+            <AccountErc7579 as IErc7579Execution>::INTERFACE_ID,
+            execution
+        );
+
+        let account_config = selector("accountId()")
+            ^ selector("supportsExecutionMode(bytes32)")
+            ^ selector("supportsModule(uint256)");
+        assert_eq!(
+            <AccountErc7579 as IErc7579AccountConfig>::INTERFACE_ID,
+            account_config
+        );
+
+        let module_config = selector("installModule(uint256,address,bytes)")
+            ^ selector("uninstallModule(uint256,address,bytes)")
+            ^ selector("isModuleInstalled(uint256,address,bytes)");
+        assert_eq!(
+            <AccountErc7579 as IErc7579ModuleConfig>::INTERFACE_ID,
+            module_config
+        );
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(AccountErc7579::supports_interface(
+            <AccountErc7579 as IErc7579Execution>::INTERFACE_ID.into()
+        ));
+        assert!(AccountErc7579::supports_interface(
+            <AccountErc7579 as IErc7579AccountConfig>::INTERFACE_ID.into()
+        ));
+        assert!(AccountErc7579::supports_interface(
+            <AccountErc7579 as IErc7579ModuleConfig>::INTERFACE_ID.into()
+        ));
+        assert!(AccountErc7579::supports_interface(
+            <AccountErc7579 as IErc1271>::INTERFACE_ID.into()
+        ));
+        assert!(AccountErc7579::supports_interface(
+            <AccountErc7579 as IErc165>::INTERFACE_ID.into()
+        ));
+
+        let fake_interface_id = 0x12345678u32;
+        assert!(!AccountErc7579::supports_interface(fake_interface_id.into()));
+    }
+
+    #[motsu::test]
+    fn account_config(account: Contract<AccountErc7579>, alice: Address) {
+        let account = account.sender(alice);
+        assert_eq!(account.account_id(), ACCOUNT_ID);
+
+        for module_type_id in 1..=4 {
+            assert!(account.supports_module(U256::from(module_type_id)));
+        }
+        assert!(!account.supports_module(U256::ZERO));
+        assert!(!account.supports_module(U256::from(5)));
+
+        for call_type in
+            [CALLTYPE_SINGLE, CALLTYPE_BATCH, CALLTYPE_DELEGATECALL]
+        {
+            for exec_type in [EXECTYPE_DEFAULT, EXECTYPE_TRY] {
+                let mode = mode(call_type, exec_type);
+                assert!(account.supports_execution_mode(mode));
+            }
+        }
+        let unsupported = fixed_bytes!("02");
+        assert!(!account
+            .supports_execution_mode(mode(unsupported, EXECTYPE_DEFAULT)));
+        assert!(!account
+            .supports_execution_mode(mode(CALLTYPE_SINGLE, unsupported)));
+    }
+
+    #[motsu::test]
+    fn install_module_reverts_when_unauthorized(
+        account: Contract<AccountErc7579>,
+        module: Contract<ModuleMock>,
+        alice: Address,
+    ) {
+        let err = account
+            .sender(alice)
+            .install_module(
+                MODULE_TYPE_VALIDATOR,
+                module.address(),
+                Bytes::from(vec![]),
+            )
+            .motsu_expect_err("should revert when unauthorized");
+
+        assert!(matches!(
+            err,
+            Error::Unauthorized(AccountUnauthorized { sender })
+                if sender == alice
+        ));
+    }
+
+    #[motsu::test]
+    fn installs_and_uninstalls_modules(
+        account: Contract<AccountErc7579>,
+        module: Contract<ModuleMock>,
+    ) {
+        for module_type_id in [MODULE_TYPE_VALIDATOR, MODULE_TYPE_EXECUTOR] {
+            install(&account, &module, module_type_id, &[]);
+            account.assert_emitted(&ModuleInstalled {
+                moduleTypeId: module_type_id,
+                module: module.address(),
+            });
+            assert!(account.sender(ENTRYPOINT_V07).is_module_installed(
+                module_type_id,
+                module.address(),
+                Bytes::from(vec![]),
+            ));
+
+            account
+                .sender(ENTRYPOINT_V07)
+                .uninstall_module(
+                    module_type_id,
+                    module.address(),
+                    Bytes::from(vec![]),
+                )
+                .motsu_expect("should uninstall module");
+            account.assert_emitted(&ModuleUninstalled {
+                moduleTypeId: module_type_id,
+                module: module.address(),
+            });
+            assert!(!account.sender(ENTRYPOINT_V07).is_module_installed(
+                module_type_id,
+                module.address(),
+                Bytes::from(vec![]),
+            ));
+        }
+
+        let module = module.sender(ENTRYPOINT_V07);
+        assert_eq!(module.installs.get(), U256::from(2));
+        assert_eq!(module.uninstalls.get(), U256::from(2));
+    }
+
+    #[motsu::test]
+    fn install_module_reverts_when_already_installed(
+        account: Contract<AccountErc7579>,
+        module: Contract<ModuleMock>,
+    ) {
+        install(&account, &module, MODULE_TYPE_EXECUTOR, &[]);
+
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .install_module(
+                MODULE_TYPE_EXECUTOR,
+                module.address(),
+                Bytes::from(vec![]),
+            )
+            .motsu_expect_err("should revert when already installed");
+
+        assert!(matches!(
+            err,
+            Error::AlreadyInstalledModule(ERC7579AlreadyInstalledModule {
+                module_type_id,
+                module: installed,
+            }) if module_type_id == MODULE_TYPE_EXECUTOR
+                && installed == module.address()
+        ));
+    }
+
+    #[motsu::test]
+    fn install_module_reverts_on_unsupported_module_type(
+        account: Contract<AccountErc7579>,
+        module: Contract<ModuleMock>,
+    ) {
+        let unsupported = U256::from(5);
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .install_module(unsupported, module.address(), Bytes::from(vec![]))
+            .motsu_expect_err("should revert on unsupported module type");
+
+        assert!(matches!(
+            err,
+            Error::UnsupportedModuleType(ERC7579UnsupportedModuleType {
+                module_type_id,
+            }) if module_type_id == unsupported
+        ));
+    }
+
+    #[motsu::test]
+    fn install_module_reverts_on_mismatched_module_type(
+        account: Contract<AccountErc7579>,
+        module: Contract<ModuleMock>,
+    ) {
+        module.sender(ENTRYPOINT_V07).module_type.set(MODULE_TYPE_EXECUTOR);
+
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .install_module(
+                MODULE_TYPE_VALIDATOR,
+                module.address(),
+                Bytes::from(vec![]),
+            )
+            .motsu_expect_err("should revert on mismatched module type");
+
+        assert!(matches!(
+            err,
+            Error::MismatchedModuleTypeId(ERC7579MismatchedModuleTypeId {
+                module_type_id,
+                ..
+            }) if module_type_id == MODULE_TYPE_VALIDATOR
+        ));
+    }
+
+    #[motsu::test]
+    fn uninstall_module_reverts_when_not_installed(
+        account: Contract<AccountErc7579>,
+        module: Contract<ModuleMock>,
+    ) {
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .uninstall_module(
+                MODULE_TYPE_VALIDATOR,
+                module.address(),
+                Bytes::from(vec![]),
+            )
+            .motsu_expect_err("should revert when not installed");
+
+        assert!(matches!(
+            err,
+            Error::UninstalledModule(ERC7579UninstalledModule {
+                module_type_id,
+                module: uninstalled,
+            }) if module_type_id == MODULE_TYPE_VALIDATOR
+                && uninstalled == module.address()
+        ));
+    }
+
+    #[motsu::test]
+    fn installs_and_uninstalls_fallback_handlers(
+        account: Contract<AccountErc7579>,
+        module: Contract<ModuleMock>,
+        alice: Address,
+    ) {
+        install(&account, &module, MODULE_TYPE_FALLBACK, SELECTOR.as_slice());
+        assert!(account.sender(alice).is_module_installed(
+            MODULE_TYPE_FALLBACK,
+            module.address(),
+            SELECTOR.to_vec().into(),
+        ));
+        assert!(!account.sender(alice).is_module_installed(
+            MODULE_TYPE_FALLBACK,
+            module.address(),
+            Bytes::from(vec![]),
+        ));
+
+        account
+            .sender(ENTRYPOINT_V07)
+            .uninstall_module(
+                MODULE_TYPE_FALLBACK,
+                module.address(),
+                SELECTOR.to_vec().into(),
+            )
+            .motsu_expect("should uninstall fallback handler");
+        assert!(!account.sender(alice).is_module_installed(
+            MODULE_TYPE_FALLBACK,
+            module.address(),
+            SELECTOR.to_vec().into(),
+        ));
+        assert_eq!(module.sender(alice).uninstalls.get(), U256::from(1));
+    }
+
+    #[motsu::test]
+    fn fallback_reverts_without_handler(
+        account: Contract<AccountErc7579>,
+        alice: Address,
+    ) {
+        let err = account
+            .sender(alice)
+            ._fallback(SELECTOR.as_slice())
+            .motsu_expect_err("should revert without handler");
+
+        assert!(matches!(
+            err,
+            Error::MissingFallbackHandler(ERC7579MissingFallbackHandler {
+                selector,
+            }) if selector == SELECTOR
+        ));
+    }
+
+    #[motsu::test]
+    fn install_fallback_reverts_on_missing_selector(
+        account: Contract<AccountErc7579>,
+        module: Contract<ModuleMock>,
+    ) {
+        module.sender(ENTRYPOINT_V07).module_type.set(MODULE_TYPE_FALLBACK);
+
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .install_module(
+                MODULE_TYPE_FALLBACK,
+                module.address(),
+                Bytes::from(vec![0x12, 0x34]),
+            )
+            .motsu_expect_err("should revert on missing selector");
+
+        assert!(matches!(err, Error::CannotDecodeFallbackData(_)));
+    }
+
+    #[motsu::test]
+    fn hook_wraps_executions(
+        account: Contract<AccountErc7579>,
+        hook: Contract<ModuleMock>,
+        other_hook: Contract<ModuleMock>,
+        target: Contract<TargetMock>,
+    ) {
+        install(&account, &hook, MODULE_TYPE_HOOK, &[]);
+        assert_eq!(account.sender(ENTRYPOINT_V07).hook(), hook.address());
+
+        let calldata =
+            encode_single(target.address(), U256::ZERO, &set_value_call(1));
+        account
+            .sender(ENTRYPOINT_V07)
+            .execute(mode(CALLTYPE_SINGLE, EXECTYPE_DEFAULT), calldata.into())
+            .motsu_expect("should execute call");
+
+        assert_eq!(hook.sender(ENTRYPOINT_V07).pre_checks.get(), U256::from(1));
+        assert_eq!(
+            hook.sender(ENTRYPOINT_V07).post_checks.get(),
+            U256::from(1)
+        );
+
+        other_hook.sender(ENTRYPOINT_V07).module_type.set(MODULE_TYPE_HOOK);
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .install_module(
+                MODULE_TYPE_HOOK,
+                other_hook.address(),
+                Bytes::from(vec![]),
+            )
+            .motsu_expect_err("should revert when a hook is installed");
+
+        assert!(matches!(
+            err,
+            Error::AlreadyInstalledModule(ERC7579AlreadyInstalledModule {
+                module,
+                ..
+            }) if module == hook.address()
+        ));
+    }
+
+    #[motsu::test]
+    fn execute_reverts_when_unauthorized(
+        account: Contract<AccountErc7579>,
+        alice: Address,
+    ) {
+        let err = account
+            .sender(alice)
+            .execute(
+                mode(CALLTYPE_SINGLE, EXECTYPE_DEFAULT),
+                Bytes::from(vec![]),
+            )
+            .motsu_expect_err("should revert when unauthorized");
+
+        assert!(matches!(err, Error::Unauthorized(_)));
+    }
+
+    #[motsu::test]
+    fn execute_single_and_batch(
+        account: Contract<AccountErc7579>,
+        target: Contract<TargetMock>,
+    ) {
+        let calldata =
+            encode_single(target.address(), U256::ZERO, &set_value_call(1));
+        account
+            .sender(ENTRYPOINT_V07)
+            .execute(mode(CALLTYPE_SINGLE, EXECTYPE_DEFAULT), calldata.into())
+            .motsu_expect("should execute single call");
+        assert_eq!(target.sender(ENTRYPOINT_V07).value.get(), U256::from(1));
+
+        let calldata = encode_batch(&[
+            (target.address(), U256::ZERO, set_value_call(2).into()),
+            (target.address(), U256::ZERO, set_value_call(3).into()),
+        ]);
+        account
+            .sender(account.address())
+            .execute(mode(CALLTYPE_BATCH, EXECTYPE_DEFAULT), calldata.into())
+            .motsu_expect("should execute batch");
+        assert_eq!(target.sender(ENTRYPOINT_V07).value.get(), U256::from(3));
+    }
+
+    #[motsu::test]
+    fn execute_bubbles_up_revert(
+        account: Contract<AccountErc7579>,
+        target: Contract<TargetMock>,
+    ) {
+        let calldata = encode_single(
+            target.address(),
+            U256::ZERO,
+            &target_abi::failCall {}.abi_encode(),
+        );
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .execute(mode(CALLTYPE_SINGLE, EXECTYPE_DEFAULT), calldata.into())
+            .motsu_expect_err("should bubble up revert");
+
+        assert!(matches!(
+            err,
+            Error::FailedCall(call::Error::Revert(data))
+                if data == b"target failed"
+        ));
+    }
+
+    #[motsu::test]
+    fn try_execute_emits_failures(
+        account: Contract<AccountErc7579>,
+        target: Contract<TargetMock>,
+    ) {
+        let calldata = encode_batch(&[
+            (
+                target.address(),
+                U256::ZERO,
+                target_abi::failCall {}.abi_encode().into(),
+            ),
+            (target.address(), U256::ZERO, set_value_call(7).into()),
+        ]);
+        account
+            .sender(ENTRYPOINT_V07)
+            .execute(mode(CALLTYPE_BATCH, EXECTYPE_TRY), calldata.into())
+            .motsu_expect("should not revert on failed call");
+
+        account.assert_emitted(&ERC7579TryExecuteFail {
+            batchExecutionIndex: U256::ZERO,
+            returndata: b"target failed".to_vec().into(),
+        });
+        assert_eq!(target.sender(ENTRYPOINT_V07).value.get(), U256::from(7));
+    }
+
+    #[motsu::test]
+    fn execute_reverts_on_unsupported_mode(account: Contract<AccountErc7579>) {
+        let call_type = fixed_bytes!("02");
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .execute(mode(call_type, EXECTYPE_DEFAULT), Bytes::from(vec![]))
+            .motsu_expect_err("should revert on unsupported call type");
+        assert!(matches!(
+            err,
+            Error::Erc7579(erc7579::Error::UnsupportedCallType(
+                ERC7579UnsupportedCallType { call_type: unsupported }
+            )) if unsupported == call_type
+        ));
+
+        let exec_type = fixed_bytes!("02");
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .execute(mode(CALLTYPE_SINGLE, exec_type), Bytes::from(vec![]))
+            .motsu_expect_err("should revert on unsupported exec type");
+        assert!(matches!(
+            err,
+            Error::Erc7579(erc7579::Error::UnsupportedExecType(
+                ERC7579UnsupportedExecType { exec_type: unsupported }
+            )) if unsupported == exec_type
+        ));
+
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .execute(
+                mode(CALLTYPE_SINGLE, EXECTYPE_DEFAULT),
+                Bytes::from(vec![0; 10]),
+            )
+            .motsu_expect_err("should revert on invalid calldata");
+        assert!(matches!(
+            err,
+            Error::Erc7579(erc7579::Error::DecodingError(_))
+        ));
+    }
+
+    #[motsu::test]
+    fn execute_from_executor(
+        account: Contract<AccountErc7579>,
+        executor: Contract<ModuleMock>,
+        target: Contract<TargetMock>,
+    ) {
+        let calldata =
+            encode_single(target.address(), U256::ZERO, &set_value_call(5));
+
+        let err = account
+            .sender(executor.address())
+            .execute_from_executor(
+                mode(CALLTYPE_SINGLE, EXECTYPE_DEFAULT),
+                calldata.clone().into(),
+            )
+            .motsu_expect_err("should revert when executor is not installed");
+        assert!(matches!(
+            err,
+            Error::UninstalledModule(ERC7579UninstalledModule {
+                module_type_id,
+                module,
+            }) if module_type_id == MODULE_TYPE_EXECUTOR
+                && module == executor.address()
+        ));
+
+        install(&account, &executor, MODULE_TYPE_EXECUTOR, &[]);
+        let results = account
+            .sender(executor.address())
+            .execute_from_executor(
+                mode(CALLTYPE_SINGLE, EXECTYPE_DEFAULT),
+                calldata.into(),
+            )
+            .motsu_expect("should execute call from executor");
+
+        assert_eq!(results, vec![Bytes::from(vec![])]);
+        assert_eq!(target.sender(ENTRYPOINT_V07).value.get(), U256::from(5));
+    }
+
+    #[motsu::test]
+    fn validate_user_op_reverts_when_not_entry_point(
+        account: Contract<AccountErc7579>,
+        alice: Address,
+    ) {
+        let err = account
+            .sender(alice)
+            .validate_user_op(user_op(&[]), B256::ZERO, U256::ZERO)
+            .motsu_expect_err("should revert when not called by entry point");
+
+        assert!(matches!(err, Error::Unauthorized(_)));
+    }
+
+    #[motsu::test]
+    fn validate_user_op_with_validator(
+        account: Contract<AccountErc7579>,
+        validator: Contract<ModuleMock>,
+    ) {
+        let hash = B256::repeat_byte(0x11);
+        let signature =
+            [validator.address().as_slice(), hash.as_slice()].concat();
+
+        // Validator not installed.
+        let validation_data = account
+            .sender(ENTRYPOINT_V07)
+            .validate_user_op(user_op(&signature), hash, U256::ZERO)
+            .motsu_expect("should validate user operation");
+        assert_eq!(validation_data, SIG_VALIDATION_FAILED);
+
+        install(&account, &validator, MODULE_TYPE_VALIDATOR, &[]);
+
+        let validation_data = account
+            .sender(ENTRYPOINT_V07)
+            .validate_user_op(user_op(&signature), hash, U256::ZERO)
+            .motsu_expect("should validate user operation");
+        assert_eq!(validation_data, SIG_VALIDATION_SUCCESS);
+
+        let validation_data = account
+            .sender(ENTRYPOINT_V07)
+            .validate_user_op(user_op(&signature), B256::ZERO, U256::ZERO)
+            .motsu_expect("should validate user operation");
+        assert_eq!(validation_data, SIG_VALIDATION_FAILED);
+
+        // Signature too short to contain a validator.
+        let validation_data = account
+            .sender(ENTRYPOINT_V07)
+            .validate_user_op(user_op(&[0x01; 19]), hash, U256::ZERO)
+            .motsu_expect("should validate user operation");
+        assert_eq!(validation_data, SIG_VALIDATION_FAILED);
+    }
+
+    #[motsu::test]
+    fn is_valid_signature_with_validator(
+        account: Contract<AccountErc7579>,
+        validator: Contract<ModuleMock>,
+        alice: Address,
+    ) {
+        let hash = B256::repeat_byte(0x11);
+        let signature: Bytes =
+            [validator.address().as_slice(), hash.as_slice()].concat().into();
+        let invalid = FixedBytes::repeat_byte(0xff);
+
+        // Validator not installed.
+        let magic_value =
+            account.sender(alice).is_valid_signature(hash, signature.clone());
+        assert_eq!(magic_value, invalid);
+
+        install(&account, &validator, MODULE_TYPE_VALIDATOR, &[]);
+
+        let magic_value =
+            account.sender(alice).is_valid_signature(hash, signature.clone());
+        assert_eq!(magic_value, ERC1271_MAGIC_VALUE);
+
+        let magic_value =
+            account.sender(alice).is_valid_signature(B256::ZERO, signature);
+        assert_eq!(magic_value, FixedBytes::ZERO);
+
+        // Signature too short to contain a validator.
+        let magic_value = account
+            .sender(alice)
+            .is_valid_signature(hash, vec![0x01; 19].into());
+        assert_eq!(magic_value, invalid);
+    }
+}
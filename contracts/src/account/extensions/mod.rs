@@ -0,0 +1,7 @@
+//! Common extensions to smart accounts.
+pub mod erc7579;
+
+pub use erc7579::{
+    AccountErc7579, IErc7579AccountConfig, IErc7579Execution,
+    IErc7579ModuleConfig,
+};
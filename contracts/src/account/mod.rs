@@ -0,0 +1,582 @@
+//! A simple [ERC-4337] smart account.
+//!
+//! The account validates user operations sent by the [entry point v0.7], pays
+//! the missing prefund of each operation back to it, and executes calls on
+//! its behalf. Signature validation is delegated to an [`AbstractSigner`],
+//! so that the same account works with any signing scheme:
+//!
+//! ```ignore
+//! #[entrypoint]
+//! #[storage]
+//! struct MyAccount {
+//!     #[borrow]
+//!     initializable: Initializable,
+//!     #[borrow]
+//!     account: Account<SignerP256>,
+//! }
+//!
+//! #[public]
+//! #[inherit(Account<SignerP256>)]
+//! impl MyAccount {
+//!     fn initialize(&mut self, qx: B256, qy: B256) -> Result<(), Vec<u8>> {
+//!         Ok(Initializable::initializer(self, |contract| {
+//!             contract.account.signer._set_signer(qx, qy);
+//!             Ok::<_, initializable::Error>(())
+//!         })?)
+//!     }
+//!
+//!     #[receive]
+//!     fn receive(&mut self) -> Result<(), Vec<u8>> {
+//!         Ok(())
+//!     }
+//! }
+//! ```
+//!
+//! User operation nonces are tracked by the entry point. Each nonce is made of
+//! a 192-bit key and a 64-bit sequence, see [`utils::erc4337::nonce_key`] and
+//! [`utils::erc4337::nonce_sequence`]. Operations with different keys don't
+//! need to be executed in order, and [`Account::get_nonce_with_key`] returns
+//! the next nonce of a given key.
+//!
+//! NOTE: The account can't receive native tokens by itself, so the contract
+//! using it should add a `#[receive]` function to be able to pay prefunds.
+//!
+//! [ERC-4337]: https://eips.ethereum.org/EIPS/eip-4337
+//! [entry point v0.7]: utils::erc4337::ENTRYPOINT_V07
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{aliases::U192, Address, B256, U256};
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    call::{self, Call, MethodError, RawCall},
+    contract, msg,
+    prelude::*,
+};
+
+use self::utils::erc4337::{
+    PackedUserOperation, ENTRYPOINT_V07, SIG_VALIDATION_FAILED,
+    SIG_VALIDATION_SUCCESS,
+};
+use crate::utils::{
+    cryptography::signer::AbstractSigner, ReentrantCallHandler,
+};
+
+pub mod extensions;
+pub mod utils;
+
+/// A call executed by the account, as the `(target, value, data)` tuple.
+pub type Execution = (Address, U256, Bytes);
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Unauthorized call to the account.
+        ///
+        /// * `sender` - Address of the caller.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error AccountUnauthorized(address sender);
+    }
+}
+
+mod interface {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::prelude::sol_interface;
+    sol_interface! {
+        interface IEntryPointNonces {
+            function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+        }
+    }
+}
+
+/// An [`Account`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Unauthorized call to the account.
+    Unauthorized(AccountUnauthorized),
+    /// A call to an address target failed. Contains its revert data as is.
+    FailedCall(call::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`Account`] contract.
+#[storage]
+pub struct Account<T: AbstractSigner + StorageType> {
+    /// Signer validating the signatures of user operations.
+    pub signer: T,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl<T: AbstractSigner + StorageType> TopLevelStorage for Account<T> {}
+
+#[public]
+impl<T: AbstractSigner + StorageType> Account<T> {
+    /// Returns the address of the entry point trusted by the account.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn entry_point(&self) -> Address {
+        ENTRYPOINT_V07
+    }
+
+    /// Returns the next nonce of the account for the default key `0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call to the entry point fails.
+    pub fn get_nonce(&mut self) -> Result<U256, Error> {
+        self.get_nonce_with_key(U192::ZERO)
+    }
+
+    /// Returns the next nonce of the account for the given `key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `key` - The 192-bit key of the nonce.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call to the entry point fails.
+    #[selector(name = "getNonce")]
+    pub fn get_nonce_with_key(&mut self, key: U192) -> Result<U256, Error> {
+        Ok(get_nonce(self, key)?)
+    }
+
+    /// Validates a user operation and pays the entry point the missing funds
+    /// for its execution.
+    ///
+    /// Returns [`SIG_VALIDATION_SUCCESS`] if the signature of the operation is
+    /// valid, and [`SIG_VALIDATION_FAILED`] otherwise. An invalid signature
+    /// doesn't make this function revert, so that the entry point can simulate
+    /// operations.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `user_op` - The user operation to validate.
+    /// * `user_op_hash` - Hash of the user operation, computed by the entry
+    ///   point.
+    /// * `missing_account_funds` - Amount the account must send to the entry
+    ///   point to pay for the operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is not the entry point.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn validate_user_op(
+        &mut self,
+        user_op: PackedUserOperation,
+        user_op_hash: B256,
+        missing_account_funds: U256,
+    ) -> Result<U256, Error> {
+        only_entry_point()?;
+        let validation_data = self._validate_user_op(&user_op, user_op_hash);
+        self._pay_prefund(missing_account_funds);
+        Ok(validation_data)
+    }
+
+    /// Executes a call from the account.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - Address of the called contract.
+    /// * `value` - Amount of native tokens sent with the call.
+    /// * `data` - Calldata of the call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is neither the entry point nor
+    ///   the account itself.
+    /// * [`Error::FailedCall`] - If the call reverts. Its revert data is
+    ///   returned unchanged.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn execute(
+        &mut self,
+        target: Address,
+        value: U256,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        only_entry_point_or_self()?;
+        Self::_call(target, value, &data)
+    }
+
+    /// Executes a batch of calls from the account, in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `calls` - List of `(target, value, data)` calls.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Unauthorized`] - If the caller is neither the entry point nor
+    ///   the account itself.
+    /// * [`Error::FailedCall`] - If any of the calls reverts. The revert data
+    ///   of the first failing call is returned unchanged.
+    pub fn execute_batch(
+        &mut self,
+        calls: Vec<Execution>,
+    ) -> Result<(), Error> {
+        only_entry_point_or_self()?;
+        for (target, value, data) in calls {
+            Self::_call(target, value, &data)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: AbstractSigner + StorageType> Account<T> {
+    /// Validates the signature of `user_op` against `user_op_hash`.
+    ///
+    /// Returns [`SIG_VALIDATION_SUCCESS`] if the signature is valid, and
+    /// [`SIG_VALIDATION_FAILED`] otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `user_op` - The user operation to validate.
+    /// * `user_op_hash` - Hash of the user operation.
+    pub fn _validate_user_op(
+        &mut self,
+        user_op: &PackedUserOperation,
+        user_op_hash: B256,
+    ) -> U256 {
+        if self.signer.raw_signature_validation(user_op_hash, &user_op.8) {
+            SIG_VALIDATION_SUCCESS
+        } else {
+            SIG_VALIDATION_FAILED
+        }
+    }
+
+    /// Sends the missing funds for the execution of a user operation to the
+    /// entry point.
+    ///
+    /// The result of the transfer is ignored, since the entry point checks
+    /// that it received enough funds.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `missing_account_funds` - Amount to send to the entry point.
+    pub fn _pay_prefund(&mut self, missing_account_funds: U256) {
+        pay_prefund(missing_account_funds);
+    }
+
+    /// Calls `target` with `value` and `data`.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Address of the called contract.
+    /// * `value` - Amount of native tokens sent with the call.
+    /// * `data` - Calldata of the call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call reverts.
+    fn _call(target: Address, value: U256, data: &[u8]) -> Result<(), Error> {
+        RawCall::new_with_value(value)
+            .call_with_reentrant_handling(target, data)
+            .map_err(call::Error::Revert)?;
+        Ok(())
+    }
+}
+
+/// Returns the next nonce of the calling account for the given `key`, as
+/// tracked by the [entry point v0.7].
+///
+/// # Arguments
+///
+/// * `storage` - Write access to the account's storage.
+/// * `key` - The 192-bit key of the nonce.
+///
+/// # Errors
+///
+/// * [`call::Error`] - If the call to the entry point fails.
+///
+/// [entry point v0.7]: ENTRYPOINT_V07
+pub(crate) fn get_nonce(
+    storage: &mut impl TopLevelStorage,
+    key: U192,
+) -> Result<U256, call::Error> {
+    interface::IEntryPointNonces::new(ENTRYPOINT_V07).get_nonce(
+        Call::new_in(storage),
+        contract::address(),
+        key,
+    )
+}
+
+/// Sends the missing funds for the execution of a user operation to the
+/// entry point, i.e. the caller of `validateUserOp`.
+///
+/// The result of the transfer is ignored, since the entry point checks that it
+/// received enough funds.
+///
+/// # Arguments
+///
+/// * `missing_account_funds` - Amount to send to the entry point.
+pub(crate) fn pay_prefund(missing_account_funds: U256) {
+    if missing_account_funds.is_zero() {
+        return;
+    }
+
+    let _ = RawCall::new_with_value(missing_account_funds)
+        .call_with_reentrant_handling(msg::sender(), &[]);
+}
+
+/// Checks that the caller is the entry point.
+///
+/// # Errors
+///
+/// * [`AccountUnauthorized`] - If the caller is not the entry point.
+pub(crate) fn only_entry_point() -> Result<(), AccountUnauthorized> {
+    let sender = msg::sender();
+    if sender != ENTRYPOINT_V07 {
+        return Err(AccountUnauthorized { sender });
+    }
+    Ok(())
+}
+
+/// Checks that the caller is the entry point or the account itself.
+///
+/// # Errors
+///
+/// * [`AccountUnauthorized`] - If the caller is neither the entry point nor the
+///   account itself.
+pub(crate) fn only_entry_point_or_self() -> Result<(), AccountUnauthorized> {
+    let sender = msg::sender();
+    if sender != ENTRYPOINT_V07 && sender != contract::address() {
+        return Err(AccountUnauthorized { sender });
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{Address, B256, U256};
+    use alloy_sol_types::SolCall;
+    use motsu::prelude::*;
+    use stylus_sdk::{abi::Bytes, call, prelude::*, storage::StorageU256};
+
+    use super::{
+        utils::erc4337::{
+            PackedUserOperation, ENTRYPOINT_V07, SIG_VALIDATION_FAILED,
+            SIG_VALIDATION_SUCCESS,
+        },
+        Account, AccountUnauthorized, Error,
+    };
+    use crate::utils::cryptography::signer::AbstractSigner;
+
+    /// Signer accepting signatures equal to the signed hash.
+    #[storage]
+    struct SignerMock;
+
+    impl AbstractSigner for SignerMock {
+        fn raw_signature_validation(
+            &mut self,
+            hash: B256,
+            signature: &[u8],
+        ) -> bool {
+            signature == hash.as_slice()
+        }
+    }
+
+    type AccountMock = Account<SignerMock>;
+
+    mod abi {
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function setValue(uint256 value);
+            function fail();
+        }
+    }
+
+    #[storage]
+    struct TargetMock {
+        value: StorageU256,
+    }
+
+    unsafe impl TopLevelStorage for TargetMock {}
+
+    #[public]
+    impl TargetMock {
+        fn set_value(&mut self, value: U256) {
+            self.value.set(value);
+        }
+
+        #[allow(clippy::unused_self)]
+        fn fail(&mut self) -> Result<(), Vec<u8>> {
+            Err(b"target failed".to_vec())
+        }
+    }
+
+    const HASH: B256 = B256::repeat_byte(0x11);
+
+    fn user_op(signature: &[u8]) -> PackedUserOperation {
+        (
+            Address::ZERO,
+            U256::ZERO,
+            Bytes::from(vec![]),
+            Bytes::from(vec![]),
+            B256::ZERO,
+            U256::ZERO,
+            B256::ZERO,
+            Bytes::from(vec![]),
+            Bytes::from(signature.to_vec()),
+        )
+    }
+
+    fn set_value_call(value: u64) -> Bytes {
+        abi::setValueCall { value: U256::from(value) }.abi_encode().into()
+    }
+
+    #[motsu::test]
+    fn entry_point_is_v07(account: Contract<AccountMock>, alice: Address) {
+        assert_eq!(account.sender(alice).entry_point(), ENTRYPOINT_V07);
+    }
+
+    #[motsu::test]
+    fn validate_user_op_reverts_when_not_entry_point(
+        account: Contract<AccountMock>,
+        alice: Address,
+    ) {
+        let err = account
+            .sender(alice)
+            .validate_user_op(user_op(HASH.as_slice()), HASH, U256::ZERO)
+            .motsu_expect_err("should revert when not called by entry point");
+
+        assert!(matches!(
+            err,
+            Error::Unauthorized(AccountUnauthorized { sender })
+                if sender == alice
+        ));
+    }
+
+    #[motsu::test]
+    fn validate_user_op_succeeds_with_valid_signature(
+        account: Contract<AccountMock>,
+    ) {
+        let validation_data = account
+            .sender(ENTRYPOINT_V07)
+            .validate_user_op(user_op(HASH.as_slice()), HASH, U256::ZERO)
+            .motsu_expect("should validate user operation");
+
+        assert_eq!(validation_data, SIG_VALIDATION_SUCCESS);
+    }
+
+    #[motsu::test]
+    fn validate_user_op_fails_with_invalid_signature(
+        account: Contract<AccountMock>,
+    ) {
+        let validation_data = account
+            .sender(ENTRYPOINT_V07)
+            .validate_user_op(user_op(&[0x22; 32]), HASH, U256::ZERO)
+            .motsu_expect("should not revert on invalid signature");
+
+        assert_eq!(validation_data, SIG_VALIDATION_FAILED);
+    }
+
+    #[motsu::test]
+    fn execute_reverts_when_unauthorized(
+        account: Contract<AccountMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        let err = account
+            .sender(alice)
+            .execute(target.address(), U256::ZERO, set_value_call(1))
+            .motsu_expect_err("should revert when unauthorized");
+
+        assert!(matches!(
+            err,
+            Error::Unauthorized(AccountUnauthorized { sender })
+                if sender == alice
+        ));
+    }
+
+    #[motsu::test]
+    fn execute_calls_target(
+        account: Contract<AccountMock>,
+        target: Contract<TargetMock>,
+    ) {
+        account
+            .sender(ENTRYPOINT_V07)
+            .execute(target.address(), U256::ZERO, set_value_call(7))
+            .motsu_expect("should execute call");
+
+        assert_eq!(target.sender(ENTRYPOINT_V07).value.get(), U256::from(7));
+    }
+
+    #[motsu::test]
+    fn execute_bubbles_up_revert(
+        account: Contract<AccountMock>,
+        target: Contract<TargetMock>,
+    ) {
+        let err = account
+            .sender(ENTRYPOINT_V07)
+            .execute(
+                target.address(),
+                U256::ZERO,
+                abi::failCall {}.abi_encode().into(),
+            )
+            .motsu_expect_err("should bubble up revert");
+
+        assert!(matches!(
+            err,
+            Error::FailedCall(call::Error::Revert(data))
+                if data == b"target failed"
+        ));
+    }
+
+    #[motsu::test]
+    fn execute_batch_calls_targets_in_order(
+        account: Contract<AccountMock>,
+        target: Contract<TargetMock>,
+    ) {
+        let account_address = account.address();
+        account
+            .sender(account_address)
+            .execute_batch(vec![
+                (target.address(), U256::ZERO, set_value_call(1)),
+                (target.address(), U256::ZERO, set_value_call(2)),
+            ])
+            .motsu_expect("should execute batch");
+
+        assert_eq!(target.sender(account_address).value.get(), U256::from(2));
+    }
+
+    #[motsu::test]
+    fn execute_batch_reverts_when_unauthorized(
+        account: Contract<AccountMock>,
+        alice: Address,
+    ) {
+        let err = account
+            .sender(alice)
+            .execute_batch(vec![])
+            .motsu_expect_err("should revert when unauthorized");
+
+        assert!(matches!(err, Error::Unauthorized(_)));
+    }
+}
@@ -0,0 +1,259 @@
+//! General [ERC-4337] utility functions.
+//!
+//! [ERC-4337]: https://eips.ethereum.org/EIPS/eip-4337
+use alloy_primitives::{
+    address,
+    aliases::{U192, U48},
+    keccak256, uint, Address, B256, U256,
+};
+use alloy_sol_types::{sol, SolType};
+use stylus_sdk::abi::Bytes;
+
+/// Address of the canonical entry point v0.7 contract.
+pub const ENTRYPOINT_V07: Address =
+    address!("0000000071727De22E5E9d8BAf0edAc6f37da032");
+
+/// Validation data returned for a valid signature, with no aggregator and no
+/// time range.
+pub const SIG_VALIDATION_SUCCESS: U256 = U256::ZERO;
+
+/// Validation data returned for an invalid signature.
+pub const SIG_VALIDATION_FAILED: U256 = uint!(1_U256);
+
+/// A user operation of the entry point v0.7.
+///
+/// It's the `(sender, nonce, initCode, callData, accountGasLimits,
+/// preVerificationGas, gasFees, paymasterAndData, signature)` tuple matching
+/// the `PackedUserOperation` struct of the Solidity implementation.
+pub type PackedUserOperation =
+    (Address, U256, Bytes, Bytes, B256, U256, B256, Bytes, Bytes);
+
+/// Tuple for the packed fields of a [`PackedUserOperation`].
+type PackedUserOperationTuple = sol! {
+    tuple(address, uint256, bytes32, bytes32, bytes32, uint256, bytes32, bytes32)
+};
+
+/// Tuple for the hash of a [`PackedUserOperation`].
+type UserOperationHashTuple = sol! {
+    tuple(bytes32, address, uint256)
+};
+
+/// Returns the hash of `user_op`, as computed by the entry point v0.7
+/// `getUserOpHash` function.
+///
+/// # Arguments
+///
+/// * `user_op` - The user operation.
+/// * `entry_point` - Address of the entry point contract.
+/// * `chain_id` - Id of the chain the user operation is executed on.
+#[must_use]
+pub fn hash(
+    user_op: &PackedUserOperation,
+    entry_point: Address,
+    chain_id: U256,
+) -> B256 {
+    let (
+        sender,
+        nonce,
+        init_code,
+        call_data,
+        account_gas_limits,
+        pre_verification_gas,
+        gas_fees,
+        paymaster_and_data,
+        _,
+    ) = user_op;
+
+    let packed = keccak256(PackedUserOperationTuple::abi_encode(&(
+        *sender,
+        *nonce,
+        keccak256(init_code),
+        keccak256(call_data),
+        *account_gas_limits,
+        *pre_verification_gas,
+        *gas_fees,
+        keccak256(paymaster_and_data),
+    )));
+
+    keccak256(UserOperationHashTuple::abi_encode(&(
+        packed,
+        entry_point,
+        chain_id,
+    )))
+}
+
+/// Packs validation data from an `aggregator` and the time range in which
+/// the user operation is valid.
+///
+/// # Arguments
+///
+/// * `aggregator` - Address of the signature aggregator, or `Address::ZERO` for
+///   a valid signature, or `0x..01` for an invalid one.
+/// * `valid_after` - Timestamp from which the user operation is valid.
+/// * `valid_until` - Timestamp until which the user operation is valid, or `0`
+///   for no expiration.
+#[must_use]
+pub fn pack_validation_data(
+    aggregator: Address,
+    valid_after: U48,
+    valid_until: U48,
+) -> U256 {
+    U256::from_be_slice(aggregator.as_slice())
+        | (U256::from(valid_until) << 160)
+        | (U256::from(valid_after) << 208)
+}
+
+/// Parses validation data into its aggregator and time range.
+///
+/// Returns the `(aggregator, valid_after, valid_until)` tuple, where a
+/// `valid_until` of `0` is replaced with `U48::MAX`.
+///
+/// # Arguments
+///
+/// * `validation_data` - The packed validation data.
+#[must_use]
+pub fn parse_validation_data(validation_data: U256) -> (Address, U48, U48) {
+    let bytes = validation_data.to_be_bytes::<32>();
+    let aggregator = Address::from_slice(&bytes[12..]);
+    let valid_until = U48::from_be_slice(&bytes[6..12]);
+    let valid_after = U48::from_be_slice(&bytes[..6]);
+    let valid_until =
+        if valid_until.is_zero() { U48::MAX } else { valid_until };
+    (aggregator, valid_after, valid_until)
+}
+
+/// Returns the key of a user operation `nonce`, i.e. its 192 most significant
+/// bits.
+///
+/// Nonces of different keys are independent, so user operations with
+/// different keys can be executed in any order.
+///
+/// # Arguments
+///
+/// * `nonce` - The nonce of the user operation.
+#[must_use]
+pub fn nonce_key(nonce: U256) -> U192 {
+    U192::from(nonce >> 64)
+}
+
+/// Returns the sequence of a user operation `nonce`, i.e. its 64 least
+/// significant bits.
+///
+/// # Arguments
+///
+/// * `nonce` - The nonce of the user operation.
+#[must_use]
+pub fn nonce_sequence(nonce: U256) -> u64 {
+    nonce.as_limbs()[0]
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{
+        address, aliases::U48, b256, keccak256, uint, Address, B256, U256,
+    };
+    use stylus_sdk::abi::Bytes;
+
+    use super::*;
+
+    fn user_op() -> PackedUserOperation {
+        (
+            address!("1111111111111111111111111111111111111111"),
+            uint!(0x2a_U256),
+            Bytes::from(vec![0xaa; 3]),
+            Bytes::from(vec![0xbb; 40]),
+            B256::repeat_byte(0x01),
+            uint!(21000_U256),
+            B256::repeat_byte(0x02),
+            Bytes::from(vec![]),
+            Bytes::from(vec![0xcc; 65]),
+        )
+    }
+
+    #[test]
+    fn hashes_user_operation() {
+        let op = user_op();
+        let chain_id = uint!(42161_U256);
+
+        let word = |value: U256| value.to_be_bytes::<32>();
+        let address_word =
+            |address: Address| word(U256::from_be_slice(address.as_slice()));
+        let packed = keccak256(
+            [
+                address_word(op.0),
+                word(op.1),
+                keccak256(&op.2).0,
+                keccak256(&op.3).0,
+                op.4 .0,
+                word(op.5),
+                op.6 .0,
+                keccak256(&op.7).0,
+            ]
+            .concat(),
+        );
+        let expected = keccak256(
+            [packed.0, address_word(ENTRYPOINT_V07), word(chain_id)].concat(),
+        );
+
+        assert_eq!(hash(&op, ENTRYPOINT_V07, chain_id), expected);
+    }
+
+    #[test]
+    fn signature_is_not_hashed() {
+        let mut op = user_op();
+        let expected = hash(&op, ENTRYPOINT_V07, U256::from(1));
+        op.8 = Bytes::from(vec![0xdd; 64]);
+        assert_eq!(hash(&op, ENTRYPOINT_V07, U256::from(1)), expected);
+        assert_ne!(hash(&op, ENTRYPOINT_V07, U256::from(2)), expected);
+    }
+
+    #[test]
+    fn packs_and_parses_validation_data() {
+        let aggregator = address!("000000000000000000000000000000000000dEaD");
+        let valid_after = U48::from(1_000);
+        let valid_until = U48::from(2_000);
+
+        let data = pack_validation_data(aggregator, valid_after, valid_until);
+        assert_eq!(
+            data,
+            U256::from_be_slice(aggregator.as_slice())
+                | (U256::from(2_000) << 160)
+                | (U256::from(1_000) << 208)
+        );
+        assert_eq!(
+            parse_validation_data(data),
+            (aggregator, valid_after, valid_until)
+        );
+    }
+
+    #[test]
+    fn parses_signature_validation_results() {
+        assert_eq!(
+            parse_validation_data(SIG_VALIDATION_SUCCESS),
+            (Address::ZERO, U48::ZERO, U48::MAX)
+        );
+        assert_eq!(
+            parse_validation_data(SIG_VALIDATION_FAILED),
+            (
+                address!("0000000000000000000000000000000000000001"),
+                U48::ZERO,
+                U48::MAX
+            )
+        );
+    }
+
+    #[test]
+    fn splits_nonce() {
+        let nonce = U256::from_be_bytes(
+            b256!(
+                "000000000000000000000000000000000000000000000001000000000000002a"
+            )
+            .0,
+        );
+        assert_eq!(nonce_key(nonce), U192::from(1));
+        assert_eq!(nonce_sequence(nonce), 42);
+
+        assert_eq!(nonce_key(U256::MAX), U192::MAX);
+        assert_eq!(nonce_sequence(U256::MAX), u64::MAX);
+    }
+}
@@ -0,0 +1,351 @@
+//! General [ERC-7579] utility functions, to encode and decode execution modes
+//! and execution calldata.
+//!
+//! An execution mode is a `bytes32` made of:
+//!
+//! * `callType` (1 byte): [`CALLTYPE_SINGLE`], [`CALLTYPE_BATCH`] or
+//!   [`CALLTYPE_DELEGATECALL`].
+//! * `execType` (1 byte): [`EXECTYPE_DEFAULT`], which reverts on failure, or
+//!   [`EXECTYPE_TRY`], which emits [`ERC7579TryExecuteFail`] instead.
+//! * 4 unused bytes.
+//! * `modeSelector` (4 bytes): An optional mode selector.
+//! * `modePayload` (22 bytes): An optional payload for the mode selector.
+//!
+//! [ERC-7579]: https://eips.ethereum.org/EIPS/eip-7579
+use alloc::vec::Vec;
+
+use alloy_primitives::{fixed_bytes, uint, Address, FixedBytes, B256, U256};
+use alloy_sol_types::SolType;
+pub use sol::*;
+use stylus_sdk::{abi::Bytes, call::MethodError, prelude::*};
+
+use crate::account::Execution;
+
+/// Module type of validator modules, used to validate user operations and
+/// signatures.
+pub const MODULE_TYPE_VALIDATOR: U256 = uint!(1_U256);
+/// Module type of executor modules, allowed to execute calls from the
+/// account.
+pub const MODULE_TYPE_EXECUTOR: U256 = uint!(2_U256);
+/// Module type of fallback modules, used to extend the account with new
+/// functions.
+pub const MODULE_TYPE_FALLBACK: U256 = uint!(3_U256);
+/// Module type of hook modules, called before and after each execution.
+pub const MODULE_TYPE_HOOK: U256 = uint!(4_U256);
+
+/// Call type executing a single call.
+pub const CALLTYPE_SINGLE: FixedBytes<1> = fixed_bytes!("00");
+/// Call type executing a batch of calls.
+pub const CALLTYPE_BATCH: FixedBytes<1> = fixed_bytes!("01");
+/// Call type executing a single delegate call.
+pub const CALLTYPE_DELEGATECALL: FixedBytes<1> = fixed_bytes!("ff");
+
+/// Execution type reverting if any call fails.
+pub const EXECTYPE_DEFAULT: FixedBytes<1> = fixed_bytes!("00");
+/// Execution type emitting [`ERC7579TryExecuteFail`] if a call fails, without
+/// reverting.
+pub const EXECTYPE_TRY: FixedBytes<1> = fixed_bytes!("01");
+
+/// Calldata of a batch of executions, i.e. `abi.encode(Execution[])`.
+type ExecutionBatch = alloy_sol_types::sol! { (address, uint256, bytes)[] };
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when a call fails with the [`super::EXECTYPE_TRY`]
+        /// execution type.
+        ///
+        /// * `batch_execution_index` - Index of the failed call in the batch.
+        /// * `returndata` - Revert data of the failed call.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event ERC7579TryExecuteFail(uint256 batchExecutionIndex, bytes returndata);
+    }
+
+    sol! {
+        /// The execution calldata can't be decoded.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7579DecodingError();
+
+        /// The `call_type` of an execution mode is not supported.
+        ///
+        /// * `call_type` - The unsupported call type.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7579UnsupportedCallType(bytes1 call_type);
+
+        /// The `exec_type` of an execution mode is not supported.
+        ///
+        /// * `exec_type` - The unsupported execution type.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC7579UnsupportedExecType(bytes1 exec_type);
+    }
+}
+
+/// An [ERC-7579] utilities error.
+///
+/// [ERC-7579]: https://eips.ethereum.org/EIPS/eip-7579
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// The execution calldata can't be decoded.
+    DecodingError(ERC7579DecodingError),
+    /// The call type of an execution mode is not supported.
+    UnsupportedCallType(ERC7579UnsupportedCallType),
+    /// The execution type of an execution mode is not supported.
+    UnsupportedExecType(ERC7579UnsupportedExecType),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// Encodes an execution mode.
+///
+/// # Arguments
+///
+/// * `call_type` - The call type.
+/// * `exec_type` - The execution type.
+/// * `selector` - The mode selector.
+/// * `payload` - The payload of the mode selector.
+#[must_use]
+pub fn encode_mode(
+    call_type: FixedBytes<1>,
+    exec_type: FixedBytes<1>,
+    selector: FixedBytes<4>,
+    payload: FixedBytes<22>,
+) -> B256 {
+    let mut mode = B256::ZERO;
+    mode[0] = call_type[0];
+    mode[1] = exec_type[0];
+    mode[6..10].copy_from_slice(selector.as_slice());
+    mode[10..].copy_from_slice(payload.as_slice());
+    mode
+}
+
+/// Decodes an execution mode into its `(call_type, exec_type, selector,
+/// payload)` components.
+///
+/// # Arguments
+///
+/// * `mode` - The execution mode.
+#[must_use]
+pub fn decode_mode(
+    mode: B256,
+) -> (FixedBytes<1>, FixedBytes<1>, FixedBytes<4>, FixedBytes<22>) {
+    (
+        FixedBytes::from_slice(&mode[..1]),
+        FixedBytes::from_slice(&mode[1..2]),
+        FixedBytes::from_slice(&mode[6..10]),
+        FixedBytes::from_slice(&mode[10..]),
+    )
+}
+
+/// Encodes the calldata of a single call, i.e. `abi.encodePacked(target,
+/// value, call_data)`.
+///
+/// # Arguments
+///
+/// * `target` - Address of the called contract.
+/// * `value` - Amount of native tokens sent with the call.
+/// * `call_data` - Calldata of the call.
+#[must_use]
+pub fn encode_single(
+    target: Address,
+    value: U256,
+    call_data: &[u8],
+) -> Vec<u8> {
+    [target.as_slice(), &value.to_be_bytes::<32>(), call_data].concat()
+}
+
+/// Decodes the calldata of a single call into its `(target, value,
+/// call_data)` components.
+///
+/// # Arguments
+///
+/// * `execution_calldata` - The encoded call.
+///
+/// # Errors
+///
+/// * [`Error::DecodingError`] - If `execution_calldata` is shorter than 52
+///   bytes.
+pub fn decode_single(
+    execution_calldata: &[u8],
+) -> Result<(Address, U256, &[u8]), Error> {
+    if execution_calldata.len() < 52 {
+        return Err(ERC7579DecodingError {}.into());
+    }
+
+    let target = Address::from_slice(&execution_calldata[..20]);
+    let value = U256::from_be_slice(&execution_calldata[20..52]);
+    Ok((target, value, &execution_calldata[52..]))
+}
+
+/// Encodes the calldata of a delegate call, i.e. `abi.encodePacked(target,
+/// call_data)`.
+///
+/// # Arguments
+///
+/// * `target` - Address of the called contract.
+/// * `call_data` - Calldata of the call.
+#[must_use]
+pub fn encode_delegate(target: Address, call_data: &[u8]) -> Vec<u8> {
+    [target.as_slice(), call_data].concat()
+}
+
+/// Decodes the calldata of a delegate call into its `(target, call_data)`
+/// components.
+///
+/// # Arguments
+///
+/// * `execution_calldata` - The encoded call.
+///
+/// # Errors
+///
+/// * [`Error::DecodingError`] - If `execution_calldata` is shorter than 20
+///   bytes.
+pub fn decode_delegate(
+    execution_calldata: &[u8],
+) -> Result<(Address, &[u8]), Error> {
+    if execution_calldata.len() < 20 {
+        return Err(ERC7579DecodingError {}.into());
+    }
+
+    let target = Address::from_slice(&execution_calldata[..20]);
+    Ok((target, &execution_calldata[20..]))
+}
+
+/// Encodes the calldata of a batch of calls, i.e. `abi.encode(executions)`.
+///
+/// # Arguments
+///
+/// * `executions` - List of `(target, value, call_data)` calls.
+#[must_use]
+pub fn encode_batch(executions: &[Execution]) -> Vec<u8> {
+    let executions: Vec<_> = executions
+        .iter()
+        .map(|(target, value, call_data)| {
+            (*target, *value, alloy_primitives::Bytes::from(call_data.to_vec()))
+        })
+        .collect();
+    ExecutionBatch::abi_encode(&executions)
+}
+
+/// Decodes the calldata of a batch of calls.
+///
+/// # Arguments
+///
+/// * `execution_calldata` - The encoded batch.
+///
+/// # Errors
+///
+/// * [`Error::DecodingError`] - If `execution_calldata` is not a valid
+///   ABI-encoded array of executions.
+pub fn decode_batch(
+    execution_calldata: &[u8],
+) -> Result<Vec<Execution>, Error> {
+    let executions = ExecutionBatch::abi_decode(execution_calldata, true)
+        .map_err(|_| ERC7579DecodingError {})?;
+
+    Ok(executions
+        .into_iter()
+        .map(|(target, value, call_data)| {
+            (target, value, Bytes::from(call_data.to_vec()))
+        })
+        .collect())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{address, fixed_bytes, Address, FixedBytes, U256};
+    use stylus_sdk::abi::Bytes;
+
+    use super::*;
+
+    const TARGET: Address =
+        address!("1111111111111111111111111111111111111111");
+
+    #[test]
+    fn encodes_and_decodes_mode() {
+        let selector = fixed_bytes!("12345678");
+        let payload = FixedBytes::<22>::repeat_byte(0xab);
+
+        let mode = encode_mode(CALLTYPE_BATCH, EXECTYPE_TRY, selector, payload);
+        assert_eq!(mode[0], 0x01);
+        assert_eq!(mode[1], 0x01);
+        assert_eq!(&mode[2..6], &[0; 4]);
+        assert_eq!(
+            decode_mode(mode),
+            (CALLTYPE_BATCH, EXECTYPE_TRY, selector, payload)
+        );
+    }
+
+    #[test]
+    fn encodes_and_decodes_single() {
+        let value = U256::from(42);
+        let data = encode_single(TARGET, value, &[0xde, 0xad]);
+        assert_eq!(data.len(), 54);
+        assert_eq!(
+            decode_single(&data).expect("should decode single call"),
+            (TARGET, value, &[0xde, 0xad][..])
+        );
+    }
+
+    #[test]
+    fn decode_single_reverts_on_short_calldata() {
+        let err = decode_single(&[0; 51]).expect_err("should fail to decode");
+        assert!(matches!(err, Error::DecodingError(_)));
+    }
+
+    #[test]
+    fn encodes_and_decodes_delegate() {
+        let data = encode_delegate(TARGET, &[0xbe, 0xef]);
+        assert_eq!(
+            decode_delegate(&data).expect("should decode delegate call"),
+            (TARGET, &[0xbe, 0xef][..])
+        );
+
+        let err = decode_delegate(&[0; 19]).expect_err("should fail to decode");
+        assert!(matches!(err, Error::DecodingError(_)));
+    }
+
+    #[test]
+    fn encodes_and_decodes_batch() {
+        let executions = vec![
+            (TARGET, U256::from(1), Bytes::from(vec![0x01, 0x02])),
+            (Address::ZERO, U256::ZERO, Bytes::from(vec![])),
+        ];
+
+        let data = encode_batch(&executions);
+        // Offset of the array, then its length.
+        assert_eq!(U256::from_be_slice(&data[..32]), U256::from(32));
+        assert_eq!(U256::from_be_slice(&data[32..64]), U256::from(2));
+        assert_eq!(
+            decode_batch(&data).expect("should decode batch"),
+            executions
+        );
+    }
+
+    #[test]
+    fn decode_batch_reverts_on_invalid_calldata() {
+        let executions =
+            vec![(TARGET, U256::from(1), Bytes::from(vec![0x01, 0x02]))];
+        let data = encode_batch(&executions);
+
+        let err = decode_batch(&data[..data.len() - 32])
+            .expect_err("should fail to decode truncated batch");
+        assert!(matches!(err, Error::DecodingError(_)));
+
+        // Length of the array pointing out of bounds.
+        let mut data = data;
+        data[63] = 0xff;
+        let err = decode_batch(&data)
+            .expect_err("should fail to decode out of bounds batch");
+        assert!(matches!(err, Error::DecodingError(_)));
+    }
+}
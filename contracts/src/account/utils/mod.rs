@@ -0,0 +1,3 @@
+//! Utilities for smart accounts.
+pub mod erc4337;
+pub mod erc7579;
@@ -0,0 +1,154 @@
+//! Extension of [`Governor`] for simple, 3 options, vote counting.
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use alloy_primitives::{Address, U256};
+use stylus_sdk::{
+    prelude::*,
+    storage::{StorageBool, StorageMap, StorageU256},
+};
+
+use crate::governance::governor::{
+    Error, Governor, GovernorAlreadyCastVote, GovernorInvalidVoteType,
+    IGovernorCounting, IGovernorQuorum, IGovernorSettings, IGovernorTimelock,
+};
+
+/// Supported vote types. Matches Governor Bravo ordering.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum VoteType {
+    /// Vote against the proposal.
+    Against = 0,
+    /// Vote for the proposal.
+    For = 1,
+    /// Abstain from voting, while still counting towards the quorum.
+    Abstain = 2,
+}
+
+impl TryFrom<u8> for VoteType {
+    type Error = Error;
+
+    fn try_from(support: u8) -> Result<Self, Self::Error> {
+        match support {
+            0 => Ok(VoteType::Against),
+            1 => Ok(VoteType::For),
+            2 => Ok(VoteType::Abstain),
+            _ => Err(GovernorInvalidVoteType {}.into()),
+        }
+    }
+}
+
+/// Votes cast on a proposal.
+#[storage]
+pub struct ProposalVote {
+    /// Number of votes against the proposal.
+    pub(crate) against_votes: StorageU256,
+    /// Number of votes for the proposal.
+    pub(crate) for_votes: StorageU256,
+    /// Number of abstain votes.
+    pub(crate) abstain_votes: StorageU256,
+    /// Whether an account has voted on the proposal.
+    pub(crate) has_voted: StorageMap<Address, StorageBool>,
+}
+
+/// State of a [`GovernorCountingSimple`] module.
+#[storage]
+pub struct GovernorCountingSimple {
+    /// Maps proposal ids to the votes cast on them.
+    pub(crate) proposal_votes: StorageMap<U256, ProposalVote>,
+}
+
+impl GovernorCountingSimple {
+    /// Accessor to the internal vote counts, returned as
+    /// `(against_votes, for_votes, abstain_votes)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    #[must_use]
+    pub fn proposal_votes(&self, proposal_id: U256) -> (U256, U256, U256) {
+        let proposal_vote = self.proposal_votes.getter(proposal_id);
+        (
+            proposal_vote.against_votes.get(),
+            proposal_vote.for_votes.get(),
+            proposal_vote.abstain_votes.get(),
+        )
+    }
+}
+
+impl IGovernorCounting for GovernorCountingSimple {
+    fn counting_mode(&self) -> String {
+        "support=bravo&quorum=for,abstain".to_string()
+    }
+
+    fn has_voted(&self, proposal_id: U256, account: Address) -> bool {
+        self.proposal_votes.getter(proposal_id).has_voted.get(account)
+    }
+
+    /// In this module, both the for votes and the abstain votes count
+    /// towards the quorum.
+    fn _quorum_reached(&self, proposal_id: U256, quorum: U256) -> bool {
+        let proposal_vote = self.proposal_votes.getter(proposal_id);
+        quorum
+            <= proposal_vote.for_votes.get() + proposal_vote.abstain_votes.get()
+    }
+
+    /// In this module, the for votes must be strictly over the against
+    /// votes.
+    fn _vote_succeeded(&self, proposal_id: U256) -> bool {
+        let proposal_vote = self.proposal_votes.getter(proposal_id);
+        proposal_vote.for_votes.get() > proposal_vote.against_votes.get()
+    }
+
+    /// In this module, the support follows the [`VoteType`] enum (from
+    /// Governor Bravo).
+    fn _count_vote(
+        &mut self,
+        proposal_id: U256,
+        account: Address,
+        support: u8,
+        total_weight: U256,
+    ) -> Result<U256, Error> {
+        let vote_type = VoteType::try_from(support)?;
+
+        let mut proposal_vote = self.proposal_votes.setter(proposal_id);
+
+        if proposal_vote.has_voted.get(account) {
+            return Err(GovernorAlreadyCastVote { voter: account }.into());
+        }
+        proposal_vote.has_voted.setter(account).set(true);
+
+        let votes = match vote_type {
+            VoteType::Against => &mut proposal_vote.against_votes,
+            VoteType::For => &mut proposal_vote.for_votes,
+            VoteType::Abstain => &mut proposal_vote.abstain_votes,
+        };
+        let total = votes.get() + total_weight;
+        votes.set(total);
+
+        Ok(total_weight)
+    }
+}
+
+impl<Q, P, T> Governor<GovernorCountingSimple, Q, P, T>
+where
+    Q: IGovernorQuorum + StorageType,
+    P: IGovernorSettings + StorageType,
+    T: IGovernorTimelock + StorageType,
+{
+    /// Accessor to the internal vote counts, returned as
+    /// `(against_votes, for_votes, abstain_votes)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    #[must_use]
+    pub fn proposal_votes(&self, proposal_id: U256) -> (U256, U256, U256) {
+        self.counting.proposal_votes(proposal_id)
+    }
+}
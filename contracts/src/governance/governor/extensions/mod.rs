@@ -0,0 +1,10 @@
+//! Common extensions to the [`super::Governor`] contract.
+pub mod counting_simple;
+pub mod settings;
+pub mod timelock_control;
+pub mod votes_quorum_fraction;
+
+pub use counting_simple::{GovernorCountingSimple, VoteType};
+pub use settings::GovernorSettings;
+pub use timelock_control::GovernorTimelockControl;
+pub use votes_quorum_fraction::GovernorVotesQuorumFraction;
@@ -0,0 +1,227 @@
+//! Extension of [`Governor`] for settings updatable through governance.
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{
+    aliases::{U32, U48},
+    U256,
+};
+pub use sol::*;
+use stylus_sdk::{
+    evm,
+    prelude::*,
+    storage::{StorageU256, StorageUint},
+};
+
+use crate::governance::governor::{
+    Error, Governor, GovernorInvalidVotingPeriod, IGovernorCounting,
+    IGovernorQuorum, IGovernorSettings, IGovernorTimelock,
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the voting delay is updated.
+        ///
+        /// * `old_voting_delay` - Previous voting delay.
+        /// * `new_voting_delay` - New voting delay.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event VotingDelaySet(uint256 old_voting_delay, uint256 new_voting_delay);
+
+        /// Emitted when the voting period is updated.
+        ///
+        /// * `old_voting_period` - Previous voting period.
+        /// * `new_voting_period` - New voting period.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event VotingPeriodSet(uint256 old_voting_period, uint256 new_voting_period);
+
+        /// Emitted when the proposal threshold is updated.
+        ///
+        /// * `old_proposal_threshold` - Previous proposal threshold.
+        /// * `new_proposal_threshold` - New proposal threshold.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event ProposalThresholdSet(
+            uint256 old_proposal_threshold,
+            uint256 new_proposal_threshold
+        );
+    }
+}
+
+/// State of a [`GovernorSettings`] module.
+#[storage]
+pub struct GovernorSettings {
+    /// Delay between the proposal creation and the vote start.
+    pub(crate) voting_delay: StorageUint<48, 1>,
+    /// Duration of the voting period.
+    pub(crate) voting_period: StorageUint<32, 1>,
+    /// Number of votes required to create a proposal.
+    pub(crate) proposal_threshold: StorageU256,
+}
+
+impl IGovernorSettings for GovernorSettings {
+    fn voting_delay(&self) -> U48 {
+        self.voting_delay.get()
+    }
+
+    fn voting_period(&self) -> U32 {
+        self.voting_period.get()
+    }
+
+    fn proposal_threshold(&self) -> U256 {
+        self.proposal_threshold.get()
+    }
+}
+
+impl GovernorSettings {
+    /// Internal setter for the voting delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_voting_delay` - New voting delay.
+    ///
+    /// # Events
+    ///
+    /// * [`VotingDelaySet`].
+    pub fn _set_voting_delay(&mut self, new_voting_delay: U48) {
+        evm::log(VotingDelaySet {
+            old_voting_delay: U256::from(self.voting_delay.get()),
+            new_voting_delay: U256::from(new_voting_delay),
+        });
+        self.voting_delay.set(new_voting_delay);
+    }
+
+    /// Internal setter for the voting period.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_voting_period` - New voting period.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidVotingPeriod`] - If `new_voting_period` is zero.
+    ///
+    /// # Events
+    ///
+    /// * [`VotingPeriodSet`].
+    pub fn _set_voting_period(
+        &mut self,
+        new_voting_period: U32,
+    ) -> Result<(), Error> {
+        // Voting period must be at least one block long.
+        if new_voting_period.is_zero() {
+            return Err(GovernorInvalidVotingPeriod {
+                voting_period: U256::ZERO,
+            }
+            .into());
+        }
+        evm::log(VotingPeriodSet {
+            old_voting_period: U256::from(self.voting_period.get()),
+            new_voting_period: U256::from(new_voting_period),
+        });
+        self.voting_period.set(new_voting_period);
+        Ok(())
+    }
+
+    /// Internal setter for the proposal threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_proposal_threshold` - New proposal threshold.
+    ///
+    /// # Events
+    ///
+    /// * [`ProposalThresholdSet`].
+    pub fn _set_proposal_threshold(&mut self, new_proposal_threshold: U256) {
+        evm::log(ProposalThresholdSet {
+            old_proposal_threshold: self.proposal_threshold.get(),
+            new_proposal_threshold,
+        });
+        self.proposal_threshold.set(new_proposal_threshold);
+    }
+}
+
+impl<C, Q, T> Governor<C, Q, GovernorSettings, T>
+where
+    C: IGovernorCounting + StorageType,
+    Q: IGovernorQuorum + StorageType,
+    T: IGovernorTimelock + StorageType,
+{
+    /// Update the voting delay. This operation can only be performed through
+    /// a governance proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_voting_delay` - New voting delay.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::OnlyExecutor`] - If the sender is not the governor.
+    ///
+    /// # Events
+    ///
+    /// * [`VotingDelaySet`].
+    pub fn set_voting_delay(
+        &mut self,
+        new_voting_delay: U48,
+    ) -> Result<(), Error> {
+        self._check_governance()?;
+        self.settings._set_voting_delay(new_voting_delay);
+        Ok(())
+    }
+
+    /// Update the voting period. This operation can only be performed
+    /// through a governance proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_voting_period` - New voting period.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::OnlyExecutor`] - If the sender is not the governor.
+    /// * [`Error::InvalidVotingPeriod`] - If `new_voting_period` is zero.
+    ///
+    /// # Events
+    ///
+    /// * [`VotingPeriodSet`].
+    pub fn set_voting_period(
+        &mut self,
+        new_voting_period: U32,
+    ) -> Result<(), Error> {
+        self._check_governance()?;
+        self.settings._set_voting_period(new_voting_period)
+    }
+
+    /// Update the proposal threshold. This operation can only be performed
+    /// through a governance proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_proposal_threshold` - New proposal threshold.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::OnlyExecutor`] - If the sender is not the governor.
+    ///
+    /// # Events
+    ///
+    /// * [`ProposalThresholdSet`].
+    pub fn set_proposal_threshold(
+        &mut self,
+        new_proposal_threshold: U256,
+    ) -> Result<(), Error> {
+        self._check_governance()?;
+        self.settings._set_proposal_threshold(new_proposal_threshold);
+        Ok(())
+    }
+}
@@ -0,0 +1,339 @@
+//! Extension of [`Governor`] that binds the execution process to an instance
+//! of [`TimelockController`].
+//!
+//! This adds a delay, enforced by the [`TimelockController`], to all
+//! successful proposals (in addition to the voting duration).
+//!
+//! The [`Governor`] needs the proposer (and ideally the executor and
+//! canceller) roles for the [`Governor`] to work properly.
+//!
+//! Using this model means the proposal will be operated by the
+//! [`TimelockController`] and not by the [`Governor`]. Thus, the assets and
+//! permissions must be attached to the [`TimelockController`]. Any asset sent
+//! to the [`Governor`] will be inaccessible from a proposal.
+//!
+//! WARNING: Setting up the [`TimelockController`] to have additional
+//! proposers or cancellers besides the governor is very risky, as it grants
+//! them the ability to: 1) execute operations as the timelock, and thus
+//! possibly performing operations or accessing funds that are expected to
+//! only be accessible through a vote, and 2) block governance proposals that
+//! have been approved by the voters, effectively executing a Denial of
+//! Service attack.
+//!
+//! [`TimelockController`]: crate::governance::timelock_controller::TimelockController
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{aliases::U48, Address, B256, U256};
+use alloy_sol_types::SolCall;
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    block,
+    call::{self, RawCall},
+    contract, evm, msg,
+    prelude::*,
+    storage::{StorageAddress, StorageB256, StorageMap},
+};
+
+use crate::{
+    governance::{
+        governor::{
+            Error, Governor, IGovernorCounting, IGovernorQuorum,
+            IGovernorSettings, IGovernorTimelock, ProposalState,
+        },
+        timelock_controller::TimelockController,
+    },
+    utils::ReentrantCallHandler,
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the timelock controller used for proposal execution
+        /// is modified.
+        ///
+        /// * `old_timelock` - Address of the previous timelock.
+        /// * `new_timelock` - Address of the new timelock.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event TimelockChange(address old_timelock, address new_timelock);
+    }
+}
+
+mod abi {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloy_sol_macro::sol;
+
+    sol! {
+        function isOperationPending(bytes32 id) returns (bool);
+        function isOperationDone(bytes32 id) returns (bool);
+        function getMinDelay() returns (uint256);
+        function scheduleBatch(
+            address[] targets,
+            uint256[] values,
+            bytes[] payloads,
+            bytes32 predecessor,
+            bytes32 salt,
+            uint256 delay
+        );
+        function executeBatch(
+            address[] targets,
+            uint256[] values,
+            bytes[] payloads,
+            bytes32 predecessor,
+            bytes32 salt
+        );
+        function cancel(bytes32 id);
+    }
+}
+
+/// State of a [`GovernorTimelockControl`] module.
+#[storage]
+pub struct GovernorTimelockControl {
+    /// Address of the [`TimelockController`] executing proposals.
+    pub(crate) timelock: StorageAddress,
+    /// Maps proposal ids to the id of their operation in the timelock.
+    pub(crate) timelock_ids: StorageMap<U256, StorageB256>,
+}
+
+impl IGovernorTimelock for GovernorTimelockControl {
+    fn proposal_needs_queuing(&self, _proposal_id: U256) -> bool {
+        true
+    }
+
+    fn _executor(&self) -> Address {
+        self.timelock.get()
+    }
+
+    fn _state(
+        &self,
+        proposal_id: U256,
+        state: ProposalState,
+    ) -> Result<ProposalState, Error> {
+        if state != ProposalState::Queued {
+            return Ok(state);
+        }
+
+        let id = self.timelock_ids.get(proposal_id);
+        if self._static_call(&abi::isOperationPendingCall { id })?._0 {
+            Ok(ProposalState::Queued)
+        } else if self._static_call(&abi::isOperationDoneCall { id })?._0 {
+            // This can happen if the proposal is executed directly on the
+            // timelock.
+            Ok(ProposalState::Executed)
+        } else {
+            // This can happen if the proposal is canceled directly on the
+            // timelock.
+            Ok(ProposalState::Canceled)
+        }
+    }
+
+    fn _queue_operations(
+        &mut self,
+        proposal_id: U256,
+        targets: &[Address],
+        values: &[U256],
+        calldatas: &[Bytes],
+        description_hash: B256,
+    ) -> Result<U48, Error> {
+        let delay = self._static_call(&abi::getMinDelayCall {})?._0;
+
+        let salt = Self::_timelock_salt(description_hash);
+        let id = TimelockController::_hash_operation_batch(
+            targets,
+            values,
+            calldatas,
+            B256::ZERO,
+            salt,
+        );
+        self.timelock_ids.setter(proposal_id).set(id);
+
+        let call = abi::scheduleBatchCall {
+            targets: targets.to_vec(),
+            values: values.to_vec(),
+            payloads: to_payloads(calldatas),
+            predecessor: B256::ZERO,
+            salt,
+            delay,
+        };
+        RawCall::new()
+            .call_with_reentrant_handling(
+                self.timelock.get(),
+                &call.abi_encode(),
+            )
+            .map_err(call::Error::Revert)?;
+
+        // Panics if the ETA doesn't fit in 48 bits.
+        Ok(U48::from(U256::from(block::timestamp()) + delay))
+    }
+
+    fn _execute_operations(
+        &mut self,
+        proposal_id: U256,
+        targets: &[Address],
+        values: &[U256],
+        calldatas: &[Bytes],
+        description_hash: B256,
+    ) -> Result<(), Error> {
+        let call = abi::executeBatchCall {
+            targets: targets.to_vec(),
+            values: values.to_vec(),
+            payloads: to_payloads(calldatas),
+            predecessor: B256::ZERO,
+            salt: Self::_timelock_salt(description_hash),
+        };
+        RawCall::new_with_value(msg::value())
+            .call_with_reentrant_handling(
+                self.timelock.get(),
+                &call.abi_encode(),
+            )
+            .map_err(call::Error::Revert)?;
+
+        // Cleanup for refund.
+        self.timelock_ids.delete(proposal_id);
+
+        Ok(())
+    }
+
+    fn _cancel_operations(&mut self, proposal_id: U256) -> Result<(), Error> {
+        let id = self.timelock_ids.get(proposal_id);
+        if id.is_zero() {
+            return Ok(());
+        }
+
+        RawCall::new()
+            .call_with_reentrant_handling(
+                self.timelock.get(),
+                &abi::cancelCall { id }.abi_encode(),
+            )
+            .map_err(call::Error::Revert)?;
+        self.timelock_ids.delete(proposal_id);
+
+        Ok(())
+    }
+}
+
+impl GovernorTimelockControl {
+    /// Returns the address of the timelock.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn timelock(&self) -> Address {
+        self.timelock.get()
+    }
+
+    /// Internal setter for the timelock.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_timelock` - Address of the new timelock.
+    ///
+    /// # Events
+    ///
+    /// * [`TimelockChange`].
+    pub fn _update_timelock(&mut self, new_timelock: Address) {
+        evm::log(TimelockChange {
+            old_timelock: self.timelock.get(),
+            new_timelock,
+        });
+        self.timelock.set(new_timelock);
+    }
+
+    /// Computes the [`TimelockController`] operation salt of a proposal.
+    ///
+    /// It is computed as the governor address itself, XOR-ed with the
+    /// `description_hash`, so that two governors sharing a timelock don't
+    /// produce the same operation ids.
+    ///
+    /// # Arguments
+    ///
+    /// * `description_hash` - Hash of the proposal's description.
+    fn _timelock_salt(description_hash: B256) -> B256 {
+        let mut salt = description_hash;
+        for (byte, address_byte) in
+            salt.iter_mut().zip(contract::address().as_slice())
+        {
+            *byte ^= address_byte;
+        }
+        salt
+    }
+
+    /// Performs a static call to the timelock and decodes its return value.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `call` - Call to perform.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call reverted or returned malformed
+    ///   data.
+    fn _static_call<C: SolCall>(&self, call: &C) -> Result<C::Return, Error> {
+        let result = RawCall::new_static()
+            .call_with_reentrant_handling(
+                self.timelock.get(),
+                &call.abi_encode(),
+            )
+            .map_err(call::Error::Revert)?;
+        C::abi_decode_returns(&result, true)
+            .map_err(|_| call::Error::Revert(vec![]).into())
+    }
+}
+
+impl<C, Q, P> Governor<C, Q, P, GovernorTimelockControl>
+where
+    C: IGovernorCounting + StorageType,
+    Q: IGovernorQuorum + StorageType,
+    P: IGovernorSettings + StorageType,
+{
+    /// Returns the address of the timelock.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn timelock(&self) -> Address {
+        self.timelock.timelock()
+    }
+
+    /// Public endpoint to update the underlying timelock instance. Restricted
+    /// to the timelock itself, so updates must be proposed, scheduled, and
+    /// executed through governance proposals.
+    ///
+    /// CAUTION: It is not recommended to change the timelock while there are
+    /// other queued governance proposals.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_timelock` - Address of the new timelock.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::OnlyExecutor`] - If the sender is not the timelock.
+    ///
+    /// # Events
+    ///
+    /// * [`TimelockChange`].
+    pub fn update_timelock(
+        &mut self,
+        new_timelock: Address,
+    ) -> Result<(), Error> {
+        self._check_governance()?;
+        self.timelock._update_timelock(new_timelock);
+        Ok(())
+    }
+}
+
+/// Converts proposal calldatas to ABI `bytes` values.
+fn to_payloads(calldatas: &[Bytes]) -> Vec<alloy_primitives::Bytes> {
+    calldatas.iter().map(|data| data.to_vec().into()).collect()
+}
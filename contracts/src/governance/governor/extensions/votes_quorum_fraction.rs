@@ -0,0 +1,177 @@
+//! Extension of [`Governor`] for voting weight extraction from an
+//! [`crate::governance::utils::IVotes`] token and a quorum expressed as a
+//! fraction of the total supply.
+//!
+//! The numerator is checkpointed, so that updating the quorum through
+//! governance does not affect the outcome of proposals whose snapshot is in
+//! the past.
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{
+    aliases::{U208, U48},
+    uint, U256,
+};
+pub use sol::*;
+use stylus_sdk::{evm, prelude::*};
+
+use crate::{
+    governance::{
+        governor::{
+            Error, Governor, GovernorInvalidQuorumFraction, IGovernorCounting,
+            IGovernorQuorum, IGovernorSettings, IGovernorTimelock,
+        },
+        utils::IErc6372,
+    },
+    utils::structs::checkpoints::{Trace, S208},
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the quorum numerator is updated.
+        ///
+        /// * `old_quorum_numerator` - Previous quorum numerator.
+        /// * `new_quorum_numerator` - New quorum numerator.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event QuorumNumeratorUpdated(
+            uint256 old_quorum_numerator,
+            uint256 new_quorum_numerator
+        );
+    }
+}
+
+/// Denominator of the quorum fraction.
+pub const QUORUM_DENOMINATOR: U256 = uint!(100_U256);
+
+/// State of a [`GovernorVotesQuorumFraction`] module.
+#[storage]
+pub struct GovernorVotesQuorumFraction {
+    /// History of the quorum numerator.
+    pub(crate) quorum_numerator_history: Trace<S208>,
+}
+
+impl IGovernorQuorum for GovernorVotesQuorumFraction {
+    /// Returns the quorum for a timepoint, in terms of number of votes:
+    /// `supply * numerator / denominator`.
+    fn quorum(&self, past_total_supply: U256, timepoint: U256) -> U256 {
+        past_total_supply * self.quorum_numerator_at(timepoint)
+            / QUORUM_DENOMINATOR
+    }
+}
+
+impl GovernorVotesQuorumFraction {
+    /// Returns the current quorum numerator.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn quorum_numerator(&self) -> U256 {
+        U256::from(self.quorum_numerator_history.latest())
+    }
+
+    /// Returns the quorum numerator at a specific timepoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `timepoint` - Timepoint to query.
+    #[must_use]
+    pub fn quorum_numerator_at(&self, timepoint: U256) -> U256 {
+        // Timepoints that don't fit into a `U48` are in the far future, so
+        // the latest numerator applies.
+        if timepoint > U256::from(U48::MAX) {
+            return self.quorum_numerator();
+        }
+        U256::from(
+            self.quorum_numerator_history.upper_lookup_recent(timepoint.to()),
+        )
+    }
+
+    /// Returns the quorum denominator. Defaults to 100.
+    #[must_use]
+    pub fn quorum_denominator(&self) -> U256 {
+        QUORUM_DENOMINATOR
+    }
+
+    /// Changes the quorum numerator, recording the change at `clock`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_quorum_numerator` - New quorum numerator.
+    /// * `clock` - Current timepoint of the governor.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidQuorumFraction`] - If `new_quorum_numerator` is
+    ///   greater than [`Self::quorum_denominator`].
+    /// * [`Error::Checkpoints`] - If `clock` is lower than the latest recorded
+    ///   change.
+    ///
+    /// # Events
+    ///
+    /// * [`QuorumNumeratorUpdated`].
+    pub fn _update_quorum_numerator(
+        &mut self,
+        new_quorum_numerator: U256,
+        clock: U48,
+    ) -> Result<(), Error> {
+        let denominator = self.quorum_denominator();
+        if new_quorum_numerator > denominator {
+            return Err(GovernorInvalidQuorumFraction {
+                quorum_numerator: new_quorum_numerator,
+                quorum_denominator: denominator,
+            }
+            .into());
+        }
+
+        let old_quorum_numerator = self.quorum_numerator();
+        // Fits into `U208`, since it's bounded by the denominator.
+        self.quorum_numerator_history
+            .push(clock, U208::from(new_quorum_numerator))?;
+
+        evm::log(QuorumNumeratorUpdated {
+            old_quorum_numerator,
+            new_quorum_numerator,
+        });
+
+        Ok(())
+    }
+}
+
+impl<C, P, T> Governor<C, GovernorVotesQuorumFraction, P, T>
+where
+    C: IGovernorCounting + StorageType,
+    P: IGovernorSettings + StorageType,
+    T: IGovernorTimelock + StorageType,
+{
+    /// Changes the quorum numerator. This operation can only be performed
+    /// through a governance proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_quorum_numerator` - New quorum numerator.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::OnlyExecutor`] - If the sender is not the governor.
+    /// * [`Error::InvalidQuorumFraction`] - If `new_quorum_numerator` is
+    ///   greater than the denominator.
+    ///
+    /// # Events
+    ///
+    /// * [`QuorumNumeratorUpdated`].
+    pub fn update_quorum_numerator(
+        &mut self,
+        new_quorum_numerator: U256,
+    ) -> Result<(), Error> {
+        self._check_governance()?;
+        let clock = self.clock();
+        self.quorum._update_quorum_numerator(new_quorum_numerator, clock)
+    }
+}
@@ -0,0 +1,2873 @@
+//! Core of the governance system, designed to be extended through various
+//! modules.
+//!
+//! This contract is generic over four modules, each one of them plugged in
+//! through a trait:
+//!
+//! * A counting module implementing [`IGovernorCounting`], e.g.
+//!   [`extensions::GovernorCountingSimple`].
+//! * A quorum module implementing [`IGovernorQuorum`], e.g.
+//!   [`extensions::GovernorVotesQuorumFraction`].
+//! * A settings module implementing [`IGovernorSettings`], e.g.
+//!   [`extensions::GovernorSettings`].
+//! * A timelock module implementing [`IGovernorTimelock`], which defaults to
+//!   [`GovernorNoTimelock`]. Use [`extensions::GovernorTimelockControl`] to
+//!   queue proposals in a
+//!   [`crate::governance::timelock_controller::TimelockController`].
+//!
+//! Voting power is read from an external token implementing
+//! [`crate::governance::utils::IVotes`] (e.g.
+//! [`crate::token::erc20::extensions::Erc20Votes`]), whose address is kept in
+//! storage. The clock of the governor follows the clock of this token, as
+//! specified in [ERC-6372].
+//!
+//! Functions restricted to governance (see [`Governor::_check_governance`])
+//! can only be called by the executor of the governor, i.e. through a
+//! successful proposal. With [`GovernorNoTimelock`], the governor is its own
+//! executor: proposals go straight from [`ProposalState::Succeeded`] to
+//! execution, and [`IGovernor::queue`] returns [`Error::QueueNotImplemented`].
+//!
+//! NOTE: Executing a proposal that calls back into the governor (e.g. to
+//! update its settings) requires the `reentrant` feature to be enabled.
+//!
+//! [ERC-6372]: https://eips.ethereum.org/EIPS/eip-6372
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use alloy_primitives::{
+    aliases::{U32, U48},
+    keccak256, Address, FixedBytes, B256, U256,
+};
+use alloy_sol_types::SolType;
+use openzeppelin_stylus_proc::interface_id;
+use stylus_sdk::{
+    abi::Bytes,
+    block,
+    call::{self, MethodError, RawCall},
+    contract, evm,
+    prelude::*,
+    storage::{
+        StorageAddress, StorageBool, StorageMap, StorageString, StorageUint,
+    },
+};
+
+use crate::{
+    governance::utils::{IErc6372, VotesInterface},
+    utils::{
+        context,
+        introspection::erc165::{Erc165, IErc165},
+        structs::checkpoints,
+        ReentrantCallHandler,
+    },
+};
+
+pub mod extensions;
+
+pub use sol::*;
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    pub(crate) type ProposalIdTuple = sol! {
+        tuple(address[], uint256[], bytes[], bytes32)
+    };
+
+    sol! {
+        /// Emitted when a proposal is created.
+        ///
+        /// * `proposal_id` - Id of the proposal.
+        /// * `proposer` - Account that created the proposal.
+        /// * `targets` - Targets of the proposal's operations.
+        /// * `values` - Values sent with the proposal's operations.
+        /// * `signatures` - Unused, always empty strings.
+        /// * `calldatas` - Calldata of the proposal's operations.
+        /// * `vote_start` - Timepoint at which voting starts.
+        /// * `vote_end` - Timepoint at which voting ends.
+        /// * `description` - Description of the proposal.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event ProposalCreated(
+            uint256 proposal_id,
+            address proposer,
+            address[] targets,
+            uint256[] values,
+            string[] signatures,
+            bytes[] calldatas,
+            uint256 vote_start,
+            uint256 vote_end,
+            string description
+        );
+
+        /// Emitted when a proposal is queued.
+        ///
+        /// * `proposal_id` - Id of the proposal.
+        /// * `eta_seconds` - Timestamp at which the proposal can be executed.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event ProposalQueued(uint256 proposal_id, uint256 eta_seconds);
+
+        /// Emitted when a proposal is executed.
+        ///
+        /// * `proposal_id` - Id of the proposal.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event ProposalExecuted(uint256 proposal_id);
+
+        /// Emitted when a proposal is canceled.
+        ///
+        /// * `proposal_id` - Id of the proposal.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event ProposalCanceled(uint256 proposal_id);
+
+        /// Emitted when a vote is cast.
+        ///
+        /// Note: `support` values should be seen as buckets. Their
+        /// interpretation depends on the voting module used.
+        ///
+        /// * `voter` - Account that cast the vote.
+        /// * `proposal_id` - Id of the proposal.
+        /// * `support` - Support of the vote.
+        /// * `weight` - Voting weight of the vote.
+        /// * `reason` - Reason given by the voter.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event VoteCast(
+            address indexed voter,
+            uint256 proposal_id,
+            uint8 support,
+            uint256 weight,
+            string reason
+        );
+    }
+
+    sol! {
+        /// Empty proposal or a mismatch between the parameters length for a
+        /// proposal call.
+        ///
+        /// * `targets` - Number of targets.
+        /// * `calldatas` - Number of calldatas.
+        /// * `values` - Number of values.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorInvalidProposalLength(
+            uint256 targets,
+            uint256 calldatas,
+            uint256 values
+        );
+
+        /// The vote was already cast.
+        ///
+        /// * `voter` - Account that already voted.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorAlreadyCastVote(address voter);
+
+        /// The `account` is not the governance executor.
+        ///
+        /// * `account` - Account that attempted the call.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorOnlyExecutor(address account);
+
+        /// The `proposal_id` doesn't exist.
+        ///
+        /// * `proposal_id` - Id of the proposal.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorNonexistentProposal(uint256 proposal_id);
+
+        /// The current state of a proposal is not the required for performing
+        /// an operation. The `expected_states` is a bitmap with the bits
+        /// enabled for each [`super::ProposalState`] enum position counting
+        /// from right to left.
+        ///
+        /// * `proposal_id` - Id of the proposal.
+        /// * `current` - Current state of the proposal.
+        /// * `expected_states` - Bitmap of the expected states.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorUnexpectedProposalState(
+            uint256 proposal_id,
+            uint8 current,
+            bytes32 expected_states
+        );
+
+        /// The voting period set is not a valid period.
+        ///
+        /// * `voting_period` - Invalid voting period.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorInvalidVotingPeriod(uint256 voting_period);
+
+        /// The `proposer` does not have the required votes to create a
+        /// proposal.
+        ///
+        /// * `proposer` - Account attempting to create a proposal.
+        /// * `votes` - Voting power of the `proposer`.
+        /// * `threshold` - Required voting power.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorInsufficientProposerVotes(
+            address proposer,
+            uint256 votes,
+            uint256 threshold
+        );
+
+        /// The `proposer` is not allowed to create a proposal.
+        ///
+        /// * `proposer` - Account attempting to create a proposal.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorRestrictedProposer(address proposer);
+
+        /// The vote type used is not valid for the corresponding counting
+        /// module.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorInvalidVoteType();
+
+        /// Queue operation is not implemented for this governor. Execute
+        /// should be called directly.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorQueueNotImplemented();
+
+        /// The `account` is not the proposer of the proposal.
+        ///
+        /// * `account` - Account attempting the operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorOnlyProposer(address account);
+
+        /// The quorum set is not a valid fraction.
+        ///
+        /// * `quorum_numerator` - Invalid numerator.
+        /// * `quorum_denominator` - Denominator of the fraction.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error GovernorInvalidQuorumFraction(
+            uint256 quorum_numerator,
+            uint256 quorum_denominator
+        );
+    }
+}
+
+/// A [`Governor`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Empty proposal or a mismatch between the parameters length for a
+    /// proposal call.
+    InvalidProposalLength(GovernorInvalidProposalLength),
+    /// The vote was already cast.
+    AlreadyCastVote(GovernorAlreadyCastVote),
+    /// The `account` is not the governance executor.
+    OnlyExecutor(GovernorOnlyExecutor),
+    /// The `proposal_id` doesn't exist.
+    NonexistentProposal(GovernorNonexistentProposal),
+    /// The current state of a proposal is not the required for performing an
+    /// operation.
+    UnexpectedProposalState(GovernorUnexpectedProposalState),
+    /// The voting period set is not a valid period.
+    InvalidVotingPeriod(GovernorInvalidVotingPeriod),
+    /// The `proposer` does not have the required votes to create a proposal.
+    InsufficientProposerVotes(GovernorInsufficientProposerVotes),
+    /// The `proposer` is not allowed to create a proposal.
+    RestrictedProposer(GovernorRestrictedProposer),
+    /// The vote type used is not valid for the corresponding counting module.
+    InvalidVoteType(GovernorInvalidVoteType),
+    /// Queue operation is not implemented for this governor.
+    QueueNotImplemented(GovernorQueueNotImplemented),
+    /// The `account` is not the proposer of the proposal.
+    OnlyProposer(GovernorOnlyProposer),
+    /// The quorum set is not a valid fraction.
+    InvalidQuorumFraction(GovernorInvalidQuorumFraction),
+    /// Error type from checkpoint contract [`checkpoints::Error`].
+    Checkpoints(checkpoints::Error),
+    /// An external call (to the votes token or to a proposal's target)
+    /// failed.
+    FailedCall(call::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of a proposal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ProposalState {
+    /// Voting has not started yet.
+    Pending = 0,
+    /// Voting is ongoing.
+    Active = 1,
+    /// The proposal was canceled.
+    Canceled = 2,
+    /// Voting ended and the proposal did not pass.
+    Defeated = 3,
+    /// Voting ended and the proposal passed.
+    Succeeded = 4,
+    /// The proposal is queued for execution.
+    Queued = 5,
+    /// The proposal expired before being executed.
+    Expired = 6,
+    /// The proposal was executed.
+    Executed = 7,
+}
+
+impl ProposalState {
+    /// Encodes the state into a bitmap, with the bit at the position of the
+    /// state enabled.
+    #[must_use]
+    pub fn encode_bitmap(self) -> B256 {
+        B256::from(U256::from(1) << (self as u8))
+    }
+}
+
+/// Stores the core state of a proposal.
+#[storage]
+pub struct ProposalCore {
+    /// Account that created the proposal.
+    pub(crate) proposer: StorageAddress,
+    /// Timepoint at which voting starts (snapshot).
+    pub(crate) vote_start: StorageUint<48, 1>,
+    /// Duration of the voting period.
+    pub(crate) vote_duration: StorageUint<32, 1>,
+    /// Whether the proposal was executed.
+    pub(crate) executed: StorageBool,
+    /// Whether the proposal was canceled.
+    pub(crate) canceled: StorageBool,
+    /// Timestamp at which a queued proposal can be executed.
+    pub(crate) eta_seconds: StorageUint<48, 1>,
+}
+
+/// Counting module of a [`Governor`].
+pub trait IGovernorCounting {
+    /// Returns a description of the possible `support` values for
+    /// [`IGovernor::cast_vote`] and the way these votes are counted, meant to
+    /// be consumed by UIs to show correct vote options and interpret the
+    /// results. See [`IGovernor::counting_mode`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn counting_mode(&self) -> String;
+
+    /// Returns whether `account` has cast a vote on `proposal_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `account` - Account to check.
+    fn has_voted(&self, proposal_id: U256, account: Address) -> bool;
+
+    /// Returns whether the amount of votes cast for `proposal_id` reaches
+    /// `quorum`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `quorum` - Quorum required at the proposal's snapshot.
+    fn _quorum_reached(&self, proposal_id: U256, quorum: U256) -> bool;
+
+    /// Returns whether the proposal is successful or not.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    fn _vote_succeeded(&self, proposal_id: U256) -> bool;
+
+    /// Registers a vote for `proposal_id` by `account` with a given
+    /// `support`, voting `total_weight`. Returns the amount of votes that
+    /// were counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `account` - Account casting the vote.
+    /// * `support` - Support of the vote.
+    /// * `total_weight` - Voting power of `account` at the proposal's snapshot.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AlreadyCastVote`] - If `account` already voted.
+    /// * [`Error::InvalidVoteType`] - If `support` is not a valid vote type.
+    fn _count_vote(
+        &mut self,
+        proposal_id: U256,
+        account: Address,
+        support: u8,
+        total_weight: U256,
+    ) -> Result<U256, Error>;
+}
+
+/// Quorum module of a [`Governor`].
+pub trait IGovernorQuorum {
+    /// Returns the minimum number of votes required for a proposal to be
+    /// successful, given the total supply of votes at `timepoint`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `past_total_supply` - Total supply of votes at `timepoint`.
+    /// * `timepoint` - Timepoint the quorum is computed for.
+    fn quorum(&self, past_total_supply: U256, timepoint: U256) -> U256;
+}
+
+/// Settings module of a [`Governor`].
+pub trait IGovernorSettings {
+    /// Returns the delay between the proposal creation and the vote start.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn voting_delay(&self) -> U48;
+
+    /// Returns the delay between the vote start and the vote end.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn voting_period(&self) -> U32;
+
+    /// Returns the number of votes required in order for a voter to become a
+    /// proposer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn proposal_threshold(&self) -> U256;
+}
+
+/// Timelock module of a [`Governor`], in charge of queuing and executing the
+/// operations of successful proposals.
+pub trait IGovernorTimelock {
+    /// Returns whether a proposal needs to be queued before execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    fn proposal_needs_queuing(&self, proposal_id: U256) -> bool;
+
+    /// Returns the address through which the governor executes actions. Only
+    /// this address can call functions restricted to governance.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn _executor(&self) -> Address;
+
+    /// Returns the state of a proposal, given the `state` computed by the
+    /// [`Governor`] core.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `state` - State of the proposal computed by the core.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the state of the operations could not be
+    ///   queried.
+    fn _state(
+        &self,
+        proposal_id: U256,
+        state: ProposalState,
+    ) -> Result<ProposalState, Error>;
+
+    /// Queues the operations of a successful proposal. Returns the timestamp
+    /// at which the proposal can be executed, or zero if queuing is not
+    /// supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `targets` - Targets of the proposal's operations.
+    /// * `values` - Values sent with the proposal's operations.
+    /// * `calldatas` - Calldata of the proposal's operations.
+    /// * `description_hash` - Hash of the proposal's description.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the operations could not be queued.
+    fn _queue_operations(
+        &mut self,
+        proposal_id: U256,
+        targets: &[Address],
+        values: &[U256],
+        calldatas: &[Bytes],
+        description_hash: B256,
+    ) -> Result<U48, Error>;
+
+    /// Executes the operations of a proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `targets` - Targets of the proposal's operations.
+    /// * `values` - Values sent with the proposal's operations.
+    /// * `calldatas` - Calldata of the proposal's operations.
+    /// * `description_hash` - Hash of the proposal's description.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If one of the operations failed.
+    fn _execute_operations(
+        &mut self,
+        proposal_id: U256,
+        targets: &[Address],
+        values: &[U256],
+        calldatas: &[Bytes],
+        description_hash: B256,
+    ) -> Result<(), Error>;
+
+    /// Cancels the queued operations of a canceled proposal, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the operations could not be canceled.
+    fn _cancel_operations(&mut self, proposal_id: U256) -> Result<(), Error>;
+}
+
+/// Timelock module of a [`Governor`] without timelock: the governor is its own
+/// executor, and successful proposals are executed directly, without being
+/// queued.
+#[storage]
+pub struct GovernorNoTimelock;
+
+impl IGovernorTimelock for GovernorNoTimelock {
+    fn proposal_needs_queuing(&self, _proposal_id: U256) -> bool {
+        false
+    }
+
+    fn _executor(&self) -> Address {
+        contract::address()
+    }
+
+    fn _state(
+        &self,
+        _proposal_id: U256,
+        state: ProposalState,
+    ) -> Result<ProposalState, Error> {
+        Ok(state)
+    }
+
+    fn _queue_operations(
+        &mut self,
+        _proposal_id: U256,
+        _targets: &[Address],
+        _values: &[U256],
+        _calldatas: &[Bytes],
+        _description_hash: B256,
+    ) -> Result<U48, Error> {
+        Ok(U48::ZERO)
+    }
+
+    fn _execute_operations(
+        &mut self,
+        _proposal_id: U256,
+        targets: &[Address],
+        values: &[U256],
+        calldatas: &[Bytes],
+        _description_hash: B256,
+    ) -> Result<(), Error> {
+        for ((target, value), calldata) in
+            targets.iter().zip(values).zip(calldatas)
+        {
+            RawCall::new_with_value(*value)
+                .call_with_reentrant_handling(*target, calldata)
+                .map_err(call::Error::Revert)?;
+        }
+        Ok(())
+    }
+
+    fn _cancel_operations(&mut self, _proposal_id: U256) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// State of a [`Governor`] contract.
+#[storage]
+pub struct Governor<
+    C: IGovernorCounting + StorageType,
+    Q: IGovernorQuorum + StorageType,
+    P: IGovernorSettings + StorageType,
+    T: IGovernorTimelock + StorageType = GovernorNoTimelock,
+> {
+    /// Name of the governor.
+    pub(crate) name: StorageString,
+    /// Address of the token used for voting power.
+    pub(crate) token: StorageAddress,
+    /// Maps proposal ids to their core state.
+    pub(crate) proposals: StorageMap<U256, ProposalCore>,
+    /// Counting module.
+    pub counting: C,
+    /// Quorum module.
+    pub quorum: Q,
+    /// Settings module.
+    pub settings: P,
+    /// Timelock module.
+    pub timelock: T,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl<C, Q, P, T> TopLevelStorage for Governor<C, Q, P, T>
+where
+    C: IGovernorCounting + StorageType,
+    Q: IGovernorQuorum + StorageType,
+    P: IGovernorSettings + StorageType,
+    T: IGovernorTimelock + StorageType,
+{
+}
+
+/// Interface of the [`Governor`] core.
+#[interface_id]
+pub trait IGovernor {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Name of the governor instance (used in building the EIP-712 domain
+    /// separator).
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn name(&self) -> String;
+
+    /// Version of the governor instance (used in building the EIP-712 domain
+    /// separator). Default: "1".
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn version(&self) -> String;
+
+    /// A description of the possible `support` values for
+    /// [`Self::cast_vote`] and the way these votes are counted, meant to be
+    /// consumed by UIs to show correct vote options and interpret the
+    /// results. The string is a URL-encoded sequence of key-value pairs that
+    /// each describe one aspect, for example
+    /// `support=bravo&quorum=for,abstain`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[selector(name = "COUNTING_MODE")]
+    fn counting_mode(&self) -> String;
+
+    /// Hashing function used to (re)build the proposal id from the proposal
+    /// details.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `targets` - Targets of the proposal's operations.
+    /// * `values` - Values sent with the proposal's operations.
+    /// * `calldatas` - Calldata of the proposal's operations.
+    /// * `description_hash` - Hash of the proposal's description.
+    fn hash_proposal(
+        &self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> U256;
+
+    /// Current state of a proposal, as a [`ProposalState`] discriminant.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::NonexistentProposal`] - If the proposal does not exist.
+    /// * [`Error::FailedCall`] - If the votes token could not be queried.
+    fn state(&self, proposal_id: U256) -> Result<u8, Self::Error>;
+
+    /// The number of votes required in order for a voter to become a
+    /// proposer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn proposal_threshold(&self) -> U256;
+
+    /// Timepoint used to retrieve user's votes and quorum. If using block
+    /// number, the snapshot is performed at the end of this block. Hence,
+    /// voting for this proposal starts at the beginning of the following
+    /// block.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    fn proposal_snapshot(&self, proposal_id: U256) -> U256;
+
+    /// Timepoint at which votes close. If using block number, votes close at
+    /// the end of this block, so it is possible to cast a vote during this
+    /// block.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    fn proposal_deadline(&self, proposal_id: U256) -> U256;
+
+    /// The account that created a proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    fn proposal_proposer(&self, proposal_id: U256) -> Address;
+
+    /// The time when a queued proposal becomes executable ("ETA"). Unlike
+    /// [`Self::proposal_snapshot`] and [`Self::proposal_deadline`], this
+    /// doesn't use the governor clock, and instead relies on the executor's
+    /// clock which may be different. In most cases this will be a timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    fn proposal_eta(&self, proposal_id: U256) -> U256;
+
+    /// Whether a proposal needs to be queued before execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    fn proposal_needs_queuing(&self, proposal_id: U256) -> bool;
+
+    /// Delay, between the proposal is created and the vote starts. The unit
+    /// this duration is expressed in depends on the clock (see [ERC-6372])
+    /// this contract uses.
+    ///
+    /// [ERC-6372]: https://eips.ethereum.org/EIPS/eip-6372
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn voting_delay(&self) -> U256;
+
+    /// Delay between the vote start and vote end. The unit this duration is
+    /// expressed in depends on the clock (see [ERC-6372]) this contract uses.
+    ///
+    /// [ERC-6372]: https://eips.ethereum.org/EIPS/eip-6372
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn voting_period(&self) -> U256;
+
+    /// Minimum number of cast votes required for a proposal to be
+    /// successful.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `timepoint` - Timepoint the quorum is computed for.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the votes token could not be queried.
+    fn quorum(&self, timepoint: U256) -> Result<U256, Self::Error>;
+
+    /// Voting power of an `account` at a specific `timepoint`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the votes of.
+    /// * `timepoint` - Timepoint to query.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the votes token could not be queried.
+    fn get_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Self::Error>;
+
+    /// Returns whether `account` has cast a vote on `proposal_id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `account` - Account to check.
+    fn has_voted(&self, proposal_id: U256, account: Address) -> bool;
+
+    /// Create a new proposal. Vote start after a delay specified by
+    /// [`Self::voting_delay`] and lasts for a duration specified by
+    /// [`Self::voting_period`]. Returns the id of the proposal.
+    ///
+    /// NOTE: The state of the governor and `targets` may change between the
+    /// proposal creation and its execution. This may be the result of third
+    /// party actions on the targeted contracts, or other governor proposals.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `targets` - Targets of the proposal's operations.
+    /// * `values` - Values sent with the proposal's operations.
+    /// * `calldatas` - Calldata of the proposal's operations.
+    /// * `description` - Description of the proposal.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::RestrictedProposer`] - If the description restricts the
+    ///   proposer to another account.
+    /// * [`Error::InsufficientProposerVotes`] - If the sender's votes are below
+    ///   [`Self::proposal_threshold`].
+    /// * [`Error::InvalidProposalLength`] - If the proposal is empty or the
+    ///   parameters lengths mismatch.
+    /// * [`Error::UnexpectedProposalState`] - If the proposal already exists.
+    ///
+    /// # Events
+    ///
+    /// * [`ProposalCreated`].
+    fn propose(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description: String,
+    ) -> Result<U256, Self::Error>;
+
+    /// Queue a proposal. Some governors require this step to be performed
+    /// before execution can happen. If queuing is not necessary, this
+    /// function may revert. Returns the id of the proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `targets` - Targets of the proposal's operations.
+    /// * `values` - Values sent with the proposal's operations.
+    /// * `calldatas` - Calldata of the proposal's operations.
+    /// * `description_hash` - Hash of the proposal's description.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedProposalState`] - If the proposal has not
+    ///   succeeded.
+    /// * [`Error::QueueNotImplemented`] - If the governor does not support
+    ///   queuing.
+    /// * [`Error::FailedCall`] - If the operations could not be queued.
+    ///
+    /// # Events
+    ///
+    /// * [`ProposalQueued`].
+    fn queue(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> Result<U256, Self::Error>;
+
+    /// Execute a successful proposal. This requires the quorum to be
+    /// reached, the vote to be successful, and the deadline to be reached.
+    /// Returns the id of the proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `targets` - Targets of the proposal's operations.
+    /// * `values` - Values sent with the proposal's operations.
+    /// * `calldatas` - Calldata of the proposal's operations.
+    /// * `description_hash` - Hash of the proposal's description.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedProposalState`] - If the proposal is neither
+    ///   succeeded nor queued.
+    /// * [`Error::FailedCall`] - If one of the operations failed.
+    ///
+    /// # Events
+    ///
+    /// * [`ProposalExecuted`].
+    fn execute(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> Result<U256, Self::Error>;
+
+    /// Cancel a proposal. A proposal is cancellable by the proposer, but only
+    /// while it is pending. Returns the id of the proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `targets` - Targets of the proposal's operations.
+    /// * `values` - Values sent with the proposal's operations.
+    /// * `calldatas` - Calldata of the proposal's operations.
+    /// * `description_hash` - Hash of the proposal's description.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedProposalState`] - If the proposal is not pending.
+    /// * [`Error::OnlyProposer`] - If the sender is not the proposer.
+    ///
+    /// # Events
+    ///
+    /// * [`ProposalCanceled`].
+    fn cancel(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> Result<U256, Self::Error>;
+
+    /// Cast a vote. Returns the weight of the vote.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `support` - Support of the vote.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedProposalState`] - If the proposal is not active.
+    /// * [`Error::AlreadyCastVote`] - If the sender already voted.
+    /// * [`Error::InvalidVoteType`] - If `support` is not a valid vote type.
+    ///
+    /// # Events
+    ///
+    /// * [`VoteCast`].
+    fn cast_vote(
+        &mut self,
+        proposal_id: U256,
+        support: u8,
+    ) -> Result<U256, Self::Error>;
+
+    /// Cast a vote with a reason. Returns the weight of the vote.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `support` - Support of the vote.
+    /// * `reason` - Reason given by the voter.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedProposalState`] - If the proposal is not active.
+    /// * [`Error::AlreadyCastVote`] - If the sender already voted.
+    /// * [`Error::InvalidVoteType`] - If `support` is not a valid vote type.
+    ///
+    /// # Events
+    ///
+    /// * [`VoteCast`].
+    fn cast_vote_with_reason(
+        &mut self,
+        proposal_id: U256,
+        support: u8,
+        reason: String,
+    ) -> Result<U256, Self::Error>;
+}
+
+#[public]
+impl<C, Q, P, T> IGovernor for Governor<C, Q, P, T>
+where
+    C: IGovernorCounting + StorageType,
+    Q: IGovernorQuorum + StorageType,
+    P: IGovernorSettings + StorageType,
+    T: IGovernorTimelock + StorageType,
+{
+    type Error = Error;
+
+    fn name(&self) -> String {
+        self.name.get_string()
+    }
+
+    fn version(&self) -> String {
+        "1".to_string()
+    }
+
+    #[selector(name = "COUNTING_MODE")]
+    fn counting_mode(&self) -> String {
+        self.counting.counting_mode()
+    }
+
+    fn hash_proposal(
+        &self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> U256 {
+        Self::_hash_proposal(&targets, &values, &calldatas, description_hash)
+    }
+
+    fn state(&self, proposal_id: U256) -> Result<u8, Error> {
+        Ok(self._state(proposal_id)? as u8)
+    }
+
+    fn proposal_threshold(&self) -> U256 {
+        self.settings.proposal_threshold()
+    }
+
+    fn proposal_snapshot(&self, proposal_id: U256) -> U256 {
+        U256::from(self._proposal_snapshot(proposal_id))
+    }
+
+    fn proposal_deadline(&self, proposal_id: U256) -> U256 {
+        let proposal = self.proposals.getter(proposal_id);
+        U256::from(proposal.vote_start.get())
+            + U256::from(proposal.vote_duration.get())
+    }
+
+    fn proposal_proposer(&self, proposal_id: U256) -> Address {
+        self.proposals.getter(proposal_id).proposer.get()
+    }
+
+    fn proposal_eta(&self, proposal_id: U256) -> U256 {
+        U256::from(self.proposals.getter(proposal_id).eta_seconds.get())
+    }
+
+    fn proposal_needs_queuing(&self, proposal_id: U256) -> bool {
+        self.timelock.proposal_needs_queuing(proposal_id)
+    }
+
+    fn voting_delay(&self) -> U256 {
+        U256::from(self.settings.voting_delay())
+    }
+
+    fn voting_period(&self) -> U256 {
+        U256::from(self.settings.voting_period())
+    }
+
+    fn quorum(&self, timepoint: U256) -> Result<U256, Error> {
+        let token = VotesInterface::new(self.token());
+        let past_total_supply = token.get_past_total_supply(self, timepoint)?;
+        Ok(self.quorum.quorum(past_total_supply, timepoint))
+    }
+
+    fn get_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Error> {
+        self._get_votes(account, timepoint)
+    }
+
+    fn has_voted(&self, proposal_id: U256, account: Address) -> bool {
+        self.counting.has_voted(proposal_id, account)
+    }
+
+    fn propose(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description: String,
+    ) -> Result<U256, Error> {
+        let proposer = context::msg_sender();
+
+        // Check description restriction.
+        if !Self::_is_valid_description_for_proposer(proposer, &description) {
+            return Err(GovernorRestrictedProposer { proposer }.into());
+        }
+
+        // Check proposal threshold.
+        let votes_threshold = self.proposal_threshold();
+        if votes_threshold > U256::ZERO {
+            let timepoint =
+                U256::from(self.clock()).saturating_sub(U256::from(1));
+            let proposer_votes = self._get_votes(proposer, timepoint)?;
+            if proposer_votes < votes_threshold {
+                return Err(GovernorInsufficientProposerVotes {
+                    proposer,
+                    votes: proposer_votes,
+                    threshold: votes_threshold,
+                }
+                .into());
+            }
+        }
+
+        self._propose(targets, values, calldatas, description, proposer)
+    }
+
+    fn queue(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> Result<U256, Error> {
+        let proposal_id = Self::_hash_proposal(
+            &targets,
+            &values,
+            &calldatas,
+            description_hash,
+        );
+
+        self._validate_state_bitmap(
+            proposal_id,
+            ProposalState::Succeeded.encode_bitmap(),
+        )?;
+
+        let eta_seconds = self.timelock._queue_operations(
+            proposal_id,
+            &targets,
+            &values,
+            &calldatas,
+            description_hash,
+        )?;
+
+        if eta_seconds.is_zero() {
+            return Err(GovernorQueueNotImplemented {}.into());
+        }
+
+        self.proposals.setter(proposal_id).eta_seconds.set(eta_seconds);
+        evm::log(ProposalQueued {
+            proposal_id,
+            eta_seconds: U256::from(eta_seconds),
+        });
+
+        Ok(proposal_id)
+    }
+
+    #[payable]
+    fn execute(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> Result<U256, Error> {
+        let proposal_id = Self::_hash_proposal(
+            &targets,
+            &values,
+            &calldatas,
+            description_hash,
+        );
+
+        self._validate_state_bitmap(
+            proposal_id,
+            ProposalState::Succeeded.encode_bitmap()
+                | ProposalState::Queued.encode_bitmap(),
+        )?;
+
+        // Mark as executed before calls to avoid reentrancy.
+        self.proposals.setter(proposal_id).executed.set(true);
+
+        self.timelock._execute_operations(
+            proposal_id,
+            &targets,
+            &values,
+            &calldatas,
+            description_hash,
+        )?;
+
+        evm::log(ProposalExecuted { proposal_id });
+
+        Ok(proposal_id)
+    }
+
+    fn cancel(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description_hash: B256,
+    ) -> Result<U256, Error> {
+        let proposal_id = Self::_hash_proposal(
+            &targets,
+            &values,
+            &calldatas,
+            description_hash,
+        );
+
+        // Public cancel restrictions (on top of existing `_cancel`
+        // restrictions).
+        self._validate_state_bitmap(
+            proposal_id,
+            ProposalState::Pending.encode_bitmap(),
+        )?;
+        let sender = context::msg_sender();
+        if sender != self.proposal_proposer(proposal_id) {
+            return Err(GovernorOnlyProposer { account: sender }.into());
+        }
+
+        self._cancel(&targets, &values, &calldatas, description_hash)
+    }
+
+    fn cast_vote(
+        &mut self,
+        proposal_id: U256,
+        support: u8,
+    ) -> Result<U256, Error> {
+        self._cast_vote(
+            proposal_id,
+            context::msg_sender(),
+            support,
+            String::new(),
+        )
+    }
+
+    fn cast_vote_with_reason(
+        &mut self,
+        proposal_id: U256,
+        support: u8,
+        reason: String,
+    ) -> Result<U256, Error> {
+        self._cast_vote(proposal_id, context::msg_sender(), support, reason)
+    }
+}
+
+impl<C, Q, P, T> IErc6372 for Governor<C, Q, P, T>
+where
+    C: IGovernorCounting + StorageType,
+    Q: IGovernorQuorum + StorageType,
+    P: IGovernorSettings + StorageType,
+    T: IGovernorTimelock + StorageType,
+{
+    fn clock(&self) -> U48 {
+        VotesInterface::new(self.token())
+            .clock(self)
+            .unwrap_or_else(|_| U48::from(block::number()))
+    }
+
+    fn clock_mode(&self) -> String {
+        VotesInterface::new(self.token())
+            .clock_mode(self)
+            .unwrap_or_else(|_| "mode=blocknumber&from=default".to_string())
+    }
+}
+
+impl<C, Q, P, T> Governor<C, Q, P, T>
+where
+    C: IGovernorCounting + StorageType,
+    Q: IGovernorQuorum + StorageType,
+    P: IGovernorSettings + StorageType,
+    T: IGovernorTimelock + StorageType,
+{
+    /// Returns the address of the token that voting power is sourced from.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn token(&self) -> Address {
+        self.token.get()
+    }
+
+    /// Current state of a proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::NonexistentProposal`] - If the proposal does not exist.
+    /// * [`Error::FailedCall`] - If the votes token could not be queried.
+    pub fn _state(&self, proposal_id: U256) -> Result<ProposalState, Error> {
+        let proposal = self.proposals.getter(proposal_id);
+
+        if proposal.executed.get() {
+            return Ok(ProposalState::Executed);
+        }
+
+        if proposal.canceled.get() {
+            return Ok(ProposalState::Canceled);
+        }
+
+        let snapshot = self._proposal_snapshot(proposal_id);
+
+        if snapshot.is_zero() {
+            return Err(GovernorNonexistentProposal { proposal_id }.into());
+        }
+
+        let current_timepoint = self.clock();
+
+        if snapshot >= current_timepoint {
+            return Ok(ProposalState::Pending);
+        }
+
+        let deadline = self.proposal_deadline(proposal_id);
+
+        if deadline >= U256::from(current_timepoint) {
+            return Ok(ProposalState::Active);
+        }
+
+        let quorum = self.quorum(U256::from(snapshot))?;
+        let state = if !self.counting._quorum_reached(proposal_id, quorum)
+            || !self.counting._vote_succeeded(proposal_id)
+        {
+            ProposalState::Defeated
+        } else if self.proposal_eta(proposal_id).is_zero() {
+            ProposalState::Succeeded
+        } else {
+            ProposalState::Queued
+        };
+
+        self.timelock._state(proposal_id, state)
+    }
+
+    /// Reverts if the sender is not the executor of the governor, i.e. the
+    /// governor itself. Used to restrict functions to calls made through a
+    /// successful proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::OnlyExecutor`] - If the sender is not the executor.
+    pub fn _check_governance(&self) -> Result<(), Error> {
+        let sender = context::msg_sender();
+        if sender != self._executor() {
+            return Err(GovernorOnlyExecutor { account: sender }.into());
+        }
+        Ok(())
+    }
+
+    /// Address through which the governor executes actions, as defined by
+    /// its timelock module.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn _executor(&self) -> Address {
+        self.timelock._executor()
+    }
+
+    /// Hashing function used to (re)build the proposal id from the proposal
+    /// details.
+    ///
+    /// # Arguments
+    ///
+    /// * `targets` - Targets of the proposal's operations.
+    /// * `values` - Values sent with the proposal's operations.
+    /// * `calldatas` - Calldata of the proposal's operations.
+    /// * `description_hash` - Hash of the proposal's description.
+    #[must_use]
+    pub fn _hash_proposal(
+        targets: &[Address],
+        values: &[U256],
+        calldatas: &[Bytes],
+        description_hash: B256,
+    ) -> U256 {
+        let calldatas: Vec<alloy_primitives::Bytes> =
+            calldatas.iter().map(|data| data.to_vec().into()).collect();
+        let encoded = ProposalIdTuple::abi_encode_params(&(
+            targets.to_vec(),
+            values.to_vec(),
+            calldatas,
+            description_hash,
+        ));
+        U256::from_be_bytes(*keccak256(encoded))
+    }
+
+    /// Voting power of an `account` at a specific `timepoint`, read from the
+    /// votes token.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the votes of.
+    /// * `timepoint` - Timepoint to query.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the votes token could not be queried.
+    pub fn _get_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Error> {
+        let token = VotesInterface::new(self.token());
+        Ok(token.get_past_votes(self, account, timepoint)?)
+    }
+
+    /// Internal propose mechanism. Returns the id of the proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `targets` - Targets of the proposal's operations.
+    /// * `values` - Values sent with the proposal's operations.
+    /// * `calldatas` - Calldata of the proposal's operations.
+    /// * `description` - Description of the proposal.
+    /// * `proposer` - Account creating the proposal.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidProposalLength`] - If the proposal is empty or the
+    ///   parameters lengths mismatch.
+    /// * [`Error::UnexpectedProposalState`] - If the proposal already exists.
+    ///
+    /// # Events
+    ///
+    /// * [`ProposalCreated`].
+    pub fn _propose(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        calldatas: Vec<Bytes>,
+        description: String,
+        proposer: Address,
+    ) -> Result<U256, Error> {
+        if targets.len() != values.len()
+            || targets.len() != calldatas.len()
+            || targets.is_empty()
+        {
+            return Err(GovernorInvalidProposalLength {
+                targets: U256::from(targets.len()),
+                calldatas: U256::from(calldatas.len()),
+                values: U256::from(values.len()),
+            }
+            .into());
+        }
+
+        let proposal_id = Self::_hash_proposal(
+            &targets,
+            &values,
+            &calldatas,
+            keccak256(description.as_bytes()),
+        );
+
+        if !self._proposal_snapshot(proposal_id).is_zero() {
+            let current = self._state(proposal_id)?;
+            return Err(GovernorUnexpectedProposalState {
+                proposal_id,
+                current: current as u8,
+                expected_states: B256::ZERO,
+            }
+            .into());
+        }
+
+        let snapshot = self.clock() + self.settings.voting_delay();
+        let duration = self.settings.voting_period();
+
+        let mut proposal = self.proposals.setter(proposal_id);
+        proposal.proposer.set(proposer);
+        proposal.vote_start.set(snapshot);
+        proposal.vote_duration.set(duration);
+
+        let vote_start = U256::from(snapshot);
+        let signatures = vec![String::new(); targets.len()];
+        let calldatas =
+            calldatas.into_iter().map(|data| data.to_vec().into()).collect();
+        evm::log(ProposalCreated {
+            proposal_id,
+            proposer,
+            targets,
+            values,
+            signatures,
+            calldatas,
+            vote_start,
+            vote_end: vote_start + U256::from(duration),
+            description,
+        });
+
+        Ok(proposal_id)
+    }
+
+    /// Internal cancel mechanism with minimal restrictions. A proposal can be
+    /// cancelled in any state other than Canceled, Expired, or Executed.
+    /// Once cancelled a proposal can't be re-submitted. Returns the id of the
+    /// proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `targets` - Targets of the proposal's operations.
+    /// * `values` - Values sent with the proposal's operations.
+    /// * `calldatas` - Calldata of the proposal's operations.
+    /// * `description_hash` - Hash of the proposal's description.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedProposalState`] - If the proposal is canceled,
+    ///   expired or executed.
+    /// * [`Error::FailedCall`] - If the queued operations could not be
+    ///   canceled.
+    ///
+    /// # Events
+    ///
+    /// * [`ProposalCanceled`].
+    pub fn _cancel(
+        &mut self,
+        targets: &[Address],
+        values: &[U256],
+        calldatas: &[Bytes],
+        description_hash: B256,
+    ) -> Result<U256, Error> {
+        let proposal_id =
+            Self::_hash_proposal(targets, values, calldatas, description_hash);
+
+        let all_states = B256::from(
+            (U256::from(1) << (ProposalState::Executed as u8 + 1))
+                - U256::from(1),
+        );
+        self._validate_state_bitmap(
+            proposal_id,
+            all_states
+                ^ ProposalState::Canceled.encode_bitmap()
+                ^ ProposalState::Expired.encode_bitmap()
+                ^ ProposalState::Executed.encode_bitmap(),
+        )?;
+
+        self.proposals.setter(proposal_id).canceled.set(true);
+        self.timelock._cancel_operations(proposal_id)?;
+        evm::log(ProposalCanceled { proposal_id });
+
+        Ok(proposal_id)
+    }
+
+    /// Internal vote casting mechanism: checks that the vote is pending, that
+    /// it has not been cast yet, retrieves the voting weight using
+    /// [`IGovernor::get_votes`] and calls the
+    /// [`IGovernorCounting::_count_vote`] internal function. Returns the
+    /// weight of the vote.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `account` - Account casting the vote.
+    /// * `support` - Support of the vote.
+    /// * `reason` - Reason given by the voter.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedProposalState`] - If the proposal is not active.
+    /// * [`Error::AlreadyCastVote`] - If `account` already voted.
+    /// * [`Error::InvalidVoteType`] - If `support` is not a valid vote type.
+    ///
+    /// # Events
+    ///
+    /// * [`VoteCast`].
+    pub fn _cast_vote(
+        &mut self,
+        proposal_id: U256,
+        account: Address,
+        support: u8,
+        reason: String,
+    ) -> Result<U256, Error> {
+        self._validate_state_bitmap(
+            proposal_id,
+            ProposalState::Active.encode_bitmap(),
+        )?;
+
+        let snapshot = U256::from(self._proposal_snapshot(proposal_id));
+        let total_weight = self._get_votes(account, snapshot)?;
+        let voted_weight = self.counting._count_vote(
+            proposal_id,
+            account,
+            support,
+            total_weight,
+        )?;
+
+        evm::log(VoteCast {
+            voter: account,
+            proposal_id,
+            support,
+            weight: voted_weight,
+            reason,
+        });
+
+        Ok(voted_weight)
+    }
+
+    /// Check that the current state of a proposal matches the requirements
+    /// described by the `allowed_states` bitmap. This bitmap should be built
+    /// using [`ProposalState::encode_bitmap`]. Returns the current state.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    /// * `allowed_states` - Bitmap of the allowed states.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedProposalState`] - If the current state is not
+    ///   allowed.
+    /// * [`Error::NonexistentProposal`] - If the proposal does not exist.
+    pub fn _validate_state_bitmap(
+        &self,
+        proposal_id: U256,
+        allowed_states: B256,
+    ) -> Result<ProposalState, Error> {
+        let current_state = self._state(proposal_id)?;
+        if (current_state.encode_bitmap() & allowed_states).is_zero() {
+            return Err(GovernorUnexpectedProposalState {
+                proposal_id,
+                current: current_state as u8,
+                expected_states: allowed_states,
+            }
+            .into());
+        }
+        Ok(current_state)
+    }
+
+    /// Returns the snapshot of a proposal, i.e. the timepoint at which voting
+    /// starts.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `proposal_id` - Id of the proposal.
+    fn _proposal_snapshot(&self, proposal_id: U256) -> U48 {
+        self.proposals.getter(proposal_id).vote_start.get()
+    }
+
+    /// Checks if the proposer is authorized to submit a proposal with the
+    /// given description.
+    ///
+    /// If the proposal description ends with `#proposer=0x???`, where
+    /// `0x???` is an address written as a hex string (case insensitive),
+    /// then the submission of this proposal will only be authorized to said
+    /// address.
+    ///
+    /// This is used for frontrunning protection. By adding this pattern at
+    /// the end of their proposal, one can ensure that no other address can
+    /// submit the same proposal. An attacker would have to either remove or
+    /// change that part, which would result in a different proposal id.
+    ///
+    /// If the description does not match this pattern, it is unrestricted
+    /// and anyone can submit it. This includes:
+    /// - If the `0x???` part is not a valid hex string.
+    /// - If the `0x???` part is a valid hex string, but does not contain
+    ///   exactly 40 hex digits.
+    /// - If it ends with the expected suffix followed by newlines or other
+    ///   whitespace.
+    /// - If it ends with some other similar suffix, e.g. `#other=abc`.
+    /// - If it does not end with any such suffix.
+    ///
+    /// # Arguments
+    ///
+    /// * `proposer` - Account submitting the proposal.
+    /// * `description` - Description of the proposal.
+    #[must_use]
+    pub fn _is_valid_description_for_proposer(
+        proposer: Address,
+        description: &str,
+    ) -> bool {
+        const MARKER: &[u8] = b"#proposer=0x";
+        let description = description.as_bytes();
+        let length = description.len();
+
+        // Length is too short to contain a valid proposer suffix.
+        if length < MARKER.len() + 40 {
+            return true;
+        }
+
+        // Extract what would be the `#proposer=0x` marker beginning the
+        // suffix.
+        let marker_start = length - MARKER.len() - 40;
+        if &description[marker_start..marker_start + MARKER.len()] != MARKER {
+            return true;
+        }
+
+        // Parse the 40 characters following the marker as an address.
+        let mut recovered = [0u8; 20];
+        let digits = &description[marker_start + MARKER.len()..];
+        for (byte, pair) in recovered.iter_mut().zip(digits.chunks(2)) {
+            let (Some(high), Some(low)) =
+                (Self::_hex_digit(pair[0]), Self::_hex_digit(pair[1]))
+            else {
+                // If any of the characters is not a hex digit, ignore the
+                // suffix entirely.
+                return true;
+            };
+            *byte = (high << 4) | low;
+        }
+
+        Address::from(recovered) == proposer
+    }
+
+    /// Returns the value of an ASCII hex digit, if `char` is one.
+    fn _hex_digit(char: u8) -> Option<u8> {
+        match char {
+            b'0'..=b'9' => Some(char - b'0'),
+            b'a'..=b'f' => Some(char - b'a' + 10),
+            b'A'..=b'F' => Some(char - b'A' + 10),
+            _ => None,
+        }
+    }
+}
+
+impl<C, Q, P, T> IErc165 for Governor<C, Q, P, T>
+where
+    C: IGovernorCounting + StorageType,
+    Q: IGovernorQuorum + StorageType,
+    P: IGovernorSettings + StorageType,
+    T: IGovernorTimelock + StorageType,
+{
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IGovernor>::INTERFACE_ID == u32::from_be_bytes(*interface_id)
+            || <Self as IErc6372>::INTERFACE_ID
+                == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{
+        aliases::{U32, U48},
+        keccak256, uint, Address, B256, U256,
+    };
+    use alloy_sol_types::SolCall;
+    use motsu::prelude::*;
+    use stylus_sdk::{
+        abi::Bytes,
+        block, function_selector,
+        prelude::*,
+        storage::{StorageBool, StorageMap, StorageU256, StorageUint},
+    };
+
+    use super::{
+        extensions::{
+            timelock_control::TimelockChange, GovernorCountingSimple,
+            GovernorSettings, GovernorTimelockControl,
+            GovernorVotesQuorumFraction, VoteType,
+        },
+        Error, Governor, IGovernor, IGovernorTimelock, ProposalCanceled,
+        ProposalExecuted, ProposalQueued, ProposalState, VoteCast,
+    };
+    use crate::{
+        governance::{
+            timelock_controller::{
+                ITimelockController, TimelockController, CANCELLER_ROLE,
+                EXECUTOR_ROLE, PROPOSER_ROLE,
+            },
+            utils::IErc6372,
+        },
+        metatx::erc2771_context::{relay, Erc2771Context},
+        utils::introspection::erc165::IErc165,
+    };
+
+    type TestGovernor = Governor<
+        GovernorCountingSimple,
+        GovernorVotesQuorumFraction,
+        GovernorSettings,
+    >;
+
+    type TimelockGovernor = Governor<
+        GovernorCountingSimple,
+        GovernorVotesQuorumFraction,
+        GovernorSettings,
+        GovernorTimelockControl,
+    >;
+
+    const VOTING_DELAY: u64 = 1;
+    const VOTING_PERIOD: u64 = 5;
+    const QUORUM_NUMERATOR: U256 = uint!(4_U256);
+    const START: u64 = 10;
+    const DESCRIPTION: &str = "Proposal #1";
+
+    /// Votes token with a settable clock and voting power.
+    #[storage]
+    struct VotesMock {
+        clock: StorageUint<48, 1>,
+        votes: StorageMap<Address, StorageU256>,
+        total_supply: StorageU256,
+    }
+
+    unsafe impl TopLevelStorage for VotesMock {}
+
+    #[public]
+    impl VotesMock {
+        fn clock(&self) -> U48 {
+            self.clock.get()
+        }
+
+        #[selector(name = "CLOCK_MODE")]
+        #[allow(clippy::unused_self)]
+        fn clock_mode(&self) -> String {
+            "mode=blocknumber&from=default".to_string()
+        }
+
+        fn get_past_votes(&self, account: Address, _timepoint: U256) -> U256 {
+            self.votes.get(account)
+        }
+
+        fn get_past_total_supply(&self, _timepoint: U256) -> U256 {
+            self.total_supply.get()
+        }
+    }
+
+    impl VotesMock {
+        fn set_clock(&mut self, clock: u64) {
+            self.clock.set(U48::from(clock));
+        }
+
+        fn set_votes(&mut self, account: Address, votes: U256) {
+            let total_supply =
+                self.total_supply.get() - self.votes.get(account) + votes;
+            self.votes.setter(account).set(votes);
+            self.total_supply.set(total_supply);
+        }
+    }
+
+    mod abi {
+        #![allow(missing_docs)]
+
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function propose(address[] targets, uint256[] values, bytes[] calldatas, string description) external returns (uint256);
+            function cancel(address[] targets, uint256[] values, bytes[] calldatas, bytes32 description_hash) external returns (uint256);
+            function castVote(uint256 proposal_id, uint8 support) external returns (uint256);
+            function castVoteWithReason(uint256 proposal_id, uint8 support, string reason) external returns (uint256);
+            function checkGovernance() external;
+        }
+    }
+
+    /// Governor receiving meta-transactions through [`Erc2771Context`].
+    #[storage]
+    struct RelayedGovernorMock {
+        #[borrow]
+        erc2771: Erc2771Context,
+        governor: TestGovernor,
+    }
+
+    unsafe impl TopLevelStorage for RelayedGovernorMock {}
+
+    #[public]
+    #[inherit(Erc2771Context)]
+    impl RelayedGovernorMock {
+        fn propose(
+            &mut self,
+            targets: Vec<Address>,
+            values: Vec<U256>,
+            calldatas: Vec<Bytes>,
+            description: String,
+        ) -> Result<U256, Error> {
+            self.governor.propose(targets, values, calldatas, description)
+        }
+
+        fn cancel(
+            &mut self,
+            targets: Vec<Address>,
+            values: Vec<U256>,
+            calldatas: Vec<Bytes>,
+            description_hash: B256,
+        ) -> Result<U256, Error> {
+            self.governor.cancel(targets, values, calldatas, description_hash)
+        }
+
+        fn cast_vote(
+            &mut self,
+            proposal_id: U256,
+            support: u8,
+        ) -> Result<U256, Error> {
+            self.governor.cast_vote(proposal_id, support)
+        }
+
+        fn cast_vote_with_reason(
+            &mut self,
+            proposal_id: U256,
+            support: u8,
+            reason: String,
+        ) -> Result<U256, Error> {
+            self.governor.cast_vote_with_reason(proposal_id, support, reason)
+        }
+
+        fn check_governance(&self) -> Result<(), Error> {
+            self.governor._check_governance()
+        }
+    }
+
+    /// Target of the proposals' operations.
+    #[storage]
+    struct TargetMock {
+        called: StorageBool,
+    }
+
+    unsafe impl TopLevelStorage for TargetMock {}
+
+    #[public]
+    impl TargetMock {
+        fn mock_function(&mut self) {
+            self.called.set(true);
+        }
+    }
+
+    fn configure<T: IGovernorTimelock + StorageType>(
+        governor: &mut Governor<
+            GovernorCountingSimple,
+            GovernorVotesQuorumFraction,
+            GovernorSettings,
+            T,
+        >,
+        token: Address,
+    ) {
+        governor.name.set_str("Governor");
+        governor.token.set(token);
+        governor.settings.voting_delay.set(U48::from(VOTING_DELAY));
+        governor.settings.voting_period.set(U32::from(VOTING_PERIOD));
+        governor
+            .quorum
+            ._update_quorum_numerator(QUORUM_NUMERATOR, U48::ZERO)
+            .expect("should set the quorum numerator");
+    }
+
+    fn init(
+        governor: &Contract<TestGovernor>,
+        token: &Contract<VotesMock>,
+        alice: Address,
+    ) {
+        token.sender(alice).set_clock(START);
+        token.sender(alice).set_votes(alice, uint!(100_U256));
+
+        let token = token.address();
+        governor.init(alice, |governor| configure(governor, token));
+    }
+
+    /// Sets up a governor bound to `timelock`, with a zero minimum delay
+    /// since the block timestamp can't be advanced in tests.
+    fn init_with_timelock(
+        governor: &Contract<TimelockGovernor>,
+        timelock: &Contract<TimelockController>,
+        token: &Contract<VotesMock>,
+        alice: Address,
+    ) {
+        token.sender(alice).set_clock(START);
+        token.sender(alice).set_votes(alice, uint!(100_U256));
+
+        let governor_address = governor.address();
+        timelock.init(alice, |timelock| {
+            for role in [PROPOSER_ROLE, EXECUTOR_ROLE, CANCELLER_ROLE] {
+                timelock
+                    .access_control
+                    ._grant_role(role.into(), governor_address);
+            }
+            timelock.access_control._grant_role(CANCELLER_ROLE.into(), alice);
+        });
+
+        let token = token.address();
+        let timelock = timelock.address();
+        governor.init(alice, |governor| {
+            configure(governor, token);
+            governor.timelock.timelock.set(timelock);
+        });
+    }
+
+    /// Proposes the operation of [`proposal`] on a governor bound to a
+    /// timelock, and makes it succeed.
+    fn propose_and_succeed(
+        governor: &Contract<TimelockGovernor>,
+        token: &Contract<VotesMock>,
+        target: Address,
+        alice: Address,
+    ) -> U256 {
+        let (targets, values, calldatas) = proposal(target);
+        let proposal_id = governor
+            .sender(alice)
+            .propose(targets, values, calldatas, DESCRIPTION.to_string())
+            .motsu_unwrap();
+
+        token.sender(alice).set_clock(START + VOTING_DELAY + 1);
+        governor
+            .sender(alice)
+            .cast_vote(proposal_id, VoteType::For as u8)
+            .motsu_unwrap();
+        token.sender(alice).set_clock(START + VOTING_DELAY + VOTING_PERIOD + 1);
+
+        proposal_id
+    }
+
+    fn proposal(target: Address) -> (Vec<Address>, Vec<U256>, Vec<Bytes>) {
+        let calldata = function_selector!("mockFunction").to_vec().into();
+        (vec![target], vec![U256::ZERO], vec![calldata])
+    }
+
+    fn propose(
+        governor: &Contract<TestGovernor>,
+        target: Address,
+        proposer: Address,
+    ) -> U256 {
+        let (targets, values, calldatas) = proposal(target);
+        governor
+            .sender(proposer)
+            .propose(targets, values, calldatas, DESCRIPTION.to_string())
+            .motsu_unwrap()
+    }
+
+    #[motsu::test]
+    fn propose_creates_pending_proposal(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init(&governor, &token, alice);
+
+        let proposal_id = propose(&governor, target.address(), alice);
+
+        let (targets, values, calldatas) = proposal(target.address());
+        let expected_id = governor.sender(alice).hash_proposal(
+            targets,
+            values,
+            calldatas,
+            keccak256(DESCRIPTION),
+        );
+        assert_eq!(expected_id, proposal_id);
+
+        let snapshot = U256::from(START + VOTING_DELAY);
+        assert_eq!(
+            snapshot,
+            governor.sender(alice).proposal_snapshot(proposal_id)
+        );
+        assert_eq!(
+            snapshot + U256::from(VOTING_PERIOD),
+            governor.sender(alice).proposal_deadline(proposal_id)
+        );
+        assert_eq!(
+            alice,
+            governor.sender(alice).proposal_proposer(proposal_id)
+        );
+        assert_eq!(
+            ProposalState::Pending as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+    }
+
+    #[motsu::test]
+    fn propose_reverts_when_proposal_exists(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init(&governor, &token, alice);
+        propose(&governor, target.address(), alice);
+
+        let (targets, values, calldatas) = proposal(target.address());
+        let err = governor
+            .sender(alice)
+            .propose(targets, values, calldatas, DESCRIPTION.to_string())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnexpectedProposalState(_)));
+    }
+
+    #[motsu::test]
+    fn propose_reverts_with_invalid_length(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init(&governor, &token, alice);
+
+        let (targets, _, calldatas) = proposal(target.address());
+        let err = governor
+            .sender(alice)
+            .propose(targets, vec![], calldatas, DESCRIPTION.to_string())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidProposalLength(_)));
+
+        let (targets, values, _) = proposal(target.address());
+        let err = governor
+            .sender(alice)
+            .propose(targets, values, vec![], DESCRIPTION.to_string())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidProposalLength(_)));
+
+        let err = governor
+            .sender(alice)
+            .propose(vec![], vec![], vec![], DESCRIPTION.to_string())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidProposalLength(_)));
+    }
+
+    #[motsu::test]
+    fn propose_reverts_below_proposal_threshold(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&governor, &token, alice);
+        governor
+            .sender(governor.address())
+            .set_proposal_threshold(uint!(50_U256))
+            .motsu_unwrap();
+
+        let (targets, values, calldatas) = proposal(target.address());
+        let err = governor
+            .sender(bob)
+            .propose(targets, values, calldatas, DESCRIPTION.to_string())
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::InsufficientProposerVotes(_)));
+
+        // Alice holds enough votes.
+        propose(&governor, target.address(), alice);
+    }
+
+    #[motsu::test]
+    fn propose_reverts_for_restricted_proposer(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&governor, &token, alice);
+
+        let description = format!("{DESCRIPTION}#proposer={alice:?}");
+        let (targets, values, calldatas) = proposal(target.address());
+        let err = governor
+            .sender(bob)
+            .propose(
+                targets.clone(),
+                values.clone(),
+                calldatas.clone(),
+                description.clone(),
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::RestrictedProposer(_)));
+
+        governor
+            .sender(alice)
+            .propose(targets, values, calldatas, description)
+            .motsu_unwrap();
+    }
+
+    #[motsu::test]
+    fn cast_vote_counts_votes(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&governor, &token, alice);
+        token.sender(alice).set_votes(bob, uint!(30_U256));
+        let proposal_id = propose(&governor, target.address(), alice);
+
+        // Voting hasn't started yet.
+        let err = governor
+            .sender(alice)
+            .cast_vote(proposal_id, VoteType::For as u8)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnexpectedProposalState(_)));
+
+        token.sender(alice).set_clock(START + VOTING_DELAY + 1);
+        assert_eq!(
+            ProposalState::Active as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+
+        let weight = governor
+            .sender(alice)
+            .cast_vote(proposal_id, VoteType::For as u8)
+            .motsu_unwrap();
+        assert_eq!(uint!(100_U256), weight);
+        governor.assert_emitted(&VoteCast {
+            voter: alice,
+            proposal_id,
+            support: VoteType::For as u8,
+            weight,
+            reason: String::new(),
+        });
+
+        governor
+            .sender(bob)
+            .cast_vote_with_reason(
+                proposal_id,
+                VoteType::Against as u8,
+                "Disagree".to_string(),
+            )
+            .motsu_unwrap();
+
+        assert!(governor.sender(alice).has_voted(proposal_id, alice));
+        assert!(governor.sender(alice).has_voted(proposal_id, bob));
+        assert_eq!(
+            (uint!(30_U256), uint!(100_U256), U256::ZERO),
+            governor.sender(alice).proposal_votes(proposal_id)
+        );
+    }
+
+    #[motsu::test]
+    fn cast_vote_reverts_when_invalid(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init(&governor, &token, alice);
+        let proposal_id = propose(&governor, target.address(), alice);
+        token.sender(alice).set_clock(START + VOTING_DELAY + 1);
+
+        let err =
+            governor.sender(alice).cast_vote(proposal_id, 3).motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidVoteType(_)));
+
+        governor
+            .sender(alice)
+            .cast_vote(proposal_id, VoteType::Abstain as u8)
+            .motsu_unwrap();
+        let err = governor
+            .sender(alice)
+            .cast_vote(proposal_id, VoteType::For as u8)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::AlreadyCastVote(_)));
+    }
+
+    #[motsu::test]
+    fn state_is_defeated_without_quorum_or_majority(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&governor, &token, alice);
+        let proposal_id = propose(&governor, target.address(), alice);
+        let deadline = START + VOTING_DELAY + VOTING_PERIOD;
+
+        // No votes cast, so quorum is not reached.
+        token.sender(alice).set_clock(deadline + 1);
+        assert_eq!(
+            ProposalState::Defeated as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+
+        // Quorum is reached, but the majority votes against.
+        let description = "Proposal #2".to_string();
+        let (targets, values, calldatas) = proposal(target.address());
+        let proposal_id = governor
+            .sender(alice)
+            .propose(targets, values, calldatas, description)
+            .motsu_unwrap();
+        token.sender(alice).set_votes(bob, uint!(100_U256));
+        token.sender(alice).set_clock(deadline + VOTING_DELAY + 2);
+        governor
+            .sender(alice)
+            .cast_vote(proposal_id, VoteType::For as u8)
+            .motsu_unwrap();
+        governor
+            .sender(bob)
+            .cast_vote(proposal_id, VoteType::Against as u8)
+            .motsu_unwrap();
+
+        token.sender(alice).set_clock(2 * deadline);
+        assert_eq!(
+            ProposalState::Defeated as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+    }
+
+    #[motsu::test]
+    fn execute_runs_successful_proposal(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init(&governor, &token, alice);
+        let proposal_id = propose(&governor, target.address(), alice);
+        let (targets, values, calldatas) = proposal(target.address());
+        let description_hash = keccak256(DESCRIPTION);
+
+        token.sender(alice).set_clock(START + VOTING_DELAY + 1);
+        governor
+            .sender(alice)
+            .cast_vote(proposal_id, VoteType::For as u8)
+            .motsu_unwrap();
+
+        // Voting is still ongoing.
+        let err = governor
+            .sender(alice)
+            .execute(
+                targets.clone(),
+                values.clone(),
+                calldatas.clone(),
+                description_hash,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnexpectedProposalState(_)));
+
+        token.sender(alice).set_clock(START + VOTING_DELAY + VOTING_PERIOD + 1);
+        assert_eq!(
+            ProposalState::Succeeded as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+
+        let err = governor
+            .sender(alice)
+            .queue(
+                targets.clone(),
+                values.clone(),
+                calldatas.clone(),
+                description_hash,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::QueueNotImplemented(_)));
+
+        governor
+            .sender(alice)
+            .execute(targets, values, calldatas, description_hash)
+            .motsu_unwrap();
+
+        assert!(target.sender(alice).called.get());
+        governor.assert_emitted(&ProposalExecuted { proposal_id });
+        assert_eq!(
+            ProposalState::Executed as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+    }
+
+    #[motsu::test]
+    fn cancel_only_pending_by_proposer(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&governor, &token, alice);
+        let proposal_id = propose(&governor, target.address(), alice);
+        let (targets, values, calldatas) = proposal(target.address());
+        let description_hash = keccak256(DESCRIPTION);
+
+        let err = governor
+            .sender(bob)
+            .cancel(
+                targets.clone(),
+                values.clone(),
+                calldatas.clone(),
+                description_hash,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::OnlyProposer(_)));
+
+        governor
+            .sender(alice)
+            .cancel(
+                targets.clone(),
+                values.clone(),
+                calldatas.clone(),
+                description_hash,
+            )
+            .motsu_unwrap();
+        assert_eq!(
+            ProposalState::Canceled as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+
+        let err = governor
+            .sender(alice)
+            .cancel(targets, values, calldatas, description_hash)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnexpectedProposalState(_)));
+    }
+
+    #[motsu::test]
+    fn state_reverts_for_nonexistent_proposal(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        alice: Address,
+    ) {
+        init(&governor, &token, alice);
+        let err =
+            governor.sender(alice).state(U256::from(1)).motsu_unwrap_err();
+        assert!(matches!(err, Error::NonexistentProposal(_)));
+    }
+
+    #[motsu::test]
+    fn settings_are_restricted_to_governance(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        alice: Address,
+    ) {
+        init(&governor, &token, alice);
+
+        let err = governor
+            .sender(alice)
+            .set_voting_delay(U48::from(2))
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::OnlyExecutor(_)));
+
+        let executor = governor.address();
+        governor.sender(executor).set_voting_delay(U48::from(2)).motsu_unwrap();
+        governor
+            .sender(executor)
+            .set_voting_period(U32::from(10))
+            .motsu_unwrap();
+        assert_eq!(U256::from(2), governor.sender(alice).voting_delay());
+        assert_eq!(U256::from(10), governor.sender(alice).voting_period());
+
+        let err = governor
+            .sender(executor)
+            .set_voting_period(U32::ZERO)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidVotingPeriod(_)));
+    }
+
+    #[motsu::test]
+    fn quorum_follows_numerator_history(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        alice: Address,
+    ) {
+        init(&governor, &token, alice);
+
+        let timepoint = U256::from(START);
+        assert_eq!(
+            uint!(4_U256),
+            governor.sender(alice).quorum(timepoint).motsu_unwrap()
+        );
+
+        let err = governor
+            .sender(alice)
+            .update_quorum_numerator(uint!(10_U256))
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::OnlyExecutor(_)));
+
+        let executor = governor.address();
+        let err = governor
+            .sender(executor)
+            .update_quorum_numerator(uint!(101_U256))
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidQuorumFraction(_)));
+
+        token.sender(alice).set_clock(START + 1);
+        governor
+            .sender(executor)
+            .update_quorum_numerator(uint!(10_U256))
+            .motsu_unwrap();
+
+        // Past timepoints keep the previous numerator.
+        assert_eq!(
+            uint!(4_U256),
+            governor.sender(alice).quorum(timepoint).motsu_unwrap()
+        );
+        assert_eq!(
+            uint!(10_U256),
+            governor.sender(alice).quorum(U256::from(START + 1)).motsu_unwrap()
+        );
+    }
+
+    #[motsu::test]
+    fn clock_follows_token(
+        governor: Contract<TestGovernor>,
+        token: Contract<VotesMock>,
+        alice: Address,
+    ) {
+        init(&governor, &token, alice);
+        assert_eq!(U48::from(START), governor.sender(alice).clock());
+        assert_eq!(
+            "mode=blocknumber&from=default",
+            governor.sender(alice).clock_mode()
+        );
+        assert_eq!(
+            "support=bravo&quorum=for,abstain",
+            governor.sender(alice).counting_mode()
+        );
+        assert_eq!("Governor", governor.sender(alice).name());
+        assert_eq!("1", governor.sender(alice).version());
+    }
+
+    #[test]
+    fn validates_description_for_proposer() {
+        let proposer: Address =
+            "0x1f9090aaE28b8a3dCeaDf281B0F12828e676c326".parse().unwrap();
+
+        assert!(TestGovernor::_is_valid_description_for_proposer(
+            proposer, "Proposal"
+        ));
+        assert!(TestGovernor::_is_valid_description_for_proposer(
+            proposer,
+            "Proposal#proposer=0x1f9090aae28b8a3dceadf281b0f12828e676c326"
+        ));
+        assert!(TestGovernor::_is_valid_description_for_proposer(
+            proposer,
+            "Proposal#proposer=0x1F9090AAE28B8A3DCEADF281B0F12828E676C326"
+        ));
+        assert!(!TestGovernor::_is_valid_description_for_proposer(
+            Address::ZERO,
+            "Proposal#proposer=0x1f9090aae28b8a3dceadf281b0f12828e676c326"
+        ));
+        // Invalid hex digits and trailing characters leave the proposal
+        // unrestricted.
+        assert!(TestGovernor::_is_valid_description_for_proposer(
+            Address::ZERO,
+            "Proposal#proposer=0x1f9090aae28b8a3dceadf281b0f12828e676c32g"
+        ));
+        assert!(TestGovernor::_is_valid_description_for_proposer(
+            Address::ZERO,
+            "Proposal#proposer=0x1f9090aae28b8a3dceadf281b0f12828e676c326 "
+        ));
+    }
+
+    #[test]
+    fn encodes_state_bitmap() {
+        assert_eq!(
+            B256::from(U256::from(1)),
+            ProposalState::Pending.encode_bitmap()
+        );
+        assert_eq!(
+            B256::from(U256::from(1 << 7)),
+            ProposalState::Executed.encode_bitmap()
+        );
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(TestGovernor::supports_interface(
+            <TestGovernor as IGovernor>::INTERFACE_ID.into()
+        ));
+        assert!(TestGovernor::supports_interface(
+            <TestGovernor as IErc6372>::INTERFACE_ID.into()
+        ));
+        assert!(TestGovernor::supports_interface(
+            <TestGovernor as IErc165>::INTERFACE_ID.into()
+        ));
+
+        let fake_interface_id = 0x12345678u32;
+        assert!(!TestGovernor::supports_interface(fake_interface_id.into()));
+    }
+
+    #[motsu::test]
+    fn queues_and_executes_through_timelock(
+        governor: Contract<TimelockGovernor>,
+        timelock: Contract<TimelockController>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init_with_timelock(&governor, &timelock, &token, alice);
+        let proposal_id =
+            propose_and_succeed(&governor, &token, target.address(), alice);
+        let (targets, values, calldatas) = proposal(target.address());
+        let description_hash = keccak256(DESCRIPTION);
+
+        assert!(governor.sender(alice).proposal_needs_queuing(proposal_id));
+        assert_eq!(
+            ProposalState::Succeeded as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+
+        // The operation must be scheduled in the timelock first.
+        let err = governor
+            .sender(alice)
+            .execute(
+                targets.clone(),
+                values.clone(),
+                calldatas.clone(),
+                description_hash,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::FailedCall(_)));
+
+        governor
+            .sender(alice)
+            .queue(
+                targets.clone(),
+                values.clone(),
+                calldatas.clone(),
+                description_hash,
+            )
+            .motsu_unwrap();
+
+        let eta_seconds = U256::from(block::timestamp());
+        governor.assert_emitted(&ProposalQueued { proposal_id, eta_seconds });
+        assert_eq!(
+            eta_seconds,
+            governor.sender(alice).proposal_eta(proposal_id)
+        );
+        assert_eq!(
+            ProposalState::Queued as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+
+        governor
+            .sender(alice)
+            .execute(targets, values, calldatas, description_hash)
+            .motsu_unwrap();
+
+        assert!(target.sender(alice).called.get());
+        governor.assert_emitted(&ProposalExecuted { proposal_id });
+        assert_eq!(
+            ProposalState::Executed as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+    }
+
+    #[motsu::test]
+    fn cancel_cancels_timelock_operation(
+        governor: Contract<TimelockGovernor>,
+        timelock: Contract<TimelockController>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init_with_timelock(&governor, &timelock, &token, alice);
+        let proposal_id =
+            propose_and_succeed(&governor, &token, target.address(), alice);
+        let (targets, values, calldatas) = proposal(target.address());
+        let description_hash = keccak256(DESCRIPTION);
+
+        governor
+            .sender(alice)
+            .queue(
+                targets.clone(),
+                values.clone(),
+                calldatas.clone(),
+                description_hash,
+            )
+            .motsu_unwrap();
+        let operation_id =
+            governor.sender(alice).timelock.timelock_ids.get(proposal_id);
+        assert!(timelock.sender(alice).is_operation_pending(operation_id));
+
+        governor
+            .sender(alice)
+            ._cancel(&targets, &values, &calldatas, description_hash)
+            .motsu_unwrap();
+
+        assert!(!timelock.sender(alice).is_operation(operation_id));
+        assert_eq!(
+            ProposalState::Canceled as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+    }
+
+    #[motsu::test]
+    fn state_follows_timelock_operation(
+        governor: Contract<TimelockGovernor>,
+        timelock: Contract<TimelockController>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init_with_timelock(&governor, &timelock, &token, alice);
+        let proposal_id =
+            propose_and_succeed(&governor, &token, target.address(), alice);
+        let (targets, values, calldatas) = proposal(target.address());
+
+        governor
+            .sender(alice)
+            .queue(targets, values, calldatas, keccak256(DESCRIPTION))
+            .motsu_unwrap();
+
+        // Canceling the operation directly on the timelock cancels the
+        // proposal.
+        let operation_id =
+            governor.sender(alice).timelock.timelock_ids.get(proposal_id);
+        timelock.sender(alice).cancel(operation_id).motsu_unwrap();
+
+        assert_eq!(
+            ProposalState::Canceled as u8,
+            governor.sender(alice).state(proposal_id).motsu_unwrap()
+        );
+    }
+
+    #[motsu::test]
+    fn update_timelock_is_restricted_to_timelock(
+        governor: Contract<TimelockGovernor>,
+        timelock: Contract<TimelockController>,
+        token: Contract<VotesMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init_with_timelock(&governor, &timelock, &token, alice);
+
+        // The governor is not its own executor anymore.
+        let err = governor
+            .sender(governor.address())
+            .update_timelock(bob)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::OnlyExecutor(_)));
+
+        governor.sender(timelock.address()).update_timelock(bob).motsu_unwrap();
+
+        assert_eq!(bob, governor.sender(alice).timelock());
+        governor.assert_emitted(&TimelockChange {
+            old_timelock: timelock.address(),
+            new_timelock: bob,
+        });
+    }
+
+    /// Sets up a [`RelayedGovernorMock`] trusting `forwarder`.
+    fn init_relayed(
+        governor: &Contract<RelayedGovernorMock>,
+        token: &Contract<VotesMock>,
+        forwarder: Address,
+        alice: Address,
+    ) {
+        token.sender(alice).set_clock(START);
+        token.sender(alice).set_votes(alice, uint!(100_U256));
+
+        let token = token.address();
+        governor.init(alice, |mock| {
+            mock.erc2771._set_trusted_forwarder(forwarder);
+            configure(&mut mock.governor, token);
+        });
+    }
+
+    /// Relays the proposal of [`proposal`] from `proposer` through
+    /// `forwarder`.
+    fn relay_propose(
+        governor: &Contract<RelayedGovernorMock>,
+        target: Address,
+        forwarder: Address,
+        proposer: Address,
+    ) -> U256 {
+        let (targets, values, calldatas) = proposal(target);
+        let call = abi::proposeCall {
+            targets,
+            values,
+            calldatas: calldatas.into_iter().map(|c| c.0.into()).collect(),
+            description: DESCRIPTION.to_string(),
+        }
+        .abi_encode();
+        let output = governor
+            .init(forwarder, |_| relay::<RelayedGovernorMock>(&call, proposer))
+            .expect("should propose");
+        abi::proposeCall::abi_decode_returns(&output, true)
+            .expect("should decode the proposal id")
+            ._0
+    }
+
+    #[motsu::test]
+    fn relayed_propose_and_cancel_authorize_relayed_sender(
+        governor: Contract<RelayedGovernorMock>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        forwarder: Address,
+        alice: Address,
+    ) {
+        init_relayed(&governor, &token, forwarder, alice);
+
+        let proposal_id =
+            relay_propose(&governor, target.address(), forwarder, alice);
+        assert_eq!(
+            alice,
+            governor.sender(alice).governor.proposal_proposer(proposal_id)
+        );
+
+        let (targets, values, calldatas) = proposal(target.address());
+        let call = abi::cancelCall {
+            targets,
+            values,
+            calldatas: calldatas.into_iter().map(|c| c.0.into()).collect(),
+            description_hash: keccak256(DESCRIPTION),
+        }
+        .abi_encode();
+        governor
+            .init(forwarder, |_| relay::<RelayedGovernorMock>(&call, alice))
+            .expect("should cancel");
+        governor.assert_emitted(&ProposalCanceled { proposal_id });
+    }
+
+    #[motsu::test]
+    fn relayed_votes_are_cast_by_relayed_sender(
+        governor: Contract<RelayedGovernorMock>,
+        token: Contract<VotesMock>,
+        target: Contract<TargetMock>,
+        forwarder: Address,
+        alice: Address,
+        bob: Address,
+    ) {
+        init_relayed(&governor, &token, forwarder, alice);
+        token.sender(alice).set_votes(bob, uint!(50_U256));
+        let proposal_id =
+            relay_propose(&governor, target.address(), forwarder, alice);
+        token.sender(alice).set_clock(START + VOTING_DELAY + 1);
+
+        let call =
+            abi::castVoteCall { proposal_id, support: VoteType::For as u8 }
+                .abi_encode();
+        governor
+            .init(forwarder, |_| relay::<RelayedGovernorMock>(&call, alice))
+            .expect("should cast vote");
+        governor.assert_emitted(&VoteCast {
+            voter: alice,
+            proposal_id,
+            support: VoteType::For as u8,
+            weight: uint!(100_U256),
+            reason: String::new(),
+        });
+
+        let call = abi::castVoteWithReasonCall {
+            proposal_id,
+            support: VoteType::Against as u8,
+            reason: "reason".to_string(),
+        }
+        .abi_encode();
+        governor
+            .init(forwarder, |_| relay::<RelayedGovernorMock>(&call, bob))
+            .expect("should cast vote with reason");
+        governor.assert_emitted(&VoteCast {
+            voter: bob,
+            proposal_id,
+            support: VoteType::Against as u8,
+            weight: uint!(50_U256),
+            reason: "reason".to_string(),
+        });
+
+        assert!(governor.sender(alice).governor.has_voted(proposal_id, alice));
+        assert!(governor.sender(alice).governor.has_voted(proposal_id, bob));
+        assert!(!governor
+            .sender(alice)
+            .governor
+            .has_voted(proposal_id, forwarder));
+    }
+
+    #[motsu::test]
+    fn relayed_check_governance_authorizes_relayed_executor(
+        governor: Contract<RelayedGovernorMock>,
+        token: Contract<VotesMock>,
+        forwarder: Address,
+        alice: Address,
+    ) {
+        init_relayed(&governor, &token, forwarder, alice);
+
+        let call = abi::checkGovernanceCall {}.abi_encode();
+        let err = governor
+            .init(forwarder, |_| relay::<RelayedGovernorMock>(&call, alice))
+            .expect_err("should reject a relayed non-executor");
+        let expected: Vec<u8> =
+            Error::OnlyExecutor(super::GovernorOnlyExecutor { account: alice })
+                .into();
+        assert_eq!(expected, err);
+
+        let executor = governor.address();
+        governor
+            .init(forwarder, |_| relay::<RelayedGovernorMock>(&call, executor))
+            .expect("should accept the relayed executor");
+    }
+}
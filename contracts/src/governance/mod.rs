@@ -0,0 +1,6 @@
+//! Primitives for on-chain governance.
+pub mod governor;
+pub mod timelock_controller;
+pub mod utils;
+
+pub use timelock_controller::TimelockController;
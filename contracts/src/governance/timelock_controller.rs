@@ -0,0 +1,1750 @@
+//! Contract module which acts as a timelocked controller.
+//!
+//! When set as the owner of an `Ownable` smart contract, it enforces a
+//! timelock on all `only_owner` maintenance operations. This gives time for
+//! users of the controlled contract to exit before a potentially dangerous
+//! maintenance operation is applied.
+//!
+//! By default, this contract is self administered, meaning administration
+//! tasks have to go through the timelock process. The proposer (resp
+//! executor) role is in charge of proposing (resp executing) operations. A
+//! common use case is to position this [`TimelockController`] as the owner of
+//! a smart contract, with a multisig or a DAO as the sole proposer.
+//!
+//! Roles are managed by an [`AccessControl`] instance, whose public functions
+//! are re-exposed by [`ITimelockController`].
+//!
+//! The contract also accepts ERC-721 and ERC-1155 tokens, through
+//! [`ITimelockController::on_erc721_received`],
+//! [`ITimelockController::on_erc1155_received`] and
+//! [`ITimelockController::on_erc1155_batch_received`].
+//!
+//! NOTE: Operations targeting the timelock itself (e.g.
+//! [`ITimelockController::update_delay`]) require the `reentrant` feature to
+//! be enabled.
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{Address, FixedBytes, B256, U256};
+use alloy_sol_types::SolType;
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    block,
+    call::{self, MethodError, RawCall},
+    contract, evm, msg,
+    prelude::*,
+    storage::{StorageMap, StorageU256},
+};
+
+use crate::{
+    access::control::{self, AccessControl, IAccessControl},
+    token::{
+        erc1155::{BATCH_TRANSFER_FN_SELECTOR, SINGLE_TRANSFER_FN_SELECTOR},
+        erc721::RECEIVER_FN_SELECTOR,
+    },
+    utils::{
+        introspection::erc165::{Erc165, IErc165},
+        ReentrantCallHandler,
+    },
+};
+
+/// Role allowed to schedule operations.
+pub const PROPOSER_ROLE: [u8; 32] =
+    keccak_const::Keccak256::new().update(b"PROPOSER_ROLE").finalize();
+
+/// Role allowed to execute ready operations.
+pub const EXECUTOR_ROLE: [u8; 32] =
+    keccak_const::Keccak256::new().update(b"EXECUTOR_ROLE").finalize();
+
+/// Role allowed to cancel pending operations.
+pub const CANCELLER_ROLE: [u8; 32] =
+    keccak_const::Keccak256::new().update(b"CANCELLER_ROLE").finalize();
+
+/// Timestamp recorded for executed operations.
+pub const DONE_TIMESTAMP: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+/// Interface id of `IERC1155Receiver`.
+const ERC1155_RECEIVER_INTERFACE_ID: u32 =
+    u32::from_be_bytes(SINGLE_TRANSFER_FN_SELECTOR)
+        ^ u32::from_be_bytes(BATCH_TRANSFER_FN_SELECTOR);
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    pub(crate) type OperationTuple = sol! {
+        tuple(address, uint256, bytes, bytes32, bytes32)
+    };
+
+    pub(crate) type OperationBatchTuple = sol! {
+        tuple(address[], uint256[], bytes[], bytes32, bytes32)
+    };
+
+    sol! {
+        /// Emitted when a call is scheduled as part of operation `id`.
+        ///
+        /// * `id` - Id of the operation.
+        /// * `index` - Index of the call within the operation.
+        /// * `target` - Target of the call.
+        /// * `value` - Value sent with the call.
+        /// * `data` - Calldata of the call.
+        /// * `predecessor` - Operation that has to be executed first.
+        /// * `delay` - Delay before the operation becomes ready.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event CallScheduled(
+            bytes32 indexed id,
+            uint256 indexed index,
+            address target,
+            uint256 value,
+            bytes data,
+            bytes32 predecessor,
+            uint256 delay
+        );
+
+        /// Emitted when a call is performed as part of operation `id`.
+        ///
+        /// * `id` - Id of the operation.
+        /// * `index` - Index of the call within the operation.
+        /// * `target` - Target of the call.
+        /// * `value` - Value sent with the call.
+        /// * `data` - Calldata of the call.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event CallExecuted(
+            bytes32 indexed id,
+            uint256 indexed index,
+            address target,
+            uint256 value,
+            bytes data
+        );
+
+        /// Emitted when new proposal is scheduled with non-zero salt.
+        ///
+        /// * `id` - Id of the operation.
+        /// * `salt` - Salt of the operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event CallSalt(bytes32 indexed id, bytes32 salt);
+
+        /// Emitted when operation `id` is cancelled.
+        ///
+        /// * `id` - Id of the operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event Cancelled(bytes32 indexed id);
+
+        /// Emitted when the minimum delay for future operations is modified.
+        ///
+        /// * `old_duration` - Previous minimum delay.
+        /// * `new_duration` - New minimum delay.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event MinDelayChange(uint256 old_duration, uint256 new_duration);
+    }
+
+    sol! {
+        /// Mismatch between the parameters length for an operation call.
+        ///
+        /// * `targets` - Number of targets.
+        /// * `payloads` - Number of payloads.
+        /// * `values` - Number of values.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error TimelockInvalidOperationLength(
+            uint256 targets,
+            uint256 payloads,
+            uint256 values
+        );
+
+        /// The schedule operation doesn't meet the minimum delay.
+        ///
+        /// * `delay` - Requested delay.
+        /// * `min_delay` - Minimum delay.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error TimelockInsufficientDelay(uint256 delay, uint256 min_delay);
+
+        /// The current state of an operation is not as required. The
+        /// `expected_states` is a bitmap with the bits enabled for each
+        /// [`super::OperationState`] enum position counting from right to
+        /// left.
+        ///
+        /// * `operation_id` - Id of the operation.
+        /// * `expected_states` - Bitmap of the expected states.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error TimelockUnexpectedOperationState(
+            bytes32 operation_id,
+            bytes32 expected_states
+        );
+
+        /// The predecessor to an operation not yet done.
+        ///
+        /// * `predecessor_id` - Id of the predecessor operation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error TimelockUnexecutedPredecessor(bytes32 predecessor_id);
+
+        /// The caller account is not authorized.
+        ///
+        /// * `caller` - Account that attempted the call.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error TimelockUnauthorizedCaller(address caller);
+    }
+}
+
+/// An error that occurred in the implementation of a [`TimelockController`]
+/// contract.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Mismatch between the parameters length for an operation call.
+    InvalidOperationLength(TimelockInvalidOperationLength),
+    /// The schedule operation doesn't meet the minimum delay.
+    InsufficientDelay(TimelockInsufficientDelay),
+    /// The current state of an operation is not as required.
+    UnexpectedOperationState(TimelockUnexpectedOperationState),
+    /// The predecessor to an operation not yet done.
+    UnexecutedPredecessor(TimelockUnexecutedPredecessor),
+    /// The caller account is not authorized.
+    UnauthorizedCaller(TimelockUnauthorizedCaller),
+    /// Error type from [`AccessControl`] contract [`control::Error`].
+    AccessControl(control::Error),
+    /// An operation's call failed.
+    FailedCall(call::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OperationState {
+    /// The operation is not scheduled.
+    Unset = 0,
+    /// The operation is scheduled, but its delay has not passed yet.
+    Waiting = 1,
+    /// The operation is scheduled and its delay has passed.
+    Ready = 2,
+    /// The operation was executed.
+    Done = 3,
+}
+
+impl OperationState {
+    /// Encodes the state into a bitmap, with the bit at the position of the
+    /// state enabled.
+    #[must_use]
+    pub fn encode_bitmap(self) -> B256 {
+        B256::from(U256::from(1) << (self as u8))
+    }
+}
+
+/// State of a [`TimelockController`] contract.
+#[storage]
+pub struct TimelockController {
+    /// [`AccessControl`] contract.
+    // We leave the parent [`AccessControl`] contract instance public, so that
+    // inheritting contract have access to its internal functions.
+    pub access_control: AccessControl,
+    /// Maps operation ids to the timestamp at which they become ready.
+    pub(crate) timestamps: StorageMap<B256, StorageU256>,
+    /// Minimum delay for operations.
+    pub(crate) min_delay: StorageU256,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for TimelockController {}
+
+/// Interface for a [`TimelockController`] contract.
+#[interface_id]
+pub trait ITimelockController {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Returns `true` if `account` has been granted `role`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account to check for membership.
+    fn has_role(&self, role: B256, account: Address) -> bool;
+
+    /// Returns the admin role that controls `role`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role` - The role identifier.
+    fn get_role_admin(&self, role: B256) -> B256;
+
+    /// Grants `role` to `account`. The caller must have `role`'s admin role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account which will be granted the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`msg::sender`] has not been granted
+    ///   `role`'s admin role.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleGranted`].
+    fn grant_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Revokes `role` from `account`. The caller must have `role`'s admin
+    /// role.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `account` - The account which will be revoked the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`msg::sender`] has not been granted
+    ///   `role`'s admin role.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleRevoked`].
+    fn revoke_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Revokes `role` from the calling account.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `role` - The role identifier.
+    /// * `confirmation` - The account which will be revoked the role.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If [`msg::sender`] is not `confirmation`.
+    ///
+    /// # Events
+    ///
+    /// * [`control::RoleRevoked`].
+    fn renounce_role(
+        &mut self,
+        role: B256,
+        confirmation: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Returns whether an id corresponds to a registered operation. This
+    /// includes both Waiting, Ready, and Done operations.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Id of the operation.
+    fn is_operation(&self, id: B256) -> bool;
+
+    /// Returns whether an operation is pending or not. Note that a "pending"
+    /// operation may also be "ready".
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Id of the operation.
+    fn is_operation_pending(&self, id: B256) -> bool;
+
+    /// Returns whether an operation is ready for execution. Note that a
+    /// "ready" operation is also "pending".
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Id of the operation.
+    fn is_operation_ready(&self, id: B256) -> bool;
+
+    /// Returns whether an operation is done or not.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Id of the operation.
+    fn is_operation_done(&self, id: B256) -> bool;
+
+    /// Returns the timestamp at which an operation becomes ready (0 for
+    /// unset operations, 1 for done operations).
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Id of the operation.
+    fn get_timestamp(&self, id: B256) -> U256;
+
+    /// Returns operation state, as an [`OperationState`] discriminant.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Id of the operation.
+    fn get_operation_state(&self, id: B256) -> u8;
+
+    /// Returns the minimum delay in seconds for an operation to become valid.
+    ///
+    /// This value can be changed by executing an operation that calls
+    /// [`Self::update_delay`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn get_min_delay(&self) -> U256;
+
+    /// Returns the identifier of an operation containing a single
+    /// transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `target` - Target of the call.
+    /// * `value` - Value sent with the call.
+    /// * `data` - Calldata of the call.
+    /// * `predecessor` - Operation that has to be executed first.
+    /// * `salt` - Salt of the operation.
+    fn hash_operation(
+        &self,
+        target: Address,
+        value: U256,
+        data: Bytes,
+        predecessor: B256,
+        salt: B256,
+    ) -> B256;
+
+    /// Returns the identifier of an operation containing a batch of
+    /// transactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `targets` - Targets of the calls.
+    /// * `values` - Values sent with the calls.
+    /// * `payloads` - Calldata of the calls.
+    /// * `predecessor` - Operation that has to be executed first.
+    /// * `salt` - Salt of the operation.
+    fn hash_operation_batch(
+        &self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        payloads: Vec<Bytes>,
+        predecessor: B256,
+        salt: B256,
+    ) -> B256;
+
+    /// Schedule an operation containing a single transaction.
+    ///
+    /// Requires the caller to have the [`PROPOSER_ROLE`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - Target of the call.
+    /// * `value` - Value sent with the call.
+    /// * `data` - Calldata of the call.
+    /// * `predecessor` - Operation that has to be executed first.
+    /// * `salt` - Salt of the operation.
+    /// * `delay` - Delay before the operation becomes ready.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If the caller doesn't have the
+    ///   [`PROPOSER_ROLE`].
+    /// * [`Error::UnexpectedOperationState`] - If the operation is already
+    ///   scheduled.
+    /// * [`Error::InsufficientDelay`] - If `delay` is lower than the minimum
+    ///   delay.
+    ///
+    /// # Events
+    ///
+    /// * [`CallScheduled`].
+    /// * [`CallSalt`] - If `salt` is non-zero.
+    #[allow(clippy::too_many_arguments)]
+    fn schedule(
+        &mut self,
+        target: Address,
+        value: U256,
+        data: Bytes,
+        predecessor: B256,
+        salt: B256,
+        delay: U256,
+    ) -> Result<(), Self::Error>;
+
+    /// Schedule an operation containing a batch of transactions.
+    ///
+    /// Requires the caller to have the [`PROPOSER_ROLE`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `targets` - Targets of the calls.
+    /// * `values` - Values sent with the calls.
+    /// * `payloads` - Calldata of the calls.
+    /// * `predecessor` - Operation that has to be executed first.
+    /// * `salt` - Salt of the operation.
+    /// * `delay` - Delay before the operation becomes ready.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If the caller doesn't have the
+    ///   [`PROPOSER_ROLE`].
+    /// * [`Error::InvalidOperationLength`] - If the parameters lengths
+    ///   mismatch.
+    /// * [`Error::UnexpectedOperationState`] - If the operation is already
+    ///   scheduled.
+    /// * [`Error::InsufficientDelay`] - If `delay` is lower than the minimum
+    ///   delay.
+    ///
+    /// # Events
+    ///
+    /// * [`CallScheduled`] - For each transaction in the batch.
+    /// * [`CallSalt`] - If `salt` is non-zero.
+    #[allow(clippy::too_many_arguments)]
+    fn schedule_batch(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        payloads: Vec<Bytes>,
+        predecessor: B256,
+        salt: B256,
+        delay: U256,
+    ) -> Result<(), Self::Error>;
+
+    /// Cancel an operation.
+    ///
+    /// Requires the caller to have the [`CANCELLER_ROLE`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `id` - Id of the operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If the caller doesn't have the
+    ///   [`CANCELLER_ROLE`].
+    /// * [`Error::UnexpectedOperationState`] - If the operation is not pending.
+    ///
+    /// # Events
+    ///
+    /// * [`Cancelled`].
+    fn cancel(&mut self, id: B256) -> Result<(), Self::Error>;
+
+    /// Execute a (ready) operation containing a single transaction.
+    ///
+    /// Requires the caller to have the [`EXECUTOR_ROLE`], unless the role is
+    /// granted to `Address::ZERO`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `target` - Target of the call.
+    /// * `value` - Value sent with the call.
+    /// * `payload` - Calldata of the call.
+    /// * `predecessor` - Operation that has to be executed first.
+    /// * `salt` - Salt of the operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If the caller doesn't have the
+    ///   [`EXECUTOR_ROLE`].
+    /// * [`Error::UnexpectedOperationState`] - If the operation is not ready.
+    /// * [`Error::UnexecutedPredecessor`] - If the predecessor is not done.
+    /// * [`Error::FailedCall`] - If the call failed.
+    ///
+    /// # Events
+    ///
+    /// * [`CallExecuted`].
+    fn execute(
+        &mut self,
+        target: Address,
+        value: U256,
+        payload: Bytes,
+        predecessor: B256,
+        salt: B256,
+    ) -> Result<(), Self::Error>;
+
+    /// Execute a (ready) operation containing a batch of transactions.
+    ///
+    /// Requires the caller to have the [`EXECUTOR_ROLE`], unless the role is
+    /// granted to `Address::ZERO`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `targets` - Targets of the calls.
+    /// * `values` - Values sent with the calls.
+    /// * `payloads` - Calldata of the calls.
+    /// * `predecessor` - Operation that has to be executed first.
+    /// * `salt` - Salt of the operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If the caller doesn't have the
+    ///   [`EXECUTOR_ROLE`].
+    /// * [`Error::InvalidOperationLength`] - If the parameters lengths
+    ///   mismatch.
+    /// * [`Error::UnexpectedOperationState`] - If the operation is not ready.
+    /// * [`Error::UnexecutedPredecessor`] - If the predecessor is not done.
+    /// * [`Error::FailedCall`] - If one of the calls failed.
+    ///
+    /// # Events
+    ///
+    /// * [`CallExecuted`] - For each transaction in the batch.
+    fn execute_batch(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        payloads: Vec<Bytes>,
+        predecessor: B256,
+        salt: B256,
+    ) -> Result<(), Self::Error>;
+
+    /// Changes the minimum timelock duration for future operations.
+    ///
+    /// Requires the caller to be the timelock itself. This can only be
+    /// achieved by scheduling and later executing an operation where the
+    /// timelock is the target and the data is the ABI-encoded call to this
+    /// function.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_delay` - New minimum delay.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedCaller`] - If the caller is not the timelock.
+    ///
+    /// # Events
+    ///
+    /// * [`MinDelayChange`].
+    fn update_delay(&mut self, new_delay: U256) -> Result<(), Self::Error>;
+
+    /// Accepts ERC-721 tokens sent through a safe transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `operator` - Account performing the transfer.
+    /// * `from` - Previous owner of the token.
+    /// * `token_id` - Id of the token.
+    /// * `data` - Additional data of the transfer.
+    #[selector(name = "onERC721Received")]
+    fn on_erc721_received(
+        &mut self,
+        operator: Address,
+        from: Address,
+        token_id: U256,
+        data: Bytes,
+    ) -> FixedBytes<4>;
+
+    /// Accepts ERC-1155 tokens sent through a single transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `operator` - Account performing the transfer.
+    /// * `from` - Previous owner of the tokens.
+    /// * `id` - Id of the token.
+    /// * `value` - Amount of tokens.
+    /// * `data` - Additional data of the transfer.
+    #[selector(name = "onERC1155Received")]
+    fn on_erc1155_received(
+        &mut self,
+        operator: Address,
+        from: Address,
+        id: U256,
+        value: U256,
+        data: Bytes,
+    ) -> FixedBytes<4>;
+
+    /// Accepts ERC-1155 tokens sent through a batch transfer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `operator` - Account performing the transfer.
+    /// * `from` - Previous owner of the tokens.
+    /// * `ids` - Ids of the tokens.
+    /// * `values` - Amounts of tokens.
+    /// * `data` - Additional data of the transfer.
+    #[selector(name = "onERC1155BatchReceived")]
+    fn on_erc1155_batch_received(
+        &mut self,
+        operator: Address,
+        from: Address,
+        ids: Vec<U256>,
+        values: Vec<U256>,
+        data: Bytes,
+    ) -> FixedBytes<4>;
+}
+
+#[public]
+impl ITimelockController for TimelockController {
+    type Error = Error;
+
+    fn has_role(&self, role: B256, account: Address) -> bool {
+        self.access_control.has_role(role, account)
+    }
+
+    fn get_role_admin(&self, role: B256) -> B256 {
+        self.access_control.get_role_admin(role)
+    }
+
+    fn grant_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error> {
+        Ok(self.access_control.grant_role(role, account)?)
+    }
+
+    fn revoke_role(
+        &mut self,
+        role: B256,
+        account: Address,
+    ) -> Result<(), Self::Error> {
+        Ok(self.access_control.revoke_role(role, account)?)
+    }
+
+    fn renounce_role(
+        &mut self,
+        role: B256,
+        confirmation: Address,
+    ) -> Result<(), Self::Error> {
+        Ok(self.access_control.renounce_role(role, confirmation)?)
+    }
+
+    fn is_operation(&self, id: B256) -> bool {
+        self._get_operation_state(id) != OperationState::Unset
+    }
+
+    fn is_operation_pending(&self, id: B256) -> bool {
+        let state = self._get_operation_state(id);
+        state == OperationState::Waiting || state == OperationState::Ready
+    }
+
+    fn is_operation_ready(&self, id: B256) -> bool {
+        self._get_operation_state(id) == OperationState::Ready
+    }
+
+    fn is_operation_done(&self, id: B256) -> bool {
+        self._get_operation_state(id) == OperationState::Done
+    }
+
+    fn get_timestamp(&self, id: B256) -> U256 {
+        self.timestamps.get(id)
+    }
+
+    fn get_operation_state(&self, id: B256) -> u8 {
+        self._get_operation_state(id) as u8
+    }
+
+    fn get_min_delay(&self) -> U256 {
+        self.min_delay.get()
+    }
+
+    fn hash_operation(
+        &self,
+        target: Address,
+        value: U256,
+        data: Bytes,
+        predecessor: B256,
+        salt: B256,
+    ) -> B256 {
+        Self::_hash_operation(target, value, &data, predecessor, salt)
+    }
+
+    fn hash_operation_batch(
+        &self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        payloads: Vec<Bytes>,
+        predecessor: B256,
+        salt: B256,
+    ) -> B256 {
+        Self::_hash_operation_batch(
+            &targets,
+            &values,
+            &payloads,
+            predecessor,
+            salt,
+        )
+    }
+
+    fn schedule(
+        &mut self,
+        target: Address,
+        value: U256,
+        data: Bytes,
+        predecessor: B256,
+        salt: B256,
+        delay: U256,
+    ) -> Result<(), Self::Error> {
+        self.access_control._check_role(PROPOSER_ROLE.into(), msg::sender())?;
+
+        let id = Self::_hash_operation(target, value, &data, predecessor, salt);
+        self._schedule(id, delay)?;
+        evm::log(CallScheduled {
+            id,
+            index: U256::ZERO,
+            target,
+            value,
+            data: data.to_vec().into(),
+            predecessor,
+            delay,
+        });
+        if !salt.is_zero() {
+            evm::log(CallSalt { id, salt });
+        }
+        Ok(())
+    }
+
+    fn schedule_batch(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        payloads: Vec<Bytes>,
+        predecessor: B256,
+        salt: B256,
+        delay: U256,
+    ) -> Result<(), Self::Error> {
+        self.access_control._check_role(PROPOSER_ROLE.into(), msg::sender())?;
+        Self::_check_operation_length(&targets, &values, &payloads)?;
+
+        let id = Self::_hash_operation_batch(
+            &targets,
+            &values,
+            &payloads,
+            predecessor,
+            salt,
+        );
+        self._schedule(id, delay)?;
+        for (index, ((target, value), payload)) in
+            targets.into_iter().zip(values).zip(payloads).enumerate()
+        {
+            evm::log(CallScheduled {
+                id,
+                index: U256::from(index),
+                target,
+                value,
+                data: payload.to_vec().into(),
+                predecessor,
+                delay,
+            });
+        }
+        if !salt.is_zero() {
+            evm::log(CallSalt { id, salt });
+        }
+        Ok(())
+    }
+
+    fn cancel(&mut self, id: B256) -> Result<(), Self::Error> {
+        self.access_control
+            ._check_role(CANCELLER_ROLE.into(), msg::sender())?;
+
+        if !self.is_operation_pending(id) {
+            return Err(TimelockUnexpectedOperationState {
+                operation_id: id,
+                expected_states: OperationState::Waiting.encode_bitmap()
+                    | OperationState::Ready.encode_bitmap(),
+            }
+            .into());
+        }
+        self.timestamps.delete(id);
+
+        evm::log(Cancelled { id });
+        Ok(())
+    }
+
+    #[payable]
+    fn execute(
+        &mut self,
+        target: Address,
+        value: U256,
+        payload: Bytes,
+        predecessor: B256,
+        salt: B256,
+    ) -> Result<(), Self::Error> {
+        self._check_role_or_open_role(EXECUTOR_ROLE.into())?;
+
+        let id =
+            Self::_hash_operation(target, value, &payload, predecessor, salt);
+
+        self._before_call(id, predecessor)?;
+        Self::_execute(target, value, &payload)?;
+        evm::log(CallExecuted {
+            id,
+            index: U256::ZERO,
+            target,
+            value,
+            data: payload.to_vec().into(),
+        });
+        self._after_call(id)
+    }
+
+    #[payable]
+    fn execute_batch(
+        &mut self,
+        targets: Vec<Address>,
+        values: Vec<U256>,
+        payloads: Vec<Bytes>,
+        predecessor: B256,
+        salt: B256,
+    ) -> Result<(), Self::Error> {
+        self._check_role_or_open_role(EXECUTOR_ROLE.into())?;
+        Self::_check_operation_length(&targets, &values, &payloads)?;
+
+        let id = Self::_hash_operation_batch(
+            &targets,
+            &values,
+            &payloads,
+            predecessor,
+            salt,
+        );
+
+        self._before_call(id, predecessor)?;
+        for (index, ((target, value), payload)) in
+            targets.into_iter().zip(values).zip(payloads).enumerate()
+        {
+            Self::_execute(target, value, &payload)?;
+            evm::log(CallExecuted {
+                id,
+                index: U256::from(index),
+                target,
+                value,
+                data: payload.to_vec().into(),
+            });
+        }
+        self._after_call(id)
+    }
+
+    fn update_delay(&mut self, new_delay: U256) -> Result<(), Self::Error> {
+        let sender = msg::sender();
+        if sender != contract::address() {
+            return Err(TimelockUnauthorizedCaller { caller: sender }.into());
+        }
+        evm::log(MinDelayChange {
+            old_duration: self.min_delay.get(),
+            new_duration: new_delay,
+        });
+        self.min_delay.set(new_delay);
+        Ok(())
+    }
+
+    #[selector(name = "onERC721Received")]
+    fn on_erc721_received(
+        &mut self,
+        _operator: Address,
+        _from: Address,
+        _token_id: U256,
+        _data: Bytes,
+    ) -> FixedBytes<4> {
+        RECEIVER_FN_SELECTOR.into()
+    }
+
+    #[selector(name = "onERC1155Received")]
+    fn on_erc1155_received(
+        &mut self,
+        _operator: Address,
+        _from: Address,
+        _id: U256,
+        _value: U256,
+        _data: Bytes,
+    ) -> FixedBytes<4> {
+        SINGLE_TRANSFER_FN_SELECTOR.into()
+    }
+
+    #[selector(name = "onERC1155BatchReceived")]
+    fn on_erc1155_batch_received(
+        &mut self,
+        _operator: Address,
+        _from: Address,
+        _ids: Vec<U256>,
+        _values: Vec<U256>,
+        _data: Bytes,
+    ) -> FixedBytes<4> {
+        BATCH_TRANSFER_FN_SELECTOR.into()
+    }
+}
+
+impl TimelockController {
+    /// Returns the state of an operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Id of the operation.
+    #[must_use]
+    pub fn _get_operation_state(&self, id: B256) -> OperationState {
+        let timestamp = self.get_timestamp(id);
+        if timestamp.is_zero() {
+            OperationState::Unset
+        } else if timestamp == DONE_TIMESTAMP {
+            OperationState::Done
+        } else if timestamp > U256::from(block::timestamp()) {
+            OperationState::Waiting
+        } else {
+            OperationState::Ready
+        }
+    }
+
+    /// Returns the identifier of an operation containing a single
+    /// transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Target of the call.
+    /// * `value` - Value sent with the call.
+    /// * `data` - Calldata of the call.
+    /// * `predecessor` - Operation that has to be executed first.
+    /// * `salt` - Salt of the operation.
+    #[must_use]
+    pub fn _hash_operation(
+        target: Address,
+        value: U256,
+        data: &[u8],
+        predecessor: B256,
+        salt: B256,
+    ) -> B256 {
+        let encoded = OperationTuple::abi_encode_params(&(
+            target,
+            value,
+            alloy_primitives::Bytes::copy_from_slice(data),
+            predecessor,
+            salt,
+        ));
+        alloy_primitives::keccak256(encoded)
+    }
+
+    /// Returns the identifier of an operation containing a batch of
+    /// transactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `targets` - Targets of the calls.
+    /// * `values` - Values sent with the calls.
+    /// * `payloads` - Calldata of the calls.
+    /// * `predecessor` - Operation that has to be executed first.
+    /// * `salt` - Salt of the operation.
+    #[must_use]
+    pub fn _hash_operation_batch(
+        targets: &[Address],
+        values: &[U256],
+        payloads: &[Bytes],
+        predecessor: B256,
+        salt: B256,
+    ) -> B256 {
+        let payloads: Vec<alloy_primitives::Bytes> =
+            payloads.iter().map(|payload| payload.to_vec().into()).collect();
+        let encoded = OperationBatchTuple::abi_encode_params(&(
+            targets.to_vec(),
+            values.to_vec(),
+            payloads,
+            predecessor,
+            salt,
+        ));
+        alloy_primitives::keccak256(encoded)
+    }
+
+    /// Schedule an operation that is to become valid after a given delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `id` - Id of the operation.
+    /// * `delay` - Delay before the operation becomes ready.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedOperationState`] - If the operation is already
+    ///   scheduled.
+    /// * [`Error::InsufficientDelay`] - If `delay` is lower than the minimum
+    ///   delay.
+    fn _schedule(&mut self, id: B256, delay: U256) -> Result<(), Error> {
+        if self.is_operation(id) {
+            return Err(TimelockUnexpectedOperationState {
+                operation_id: id,
+                expected_states: OperationState::Unset.encode_bitmap(),
+            }
+            .into());
+        }
+        let min_delay = self.get_min_delay();
+        if delay < min_delay {
+            return Err(TimelockInsufficientDelay { delay, min_delay }.into());
+        }
+        self.timestamps
+            .setter(id)
+            .set(U256::from(block::timestamp()).saturating_add(delay));
+        Ok(())
+    }
+
+    /// Execute an operation's call.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Target of the call.
+    /// * `value` - Value sent with the call.
+    /// * `data` - Calldata of the call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If the call failed.
+    fn _execute(
+        target: Address,
+        value: U256,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        RawCall::new_with_value(value)
+            .call_with_reentrant_handling(target, data)
+            .map_err(call::Error::Revert)?;
+        Ok(())
+    }
+
+    /// Checks before execution of an operation's calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `id` - Id of the operation.
+    /// * `predecessor` - Operation that has to be executed first.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedOperationState`] - If the operation is not ready.
+    /// * [`Error::UnexecutedPredecessor`] - If the predecessor is not done.
+    fn _before_call(&self, id: B256, predecessor: B256) -> Result<(), Error> {
+        if !self.is_operation_ready(id) {
+            return Err(TimelockUnexpectedOperationState {
+                operation_id: id,
+                expected_states: OperationState::Ready.encode_bitmap(),
+            }
+            .into());
+        }
+        if !predecessor.is_zero() && !self.is_operation_done(predecessor) {
+            return Err(TimelockUnexecutedPredecessor {
+                predecessor_id: predecessor,
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Checks after execution of an operation's calls, and marks the
+    /// operation as done.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `id` - Id of the operation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnexpectedOperationState`] - If the operation is not ready.
+    fn _after_call(&mut self, id: B256) -> Result<(), Error> {
+        if !self.is_operation_ready(id) {
+            return Err(TimelockUnexpectedOperationState {
+                operation_id: id,
+                expected_states: OperationState::Ready.encode_bitmap(),
+            }
+            .into());
+        }
+        self.timestamps.setter(id).set(DONE_TIMESTAMP);
+        Ok(())
+    }
+
+    /// Checks that the caller has `role`, unless `role` was granted to
+    /// `Address::ZERO`, making it open to everyone.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `role` - The role identifier.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::AccessControl`] - If the caller doesn't have `role`.
+    fn _check_role_or_open_role(&self, role: B256) -> Result<(), Error> {
+        if !self.has_role(role, Address::ZERO) {
+            self.access_control._check_role(role, msg::sender())?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the parameters of a batch operation have matching
+    /// lengths.
+    ///
+    /// # Arguments
+    ///
+    /// * `targets` - Targets of the calls.
+    /// * `values` - Values sent with the calls.
+    /// * `payloads` - Calldata of the calls.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidOperationLength`] - If the lengths mismatch.
+    fn _check_operation_length(
+        targets: &[Address],
+        values: &[U256],
+        payloads: &[Bytes],
+    ) -> Result<(), Error> {
+        if targets.len() != values.len() || targets.len() != payloads.len() {
+            return Err(TimelockInvalidOperationLength {
+                targets: U256::from(targets.len()),
+                payloads: U256::from(payloads.len()),
+                values: U256::from(values.len()),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl IErc165 for TimelockController {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as ITimelockController>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || <AccessControl as IAccessControl>::INTERFACE_ID
+                == u32::from_be_bytes(*interface_id)
+            || ERC1155_RECEIVER_INTERFACE_ID
+                == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, B256, U256};
+    use motsu::prelude::*;
+    use stylus_sdk::{
+        abi::Bytes,
+        function_selector,
+        prelude::*,
+        storage::{StorageBool, StorageU256},
+    };
+
+    use super::{
+        CallExecuted, CallSalt, Cancelled, Error, ITimelockController,
+        OperationState, TimelockController, CANCELLER_ROLE, EXECUTOR_ROLE,
+        PROPOSER_ROLE,
+    };
+    use crate::{
+        access::control::{AccessControl, IAccessControl},
+        utils::introspection::erc165::IErc165,
+    };
+
+    const MIN_DELAY: U256 = uint!(60_U256);
+    const SALT: B256 = B256::repeat_byte(1);
+
+    /// Target of the operations' calls.
+    #[storage]
+    struct TargetMock {
+        value: StorageU256,
+        called: StorageBool,
+    }
+
+    unsafe impl TopLevelStorage for TargetMock {}
+
+    #[public]
+    impl TargetMock {
+        fn mock_function(&mut self) {
+            self.called.set(true);
+        }
+
+        fn set_value(&mut self, value: U256) {
+            self.value.set(value);
+        }
+
+        #[allow(clippy::unused_self)]
+        fn mock_revert(&mut self) -> Result<(), Vec<u8>> {
+            Err(b"reverted".to_vec())
+        }
+    }
+
+    fn init(timelock: &Contract<TimelockController>, alice: Address) {
+        timelock.init(alice, |timelock| {
+            timelock
+                .access_control
+                ._grant_role(AccessControl::DEFAULT_ADMIN_ROLE.into(), alice);
+            timelock.access_control._grant_role(PROPOSER_ROLE.into(), alice);
+            timelock.access_control._grant_role(CANCELLER_ROLE.into(), alice);
+            timelock.access_control._grant_role(EXECUTOR_ROLE.into(), alice);
+            timelock.min_delay.set(MIN_DELAY);
+        });
+    }
+
+    fn mock_call() -> Bytes {
+        function_selector!("mockFunction").to_vec().into()
+    }
+
+    #[motsu::test]
+    fn schedules_operation(
+        timelock: Contract<TimelockController>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init(&timelock, alice);
+        let target = target.address();
+        let id = timelock.sender(alice).hash_operation(
+            target,
+            U256::ZERO,
+            mock_call(),
+            B256::ZERO,
+            SALT,
+        );
+        assert!(!timelock.sender(alice).is_operation(id));
+
+        timelock
+            .sender(alice)
+            .schedule(
+                target,
+                U256::ZERO,
+                mock_call(),
+                B256::ZERO,
+                SALT,
+                MIN_DELAY,
+            )
+            .motsu_unwrap();
+
+        assert!(timelock.sender(alice).is_operation(id));
+        assert!(timelock.sender(alice).is_operation_pending(id));
+        assert!(!timelock.sender(alice).is_operation_ready(id));
+        assert_eq!(
+            OperationState::Waiting as u8,
+            timelock.sender(alice).get_operation_state(id)
+        );
+        timelock.assert_emitted(&CallSalt { id, salt: SALT });
+    }
+
+    #[motsu::test]
+    fn schedule_reverts_when_unauthorized_or_invalid(
+        timelock: Contract<TimelockController>,
+        target: Contract<TargetMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&timelock, alice);
+        let target = target.address();
+
+        let err = timelock
+            .sender(bob)
+            .schedule(
+                target,
+                U256::ZERO,
+                mock_call(),
+                B256::ZERO,
+                SALT,
+                MIN_DELAY,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::AccessControl(_)));
+
+        let err = timelock
+            .sender(alice)
+            .schedule(
+                target,
+                U256::ZERO,
+                mock_call(),
+                B256::ZERO,
+                SALT,
+                U256::ZERO,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::InsufficientDelay(_)));
+
+        timelock
+            .sender(alice)
+            .schedule(
+                target,
+                U256::ZERO,
+                mock_call(),
+                B256::ZERO,
+                SALT,
+                MIN_DELAY,
+            )
+            .motsu_unwrap();
+        let err = timelock
+            .sender(alice)
+            .schedule(
+                target,
+                U256::ZERO,
+                mock_call(),
+                B256::ZERO,
+                SALT,
+                MIN_DELAY,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnexpectedOperationState(_)));
+
+        let err = timelock
+            .sender(alice)
+            .schedule_batch(
+                vec![target],
+                vec![],
+                vec![mock_call()],
+                B256::ZERO,
+                SALT,
+                MIN_DELAY,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidOperationLength(_)));
+    }
+
+    #[motsu::test]
+    fn executes_ready_operation(
+        timelock: Contract<TimelockController>,
+        target: Contract<TargetMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&timelock, alice);
+        timelock.sender(alice).min_delay.set(U256::ZERO);
+        let target_address = target.address();
+
+        timelock
+            .sender(alice)
+            .schedule(
+                target_address,
+                U256::ZERO,
+                mock_call(),
+                B256::ZERO,
+                SALT,
+                U256::ZERO,
+            )
+            .motsu_unwrap();
+        let id = timelock.sender(alice).hash_operation(
+            target_address,
+            U256::ZERO,
+            mock_call(),
+            B256::ZERO,
+            SALT,
+        );
+        assert!(timelock.sender(alice).is_operation_ready(id));
+
+        let err = timelock
+            .sender(bob)
+            .execute(target_address, U256::ZERO, mock_call(), B256::ZERO, SALT)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::AccessControl(_)));
+
+        timelock
+            .sender(alice)
+            .execute(target_address, U256::ZERO, mock_call(), B256::ZERO, SALT)
+            .motsu_unwrap();
+
+        assert!(target.sender(alice).called.get());
+        assert!(timelock.sender(alice).is_operation_done(id));
+        timelock.assert_emitted(&CallExecuted {
+            id,
+            index: U256::ZERO,
+            target: target_address,
+            value: U256::ZERO,
+            data: mock_call().to_vec().into(),
+        });
+
+        let err = timelock
+            .sender(alice)
+            .execute(target_address, U256::ZERO, mock_call(), B256::ZERO, SALT)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnexpectedOperationState(_)));
+    }
+
+    #[motsu::test]
+    fn execute_reverts_when_not_ready(
+        timelock: Contract<TimelockController>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init(&timelock, alice);
+        let target = target.address();
+
+        timelock
+            .sender(alice)
+            .schedule(
+                target,
+                U256::ZERO,
+                mock_call(),
+                B256::ZERO,
+                SALT,
+                MIN_DELAY,
+            )
+            .motsu_unwrap();
+
+        let err = timelock
+            .sender(alice)
+            .execute(target, U256::ZERO, mock_call(), B256::ZERO, SALT)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnexpectedOperationState(_)));
+    }
+
+    #[motsu::test]
+    fn executes_batch_after_predecessor(
+        timelock: Contract<TimelockController>,
+        target: Contract<TargetMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&timelock, alice);
+        timelock.sender(alice).min_delay.set(U256::ZERO);
+        // Open the executor role to everyone.
+        timelock
+            .sender(alice)
+            .grant_role(EXECUTOR_ROLE.into(), Address::ZERO)
+            .motsu_unwrap();
+        let target_address = target.address();
+
+        let set_value = function_selector!("setValue", U256)
+            .into_iter()
+            .chain(U256::from(42).to_be_bytes::<32>())
+            .collect::<Vec<u8>>()
+            .into();
+        let targets = vec![target_address, target_address];
+        let values = vec![U256::ZERO, U256::ZERO];
+        let payloads = vec![mock_call(), set_value];
+
+        let predecessor = timelock.sender(alice).hash_operation(
+            target_address,
+            U256::ZERO,
+            mock_call(),
+            B256::ZERO,
+            B256::ZERO,
+        );
+        timelock
+            .sender(alice)
+            .schedule_batch(
+                targets.clone(),
+                values.clone(),
+                payloads.clone(),
+                predecessor,
+                SALT,
+                U256::ZERO,
+            )
+            .motsu_unwrap();
+
+        let err = timelock
+            .sender(bob)
+            .execute_batch(
+                targets.clone(),
+                values.clone(),
+                payloads.clone(),
+                predecessor,
+                SALT,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnexecutedPredecessor(_)));
+
+        timelock
+            .sender(alice)
+            .schedule(
+                target_address,
+                U256::ZERO,
+                mock_call(),
+                B256::ZERO,
+                B256::ZERO,
+                U256::ZERO,
+            )
+            .motsu_unwrap();
+        timelock
+            .sender(bob)
+            .execute(
+                target_address,
+                U256::ZERO,
+                mock_call(),
+                B256::ZERO,
+                B256::ZERO,
+            )
+            .motsu_unwrap();
+
+        timelock
+            .sender(bob)
+            .execute_batch(targets, values, payloads, predecessor, SALT)
+            .motsu_unwrap();
+        assert_eq!(U256::from(42), target.sender(alice).value.get());
+    }
+
+    #[motsu::test]
+    fn execute_reverts_when_call_fails(
+        timelock: Contract<TimelockController>,
+        target: Contract<TargetMock>,
+        alice: Address,
+    ) {
+        init(&timelock, alice);
+        timelock.sender(alice).min_delay.set(U256::ZERO);
+        let target = target.address();
+        let payload: Bytes = function_selector!("mockRevert").to_vec().into();
+
+        timelock
+            .sender(alice)
+            .schedule(
+                target,
+                U256::ZERO,
+                payload.clone(),
+                B256::ZERO,
+                SALT,
+                U256::ZERO,
+            )
+            .motsu_unwrap();
+        let err = timelock
+            .sender(alice)
+            .execute(target, U256::ZERO, payload, B256::ZERO, SALT)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::FailedCall(_)));
+    }
+
+    #[motsu::test]
+    fn cancels_pending_operation(
+        timelock: Contract<TimelockController>,
+        target: Contract<TargetMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&timelock, alice);
+        let target = target.address();
+        timelock
+            .sender(alice)
+            .schedule(
+                target,
+                U256::ZERO,
+                mock_call(),
+                B256::ZERO,
+                SALT,
+                MIN_DELAY,
+            )
+            .motsu_unwrap();
+        let id = timelock.sender(alice).hash_operation(
+            target,
+            U256::ZERO,
+            mock_call(),
+            B256::ZERO,
+            SALT,
+        );
+
+        let err = timelock.sender(bob).cancel(id).motsu_unwrap_err();
+        assert!(matches!(err, Error::AccessControl(_)));
+
+        timelock.sender(alice).cancel(id).motsu_unwrap();
+        assert!(!timelock.sender(alice).is_operation(id));
+        timelock.assert_emitted(&Cancelled { id });
+
+        let err = timelock.sender(alice).cancel(id).motsu_unwrap_err();
+        assert!(matches!(err, Error::UnexpectedOperationState(_)));
+    }
+
+    #[motsu::test]
+    fn update_delay_is_restricted_to_timelock(
+        timelock: Contract<TimelockController>,
+        alice: Address,
+    ) {
+        init(&timelock, alice);
+        let new_delay = uint!(120_U256);
+
+        let err =
+            timelock.sender(alice).update_delay(new_delay).motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCaller(_)));
+
+        timelock
+            .sender(timelock.address())
+            .update_delay(new_delay)
+            .motsu_unwrap();
+        assert_eq!(new_delay, timelock.sender(alice).get_min_delay());
+    }
+
+    #[motsu::test]
+    fn returns_receiver_selectors(
+        timelock: Contract<TimelockController>,
+        alice: Address,
+    ) {
+        init(&timelock, alice);
+        assert_eq!(
+            function_selector!(
+                "onERC721Received",
+                Address,
+                Address,
+                U256,
+                Bytes
+            ),
+            *timelock.sender(alice).on_erc721_received(
+                alice,
+                alice,
+                U256::ZERO,
+                vec![].into()
+            )
+        );
+        assert_eq!(
+            function_selector!(
+                "onERC1155Received",
+                Address,
+                Address,
+                U256,
+                U256,
+                Bytes
+            ),
+            *timelock.sender(alice).on_erc1155_received(
+                alice,
+                alice,
+                U256::ZERO,
+                U256::ZERO,
+                vec![].into()
+            )
+        );
+        assert_eq!(
+            function_selector!(
+                "onERC1155BatchReceived",
+                Address,
+                Address,
+                Vec<U256>,
+                Vec<U256>,
+                Bytes
+            ),
+            *timelock.sender(alice).on_erc1155_batch_received(
+                alice,
+                alice,
+                vec![],
+                vec![],
+                vec![].into()
+            )
+        );
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(TimelockController::supports_interface(
+            <TimelockController as ITimelockController>::INTERFACE_ID.into()
+        ));
+        assert!(TimelockController::supports_interface(
+            <AccessControl as IAccessControl>::INTERFACE_ID.into()
+        ));
+        assert!(TimelockController::supports_interface(0x4e23_12e0_u32.into()));
+        assert!(TimelockController::supports_interface(
+            <TimelockController as IErc165>::INTERFACE_ID.into()
+        ));
+
+        let fake_interface_id = 0x1234_5678_u32;
+        assert!(!TimelockController::supports_interface(
+            fake_interface_id.into()
+        ));
+    }
+}
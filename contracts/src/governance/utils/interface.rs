@@ -0,0 +1,22 @@
+//! Solidity Interface of a votes contract, as defined in ERC-5805.
+pub use votes::*;
+
+mod votes {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::prelude::sol_interface;
+    sol_interface! {
+        interface VotesInterface {
+            function clock() external view returns (uint48);
+            function CLOCK_MODE() external view returns (string memory);
+            function getVotes(address account) external view returns (uint256);
+            function getPastVotes(address account, uint256 timepoint) external view returns (uint256);
+            function getPastTotalSupply(uint256 timepoint) external view returns (uint256);
+            function delegates(address account) external view returns (address);
+            function delegate(address delegatee) external;
+        }
+    }
+}
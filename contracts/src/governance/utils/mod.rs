@@ -0,0 +1,6 @@
+//! Common utilities for governance contracts.
+pub mod interface;
+pub mod votes;
+
+pub use interface::VotesInterface;
+pub use votes::{IErc6372, IVotes, Votes};
@@ -0,0 +1,805 @@
+//! Base logic to track voting units and delegation, as used by
+//! [`crate::token::erc20::extensions::Erc20Votes`] and
+//! [`crate::token::erc721::extensions::Erc721Votes`].
+//!
+//! This contract keeps a history (checkpoints) of each account's vote power.
+//! Vote power can be delegated either by calling the [`IVotes::delegate`]
+//! function directly, or by providing a signature to be used with
+//! `delegate_by_sig`. Voting power can be queried through the public
+//! accessors [`IVotes::get_votes`] and [`IVotes::get_past_votes`].
+//!
+//! [`Votes`] does not know about token balances. The token contract that uses
+//! it is the one defining what a voting unit is (e.g. one token for ERC-20,
+//! one NFT for ERC-721), and is responsible for calling
+//! [`Votes::_transfer_voting_units`] on every balance change and for passing
+//! the delegator's voting units to [`Votes::_delegate`].
+//!
+//! By default, voting units do not account for voting power. This makes
+//! transfers cheaper. The downside is that it requires users to delegate to
+//! themselves in order to activate checkpoints and have their voting power
+//! tracked.
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use alloy_primitives::{
+    aliases::{U208, U48},
+    ruint::UintTryTo,
+    Address, B256, U256, U32,
+};
+use openzeppelin_stylus_proc::{interface_id, Eip712Struct};
+use stylus_sdk::{
+    block,
+    call::MethodError,
+    evm,
+    prelude::*,
+    storage::{StorageAddress, StorageMap},
+};
+
+use crate::utils::{
+    cryptography::{ecdsa, eip712::IEip712Domain},
+    nonces::{self, Nonces},
+    structs::checkpoints::{self, Trace, S208},
+};
+
+/// Delegation message signed by the delegator.
+#[derive(Eip712Struct)]
+struct Delegation {
+    delegatee: Address,
+    nonce: U256,
+    expiry: U256,
+}
+
+pub use sol::*;
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when an account changes its delegate.
+        ///
+        /// * `delegator` - Account that changed its delegate.
+        /// * `from_delegate` - Previous delegate of the `delegator`.
+        /// * `to_delegate` - New delegate of the `delegator`.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event DelegateChanged(
+            address indexed delegator,
+            address indexed from_delegate,
+            address indexed to_delegate
+        );
+
+        /// Emitted when a token transfer or delegate change results in changes
+        /// to a delegate's number of voting units.
+        ///
+        /// * `delegate` - Account whose voting units changed.
+        /// * `previous_votes` - Voting units before the change.
+        /// * `new_votes` - Voting units after the change.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event DelegateVotesChanged(
+            address indexed delegate,
+            uint256 previous_votes,
+            uint256 new_votes
+        );
+    }
+
+    sol! {
+        /// Lookup to future votes is not available.
+        ///
+        /// * `timepoint` - Requested timepoint.
+        /// * `clock` - Current timepoint of the clock.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC5805FutureLookup(uint256 timepoint, uint48 clock);
+
+        /// The signature used has expired.
+        ///
+        /// * `expiry` - Expiration time of the signature.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error VotesExpiredSignature(uint256 expiry);
+    }
+}
+
+/// A [`Votes`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Error type from checkpoint contract [`checkpoints::Error`].
+    Checkpoints(checkpoints::Error),
+    /// Error type from [`Nonces`] contract [`nonces::Error`].
+    Nonces(nonces::Error),
+    /// Error type from [`ecdsa`] contract [`ecdsa::Error`].
+    ECDSA(ecdsa::Error),
+    /// Lookup to future votes is not available.
+    FutureLookup(ERC5805FutureLookup),
+    /// The signature used has expired.
+    ExpiredSignature(VotesExpiredSignature),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of a [`Votes`] contract.
+#[storage]
+pub struct Votes<T: IEip712Domain + StorageType> {
+    /// [`Nonces`] contract used for signed delegations.
+    pub nonces: Nonces,
+    /// Contract implementing [`IEip712Domain`] trait.
+    pub eip712: T,
+    /// Maps accounts to their delegates.
+    pub(crate) delegatee: StorageMap<Address, StorageAddress>,
+    /// Maps delegates to the history of their voting units.
+    pub(crate) delegate_checkpoints: StorageMap<Address, Trace<S208>>,
+    /// History of the total supply of voting units.
+    pub(crate) total_checkpoints: Trace<S208>,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl<T: IEip712Domain + StorageType> TopLevelStorage for Votes<T> {}
+
+/// Interface of the [ERC-6372] clock, used to keep track of time in
+/// checkpoint-based contracts.
+///
+/// [ERC-6372]: https://eips.ethereum.org/EIPS/eip-6372
+#[interface_id]
+pub trait IErc6372 {
+    /// Returns the current timepoint according to the mode the contract is
+    /// operating in.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn clock(&self) -> U48;
+
+    /// Returns a machine-readable description of the clock as specified in
+    /// ERC-6372.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[selector(name = "CLOCK_MODE")]
+    fn clock_mode(&self) -> String;
+}
+
+/// Common interface for voting-power tracking contracts, as defined in
+/// [ERC-5805].
+///
+/// [ERC-5805]: https://eips.ethereum.org/EIPS/eip-5805
+#[interface_id]
+pub trait IVotes {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Returns the current amount of votes that `account` has.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the votes of.
+    fn get_votes(&self, account: Address) -> U256;
+
+    /// Returns the amount of votes that `account` had at a specific moment in
+    /// the past. If the [`IErc6372::clock`] is configured to use block
+    /// numbers, this will return the value at the end of the corresponding
+    /// block.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the votes of.
+    /// * `timepoint` - Timepoint in the past to query.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FutureLookup`] - If `timepoint` is not in the past.
+    fn get_past_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Self::Error>;
+
+    /// Returns the total supply of votes available at a specific moment in
+    /// the past. If the [`IErc6372::clock`] is configured to use block
+    /// numbers, this will return the value at the end of the corresponding
+    /// block.
+    ///
+    /// NOTE: This value is the sum of all available votes, which is not
+    /// necessarily the sum of all delegated votes. Votes that have not been
+    /// delegated are still part of total supply, even though they would not
+    /// participate in a vote.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `timepoint` - Timepoint in the past to query.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FutureLookup`] - If `timepoint` is not in the past.
+    fn get_past_total_supply(
+        &self,
+        timepoint: U256,
+    ) -> Result<U256, Self::Error>;
+
+    /// Returns the delegate that `account` has chosen.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the delegate of.
+    fn delegates(&self, account: Address) -> Address;
+
+    /// Delegates votes from the sender to `delegatee`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `delegatee` - Account to delegate votes to.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Checkpoints`] - If a checkpoint could not be pushed.
+    ///
+    /// # Events
+    ///
+    /// * [`DelegateChanged`].
+    /// * [`DelegateVotesChanged`].
+    fn delegate(&mut self, delegatee: Address) -> Result<(), Self::Error>;
+}
+
+impl<T: IEip712Domain + StorageType> IErc6372 for Votes<T> {
+    fn clock(&self) -> U48 {
+        U48::from(block::number())
+    }
+
+    fn clock_mode(&self) -> String {
+        "mode=blocknumber&from=default".to_string()
+    }
+}
+
+impl<T: IEip712Domain + StorageType> Votes<T> {
+    /// Returns the current amount of votes that `account` has.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the votes of.
+    #[must_use]
+    pub fn get_votes(&self, account: Address) -> U256 {
+        U256::from(self.delegate_checkpoints.getter(account).latest())
+    }
+
+    /// Returns the amount of votes that `account` had at a specific moment in
+    /// the past.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the votes of.
+    /// * `timepoint` - Timepoint in the past to query.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FutureLookup`] - If `timepoint` is not in the past.
+    pub fn get_past_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Error> {
+        let timepoint = self._validate_timepoint(timepoint)?;
+        Ok(U256::from(
+            self.delegate_checkpoints
+                .getter(account)
+                .upper_lookup_recent(timepoint),
+        ))
+    }
+
+    /// Returns the total supply of votes available at a specific moment in
+    /// the past.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `timepoint` - Timepoint in the past to query.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FutureLookup`] - If `timepoint` is not in the past.
+    pub fn get_past_total_supply(
+        &self,
+        timepoint: U256,
+    ) -> Result<U256, Error> {
+        let timepoint = self._validate_timepoint(timepoint)?;
+        Ok(U256::from(self.total_checkpoints.upper_lookup_recent(timepoint)))
+    }
+
+    /// Returns the delegate that `account` has chosen.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the delegate of.
+    #[must_use]
+    pub fn delegates(&self, account: Address) -> Address {
+        self.delegatee.get(account)
+    }
+
+    /// Returns the number of checkpoints for `account`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the number of checkpoints of.
+    ///
+    /// # Panics
+    ///
+    /// * If the number of checkpoints exceeds `u32::MAX`.
+    #[must_use]
+    pub fn num_checkpoints(&self, account: Address) -> u32 {
+        self.delegate_checkpoints
+            .getter(account)
+            .length()
+            .try_into()
+            .expect("number of checkpoints should not exceed `u32::MAX`")
+    }
+
+    /// Returns the `pos`-th checkpoint for `account` as a (key, value) pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the checkpoint of.
+    /// * `pos` - Index of the checkpoint.
+    ///
+    /// # Panics
+    ///
+    /// * If `pos` exceeds the number of checkpoints of `account`.
+    #[must_use]
+    pub fn checkpoints(&self, account: Address, pos: u32) -> (U48, U208) {
+        self.delegate_checkpoints.getter(account).at(U32::from(pos))
+    }
+
+    /// Returns the current total supply of voting units.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn _get_total_supply(&self) -> U256 {
+        U256::from(self.total_checkpoints.latest())
+    }
+
+    /// Validates a signed delegation and consumes the signer's nonce.
+    /// Returns the address of the signer (the delegator).
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `delegatee` - Account to delegate votes to.
+    /// * `nonce` - Nonce of the signer used in the signed message.
+    /// * `expiry` - Expiration time of the signature.
+    /// * `v` - v value from the signer's signature.
+    /// * `r` - r value from the signer's signature.
+    /// * `s` - s value from the signer's signature.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ExpiredSignature`] - If the `expiry` is in the past.
+    /// * [`ecdsa::Error::InvalidSignatureS`] - If the `s` value is greater than
+    ///   [`ecdsa::SIGNATURE_S_UPPER_BOUND`].
+    /// * [`ecdsa::Error::InvalidSignature`] - If the recovered address is
+    ///   `Address::ZERO`.
+    /// * [`nonces::Error::InvalidAccountNonce`] - If the `nonce` is not the
+    ///   next valid nonce of the signer.
+    #[allow(clippy::too_many_arguments)]
+    pub fn _recover_delegator(
+        &mut self,
+        delegatee: Address,
+        nonce: U256,
+        expiry: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<Address, Error> {
+        if U256::from(block::timestamp()) > expiry {
+            return Err(VotesExpiredSignature { expiry }.into());
+        }
+
+        let struct_hash = Delegation { delegatee, nonce, expiry }.struct_hash();
+
+        let hash: B256 = self.eip712.hash_typed_data_v4(struct_hash);
+
+        let signer: Address = ecdsa::recover(self, hash, v, r, s)?;
+
+        self.nonces.use_checked_nonce(signer, nonce)?;
+        Ok(signer)
+    }
+
+    /// Delegates all of `account`'s voting units to `delegatee`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account whose voting units are delegated.
+    /// * `delegatee` - Account to delegate votes to.
+    /// * `voting_units` - Current voting units of `account`, as defined by the
+    ///   token contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Checkpoints`] - If a checkpoint could not be pushed.
+    ///
+    /// # Events
+    ///
+    /// * [`DelegateChanged`].
+    /// * [`DelegateVotesChanged`].
+    pub fn _delegate(
+        &mut self,
+        account: Address,
+        delegatee: Address,
+        voting_units: U256,
+    ) -> Result<(), Error> {
+        let old_delegate = self.delegates(account);
+        self.delegatee.setter(account).set(delegatee);
+
+        evm::log(DelegateChanged {
+            delegator: account,
+            from_delegate: old_delegate,
+            to_delegate: delegatee,
+        });
+
+        self._move_delegate_votes(old_delegate, delegatee, voting_units)
+    }
+
+    /// Transfers, mints, or burns voting units. To register a mint, `from`
+    /// should be `Address::ZERO`. To register a burn, `to` should be
+    /// `Address::ZERO`. Total supply of voting units will be adjusted with
+    /// mints and burns.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account voting units are transferred from.
+    /// * `to` - Account voting units are transferred to.
+    /// * `value` - Amount of voting units to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Checkpoints`] - If a checkpoint could not be pushed.
+    ///
+    /// # Events
+    ///
+    /// * [`DelegateVotesChanged`].
+    ///
+    /// # Panics
+    ///
+    /// * If `value` exceeds `U208::MAX`.
+    /// * If a mint makes the total supply of voting units exceed `U208::MAX`.
+    /// * If a burn exceeds the total supply of voting units.
+    /// * If the delegate of `from` doesn't have `value` votes.
+    pub fn _transfer_voting_units(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        let amount = Self::_to_voting_units(value);
+        let clock = self.clock();
+
+        if from.is_zero() {
+            let latest = self.total_checkpoints.latest();
+            let total = latest.checked_add(amount).expect(
+                "total supply of voting units should not exceed `U208::MAX`",
+            );
+            self.total_checkpoints.push(clock, total)?;
+        }
+
+        if to.is_zero() {
+            let latest = self.total_checkpoints.latest();
+            let total = latest.checked_sub(amount).expect(
+                "burned voting units should not exceed the total supply",
+            );
+            self.total_checkpoints.push(clock, total)?;
+        }
+
+        self._move_delegate_votes(
+            self.delegates(from),
+            self.delegates(to),
+            value,
+        )
+    }
+
+    /// Moves delegated votes from one delegate to another.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Delegate votes are moved from.
+    /// * `to` - Delegate votes are moved to.
+    /// * `value` - Amount of votes to move.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Checkpoints`] - If a checkpoint could not be pushed.
+    ///
+    /// # Events
+    ///
+    /// * [`DelegateVotesChanged`].
+    ///
+    /// # Panics
+    ///
+    /// * If `value` exceeds `U208::MAX`.
+    /// * If `from` doesn't have `value` votes.
+    /// * If the votes of `to` exceed `U208::MAX`.
+    fn _move_delegate_votes(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if from == to || value.is_zero() {
+            return Ok(());
+        }
+
+        let amount = Self::_to_voting_units(value);
+        let clock = self.clock();
+
+        if !from.is_zero() {
+            let mut checkpoints = self.delegate_checkpoints.setter(from);
+            let latest = checkpoints.latest();
+            let votes = latest
+                .checked_sub(amount)
+                .expect("moved votes should not exceed the delegate's votes");
+            let (previous_votes, new_votes) = checkpoints.push(clock, votes)?;
+            evm::log(DelegateVotesChanged {
+                delegate: from,
+                previous_votes: U256::from(previous_votes),
+                new_votes: U256::from(new_votes),
+            });
+        }
+
+        if !to.is_zero() {
+            let mut checkpoints = self.delegate_checkpoints.setter(to);
+            let latest = checkpoints.latest();
+            let votes = latest
+                .checked_add(amount)
+                .expect("delegate's votes should not exceed `U208::MAX`");
+            let (previous_votes, new_votes) = checkpoints.push(clock, votes)?;
+            evm::log(DelegateVotesChanged {
+                delegate: to,
+                previous_votes: U256::from(previous_votes),
+                new_votes: U256::from(new_votes),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Converts `value` to voting units, as stored in the checkpoints.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Amount of voting units.
+    ///
+    /// # Panics
+    ///
+    /// * If `value` exceeds `U208::MAX`.
+    fn _to_voting_units(value: U256) -> U208 {
+        value.uint_try_to().expect("voting units should not exceed `U208::MAX`")
+    }
+
+    /// Validates that `timepoint` is in the past, and returns it as a
+    /// checkpoint key.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `timepoint` - Timepoint to validate.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FutureLookup`] - If `timepoint` is not in the past.
+    pub fn _validate_timepoint(&self, timepoint: U256) -> Result<U48, Error> {
+        let current_timepoint = self.clock();
+        if timepoint >= U256::from(current_timepoint) {
+            return Err(ERC5805FutureLookup {
+                timepoint,
+                clock: current_timepoint.to(),
+            }
+            .into());
+        }
+        Ok(timepoint.to())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{aliases::U208, uint, Address, B256, U256};
+    use motsu::prelude::*;
+    use stylus_sdk::prelude::*;
+
+    use super::{
+        DelegateChanged, DelegateVotesChanged, Error, IErc6372, Votes,
+    };
+    use crate::utils::cryptography::eip712::IEip712;
+
+    #[storage]
+    struct Eip712;
+
+    impl IEip712 for Eip712 {
+        const NAME: &'static str = "Votes";
+        const VERSION: &'static str = "1";
+    }
+
+    #[public]
+    impl Votes<Eip712> {}
+
+    #[motsu::test]
+    fn delegate_moves_voting_units(
+        contract: Contract<Votes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let units = uint!(3_U256);
+        contract
+            .sender(alice)
+            ._transfer_voting_units(Address::ZERO, alice, units)
+            .motsu_unwrap();
+
+        // Undelegated units carry no voting power.
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+        assert_eq!(units, contract.sender(alice)._get_total_supply());
+
+        contract.sender(alice)._delegate(alice, bob, units).motsu_unwrap();
+
+        assert_eq!(bob, contract.sender(alice).delegates(alice));
+        assert_eq!(units, contract.sender(alice).get_votes(bob));
+        assert_eq!(1, contract.sender(alice).num_checkpoints(bob));
+        contract.assert_emitted(&DelegateChanged {
+            delegator: alice,
+            from_delegate: Address::ZERO,
+            to_delegate: bob,
+        });
+        contract.assert_emitted(&DelegateVotesChanged {
+            delegate: bob,
+            previous_votes: U256::ZERO,
+            new_votes: units,
+        });
+    }
+
+    #[motsu::test]
+    fn transfer_voting_units_moves_delegated_votes(
+        contract: Contract<Votes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let units = uint!(3_U256);
+        let one = uint!(1_U256);
+        contract
+            .sender(alice)
+            ._delegate(alice, alice, U256::ZERO)
+            .motsu_unwrap();
+        contract.sender(alice)._delegate(bob, bob, U256::ZERO).motsu_unwrap();
+        contract
+            .sender(alice)
+            ._transfer_voting_units(Address::ZERO, alice, units)
+            .motsu_unwrap();
+
+        contract
+            .sender(alice)
+            ._transfer_voting_units(alice, bob, one)
+            .motsu_unwrap();
+        assert_eq!(units - one, contract.sender(alice).get_votes(alice));
+        assert_eq!(one, contract.sender(alice).get_votes(bob));
+
+        contract
+            .sender(alice)
+            ._transfer_voting_units(bob, Address::ZERO, one)
+            .motsu_unwrap();
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(bob));
+        assert_eq!(units - one, contract.sender(alice)._get_total_supply());
+    }
+
+    #[motsu::test]
+    #[should_panic = "voting units should not exceed `U208::MAX`"]
+    fn transfer_voting_units_panics_when_value_exceeds_u208(
+        contract: Contract<Votes<Eip712>>,
+        alice: Address,
+    ) {
+        let value = U256::from(U208::MAX) + uint!(1_U256);
+        _ = contract.sender(alice)._transfer_voting_units(
+            Address::ZERO,
+            alice,
+            value,
+        );
+    }
+
+    #[motsu::test]
+    #[should_panic = "total supply of voting units should not exceed `U208::MAX`"]
+    fn transfer_voting_units_panics_when_total_supply_overflows(
+        contract: Contract<Votes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract
+            .sender(alice)
+            ._transfer_voting_units(Address::ZERO, alice, U256::from(U208::MAX))
+            .motsu_unwrap();
+        _ = contract.sender(alice)._transfer_voting_units(
+            Address::ZERO,
+            bob,
+            uint!(1_U256),
+        );
+    }
+
+    #[motsu::test]
+    #[should_panic = "burned voting units should not exceed the total supply"]
+    fn transfer_voting_units_panics_when_burn_exceeds_total_supply(
+        contract: Contract<Votes<Eip712>>,
+        alice: Address,
+    ) {
+        _ = contract.sender(alice)._transfer_voting_units(
+            alice,
+            Address::ZERO,
+            uint!(1_U256),
+        );
+    }
+
+    #[motsu::test]
+    fn get_past_votes_errors_future_lookup(
+        contract: Contract<Votes<Eip712>>,
+        alice: Address,
+    ) {
+        let clock = U256::from(contract.sender(alice).clock());
+        let err = contract
+            .sender(alice)
+            .get_past_votes(alice, clock)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::FutureLookup(_)));
+
+        let err = contract
+            .sender(alice)
+            .get_past_total_supply(clock)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::FutureLookup(_)));
+    }
+
+    #[motsu::test]
+    fn recover_delegator_errors_expired_signature(
+        contract: Contract<Votes<Eip712>>,
+        alice: Address,
+    ) {
+        let expiry = uint!(1_U256);
+        let err = contract
+            .sender(alice)
+            ._recover_delegator(
+                alice,
+                U256::ZERO,
+                expiry,
+                27,
+                B256::ZERO,
+                B256::ZERO,
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::ExpiredSignature(_)));
+    }
+
+    #[motsu::test]
+    fn clock_mode_is_block_number(
+        contract: Contract<Votes<Eip712>>,
+        alice: Address,
+    ) {
+        assert_eq!(
+            "mode=blocknumber&from=default",
+            contract.sender(alice).clock_mode()
+        );
+    }
+}
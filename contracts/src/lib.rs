@@ -49,6 +49,10 @@ impl MyContract { }
 extern crate alloc;
 
 pub mod access;
+pub mod account;
 pub mod finance;
+pub mod governance;
+pub mod metatx;
+pub mod proxy;
 pub mod token;
 pub mod utils;
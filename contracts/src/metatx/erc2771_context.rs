@@ -0,0 +1,295 @@
+//! Context variant with [ERC-2771] support.
+//!
+//! A trusted forwarder relays calls on behalf of their original sender by
+//! appending the sender's address to the calldata. Stylus decodes the
+//! arguments of public functions strictly, so this suffix must be stripped
+//! before the call is routed. Contracts supporting meta-transactions therefore
+//! use [`router_entrypoint`] as their entrypoint, instead of annotating their
+//! storage struct with `#[entrypoint]`:
+//!
+//! ```ignore
+//! #[storage]
+//! struct Token {
+//!     #[borrow]
+//!     erc20: Erc20,
+//!     #[borrow]
+//!     erc2771: Erc2771Context,
+//! }
+//!
+//! unsafe impl TopLevelStorage for Token {}
+//!
+//! #[public]
+//! #[inherit(Erc20, Erc2771Context)]
+//! impl Token {}
+//!
+//! #[entrypoint]
+//! fn entrypoint(input: Vec<u8>, host: VM) -> ArbResult {
+//!     erc2771_context::router_entrypoint::<Token, Token>(input, host)
+//! }
+//! ```
+//!
+//! The original sender is then available through
+//! [`crate::utils::context::msg_sender`], which components of this library use
+//! instead of [`stylus_sdk::msg::sender`].
+//!
+//! WARNING: Avoid using this pattern in contracts that rely on a specific
+//! calldata length, as it's affected by the sender appended by the forwarder.
+//!
+//! WARNING: The trusted forwarder can impersonate any account. Only trust
+//! forwarders that verify the signature of the original sender, like
+//! [`super::erc2771_forwarder::Erc2771Forwarder`].
+//!
+//! [ERC-2771]: https://eips.ethereum.org/EIPS/eip-2771
+use alloc::{vec, vec::Vec};
+use core::borrow::BorrowMut;
+
+use alloy_primitives::Address;
+use openzeppelin_stylus_proc::storage_location;
+use stylus_sdk::{
+    abi::Router, host::VM, msg, prelude::*, storage::StorageAddress,
+    stylus_core::ValueDenier, ArbResult,
+};
+
+use crate::utils::{
+    context::{self, RELAYED_SUFFIX_LENGTH},
+    storage_slot::StorageSlot,
+};
+
+/// State of an [`Erc2771Context`] contract.
+#[storage_location(erc7201 = "openzeppelin.storage.ERC2771Context")]
+pub struct Erc2771Context {
+    /// The trusted forwarder.
+    pub(crate) trusted_forwarder: StorageAddress,
+}
+
+#[public]
+impl Erc2771Context {
+    /// Returns the address of the trusted forwarder.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn trusted_forwarder(&self) -> Address {
+        self.trusted_forwarder.get()
+    }
+
+    /// Indicates whether any particular address is the trusted forwarder.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `forwarder` - The address to check.
+    #[must_use]
+    pub fn is_trusted_forwarder(&self, forwarder: Address) -> bool {
+        forwarder == self.trusted_forwarder.get()
+    }
+}
+
+impl Erc2771Context {
+    /// Sets the trusted forwarder.
+    ///
+    /// This function is meant to be called once, when the contract is
+    /// initialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `forwarder` - The address of the trusted forwarder.
+    pub fn _set_trusted_forwarder(&mut self, forwarder: Address) {
+        self.trusted_forwarder.set(forwarder);
+    }
+}
+
+/// Routes `input` to the public functions of the contract, resolving the
+/// original sender of calls relayed by the trusted forwarder.
+///
+/// When `msg::sender` is the trusted forwarder of the [`Erc2771Context`], the
+/// last 20 bytes of `input` are stripped and used as the sender returned by
+/// [`context::msg_sender`] for the duration of the call.
+///
+/// # Arguments
+///
+/// * `input` - The calldata of the call.
+/// * `host` - The host environment of the contract.
+///
+/// # Errors
+///
+/// * If the routed call reverts, its revert data is returned.
+pub fn router_entrypoint<R, S>(mut input: Vec<u8>, host: VM) -> ArbResult
+where
+    R: Router<S>,
+    S: StorageType + TopLevelStorage + BorrowMut<R::Storage> + ValueDenier,
+{
+    // The trusted forwarder is the first field of the namespace.
+    let forwarder = StorageSlot::get_address(Erc2771Context::STORAGE_SLOT);
+
+    let sender =
+        if msg::sender() == forwarder && input.len() >= RELAYED_SUFFIX_LENGTH {
+            let suffix = input.split_off(input.len() - RELAYED_SUFFIX_LENGTH);
+            Address::from_slice(&suffix)
+        } else {
+            Address::ZERO
+        };
+
+    context::with_relayed_sender(sender, || {
+        stylus_sdk::abi::router_entrypoint::<R, S>(input, host)
+    })
+}
+
+/// Routes `calldata` through [`router_entrypoint`], with `sender` appended to
+/// it as the trusted forwarder does.
+///
+/// Meant to be called with the trusted forwarder as the current caller, e.g.
+/// from `motsu`'s `Contract::init`.
+#[cfg(all(test, feature = "std"))]
+pub(crate) fn relay<S>(calldata: &[u8], sender: Address) -> ArbResult
+where
+    S: Router<S, Storage = S>
+        + StorageType
+        + TopLevelStorage
+        + BorrowMut<S>
+        + ValueDenier,
+{
+    let input = [calldata, sender.as_slice()].concat();
+    let host = VM { host: alloc::boxed::Box::new(stylus_sdk::host::WasmVM {}) };
+    router_entrypoint::<S, S>(input, host)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloc::boxed::Box;
+
+    use alloy_primitives::{Address, B256};
+    use alloy_sol_types::SolCall;
+    use motsu::prelude::*;
+    use stylus_sdk::{
+        host::{WasmVM, VM},
+        prelude::*,
+        storage::StorageAddress,
+    };
+
+    use super::{router_entrypoint, Erc2771Context};
+    use crate::utils::{context, storage_slot::StorageSlot};
+
+    mod abi {
+        #![allow(missing_docs)]
+
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function setOwner() external returns (address);
+        }
+    }
+
+    #[storage]
+    struct RecipientMock {
+        #[borrow]
+        erc2771: Erc2771Context,
+        owner: StorageAddress,
+    }
+
+    unsafe impl TopLevelStorage for RecipientMock {}
+
+    #[public]
+    #[inherit(Erc2771Context)]
+    impl RecipientMock {
+        fn set_owner(&mut self) -> Address {
+            let sender = context::msg_sender();
+            self.owner.set(sender);
+            sender
+        }
+    }
+
+    fn host() -> VM {
+        VM { host: Box::new(WasmVM {}) }
+    }
+
+    fn relay(
+        contract: &Contract<RecipientMock>,
+        caller: Address,
+        input: Vec<u8>,
+    ) -> Address {
+        let output = contract
+            .init(caller, |_| {
+                router_entrypoint::<RecipientMock, RecipientMock>(input, host())
+            })
+            .expect("should route the call");
+        abi::setOwnerCall::abi_decode_returns(&output, true)
+            .expect("should decode the output")
+            ._0
+    }
+
+    #[motsu::test]
+    fn sets_trusted_forwarder(
+        contract: Contract<RecipientMock>,
+        alice: Address,
+        forwarder: Address,
+    ) {
+        contract.init(alice, |recipient| {
+            recipient.erc2771._set_trusted_forwarder(forwarder);
+        });
+
+        let recipient = contract.sender(alice);
+        assert_eq!(recipient.erc2771.trusted_forwarder(), forwarder);
+        assert!(recipient.erc2771.is_trusted_forwarder(forwarder));
+        assert!(!recipient.erc2771.is_trusted_forwarder(alice));
+    }
+
+    #[motsu::test]
+    fn stores_trusted_forwarder_in_namespace(
+        contract: Contract<RecipientMock>,
+        alice: Address,
+        forwarder: Address,
+    ) {
+        contract.init(alice, |recipient| {
+            recipient.erc2771._set_trusted_forwarder(forwarder);
+            assert_eq!(
+                StorageSlot::get_address(Erc2771Context::STORAGE_SLOT),
+                forwarder
+            );
+            assert_eq!(StorageSlot::get_word(B256::ZERO), B256::ZERO);
+        });
+    }
+
+    #[motsu::test]
+    fn resolves_sender_of_relayed_calls(
+        contract: Contract<RecipientMock>,
+        alice: Address,
+        bob: Address,
+        forwarder: Address,
+    ) {
+        contract.init(alice, |recipient| {
+            recipient.erc2771._set_trusted_forwarder(forwarder);
+        });
+
+        let input = [abi::setOwnerCall {}.abi_encode(), bob.to_vec()].concat();
+        assert_eq!(relay(&contract, forwarder, input), bob);
+        assert_eq!(contract.sender(alice).owner.get(), bob);
+
+        // The relayed sender only lasts for the duration of the call.
+        assert_eq!(contract.sender(alice).set_owner(), alice);
+    }
+
+    #[motsu::test]
+    fn ignores_suffix_from_untrusted_callers(
+        contract: Contract<RecipientMock>,
+        alice: Address,
+        bob: Address,
+        forwarder: Address,
+    ) {
+        contract.init(alice, |recipient| {
+            recipient.erc2771._set_trusted_forwarder(forwarder);
+        });
+
+        let input = abi::setOwnerCall {}.abi_encode();
+        assert_eq!(relay(&contract, alice, input.clone()), alice);
+
+        // Without stripping, the suffix fails to decode.
+        let input = [input, bob.to_vec()].concat();
+        let result = contract.init(alice, |_| {
+            router_entrypoint::<RecipientMock, RecipientMock>(input, host())
+        });
+        assert!(result.is_err());
+    }
+}
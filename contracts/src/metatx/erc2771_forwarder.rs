@@ -0,0 +1,737 @@
+//! A forwarder compatible with [ERC-2771] contracts.
+//!
+//! This forwarder operates on forward requests that include:
+//!
+//! * `from`: An address to operate on behalf of. It is required to be equal to
+//!   the request signer.
+//! * `to`: The address that should be called.
+//! * `value`: The amount of native token to attach with the requested call.
+//! * `gas`: The amount of gas limit that will be forwarded with the requested
+//!   call.
+//! * `nonce`: A unique transaction ordering identifier to avoid replayability
+//!   and request invalidation.
+//! * `deadline`: A timestamp after which the request is not executable anymore.
+//! * `data`: Encoded function call to execute with the `from` address appended
+//!   as a context suffix.
+//!
+//! The `nonce` is not part of [`ForwardRequestData`], since it's always the
+//! current nonce of `from` in [`Erc2771Forwarder::nonces`].
+//!
+//! The forwarder only calls targets that trust it, i.e. that return `true`
+//! from `isTrustedForwarder`, like contracts using
+//! [`super::erc2771_context::Erc2771Context`].
+//!
+//! [ERC-2771]: https://eips.ethereum.org/EIPS/eip-2771
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{aliases::U48, keccak256, Address, U256};
+use alloy_sol_types::SolType;
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    block,
+    call::{call, Call, MethodError, RawCall},
+    contract, evm, msg,
+    prelude::*,
+};
+
+use crate::utils::{
+    cryptography::{
+        ecdsa,
+        eip712::{Eip712Domain, IEip712Domain},
+    },
+    nonces::Nonces,
+    ReentrantCallHandler,
+};
+
+const FORWARD_REQUEST_TYPEHASH: [u8; 32] =
+    keccak_const::Keccak256::new()
+        .update(b"ForwardRequest(address from,address to,uint256 value,uint256 gas,uint256 nonce,uint48 deadline,bytes data)")
+        .finalize();
+
+/// A signed forward request, as the `(from, to, value, gas, deadline, data,
+/// signature)` tuple matching the `ForwardRequestData` struct of the
+/// Solidity implementation.
+pub type ForwardRequestData = (Address, Address, U256, U256, U48, Bytes, Bytes);
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    pub(crate) type StructHashTuple = sol! {
+        tuple(bytes32, address, address, uint256, uint256, uint256, uint48, bytes32)
+    };
+
+    sol! {
+        /// Emitted when a [`super::ForwardRequestData`] is executed.
+        ///
+        /// NOTE: An unsuccessful forward request could be due to an invalid
+        /// signature, an expired deadline, or simply a revert in the
+        /// requested call.
+        ///
+        /// * `signer` - Signer of the request.
+        /// * `nonce` - Nonce consumed by the request.
+        /// * `success` - Whether the requested call succeeded.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event ExecutedForwardRequest(address indexed signer, uint256 nonce, bool success);
+    }
+
+    sol! {
+        /// The request `from` doesn't match with the recovered `signer`.
+        ///
+        /// * `signer` - Recovered signer of the request.
+        /// * `from` - Account the request is made on behalf of.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC2771ForwarderInvalidSigner(address signer, address from);
+
+        /// The `requestedValue` doesn't match with the available `msgValue`.
+        ///
+        /// * `requested_value` - Value required by the requests.
+        /// * `msg_value` - Value attached to the call.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC2771ForwarderMismatchedValue(uint256 requested_value, uint256 msg_value);
+
+        /// The request `deadline` has expired.
+        ///
+        /// * `deadline` - Deadline of the request.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC2771ForwarderExpiredRequest(uint48 deadline);
+
+        /// The request target doesn't trust the `forwarder`.
+        ///
+        /// * `target` - Target of the request.
+        /// * `forwarder` - Address of this forwarder.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC2771UntrustfulTarget(address target, address forwarder);
+
+        /// A call to an address target failed.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error FailedCall();
+    }
+}
+
+mod interface {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::prelude::sol_interface;
+    sol_interface! {
+        interface IErc2771Context {
+            function isTrustedForwarder(address forwarder) external view returns (bool);
+        }
+    }
+}
+
+/// An [`Erc2771Forwarder`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// The request `from` doesn't match with the recovered `signer`.
+    InvalidSigner(ERC2771ForwarderInvalidSigner),
+    /// The `requestedValue` doesn't match with the available `msgValue`.
+    MismatchedValue(ERC2771ForwarderMismatchedValue),
+    /// The request `deadline` has expired.
+    ExpiredRequest(ERC2771ForwarderExpiredRequest),
+    /// The request target doesn't trust the `forwarder`.
+    UntrustfulTarget(ERC2771UntrustfulTarget),
+    /// A call to an address target failed.
+    FailedCall(FailedCall),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`Erc2771Forwarder`] contract.
+#[storage]
+pub struct Erc2771Forwarder<T: IEip712Domain + StorageType> {
+    /// Contract implementing [`IEip712Domain`] trait.
+    pub eip712: T,
+    /// Nonces of the signers of forward requests.
+    pub(crate) nonces: Nonces,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl<T: IEip712Domain + StorageType> TopLevelStorage
+    for Erc2771Forwarder<T>
+{
+}
+
+#[public]
+impl<T: IEip712Domain + StorageType> Erc2771Forwarder<T> {
+    /// Returns the unused nonce for the given account.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - The address for which to return the nonce.
+    #[must_use]
+    pub fn nonces(&self, owner: Address) -> U256 {
+        self.nonces.nonces(owner)
+    }
+
+    /// Returns the fields and values that describe the domain separator used
+    /// to sign forward requests, as defined by [ERC-5267].
+    ///
+    /// [ERC-5267]: https://eips.ethereum.org/EIPS/eip-5267
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn eip712_domain(&self) -> Eip712Domain {
+        self.eip712.eip712_domain()
+    }
+
+    /// Returns `true` if a request is valid for a provided `signature` at the
+    /// current block timestamp.
+    ///
+    /// A transaction is considered valid when the target trusts this
+    /// forwarder, the request hasn't expired (deadline is not met), and the
+    /// signer matches the `from` parameter of the signed request.
+    ///
+    /// NOTE: A request may return false here but it won't cause
+    /// [`Self::execute_batch`] to revert if a refund receiver is provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `request` - The signed forward request.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn verify(&mut self, request: ForwardRequestData) -> bool {
+        let (from, to, _, _, deadline, _, _) = &request;
+        self.is_trusted_by_target(*to)
+            && Self::is_active(*deadline)
+            && self.recover_forward_request_signer(&request) == Some(*from)
+    }
+
+    /// Executes a `request` on behalf of `signature`'s signer using the
+    /// [ERC-2771] protocol.
+    ///
+    /// The gas provided to the requested call may not be exactly the amount
+    /// requested, but the call will not run out of gas due to the amount of
+    /// gas provided being insufficient.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `request` - The signed forward request.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::MismatchedValue`] - If `msg::value` doesn't match the
+    ///   requested value.
+    /// * [`Error::UntrustfulTarget`] - If the target doesn't trust this
+    ///   forwarder.
+    /// * [`Error::ExpiredRequest`] - If the request has expired.
+    /// * [`Error::InvalidSigner`] - If the signer doesn't match `from`.
+    /// * [`Error::FailedCall`] - If the requested call reverts.
+    ///
+    /// # Events
+    ///
+    /// * [`ExecutedForwardRequest`].
+    ///
+    /// # Panics
+    ///
+    /// * If the requested call was not given enough gas.
+    ///
+    /// [ERC-2771]: https://eips.ethereum.org/EIPS/eip-2771
+    #[payable]
+    pub fn execute(
+        &mut self,
+        request: ForwardRequestData,
+    ) -> Result<(), Error> {
+        let value = request.2;
+        // We make sure that msg.value and request.value match exactly. If
+        // the request is invalid or the call reverts, this whole function
+        // will revert, ensuring value isn't stuck.
+        if msg::value() != value {
+            return Err(ERC2771ForwarderMismatchedValue {
+                requested_value: value,
+                msg_value: msg::value(),
+            }
+            .into());
+        }
+
+        if !self._execute(request, true)? {
+            return Err(FailedCall {}.into());
+        }
+
+        Ok(())
+    }
+
+    /// Batch version of [`Self::execute`] with optional refunding and atomic
+    /// execution.
+    ///
+    /// In case a batch contains at least one invalid request (see
+    /// [`Self::verify`]), the request will be skipped and the
+    /// `refund_receiver` will receive back the unused requested value at the
+    /// end of the execution. This is done to prevent reverting the entire
+    /// batch when a request is invalid or has already been submitted.
+    ///
+    /// If the `refund_receiver` is [`Address::ZERO`], this function will
+    /// revert when at least one of the requests was not valid instead of
+    /// skipping it. This could be useful if a batch is required to get
+    /// executed atomically (at least at the top-level).
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `requests` - The signed forward requests.
+    /// * `refund_receiver` - Receiver of the value of skipped requests, or
+    ///   [`Address::ZERO`] for atomic execution.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::MismatchedValue`] - If `msg::value` doesn't match the value
+    ///   of all the requests.
+    /// * [`Error::UntrustfulTarget`] - If the batch is atomic and a target
+    ///   doesn't trust this forwarder.
+    /// * [`Error::ExpiredRequest`] - If the batch is atomic and a request has
+    ///   expired.
+    /// * [`Error::InvalidSigner`] - If the batch is atomic and a signer doesn't
+    ///   match `from`.
+    /// * [`Error::FailedCall`] - If the refund couldn't be sent.
+    ///
+    /// # Events
+    ///
+    /// * [`ExecutedForwardRequest`] - For each executed request.
+    ///
+    /// # Panics
+    ///
+    /// * If a requested call was not given enough gas.
+    /// * If the value of the requests overflows [`U256::MAX`].
+    #[payable]
+    pub fn execute_batch(
+        &mut self,
+        requests: Vec<ForwardRequestData>,
+        refund_receiver: Address,
+    ) -> Result<(), Error> {
+        let atomic = refund_receiver.is_zero();
+
+        let mut requests_value = U256::ZERO;
+        let mut refund_value = U256::ZERO;
+
+        for request in requests {
+            let value = request.2;
+            requests_value = requests_value
+                .checked_add(value)
+                .expect("value of the requests should not exceed `U256::MAX`");
+
+            if !self._execute(request, atomic)? {
+                refund_value += value;
+            }
+        }
+
+        // The batch should revert if there's a mismatched msg.value provided
+        // to avoid request value tampering.
+        if requests_value != msg::value() {
+            return Err(ERC2771ForwarderMismatchedValue {
+                requested_value: requests_value,
+                msg_value: msg::value(),
+            }
+            .into());
+        }
+
+        // Some requests with value were invalid (possibly due to
+        // frontrunning). To avoid leaving ETH in the contract this value is
+        // refunded.
+        if !refund_value.is_zero() {
+            call(Call::new_in(self).value(refund_value), refund_receiver, &[])
+                .map_err(|_| FailedCall {})?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: IEip712Domain + StorageType> Erc2771Forwarder<T> {
+    /// Validates and executes a signed request, returning whether the
+    /// requested call succeeded.
+    ///
+    /// Invalid requests are skipped, unless `require_valid_request` is set.
+    /// Valid requests consume a nonce of their signer, even if the requested
+    /// call reverts.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `request` - The signed forward request.
+    /// * `require_valid_request` - Whether to return an error for invalid
+    ///   requests instead of skipping them.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UntrustfulTarget`] - If `require_valid_request` is set and
+    ///   the target doesn't trust this forwarder.
+    /// * [`Error::ExpiredRequest`] - If `require_valid_request` is set and the
+    ///   request has expired.
+    /// * [`Error::InvalidSigner`] - If `require_valid_request` is set and the
+    ///   signer doesn't match `from`.
+    ///
+    /// # Events
+    ///
+    /// * [`ExecutedForwardRequest`] - If the request is valid.
+    ///
+    /// # Panics
+    ///
+    /// * If the requested call was not given enough gas.
+    pub fn _execute(
+        &mut self,
+        request: ForwardRequestData,
+        require_valid_request: bool,
+    ) -> Result<bool, Error> {
+        let (to, deadline) = (request.1, request.4);
+
+        if !self.is_trusted_by_target(to) {
+            if require_valid_request {
+                return Err(ERC2771UntrustfulTarget {
+                    target: to,
+                    forwarder: contract::address(),
+                }
+                .into());
+            }
+            return Ok(false);
+        }
+
+        if !Self::is_active(deadline) {
+            if require_valid_request {
+                return Err(ERC2771ForwarderExpiredRequest { deadline }.into());
+            }
+            return Ok(false);
+        }
+
+        let signer = self.recover_forward_request_signer(&request);
+        let (from, to, value, gas, _, data, _) = request;
+        if signer != Some(from) {
+            if require_valid_request {
+                return Err(ERC2771ForwarderInvalidSigner {
+                    signer: signer.unwrap_or_default(),
+                    from,
+                }
+                .into());
+            }
+            return Ok(false);
+        }
+
+        // Nonce should be used before the call to prevent reusing by
+        // reentrancy.
+        let nonce = self.nonces.use_nonce(from);
+
+        let gas = u64::try_from(gas).unwrap_or(u64::MAX);
+        let calldata = [data.as_slice(), from.as_slice()].concat();
+        let success = RawCall::new_with_value(value)
+            .gas(gas)
+            .call_with_reentrant_handling(to, &calldata)
+            .is_ok();
+
+        Self::check_forwarded_gas(gas);
+
+        evm::log(ExecutedForwardRequest { signer: from, nonce, success });
+
+        Ok(success)
+    }
+
+    /// Returns whether the target trusts this forwarder.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `target` - Target of a forward request.
+    fn is_trusted_by_target(&self, target: Address) -> bool {
+        interface::IErc2771Context::new(target)
+            .is_trusted_forwarder(self, contract::address())
+            .unwrap_or(false)
+    }
+
+    /// Returns whether a request with `deadline` is still executable.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - Deadline of a forward request.
+    fn is_active(deadline: U48) -> bool {
+        U48::from(block::timestamp()) <= deadline
+    }
+
+    /// Returns the signer of `request`, or `None` if its signature is
+    /// invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `request` - The signed forward request.
+    fn recover_forward_request_signer(
+        &mut self,
+        request: &ForwardRequestData,
+    ) -> Option<Address> {
+        let (from, to, value, gas, deadline, data, signature) = request;
+
+        let struct_hash = keccak256(StructHashTuple::abi_encode(&(
+            FORWARD_REQUEST_TYPEHASH,
+            *from,
+            *to,
+            *value,
+            *gas,
+            self.nonces.nonces(*from),
+            *deadline,
+            keccak256(data),
+        )));
+        let hash = self.eip712.hash_typed_data_v4(struct_hash);
+
+        ecdsa::try_recover(self, hash, signature).ok()
+    }
+
+    /// Checks that the requested call was given at least the requested gas.
+    ///
+    /// To avoid insufficient gas griefing attacks, as referenced in
+    /// [EIP-150], the requested call receives at most 63/64 of the gas left.
+    /// If the gas left after the call is below 1/63 of the requested gas, the
+    /// call was not given enough gas, and the whole transaction must fail, so
+    /// that the relayer can't make a valid request revert on purpose.
+    ///
+    /// # Arguments
+    ///
+    /// * `gas` - Gas requested for the call.
+    ///
+    /// # Panics
+    ///
+    /// * If the requested call was not given enough gas.
+    ///
+    /// [EIP-150]: https://eips.ethereum.org/EIPS/eip-150
+    fn check_forwarded_gas(gas: u64) {
+        assert!(
+            evm::gas_left() >= gas / 63,
+            "forwarded call should be given the requested gas"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{aliases::U48, Address, U256};
+    use motsu::prelude::*;
+    use stylus_sdk::{abi::Bytes, prelude::*};
+
+    use super::{
+        ERC2771ForwarderExpiredRequest, ERC2771ForwarderMismatchedValue,
+        ERC2771UntrustfulTarget, Erc2771Forwarder, Error, ForwardRequestData,
+    };
+    use crate::{
+        metatx::erc2771_context::Erc2771Context,
+        utils::cryptography::eip712::IEip712,
+    };
+
+    #[storage]
+    struct Eip712;
+
+    impl IEip712 for Eip712 {
+        const NAME: &'static str = "Erc2771Forwarder";
+        const VERSION: &'static str = "1";
+    }
+
+    type Forwarder = Erc2771Forwarder<Eip712>;
+
+    #[storage]
+    struct RecipientMock {
+        #[borrow]
+        erc2771: Erc2771Context,
+    }
+
+    unsafe impl TopLevelStorage for RecipientMock {}
+
+    #[public]
+    #[inherit(Erc2771Context)]
+    impl RecipientMock {}
+
+    fn request(
+        from: Address,
+        to: Address,
+        value: U256,
+        deadline: U48,
+    ) -> ForwardRequestData {
+        (
+            from,
+            to,
+            value,
+            U256::ZERO,
+            deadline,
+            Bytes::from(vec![]),
+            Bytes::from(vec![0; 65]),
+        )
+    }
+
+    fn trust(recipient: &Contract<RecipientMock>, forwarder: Address) {
+        recipient.init(Address::ZERO, |recipient| {
+            recipient.erc2771._set_trusted_forwarder(forwarder);
+        });
+    }
+
+    #[motsu::test]
+    fn nonces_start_at_zero(forwarder: Contract<Forwarder>, alice: Address) {
+        assert_eq!(forwarder.sender(alice).nonces(alice), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn eip712_domain(forwarder: Contract<Forwarder>, alice: Address) {
+        let (_, name, version, _, verifying_contract, ..) =
+            forwarder.sender(alice).eip712_domain();
+        assert_eq!(name, Eip712::NAME);
+        assert_eq!(version, Eip712::VERSION);
+        assert_eq!(verifying_contract, forwarder.address());
+    }
+
+    #[motsu::test]
+    fn verify_rejects_untrustful_target(
+        forwarder: Contract<Forwarder>,
+        recipient: Contract<RecipientMock>,
+        alice: Address,
+    ) {
+        let request = request(alice, recipient.address(), U256::ZERO, U48::MAX);
+        assert!(!forwarder.sender(alice).verify(request));
+    }
+
+    #[motsu::test]
+    fn verify_rejects_expired_request(
+        forwarder: Contract<Forwarder>,
+        recipient: Contract<RecipientMock>,
+        alice: Address,
+    ) {
+        trust(&recipient, forwarder.address());
+
+        let request =
+            request(alice, recipient.address(), U256::ZERO, U48::ZERO);
+        assert!(!forwarder.sender(alice).verify(request));
+    }
+
+    #[motsu::test]
+    fn execute_reverts_on_mismatched_value(
+        forwarder: Contract<Forwarder>,
+        recipient: Contract<RecipientMock>,
+        alice: Address,
+    ) {
+        trust(&recipient, forwarder.address());
+
+        let value = U256::from(1);
+        let err = forwarder
+            .sender(alice)
+            .execute(request(alice, recipient.address(), value, U48::MAX))
+            .motsu_expect_err("should revert on mismatched value");
+
+        assert!(matches!(
+            err,
+            Error::MismatchedValue(ERC2771ForwarderMismatchedValue {
+                requested_value,
+                msg_value,
+            }) if requested_value == value && msg_value.is_zero()
+        ));
+    }
+
+    #[motsu::test]
+    fn execute_reverts_on_untrustful_target(
+        forwarder: Contract<Forwarder>,
+        recipient: Contract<RecipientMock>,
+        alice: Address,
+    ) {
+        let err = forwarder
+            .sender(alice)
+            .execute(request(alice, recipient.address(), U256::ZERO, U48::MAX))
+            .motsu_expect_err("should revert on untrustful target");
+
+        assert!(matches!(
+            err,
+            Error::UntrustfulTarget(ERC2771UntrustfulTarget {
+                target,
+                forwarder: forwarder_address,
+            }) if target == recipient.address()
+                && forwarder_address == forwarder.address()
+        ));
+    }
+
+    #[motsu::test]
+    fn execute_reverts_on_expired_request(
+        forwarder: Contract<Forwarder>,
+        recipient: Contract<RecipientMock>,
+        alice: Address,
+    ) {
+        trust(&recipient, forwarder.address());
+
+        let err = forwarder
+            .sender(alice)
+            .execute(request(alice, recipient.address(), U256::ZERO, U48::ZERO))
+            .motsu_expect_err("should revert on expired request");
+
+        assert!(matches!(
+            err,
+            Error::ExpiredRequest(ERC2771ForwarderExpiredRequest { deadline })
+                if deadline.is_zero()
+        ));
+    }
+
+    #[motsu::test]
+    fn execute_batch_skips_invalid_requests(
+        forwarder: Contract<Forwarder>,
+        recipient: Contract<RecipientMock>,
+        untrustful: Contract<RecipientMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        trust(&recipient, forwarder.address());
+
+        let requests = vec![
+            request(alice, recipient.address(), U256::ZERO, U48::ZERO),
+            request(alice, untrustful.address(), U256::ZERO, U48::MAX),
+        ];
+        forwarder
+            .sender(alice)
+            .execute_batch(requests, bob)
+            .motsu_expect("should skip invalid requests");
+
+        assert_eq!(forwarder.sender(alice).nonces(alice), U256::ZERO);
+    }
+
+    #[motsu::test]
+    fn execute_batch_reverts_on_invalid_request_when_atomic(
+        forwarder: Contract<Forwarder>,
+        recipient: Contract<RecipientMock>,
+        alice: Address,
+    ) {
+        trust(&recipient, forwarder.address());
+
+        let requests =
+            vec![request(alice, recipient.address(), U256::ZERO, U48::ZERO)];
+        let err = forwarder
+            .sender(alice)
+            .execute_batch(requests, Address::ZERO)
+            .motsu_expect_err("should revert on expired request");
+
+        assert!(matches!(err, Error::ExpiredRequest(_)));
+    }
+
+    #[motsu::test]
+    fn execute_batch_reverts_on_mismatched_value(
+        forwarder: Contract<Forwarder>,
+        recipient: Contract<RecipientMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let requests =
+            vec![request(alice, recipient.address(), U256::from(1), U48::MAX)];
+        let err = forwarder
+            .sender(alice)
+            .execute_batch(requests, bob)
+            .motsu_expect_err("should revert on mismatched value");
+
+        assert!(matches!(err, Error::MismatchedValue(_)));
+    }
+}
@@ -0,0 +1,15 @@
+//! Meta-transactions, as specified by [ERC-2771].
+//!
+//! * [`erc2771_context::Erc2771Context`] - Component that lets a contract
+//!   accept calls relayed by a trusted forwarder, on behalf of their original
+//!   sender.
+//! * [`erc2771_forwarder::Erc2771Forwarder`] - Forwarder that verifies and
+//!   executes signed [EIP-712] requests.
+//!
+//! [ERC-2771]: https://eips.ethereum.org/EIPS/eip-2771
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+pub mod erc2771_context;
+pub mod erc2771_forwarder;
+
+pub use erc2771_context::Erc2771Context;
+pub use erc2771_forwarder::Erc2771Forwarder;
@@ -0,0 +1,27 @@
+//! Proxies that resolve their implementation from a beacon.
+//!
+//! A beacon is a contract that returns the address of an implementation. Many
+//! [`BeaconProxy`] instances can point at the same [`UpgradeableBeacon`], so
+//! that all of them are upgraded at once when the beacon is upgraded.
+pub mod proxy;
+pub mod upgradeable;
+
+pub use interface::IBeaconInterface;
+pub use proxy::BeaconProxy;
+pub use upgradeable::UpgradeableBeacon;
+
+mod interface {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::prelude::sol_interface;
+    sol_interface! {
+        /// Interface that a beacon must implement, so that a
+        /// [`super::BeaconProxy`] can resolve its implementation from it.
+        interface IBeaconInterface {
+            function implementation() external view returns (address);
+        }
+    }
+}
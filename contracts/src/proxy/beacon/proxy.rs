@@ -0,0 +1,183 @@
+//! This contract implements a proxy that gets the implementation address for
+//! each call from an [`super::UpgradeableBeacon`].
+//!
+//! The beacon address is stored in the [`BEACON_SLOT`] specified by
+//! [ERC-1967], so that it doesn't conflict with the storage layout of the
+//! implementation behind the proxy.
+//!
+//! The beacon address has to be written when the proxy is deployed, either in
+//! the contract's Solidity constructor, or by calling
+//! [`Erc1967Utils::upgrade_beacon_to_and_call`]. It can't be changed
+//! afterwards through the proxy.
+//!
+//! [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+//! [`BEACON_SLOT`]: crate::proxy::erc1967::utils::BEACON_SLOT
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::Address;
+use stylus_sdk::{prelude::*, ArbResult};
+
+use crate::proxy::{beacon::IBeaconInterface, erc1967::Erc1967Utils, IProxy};
+
+/// State of a [`BeaconProxy`] contract.
+///
+/// The proxy has no regular storage fields, since they would conflict with
+/// the storage of the implementation. The beacon address lives in the
+/// [ERC-1967] beacon slot managed by [`Erc1967Utils`].
+///
+/// [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+#[storage]
+pub struct BeaconProxy {}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for BeaconProxy {}
+
+#[public]
+impl BeaconProxy {
+    /// Delegates every call that doesn't match a function of the contract
+    /// to the implementation returned by the beacon.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `calldata` - The calldata of the call.
+    ///
+    /// # Errors
+    ///
+    /// * If the call to the beacon or the delegated call reverts, its revert
+    ///   data is returned.
+    #[fallback]
+    #[payable]
+    fn fallback(&mut self, calldata: &[u8]) -> ArbResult {
+        self.do_fallback(calldata)
+    }
+}
+
+impl BeaconProxy {
+    /// Returns the beacon.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn get_beacon(&self) -> Address {
+        Erc1967Utils::get_beacon()
+    }
+}
+
+impl IProxy for BeaconProxy {
+    fn implementation(&self) -> Result<Address, Vec<u8>> {
+        let beacon = IBeaconInterface::new(self.get_beacon());
+        Ok(beacon.implementation(self)?)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::Address;
+    use motsu::prelude::*;
+    use stylus_sdk::abi::Bytes;
+
+    use super::BeaconProxy;
+    use crate::{
+        proxy::{
+            beacon::{upgradeable::IUpgradeableBeacon, UpgradeableBeacon},
+            erc1967::{
+                utils::{BeaconUpgraded, Error},
+                Erc1967Utils,
+            },
+            IProxy,
+        },
+        token::erc20::Erc20,
+    };
+
+    fn init(
+        proxy: &Contract<BeaconProxy>,
+        beacon: &Contract<UpgradeableBeacon>,
+        implementation: &Contract<Erc20>,
+        alice: Address,
+    ) {
+        let implementation = implementation.address();
+        beacon.init(alice, |beacon| {
+            beacon.ownable._transfer_ownership(alice);
+            beacon
+                ._set_implementation(implementation)
+                .expect("should set the implementation");
+        });
+
+        let beacon = beacon.address();
+        proxy.init(alice, |proxy| {
+            Erc1967Utils::upgrade_beacon_to_and_call(
+                proxy,
+                beacon,
+                &Bytes::from(vec![]),
+            )
+            .expect("should set the beacon");
+        });
+    }
+
+    #[motsu::test]
+    fn sets_beacon(
+        proxy: Contract<BeaconProxy>,
+        beacon: Contract<UpgradeableBeacon>,
+        implementation: Contract<Erc20>,
+        alice: Address,
+    ) {
+        init(&proxy, &beacon, &implementation, alice);
+
+        assert_eq!(proxy.sender(alice).get_beacon(), beacon.address());
+        assert_eq!(
+            proxy.sender(alice).implementation().motsu_unwrap(),
+            implementation.address()
+        );
+        proxy.assert_emitted(&BeaconUpgraded { beacon: beacon.address() });
+    }
+
+    #[motsu::test]
+    fn upgrade_reverts_when_beacon_has_no_code(
+        proxy: Contract<BeaconProxy>,
+        alice: Address,
+        bob: Address,
+    ) {
+        proxy.init(alice, |proxy| {
+            let err = Erc1967Utils::upgrade_beacon_to_and_call(
+                proxy,
+                bob,
+                &Bytes::from(vec![]),
+            )
+            .expect_err("should reject an account without code");
+            assert!(matches!(
+                err,
+                Error::InvalidBeacon(
+                    crate::proxy::erc1967::utils::ERC1967InvalidBeacon {
+                        beacon
+                    }
+                ) if beacon == bob
+            ));
+        });
+        assert_eq!(proxy.sender(alice).get_beacon(), Address::ZERO);
+    }
+
+    #[motsu::test]
+    fn implementation_follows_beacon_upgrades(
+        proxy: Contract<BeaconProxy>,
+        beacon: Contract<UpgradeableBeacon>,
+        implementation: Contract<Erc20>,
+        new_implementation: Contract<Erc20>,
+        alice: Address,
+    ) {
+        init(&proxy, &beacon, &implementation, alice);
+
+        beacon
+            .sender(alice)
+            .upgrade_to(new_implementation.address())
+            .motsu_unwrap();
+
+        assert_eq!(
+            proxy.sender(alice).implementation().motsu_unwrap(),
+            new_implementation.address()
+        );
+    }
+}
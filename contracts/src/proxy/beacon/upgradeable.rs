@@ -0,0 +1,369 @@
+//! This contract is used in conjunction with one or more instances of
+//! [`super::BeaconProxy`] to determine their implementation contract, which is
+//! where they will delegate all function calls.
+//!
+//! An owner is able to change the implementation the beacon points to, thus
+//! upgrading the proxies that use this beacon.
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{Address, FixedBytes};
+use openzeppelin_stylus_proc::interface_id;
+pub use sol::*;
+use stylus_sdk::{call::MethodError, evm, prelude::*, storage::StorageAddress};
+
+use crate::{
+    access::ownable::{self, IOwnable, Ownable},
+    utils::introspection::erc165::{Erc165, IErc165},
+};
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the implementation returned by the beacon is changed.
+        ///
+        /// * `implementation` - Address of the new implementation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event Upgraded(address indexed implementation);
+    }
+
+    sol! {
+        /// The `implementation` of the beacon is invalid.
+        ///
+        /// * `implementation` - Address of the invalid implementation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error BeaconInvalidImplementation(address implementation);
+    }
+}
+
+/// An [`UpgradeableBeacon`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// The `implementation` of the beacon is invalid.
+    InvalidImplementation(BeaconInvalidImplementation),
+    /// Error type from [`Ownable`] contract [`ownable::Error`].
+    Ownable(ownable::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`UpgradeableBeacon`] contract.
+#[storage]
+pub struct UpgradeableBeacon {
+    /// Address of the current implementation.
+    pub(crate) implementation: StorageAddress,
+    /// [`Ownable`] contract.
+    // We leave the parent [`Ownable`] contract instance public, so that
+    // inheritting contract have access to its internal functions.
+    pub ownable: Ownable,
+}
+
+/// Required interface of an [`UpgradeableBeacon`] compliant contract.
+#[interface_id]
+pub trait IUpgradeableBeacon {
+    /// The error type associated to this trait implementation.
+    type Error: Into<alloc::vec::Vec<u8>>;
+
+    /// Returns the current implementation address.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn implementation(&self) -> Address;
+
+    /// Upgrades the beacon to a new implementation. Can only be called by the
+    /// current owner.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_implementation` - Address of the new implementation.
+    ///
+    /// # Errors
+    ///
+    /// * [`ownable::Error::UnauthorizedAccount`] - If called by any account
+    ///   other than the owner.
+    /// * [`Error::InvalidImplementation`] - If `new_implementation` is not a
+    ///   contract.
+    ///
+    /// # Events
+    ///
+    /// * [`Upgraded`].
+    fn upgrade_to(
+        &mut self,
+        new_implementation: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Returns the address of the current owner.
+    ///
+    /// Re-export of [`Ownable::owner`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn owner(&self) -> Address;
+
+    /// Transfers ownership of the contract to a new account (`new_owner`). Can
+    /// only be called by the current owner.
+    ///
+    /// Re-export of [`Ownable::transfer_ownership`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_owner` - The next owner of this contract.
+    ///
+    /// # Errors
+    ///
+    /// * [`ownable::Error::UnauthorizedAccount`] - If called by any account
+    ///   other than the owner.
+    /// * [`ownable::Error::InvalidOwner`] - If `new_owner` is the
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`ownable::OwnershipTransferred`].
+    fn transfer_ownership(
+        &mut self,
+        new_owner: Address,
+    ) -> Result<(), Self::Error>;
+
+    /// Leaves the contract without owner. It will not be possible to call
+    /// [`Ownable::only_owner`] functions. Can only be called by the current
+    /// owner.
+    ///
+    /// Re-export of [`Ownable::renounce_ownership`].
+    ///
+    /// NOTE: Renouncing ownership will leave the beacon without an owner,
+    /// thereby freezing the implementation of all its proxies.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`ownable::Error::UnauthorizedAccount`] - If not called by the owner.
+    ///
+    /// # Events
+    ///
+    /// * [`ownable::OwnershipTransferred`].
+    fn renounce_ownership(&mut self) -> Result<(), Self::Error>;
+}
+
+#[public]
+impl IUpgradeableBeacon for UpgradeableBeacon {
+    type Error = Error;
+
+    fn implementation(&self) -> Address {
+        self.implementation.get()
+    }
+
+    fn upgrade_to(
+        &mut self,
+        new_implementation: Address,
+    ) -> Result<(), Self::Error> {
+        self.ownable.only_owner()?;
+        self._set_implementation(new_implementation)
+    }
+
+    fn owner(&self) -> Address {
+        self.ownable.owner()
+    }
+
+    fn transfer_ownership(
+        &mut self,
+        new_owner: Address,
+    ) -> Result<(), Self::Error> {
+        Ok(self.ownable.transfer_ownership(new_owner)?)
+    }
+
+    fn renounce_ownership(&mut self) -> Result<(), Self::Error> {
+        Ok(self.ownable.renounce_ownership()?)
+    }
+}
+
+impl UpgradeableBeacon {
+    /// Sets the implementation contract address for this beacon.
+    /// Internal function without access restriction.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_implementation` - Address of the new implementation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidImplementation`] - If `new_implementation` is not a
+    ///   contract.
+    ///
+    /// # Events
+    ///
+    /// * [`Upgraded`].
+    pub fn _set_implementation(
+        &mut self,
+        new_implementation: Address,
+    ) -> Result<(), Error> {
+        if !new_implementation.has_code() {
+            return Err(Error::InvalidImplementation(
+                BeaconInvalidImplementation {
+                    implementation: new_implementation,
+                },
+            ));
+        }
+        self.implementation.set(new_implementation);
+        evm::log(Upgraded { implementation: new_implementation });
+        Ok(())
+    }
+}
+
+impl IErc165 for UpgradeableBeacon {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IUpgradeableBeacon>::INTERFACE_ID
+            == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::Address;
+    use motsu::prelude::*;
+    use stylus_sdk::prelude::TopLevelStorage;
+
+    use super::{
+        BeaconInvalidImplementation, Error, IUpgradeableBeacon,
+        UpgradeableBeacon, Upgraded,
+    };
+    use crate::{
+        access::ownable::{self, OwnableUnauthorizedAccount},
+        token::erc20::Erc20,
+        utils::introspection::erc165::IErc165,
+    };
+
+    unsafe impl TopLevelStorage for UpgradeableBeacon {}
+
+    fn init(
+        beacon: &Contract<UpgradeableBeacon>,
+        implementation: &Contract<Erc20>,
+        alice: Address,
+    ) {
+        let implementation = implementation.address();
+        beacon.init(alice, |beacon| {
+            beacon.ownable._transfer_ownership(alice);
+            beacon
+                ._set_implementation(implementation)
+                .expect("should set the implementation");
+        });
+    }
+
+    #[motsu::test]
+    fn reads_implementation(
+        beacon: Contract<UpgradeableBeacon>,
+        implementation: Contract<Erc20>,
+        alice: Address,
+    ) {
+        init(&beacon, &implementation, alice);
+
+        assert_eq!(
+            beacon.sender(alice).implementation(),
+            implementation.address()
+        );
+        assert_eq!(beacon.sender(alice).owner(), alice);
+    }
+
+    #[motsu::test]
+    fn owner_upgrades_implementation(
+        beacon: Contract<UpgradeableBeacon>,
+        implementation: Contract<Erc20>,
+        new_implementation: Contract<Erc20>,
+        alice: Address,
+    ) {
+        init(&beacon, &implementation, alice);
+
+        beacon
+            .sender(alice)
+            .upgrade_to(new_implementation.address())
+            .motsu_unwrap();
+
+        assert_eq!(
+            beacon.sender(alice).implementation(),
+            new_implementation.address()
+        );
+        beacon.assert_emitted(&Upgraded {
+            implementation: new_implementation.address(),
+        });
+    }
+
+    #[motsu::test]
+    fn upgrade_reverts_when_not_owner(
+        beacon: Contract<UpgradeableBeacon>,
+        implementation: Contract<Erc20>,
+        new_implementation: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&beacon, &implementation, alice);
+
+        let err = beacon
+            .sender(bob)
+            .upgrade_to(new_implementation.address())
+            .motsu_unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::Ownable(ownable::Error::UnauthorizedAccount(
+                OwnableUnauthorizedAccount { account }
+            )) if account == bob
+        ));
+        assert_eq!(
+            beacon.sender(alice).implementation(),
+            implementation.address()
+        );
+    }
+
+    #[motsu::test]
+    fn upgrade_reverts_when_implementation_has_no_code(
+        beacon: Contract<UpgradeableBeacon>,
+        implementation: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+    ) {
+        init(&beacon, &implementation, alice);
+
+        let err = beacon.sender(alice).upgrade_to(bob).motsu_unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::InvalidImplementation(BeaconInvalidImplementation {
+                implementation
+            }) if implementation == bob
+        ));
+    }
+
+    #[motsu::test]
+    fn interface_id() {
+        let actual = <UpgradeableBeacon as IUpgradeableBeacon>::INTERFACE_ID;
+        let expected = 0x6431258b;
+        assert_eq!(actual, expected);
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(UpgradeableBeacon::supports_interface(
+            <UpgradeableBeacon as IUpgradeableBeacon>::INTERFACE_ID.into()
+        ));
+
+        let fake_interface_id = 0x12345678u32;
+        assert!(!UpgradeableBeacon::supports_interface(
+            fake_interface_id.into()
+        ));
+    }
+}
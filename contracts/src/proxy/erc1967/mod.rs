@@ -0,0 +1,166 @@
+//! This contract implements an upgradeable proxy.
+//!
+//! It is upgradeable because calls are delegated to an implementation address
+//! that can be changed. This address is stored in storage in the location
+//! specified by [ERC-1967], so that it doesn't conflict with the storage layout
+//! of the implementation behind the proxy.
+//!
+//! The implementation address has to be written to
+//! [`utils::IMPLEMENTATION_SLOT`] when the proxy is deployed, either in the
+//! contract's Solidity constructor, or by calling
+//! [`Erc1967Utils::upgrade_to_and_call`]. Upgrades are then performed by the
+//! implementation itself, see [`crate::proxy::utils::UupsUpgradeable`].
+//!
+//! [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::Address;
+use stylus_sdk::{prelude::*, ArbResult};
+
+use crate::proxy::IProxy;
+
+pub mod utils;
+
+pub use utils::Erc1967Utils;
+
+/// State of an [`Erc1967Proxy`] contract.
+///
+/// The proxy has no regular storage fields, since they would conflict with
+/// the storage of the implementation. Its state lives in the [ERC-1967]
+/// slots managed by [`Erc1967Utils`].
+///
+/// [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+#[storage]
+pub struct Erc1967Proxy {}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for Erc1967Proxy {}
+
+#[public]
+impl Erc1967Proxy {
+    /// Delegates every call that doesn't match a function of the contract
+    /// to the current implementation.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `calldata` - The calldata of the call.
+    ///
+    /// # Errors
+    ///
+    /// * If the delegated call reverts, its revert data is returned.
+    #[fallback]
+    #[payable]
+    fn fallback(&mut self, calldata: &[u8]) -> ArbResult {
+        self.do_fallback(calldata)
+    }
+}
+
+impl IProxy for Erc1967Proxy {
+    fn implementation(&self) -> Result<Address, Vec<u8>> {
+        Ok(Erc1967Utils::get_implementation())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{keccak256, Address, B256, U256};
+    use motsu::prelude::*;
+    use stylus_sdk::abi::Bytes;
+
+    use super::{Erc1967Proxy, Erc1967Utils};
+    use crate::{
+        proxy::{
+            erc1967::utils::{
+                Error, Upgraded, ADMIN_SLOT, BEACON_SLOT, IMPLEMENTATION_SLOT,
+            },
+            IProxy,
+        },
+        token::erc20::Erc20,
+    };
+
+    fn init(
+        proxy: &Contract<Erc1967Proxy>,
+        implementation: &Contract<Erc20>,
+        alice: Address,
+    ) {
+        let implementation = implementation.address();
+        proxy.init(alice, |_| {
+            Erc1967Utils::upgrade_to_and_call(
+                implementation,
+                &Bytes::from(vec![]),
+            )
+            .expect("should set the implementation");
+        });
+    }
+
+    #[test]
+    fn slots_match_erc1967() {
+        let slot = |id: &str| {
+            B256::from(U256::from_be_bytes(keccak256(id).0) - U256::from(1))
+        };
+        assert_eq!(IMPLEMENTATION_SLOT, slot("eip1967.proxy.implementation"));
+        assert_eq!(ADMIN_SLOT, slot("eip1967.proxy.admin"));
+        assert_eq!(BEACON_SLOT, slot("eip1967.proxy.beacon"));
+    }
+
+    #[motsu::test]
+    fn sets_implementation(
+        proxy: Contract<Erc1967Proxy>,
+        implementation: Contract<Erc20>,
+        alice: Address,
+    ) {
+        init(&proxy, &implementation, alice);
+
+        assert_eq!(
+            proxy.sender(alice).implementation().motsu_unwrap(),
+            implementation.address()
+        );
+        proxy.assert_emitted(&Upgraded {
+            implementation: implementation.address(),
+        });
+    }
+
+    #[motsu::test]
+    fn upgrade_reverts_when_implementation_has_no_code(
+        proxy: Contract<Erc1967Proxy>,
+        alice: Address,
+        bob: Address,
+    ) {
+        proxy.init(alice, |_| {
+            let err =
+                Erc1967Utils::upgrade_to_and_call(bob, &Bytes::from(vec![]))
+                    .expect_err("should reject an account without code");
+            assert!(matches!(
+                err,
+                Error::InvalidImplementation(
+                    super::utils::ERC1967InvalidImplementation {
+                        implementation
+                    }
+                ) if implementation == bob
+            ));
+        });
+        assert_eq!(
+            proxy.sender(alice).implementation().motsu_unwrap(),
+            Address::ZERO
+        );
+    }
+
+    #[motsu::test]
+    fn changes_admin(proxy: Contract<Erc1967Proxy>, alice: Address) {
+        proxy.init(alice, |_| {
+            let err = Erc1967Utils::change_admin(Address::ZERO)
+                .expect_err("should reject the zero address");
+            assert!(matches!(err, Error::InvalidAdmin(_)));
+
+            Erc1967Utils::change_admin(alice).expect("should change admin");
+            assert_eq!(Erc1967Utils::get_admin(), alice);
+        });
+        proxy.assert_emitted(&super::utils::AdminChanged {
+            previous_admin: Address::ZERO,
+            new_admin: alice,
+        });
+    }
+}
@@ -0,0 +1,333 @@
+//! This library provides getters and event emitting update functions for
+//! [ERC-1967] slots.
+//!
+//! [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+use alloy_primitives::{b256, Address, B256};
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    call::{self, MethodError, RawCall},
+    evm, msg,
+    prelude::*,
+};
+
+use crate::{
+    proxy::beacon::IBeaconInterface,
+    utils::{storage_slot::StorageSlot, ReentrantCallHandler},
+};
+
+/// Storage slot with the address of the current implementation.
+/// This is the keccak-256 hash of "eip1967.proxy.implementation" subtracted
+/// by 1.
+pub const IMPLEMENTATION_SLOT: B256 =
+    b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+
+/// Storage slot with the admin of the contract.
+/// This is the keccak-256 hash of "eip1967.proxy.admin" subtracted by 1.
+pub const ADMIN_SLOT: B256 =
+    b256!("b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103");
+
+/// Storage slot with the address of the beacon of the proxy.
+/// This is the keccak-256 hash of "eip1967.proxy.beacon" subtracted by 1.
+pub const BEACON_SLOT: B256 =
+    b256!("a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50");
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the implementation is upgraded.
+        ///
+        /// * `implementation` - Address of the new implementation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event Upgraded(address indexed implementation);
+
+        /// Emitted when the admin account has changed.
+        ///
+        /// * `previous_admin` - Address of the previous admin.
+        /// * `new_admin` - Address of the new admin.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event AdminChanged(address previous_admin, address new_admin);
+
+        /// Emitted when the beacon is changed.
+        ///
+        /// * `beacon` - Address of the new beacon.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event BeaconUpgraded(address indexed beacon);
+    }
+
+    sol! {
+        /// The `implementation` of the proxy is invalid.
+        ///
+        /// * `implementation` - Address of the invalid implementation.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC1967InvalidImplementation(address implementation);
+
+        /// The `admin` of the proxy is invalid.
+        ///
+        /// * `admin` - Address of the invalid admin.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC1967InvalidAdmin(address admin);
+
+        /// The `beacon` of the proxy is invalid.
+        ///
+        /// * `beacon` - Address of the invalid beacon.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC1967InvalidBeacon(address beacon);
+
+        /// An upgrade function sees `msg.value > 0` that may be lost.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC1967NonPayable();
+    }
+}
+
+/// An [`Erc1967Utils`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// The `implementation` of the proxy is invalid.
+    InvalidImplementation(ERC1967InvalidImplementation),
+    /// The `admin` of the proxy is invalid.
+    InvalidAdmin(ERC1967InvalidAdmin),
+    /// The `beacon` of the proxy is invalid.
+    InvalidBeacon(ERC1967InvalidBeacon),
+    /// An upgrade function sees `msg.value > 0` that may be lost.
+    NonPayable(ERC1967NonPayable),
+    /// A call to the beacon or the delegated call to the new implementation
+    /// failed.
+    FailedCall(call::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// Getters and event emitting update functions for [ERC-1967] slots.
+///
+/// [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+pub struct Erc1967Utils;
+
+impl Erc1967Utils {
+    /// Returns the current implementation address.
+    #[must_use]
+    pub fn get_implementation() -> Address {
+        StorageSlot::get_address(IMPLEMENTATION_SLOT)
+    }
+
+    /// Performs implementation upgrade with additional setup call if data is
+    /// nonempty. This function is payable only if the setup call is
+    /// performed, otherwise `msg.value` is rejected to avoid stuck value in
+    /// the contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_implementation` - Address of the new implementation.
+    /// * `data` - Calldata of the setup call, delegated to
+    ///   `new_implementation`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidImplementation`] - If `new_implementation` has no
+    ///   code.
+    /// * [`Error::FailedCall`] - If the setup call reverts.
+    /// * [`Error::NonPayable`] - If `data` is empty and `msg.value` is not
+    ///   zero.
+    ///
+    /// # Events
+    ///
+    /// * [`Upgraded`].
+    pub fn upgrade_to_and_call(
+        new_implementation: Address,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        Erc1967Utils::set_implementation(new_implementation)?;
+        evm::log(Upgraded { implementation: new_implementation });
+
+        if data.is_empty() {
+            Erc1967Utils::check_non_payable()
+        } else {
+            RawCall::new_delegate()
+                .call_with_reentrant_handling(new_implementation, data)
+                .map_err(call::Error::Revert)?;
+            Ok(())
+        }
+    }
+
+    /// Returns the current admin.
+    #[must_use]
+    pub fn get_admin() -> Address {
+        StorageSlot::get_address(ADMIN_SLOT)
+    }
+
+    /// Changes the admin of the proxy.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_admin` - Address of the new admin.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidAdmin`] - If `new_admin` is [`Address::ZERO`].
+    ///
+    /// # Events
+    ///
+    /// * [`AdminChanged`].
+    pub fn change_admin(new_admin: Address) -> Result<(), Error> {
+        let previous_admin = Erc1967Utils::get_admin();
+        Erc1967Utils::set_admin(new_admin)?;
+        evm::log(AdminChanged { previous_admin, new_admin });
+        Ok(())
+    }
+
+    /// Returns the current beacon.
+    #[must_use]
+    pub fn get_beacon() -> Address {
+        StorageSlot::get_address(BEACON_SLOT)
+    }
+
+    /// Changes the beacon and triggers a setup call if data is nonempty.
+    /// This function is payable only if the setup call is performed,
+    /// otherwise `msg.value` is rejected to avoid stuck value in the
+    /// contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Access to the contract's state, used to call the beacon.
+    /// * `new_beacon` - Address of the new beacon.
+    /// * `data` - Calldata of the setup call, delegated to the implementation
+    ///   returned by `new_beacon`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidBeacon`] - If `new_beacon` has no code.
+    /// * [`Error::InvalidImplementation`] - If the implementation returned by
+    ///   `new_beacon` has no code.
+    /// * [`Error::FailedCall`] - If the call to `new_beacon` or the setup call
+    ///   reverts.
+    /// * [`Error::NonPayable`] - If `data` is empty and `msg.value` is not
+    ///   zero.
+    ///
+    /// # Events
+    ///
+    /// * [`BeaconUpgraded`].
+    pub fn upgrade_beacon_to_and_call(
+        context: &mut impl TopLevelStorage,
+        new_beacon: Address,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        let implementation = Erc1967Utils::set_beacon(context, new_beacon)?;
+        evm::log(BeaconUpgraded { beacon: new_beacon });
+
+        if data.is_empty() {
+            Erc1967Utils::check_non_payable()
+        } else {
+            RawCall::new_delegate()
+                .call_with_reentrant_handling(implementation, data)
+                .map_err(call::Error::Revert)?;
+            Ok(())
+        }
+    }
+}
+
+impl Erc1967Utils {
+    /// Stores a new address in the ERC-1967 implementation slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_implementation` - Address of the new implementation.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidImplementation`] - If `new_implementation` has no
+    ///   code.
+    fn set_implementation(new_implementation: Address) -> Result<(), Error> {
+        if !new_implementation.has_code() {
+            return Err(Error::InvalidImplementation(
+                ERC1967InvalidImplementation {
+                    implementation: new_implementation,
+                },
+            ));
+        }
+        StorageSlot::set_address(IMPLEMENTATION_SLOT, new_implementation);
+        Ok(())
+    }
+
+    /// Stores a new address in the ERC-1967 admin slot.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_admin` - Address of the new admin.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidAdmin`] - If `new_admin` is [`Address::ZERO`].
+    fn set_admin(new_admin: Address) -> Result<(), Error> {
+        if new_admin.is_zero() {
+            return Err(Error::InvalidAdmin(ERC1967InvalidAdmin {
+                admin: Address::ZERO,
+            }));
+        }
+        StorageSlot::set_address(ADMIN_SLOT, new_admin);
+        Ok(())
+    }
+
+    /// Stores a new beacon in the ERC-1967 beacon slot, and returns the
+    /// implementation it currently points to.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - Access to the contract's state, used to call the beacon.
+    /// * `new_beacon` - Address of the new beacon.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidBeacon`] - If `new_beacon` has no code.
+    /// * [`Error::InvalidImplementation`] - If the implementation returned by
+    ///   `new_beacon` has no code.
+    /// * [`Error::FailedCall`] - If the call to `new_beacon` reverts.
+    fn set_beacon(
+        context: &mut impl TopLevelStorage,
+        new_beacon: Address,
+    ) -> Result<Address, Error> {
+        if !new_beacon.has_code() {
+            return Err(Error::InvalidBeacon(ERC1967InvalidBeacon {
+                beacon: new_beacon,
+            }));
+        }
+
+        StorageSlot::set_address(BEACON_SLOT, new_beacon);
+
+        let implementation =
+            IBeaconInterface::new(new_beacon).implementation(&*context)?;
+        if !implementation.has_code() {
+            return Err(Error::InvalidImplementation(
+                ERC1967InvalidImplementation { implementation },
+            ));
+        }
+        Ok(implementation)
+    }
+
+    /// Reverts if `msg.value` is not zero. It can be used to avoid
+    /// `msg.value` stuck in the contract if an upgrade doesn't perform an
+    /// initialization call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::NonPayable`] - If `msg.value` is not zero.
+    fn check_non_payable() -> Result<(), Error> {
+        if msg::value().is_zero() {
+            Ok(())
+        } else {
+            Err(Error::NonPayable(ERC1967NonPayable {}))
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Proxy contracts and upgradeability utilities.
+//!
+//! A proxy delegates all of its calls to an implementation contract, so that
+//! the implementation runs against the proxy's storage. Upgrading the proxy
+//! means pointing it to a new implementation, while the state stays in place.
+//!
+//! * [`erc1967::Erc1967Proxy`] - Proxy whose implementation address is stored
+//!   in the slot specified by [ERC-1967].
+//! * [`beacon::BeaconProxy`] - Proxy that resolves its implementation from an
+//!   [`beacon::UpgradeableBeacon`] on every call, so that many proxies can be
+//!   upgraded at once.
+//! * [`utils::UupsUpgradeable`] - Component that adds the upgrade logic to the
+//!   implementation, as specified by [ERC-1822].
+//!
+//! [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+//! [ERC-1822]: https://eips.ethereum.org/EIPS/eip-1822
+use alloc::vec::Vec;
+
+use alloy_primitives::Address;
+use stylus_sdk::{call::RawCall, prelude::*, ArbResult};
+
+use crate::utils::ReentrantCallHandler;
+
+pub mod beacon;
+pub mod erc1967;
+pub mod utils;
+
+/// Delegates all calls to another contract.
+///
+/// This trait provides a fallback function that delegates all calls to
+/// another contract using the EVM instruction `DELEGATECALL`. We refer to the
+/// second contract as the _implementation_ behind the proxy, and it has to be
+/// specified by overriding the virtual [`IProxy::implementation`] function.
+///
+/// The success and return data of the delegated call will be returned back
+/// to the caller of the proxy.
+pub trait IProxy: TopLevelStorage + Sized {
+    /// Delegates the current call to `implementation`.
+    ///
+    /// This function does not return to its internal call site, it will
+    /// return directly to the external caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `implementation` - The address of the implementation contract.
+    /// * `calldata` - The calldata to delegate.
+    ///
+    /// # Errors
+    ///
+    /// * If the delegated call reverts, its revert data is returned.
+    fn delegate(
+        &mut self,
+        implementation: Address,
+        calldata: &[u8],
+    ) -> ArbResult {
+        RawCall::new_delegate()
+            .call_with_reentrant_handling(implementation, calldata)
+    }
+
+    /// This is a virtual function that should be overridden so it returns
+    /// the address to which the fallback function should delegate.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * If the implementation can't be resolved, the error should be returned
+    ///   as revert data.
+    fn implementation(&self) -> Result<Address, Vec<u8>>;
+
+    /// Delegates the current call to the address returned by
+    /// [`IProxy::implementation`].
+    ///
+    /// This function does not return to its internal call site, it will
+    /// return directly to the external caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `calldata` - The calldata to delegate.
+    ///
+    /// # Errors
+    ///
+    /// * If the implementation can't be resolved or the delegated call reverts,
+    ///   the revert data is returned.
+    fn do_fallback(&mut self, calldata: &[u8]) -> ArbResult {
+        let implementation = self.implementation()?;
+        self.delegate(implementation, calldata)
+    }
+}
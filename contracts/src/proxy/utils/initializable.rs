@@ -0,0 +1,520 @@
+//! This is a helper for writing upgradeable contracts, or any kind of contract
+//! that will be deployed behind a proxy.
+//!
+//! Since proxied contracts do not make use of a constructor, it's common to
+//! move constructor logic to an external initializer function, usually called
+//! `initialize`. It then becomes necessary to protect this initializer
+//! function so it can only be called once. [`Initializable::initializer`]
+//! provides this protection.
+//!
+//! The initialization functions use a version number. Once a version number is
+//! used, it is consumed and cannot be reused. This mechanism prevents
+//! re-execution of each "step" but allows the creation of new initialization
+//! steps in case an upgrade adds a component that needs to be initialized,
+//! see [`Initializable::reinitializer`].
+//!
+//! The state of [`Initializable`] is stored in the [ERC-7201] namespace
+//! `openzeppelin.storage.Initializable`, so that it doesn't take up any slot of
+//! the contract's `#[storage]` layout, and can't collide with it. The layout
+//! of the namespace matches the one of the Solidity implementation.
+//! See [`openzeppelin_stylus_proc::storage_location`].
+//!
+//! CAUTION: When used with inheritance, manual care must be taken to not
+//! invoke a parent initializer twice, or to ensure that all initializers are
+//! idempotent.
+//!
+//! CAUTION: Avoid leaving a contract uninitialized. An uninitialized contract
+//! can be taken over by an attacker. This applies to both a proxy and its
+//! implementation contract. To prevent the implementation contract from being
+//! used, call [`Initializable::disable_initializers`] on it right after
+//! deployment.
+//!
+//! ```ignore
+//! #[entrypoint]
+//! #[storage]
+//! struct MyToken {
+//!     #[borrow]
+//!     initializable: Initializable,
+//!     #[borrow]
+//!     erc20: Erc20,
+//!     #[borrow]
+//!     ownable: Ownable,
+//! }
+//!
+//! #[public]
+//! #[inherit(Erc20, Ownable)]
+//! impl MyToken {
+//!     fn initialize(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+//!         Ok(Initializable::initializer(self, |token| {
+//!             token.ownable._transfer_ownership(owner);
+//!             Ok::<_, initializable::Error>(())
+//!         })?)
+//!     }
+//! }
+//! ```
+//!
+//! [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+use alloc::{vec, vec::Vec};
+use core::borrow::BorrowMut;
+
+use alloy_primitives::{B256, U64};
+use openzeppelin_stylus_proc::storage_location;
+pub use sol::*;
+use stylus_sdk::{
+    call::MethodError,
+    evm,
+    prelude::*,
+    storage::{StorageBool, StorageU64},
+};
+
+/// Storage slot of the [`Initializable`] state.
+///
+/// This is the [ERC-7201] location of the `openzeppelin.storage.Initializable`
+/// namespace.
+///
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+pub const INITIALIZABLE_STORAGE: B256 = Initializable::STORAGE_SLOT;
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Emitted when the contract has been initialized or reinitialized.
+        ///
+        /// * `version` - The initialized version.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        event Initialized(uint64 version);
+    }
+
+    sol! {
+        /// The contract is already initialized.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error InvalidInitialization();
+
+        /// The contract is not initializing.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error NotInitializing();
+    }
+}
+
+/// An [`Initializable`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// The contract is already initialized.
+    InvalidInitialization(InvalidInitialization),
+    /// The contract is not initializing.
+    NotInitializing(NotInitializing),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`Initializable`] contract.
+///
+/// Unlike regular `#[storage]` structs, it ignores the slot it is assigned by
+/// the contract's layout, and always lives at [`INITIALIZABLE_STORAGE`].
+#[storage_location(erc7201 = "openzeppelin.storage.Initializable")]
+pub struct Initializable {
+    /// Indicates that the contract has been initialized.
+    pub(crate) initialized: StorageU64,
+    /// Indicates that the contract is in the process of being initialized.
+    pub(crate) initializing: StorageBool,
+}
+
+impl Initializable {
+    /// Runs `f` as the initializer of the contract, which can be invoked at
+    /// most once.
+    ///
+    /// Similar to [`Initializable::reinitializer`] with version `1`. During
+    /// `f`, [`Initializable::only_initializing`] passes.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - Write access to the contract's state.
+    /// * `f` - The initialization logic.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidInitialization`] - If the contract is already
+    ///   initialized or initializing.
+    /// * If `f` returns an error, it is returned.
+    ///
+    /// # Events
+    ///
+    /// * [`Initialized`].
+    pub fn initializer<S, T, E>(
+        storage: &mut S,
+        f: impl FnOnce(&mut S) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        S: BorrowMut<Self>,
+        E: From<Error>,
+    {
+        Initializable::reinitializer(storage, 1, f)
+    }
+
+    /// Runs `f` as a reinitializer of the contract, which can be invoked at
+    /// most once, and only if the contract hasn't been initialized to a
+    /// greater version before.
+    ///
+    /// Reinitializers are used to initialize components added by upgrades.
+    /// The version can jump in increments greater than 1, so that if multiple
+    /// reinitializers coexist in a contract, executing them in the right order
+    /// is up to the developer or operator.
+    ///
+    /// NOTE: Setting the version to [`u64::MAX`] prevents any future
+    /// reinitialization.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - Write access to the contract's state.
+    /// * `version` - The version to initialize the contract to.
+    /// * `f` - The initialization logic.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidInitialization`] - If the contract is initializing, or
+    ///   already initialized to `version` or greater.
+    /// * If `f` returns an error, it is returned, and the contract is left at
+    ///   its previous version.
+    ///
+    /// # Events
+    ///
+    /// * [`Initialized`].
+    pub fn reinitializer<S, T, E>(
+        storage: &mut S,
+        version: u64,
+        f: impl FnOnce(&mut S) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        S: BorrowMut<Self>,
+        E: From<Error>,
+    {
+        let initializable: &mut Self = storage.borrow_mut();
+        if initializable.is_initializing()
+            || initializable.get_initialized_version() >= version
+        {
+            return Err(
+                Error::InvalidInitialization(InvalidInitialization {}).into()
+            );
+        }
+        let previous_version = initializable.initialized.get();
+        initializable.initialized.set(U64::from(version));
+        initializable.initializing.set(true);
+
+        let result = f(storage);
+
+        let initializable: &mut Self = storage.borrow_mut();
+        initializable.initializing.set(false);
+        if result.is_ok() {
+            evm::log(Initialized { version });
+        } else {
+            // Leave the contract initializable again when the error is
+            // handled instead of reverting the whole call.
+            initializable.initialized.set(previous_version);
+        }
+        result
+    }
+
+    /// Checks that the contract is initializing. Functions guarded by this
+    /// check can only be called from within [`Initializable::initializer`]
+    /// and [`Initializable::reinitializer`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::NotInitializing`] - If the contract is not initializing.
+    pub fn only_initializing(&self) -> Result<(), Error> {
+        if self.is_initializing() {
+            Ok(())
+        } else {
+            Err(Error::NotInitializing(NotInitializing {}))
+        }
+    }
+
+    /// Locks the contract, preventing any future reinitialization. This
+    /// cannot be part of an initializer call.
+    ///
+    /// Calling this on the implementation contract right after deployment
+    /// prevents it from being initialized by anyone. It is recommended to use
+    /// this to lock implementation contracts that are designed to be called
+    /// through proxies.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidInitialization`] - If the contract is initializing.
+    ///
+    /// # Events
+    ///
+    /// * [`Initialized`] - The first time this is successfully called.
+    pub fn disable_initializers(&mut self) -> Result<(), Error> {
+        if self.is_initializing() {
+            return Err(Error::InvalidInitialization(InvalidInitialization {}));
+        }
+        if self.get_initialized_version() != u64::MAX {
+            self.initialized.set(U64::MAX);
+            evm::log(Initialized { version: u64::MAX });
+        }
+        Ok(())
+    }
+
+    /// Returns the highest version that has been initialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn get_initialized_version(&self) -> u64 {
+        self.initialized.get().to()
+    }
+
+    /// Returns `true` if the contract is currently initializing.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn is_initializing(&self) -> bool {
+        self.initializing.get()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{keccak256, Address, B256, U256};
+    use motsu::prelude::*;
+    use stylus_sdk::{prelude::*, storage::StorageU256};
+
+    use super::{
+        Error, Initializable, Initialized, InvalidInitialization,
+        NotInitializing, INITIALIZABLE_STORAGE,
+    };
+    use crate::utils::storage_slot::StorageSlot;
+
+    #[storage]
+    struct InitializableMock {
+        value: StorageU256,
+        #[borrow]
+        initializable: Initializable,
+        other: StorageU256,
+    }
+
+    unsafe impl TopLevelStorage for InitializableMock {}
+
+    #[public]
+    impl InitializableMock {
+        fn initialize(&mut self, value: U256) -> Result<(), Error> {
+            Initializable::initializer(self, |mock| mock.set_value(value))
+        }
+
+        fn reinitialize(
+            &mut self,
+            version: u64,
+            value: U256,
+        ) -> Result<(), Error> {
+            Initializable::reinitializer(self, version, |mock| {
+                mock.set_value(value)
+            })
+        }
+
+        fn initialize_nested(&mut self) -> Result<(), Error> {
+            Initializable::initializer(self, |mock| {
+                mock.initialize(U256::from(1))
+            })
+        }
+
+        fn disable_initializers(&mut self) -> Result<(), Error> {
+            Initializable::initializer(self, |mock| {
+                mock.initializable.disable_initializers()
+            })
+        }
+
+        fn initialize_failing(&mut self) -> bool {
+            Initializable::initializer(self, |mock| {
+                mock.set_value(U256::from(1))?;
+                Err::<(), _>(Error::NotInitializing(NotInitializing {}))
+            })
+            .is_err()
+        }
+
+        fn set_value(&mut self, value: U256) -> Result<(), Error> {
+            self.initializable.only_initializing()?;
+            self.value.set(value);
+            self.other.set(value);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn slot_matches_erc7201() {
+        let id = keccak256("openzeppelin.storage.Initializable");
+        let location = U256::from_be_bytes(id.0) - U256::from(1);
+        let mut slot = keccak256(location.to_be_bytes::<32>());
+        slot.0[31] = 0;
+        assert_eq!(INITIALIZABLE_STORAGE, slot);
+        assert_eq!(
+            Initializable::STORAGE_LOCATION,
+            "erc7201:openzeppelin.storage.Initializable"
+        );
+    }
+
+    #[motsu::test]
+    fn initializes_once(contract: Contract<InitializableMock>, alice: Address) {
+        let value = U256::from(42);
+        contract.sender(alice).initialize(value).motsu_unwrap();
+
+        assert_eq!(contract.sender(alice).value.get(), value);
+        assert_eq!(contract.sender(alice).other.get(), value);
+        assert_eq!(
+            contract.sender(alice).initializable.get_initialized_version(),
+            1
+        );
+        assert!(!contract.sender(alice).initializable.is_initializing());
+        contract.assert_emitted(&Initialized { version: 1 });
+
+        let err =
+            contract.sender(alice).initialize(U256::from(1)).motsu_unwrap_err();
+        assert!(matches!(
+            err,
+            Error::InvalidInitialization(InvalidInitialization {})
+        ));
+    }
+
+    #[motsu::test]
+    fn stores_state_in_namespace(
+        contract: Contract<InitializableMock>,
+        alice: Address,
+    ) {
+        contract.sender(alice).initialize(U256::from(42)).motsu_unwrap();
+
+        contract.init(alice, |_| {
+            let mut expected = B256::ZERO;
+            expected.0[31] = 1;
+            assert_eq!(StorageSlot::get_word(INITIALIZABLE_STORAGE), expected);
+            assert_eq!(StorageSlot::get_uint(B256::ZERO), U256::from(42));
+            assert_eq!(
+                StorageSlot::get_uint(B256::with_last_byte(1)),
+                U256::from(42)
+            );
+        });
+    }
+
+    #[motsu::test]
+    fn reinitializes_to_greater_version(
+        contract: Contract<InitializableMock>,
+        alice: Address,
+    ) {
+        contract.sender(alice).initialize(U256::from(1)).motsu_unwrap();
+        contract.sender(alice).reinitialize(3, U256::from(3)).motsu_unwrap();
+
+        assert_eq!(contract.sender(alice).value.get(), U256::from(3));
+        assert_eq!(
+            contract.sender(alice).initializable.get_initialized_version(),
+            3
+        );
+        contract.assert_emitted(&Initialized { version: 3 });
+
+        for version in [2, 3] {
+            let err = contract
+                .sender(alice)
+                .reinitialize(version, U256::from(version))
+                .motsu_unwrap_err();
+            assert!(matches!(err, Error::InvalidInitialization(_)));
+        }
+    }
+
+    #[motsu::test]
+    fn failed_initializer_stops_initializing(
+        contract: Contract<InitializableMock>,
+        alice: Address,
+    ) {
+        assert!(contract.sender(alice).initialize_failing());
+
+        assert!(!contract.sender(alice).initializable.is_initializing());
+        assert_eq!(
+            contract.sender(alice).initializable.get_initialized_version(),
+            0
+        );
+        let err =
+            contract.sender(alice).set_value(U256::from(2)).motsu_unwrap_err();
+        assert!(matches!(err, Error::NotInitializing(_)));
+
+        contract.sender(alice).initialize(U256::from(42)).motsu_unwrap();
+        assert_eq!(contract.sender(alice).value.get(), U256::from(42));
+    }
+
+    #[motsu::test]
+    fn initializer_reverts_when_nested(
+        contract: Contract<InitializableMock>,
+        alice: Address,
+    ) {
+        let err = contract.sender(alice).initialize_nested().motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidInitialization(_)));
+    }
+
+    #[motsu::test]
+    fn only_initializing_reverts_outside_initializer(
+        contract: Contract<InitializableMock>,
+        alice: Address,
+    ) {
+        let err =
+            contract.sender(alice).set_value(U256::from(1)).motsu_unwrap_err();
+        assert!(matches!(err, Error::NotInitializing(_)));
+
+        contract.sender(alice).initialize(U256::from(1)).motsu_unwrap();
+        let err =
+            contract.sender(alice).set_value(U256::from(2)).motsu_unwrap_err();
+        assert!(matches!(err, Error::NotInitializing(_)));
+    }
+
+    #[motsu::test]
+    fn disables_initializers(
+        contract: Contract<InitializableMock>,
+        alice: Address,
+    ) {
+        contract
+            .sender(alice)
+            .initializable
+            .disable_initializers()
+            .motsu_unwrap();
+
+        assert_eq!(
+            contract.sender(alice).initializable.get_initialized_version(),
+            u64::MAX
+        );
+        contract.assert_emitted(&Initialized { version: u64::MAX });
+
+        let err =
+            contract.sender(alice).initialize(U256::from(1)).motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidInitialization(_)));
+        let err = contract
+            .sender(alice)
+            .reinitialize(u64::MAX, U256::from(1))
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidInitialization(_)));
+    }
+
+    #[motsu::test]
+    fn disable_initializers_reverts_when_initializing(
+        contract: Contract<InitializableMock>,
+        alice: Address,
+    ) {
+        let err =
+            contract.sender(alice).disable_initializers().motsu_unwrap_err();
+        assert!(matches!(err, Error::InvalidInitialization(_)));
+    }
+}
@@ -0,0 +1,6 @@
+//! Utilities for contracts deployed behind proxies.
+pub mod initializable;
+pub mod uups_upgradeable;
+
+pub use initializable::Initializable;
+pub use uups_upgradeable::UupsUpgradeable;
@@ -0,0 +1,586 @@
+//! An upgradeability mechanism designed for UUPS proxies.
+//!
+//! The functions included here can perform an upgrade of an
+//! [`crate::proxy::erc1967::Erc1967Proxy`], when this contract is set as the
+//! implementation behind such a proxy.
+//!
+//! A security mechanism ensures that an upgrade does not turn off
+//! upgradeability accidentally, although this risk is reinstated if the
+//! upgrade retains upgradeability but removes the security mechanism, e.g. by
+//! replacing [`UupsUpgradeable`] with a custom implementation of upgrades.
+//!
+//! IMPORTANT: `upgradeToAndCall` has no access control of its own. Contracts
+//! inheriting [`UupsUpgradeable`] must override it in their own `#[public]`
+//! impl, guarded by the appropriate access control check (e.g.
+//! [`crate::access::ownable::Ownable::only_owner`]). Otherwise, anyone can
+//! upgrade the proxy.
+//!
+//! IMPORTANT: Deploying an implementation takes two steps. Since Stylus
+//! contracts have no immutable variables and `cargo-stylus` doesn't support
+//! constructors yet, the implementation records its own address in storage
+//! through `initializeImplementation`, which must be called on the
+//! implementation contract right after it is deployed. Until then,
+//! `proxiableUUID` reverts, so no proxy can be upgraded to it.
+//!
+//! Calls are considered to go through a proxy when the address recorded in
+//! the current storage is not the current contract's address, and the
+//! proxy's [ERC-1967] implementation slot points at an implementation that
+//! has recorded its own address.
+//!
+//! [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+use alloc::{string::String, vec, vec::Vec};
+
+use alloy_primitives::{Address, B256};
+pub use sol::*;
+use stylus_sdk::{
+    abi::Bytes,
+    call::{Call, MethodError},
+    contract,
+    prelude::*,
+    storage::StorageAddress,
+};
+
+use crate::proxy::erc1967::{
+    self,
+    utils::{ERC1967InvalidImplementation, IMPLEMENTATION_SLOT},
+    Erc1967Utils,
+};
+
+/// The version of the upgrade interface of the contract.
+pub const UPGRADE_INTERFACE_VERSION: &str = "5.0.0";
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// The call is from an unauthorized context.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error UUPSUnauthorizedCallContext();
+
+        /// The storage `slot` is unsupported as a UUID.
+        ///
+        /// * `slot` - The unsupported UUID.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error UUPSUnsupportedProxiableUUID(bytes32 slot);
+    }
+}
+
+pub use interface::IErc1822ProxiableInterface;
+mod interface {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+
+    use alloc::vec;
+
+    use stylus_sdk::prelude::sol_interface;
+    sol_interface! {
+        interface IErc1822ProxiableInterface {
+            function proxiableUUID() external view returns (bytes32);
+        }
+    }
+}
+
+/// An [`UupsUpgradeable`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// The call is from an unauthorized context.
+    UnauthorizedCallContext(UUPSUnauthorizedCallContext),
+    /// The storage `slot` is unsupported as a UUID.
+    UnsupportedProxiableUuid(UUPSUnsupportedProxiableUUID),
+    /// Error from the [ERC-1967] slots management.
+    ///
+    /// [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+    Erc1967(erc1967::utils::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`UupsUpgradeable`] contract.
+///
+/// Besides its own address, recorded in the implementation's storage, the
+/// component's state lives in the [ERC-1967] slots of the proxy.
+///
+/// [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+#[storage]
+pub struct UupsUpgradeable {
+    /// Address of the implementation contract, recorded in its own storage.
+    /// Left unset in the storage of proxies.
+    pub(crate) self_address: StorageAddress,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for UupsUpgradeable {}
+
+#[public]
+impl UupsUpgradeable {
+    /// The version of the upgrade interface of the contract. The presence of
+    /// this getter signals that `upgradeToAndCall` expects its `data` argument
+    /// to be passed, and that an empty `data` performs no setup call.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[selector(name = "UPGRADE_INTERFACE_VERSION")]
+    #[allow(clippy::unused_self)]
+    fn upgrade_interface_version(&self) -> String {
+        UPGRADE_INTERFACE_VERSION.into()
+    }
+
+    /// Implementation of the ERC-1822 `proxiableUUID` function. This returns
+    /// the storage slot used by the implementation. It is used to validate
+    /// the implementation's compatibility when performing an upgrade.
+    ///
+    /// IMPORTANT: A proxy pointing at a proxiable contract should not be
+    /// considered proxiable itself, because this risks bricking a proxy that
+    /// upgrades to it, by delegating to itself until out of gas. Thus it is
+    /// critical that this function reverts if invoked through a proxy.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedCallContext`] - If called through a proxy.
+    #[selector(name = "proxiableUUID")]
+    fn proxiable_uuid(&self) -> Result<B256, Error> {
+        self.not_delegated()?;
+        Ok(IMPLEMENTATION_SLOT)
+    }
+
+    /// Records the address of the implementation contract in its own
+    /// storage, enabling [`UupsUpgradeable::proxiable_uuid`] and upgrades of
+    /// the proxies pointing at it.
+    ///
+    /// This is a mandatory deployment step: it must be called on the
+    /// implementation contract itself, right after it is deployed. Since the
+    /// recorded address is always the contract's own, the function has no
+    /// access control.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedCallContext`] - If called through a proxy.
+    fn initialize_implementation(&mut self) -> Result<(), Error> {
+        if !Erc1967Utils::get_implementation().is_zero() {
+            return Err(Error::UnauthorizedCallContext(
+                UUPSUnauthorizedCallContext {},
+            ));
+        }
+        self.self_address.set(contract::address());
+        Ok(())
+    }
+
+    /// Upgrade the implementation of the proxy to `new_implementation`, and
+    /// subsequently execute the function call encoded in `data`.
+    ///
+    /// IMPORTANT: This function has no access control. Contracts must
+    /// override it in their own `#[public]` impl, behind the appropriate
+    /// access control check.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_implementation` - Address of the new implementation.
+    /// * `data` - Calldata of the setup call, delegated to
+    ///   `new_implementation`.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedCallContext`] - If not called through a proxy.
+    /// * [`Error::Erc1967`] - If `new_implementation` is not a UUPS
+    ///   implementation, or the setup call fails.
+    /// * [`Error::UnsupportedProxiableUuid`] - If `new_implementation` returns
+    ///   an unsupported UUID.
+    ///
+    /// # Events
+    ///
+    /// * [`erc1967::utils::Upgraded`].
+    #[payable]
+    #[selector(name = "upgradeToAndCall")]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn upgrade_to_and_call(
+        &mut self,
+        new_implementation: Address,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        self.only_proxy()?;
+        self._upgrade_to_and_call_uups(new_implementation, &data)
+    }
+}
+
+impl UupsUpgradeable {
+    /// Check that the execution is being performed through a delegate call
+    /// from an [ERC-1967] proxy, whose implementation is a UUPS
+    /// implementation that has recorded its own address.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedCallContext`] - If not called through a proxy, or
+    ///   if the proxy points at an implementation that didn't record its own
+    ///   address.
+    ///
+    /// [ERC-1967]: https://eips.ethereum.org/EIPS/eip-1967
+    pub fn only_proxy(&self) -> Result<(), Error> {
+        let implementation = Erc1967Utils::get_implementation();
+        // The implementation's `proxiableUUID` only succeeds when called on
+        // the contract that recorded its own address.
+        let is_proxied = self.self_address.get() != contract::address()
+            && !implementation.is_zero()
+            && IErc1822ProxiableInterface::new(implementation)
+                .proxiable_uuid(self)
+                .is_ok_and(|slot| slot == IMPLEMENTATION_SLOT);
+
+        if !is_proxied {
+            return Err(Error::UnauthorizedCallContext(
+                UUPSUnauthorizedCallContext {},
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that the execution is not being performed through a delegate
+    /// call. This allows a function to be callable on the implementing
+    /// contract but not through proxies.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::UnauthorizedCallContext`] - If called through a proxy, or if
+    ///   the implementation didn't record its own address.
+    pub fn not_delegated(&self) -> Result<(), Error> {
+        if self.self_address.get() == contract::address() {
+            Ok(())
+        } else {
+            Err(Error::UnauthorizedCallContext(UUPSUnauthorizedCallContext {}))
+        }
+    }
+
+    /// Performs an implementation upgrade with a security check for UUPS
+    /// proxies, and additional setup call.
+    ///
+    /// As a security check, [`UupsUpgradeable::proxiable_uuid`] is invoked
+    /// in the new implementation, and the return value is expected to be the
+    /// implementation slot in ERC-1967.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `new_implementation` - Address of the new implementation.
+    /// * `data` - Calldata of the setup call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::Erc1967`] - If `new_implementation` is not a UUPS
+    ///   implementation, or the setup call fails.
+    /// * [`Error::UnsupportedProxiableUuid`] - If `new_implementation` returns
+    ///   an unsupported UUID.
+    ///
+    /// # Events
+    ///
+    /// * [`erc1967::utils::Upgraded`].
+    fn _upgrade_to_and_call_uups(
+        &mut self,
+        new_implementation: Address,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        let slot = IErc1822ProxiableInterface::new(new_implementation)
+            .proxiable_uuid(Call::new_in(self))
+            .map_err(|_| {
+                Error::Erc1967(erc1967::utils::Error::InvalidImplementation(
+                    ERC1967InvalidImplementation {
+                        implementation: new_implementation,
+                    },
+                ))
+            })?;
+
+        if slot != IMPLEMENTATION_SLOT {
+            return Err(Error::UnsupportedProxiableUuid(
+                UUPSUnsupportedProxiableUUID { slot },
+            ));
+        }
+
+        Erc1967Utils::upgrade_to_and_call(new_implementation, data)?;
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{Address, B256};
+    use motsu::prelude::*;
+    use stylus_sdk::{abi::Bytes, prelude::*};
+
+    use super::{Error, UupsUpgradeable, IMPLEMENTATION_SLOT};
+    use crate::{
+        proxy::erc1967::{
+            utils::{self, Upgraded},
+            Erc1967Utils,
+        },
+        utils::storage_slot::StorageSlot,
+    };
+
+    /// Upgradeable implementation.
+    #[storage]
+    struct UupsMock {
+        #[borrow]
+        uups: UupsUpgradeable,
+    }
+
+    unsafe impl TopLevelStorage for UupsMock {}
+
+    #[public]
+    #[inherit(UupsUpgradeable)]
+    impl UupsMock {}
+
+    /// Implementation with an unsupported UUID.
+    #[storage]
+    struct BadUuidMock {}
+
+    unsafe impl TopLevelStorage for BadUuidMock {}
+
+    #[public]
+    impl BadUuidMock {
+        #[selector(name = "proxiableUUID")]
+        #[allow(clippy::unused_self)]
+        fn proxiable_uuid(&self) -> B256 {
+            B256::ZERO
+        }
+    }
+
+    /// Records the address of a freshly deployed `implementation`.
+    fn init_implementation(
+        implementation: &Contract<UupsMock>,
+        alice: Address,
+    ) {
+        implementation
+            .sender(alice)
+            .uups
+            .initialize_implementation()
+            .motsu_unwrap();
+    }
+
+    /// Simulates the storage of a proxy pointing at `implementation`.
+    fn init_as_proxy(
+        contract: &Contract<UupsMock>,
+        implementation: Address,
+        alice: Address,
+    ) {
+        contract.init(alice, |_| {
+            StorageSlot::set_address(IMPLEMENTATION_SLOT, implementation);
+        });
+    }
+
+    #[motsu::test]
+    fn proxiable_uuid_returns_implementation_slot(
+        contract: Contract<UupsMock>,
+        alice: Address,
+    ) {
+        init_implementation(&contract, alice);
+        let uuid = contract.sender(alice).uups.proxiable_uuid().motsu_unwrap();
+        assert_eq!(uuid, IMPLEMENTATION_SLOT);
+    }
+
+    #[motsu::test]
+    fn proxiable_uuid_reverts_when_implementation_not_initialized(
+        contract: Contract<UupsMock>,
+        alice: Address,
+    ) {
+        let err =
+            contract.sender(alice).uups.proxiable_uuid().motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCallContext(_)));
+    }
+
+    #[motsu::test]
+    fn initialize_implementation_reverts_when_delegated(
+        proxy: Contract<UupsMock>,
+        implementation: Contract<UupsMock>,
+        alice: Address,
+    ) {
+        init_implementation(&implementation, alice);
+        init_as_proxy(&proxy, implementation.address(), alice);
+
+        let err = proxy
+            .sender(alice)
+            .uups
+            .initialize_implementation()
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCallContext(_)));
+    }
+
+    #[motsu::test]
+    fn proxiable_uuid_reverts_when_delegated(
+        proxy: Contract<UupsMock>,
+        implementation: Contract<UupsMock>,
+        alice: Address,
+    ) {
+        init_implementation(&implementation, alice);
+        init_as_proxy(&proxy, implementation.address(), alice);
+        let err = proxy.sender(alice).uups.proxiable_uuid().motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCallContext(_)));
+    }
+
+    #[motsu::test]
+    fn upgrade_reverts_when_not_delegated(
+        contract: Contract<UupsMock>,
+        new_implementation: Contract<UupsMock>,
+        alice: Address,
+    ) {
+        init_implementation(&contract, alice);
+        init_implementation(&new_implementation, alice);
+
+        let err = contract
+            .sender(alice)
+            .uups
+            .upgrade_to_and_call(
+                new_implementation.address(),
+                Bytes::from(vec![]),
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCallContext(_)));
+    }
+
+    #[motsu::test]
+    fn upgrade_reverts_when_proxy_points_at_different_implementation(
+        proxy: Contract<UupsMock>,
+        other_implementation: Contract<BadUuidMock>,
+        new_implementation: Contract<UupsMock>,
+        alice: Address,
+    ) {
+        init_implementation(&new_implementation, alice);
+        init_as_proxy(&proxy, other_implementation.address(), alice);
+
+        let err = proxy
+            .sender(alice)
+            .uups
+            .upgrade_to_and_call(
+                new_implementation.address(),
+                Bytes::from(vec![]),
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCallContext(_)));
+    }
+
+    #[motsu::test]
+    fn upgrade_reverts_when_proxy_points_at_uninitialized_implementation(
+        proxy: Contract<UupsMock>,
+        implementation: Contract<UupsMock>,
+        new_implementation: Contract<UupsMock>,
+        alice: Address,
+    ) {
+        init_implementation(&new_implementation, alice);
+        init_as_proxy(&proxy, implementation.address(), alice);
+
+        let err = proxy
+            .sender(alice)
+            .uups
+            .upgrade_to_and_call(
+                new_implementation.address(),
+                Bytes::from(vec![]),
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::UnauthorizedCallContext(_)));
+    }
+
+    #[motsu::test]
+    fn upgrades_to_uups_implementation(
+        proxy: Contract<UupsMock>,
+        implementation: Contract<UupsMock>,
+        new_implementation: Contract<UupsMock>,
+        alice: Address,
+    ) {
+        init_implementation(&implementation, alice);
+        init_implementation(&new_implementation, alice);
+        init_as_proxy(&proxy, implementation.address(), alice);
+
+        proxy
+            .sender(alice)
+            .uups
+            .upgrade_to_and_call(
+                new_implementation.address(),
+                Bytes::from(vec![]),
+            )
+            .motsu_unwrap();
+
+        proxy.assert_emitted(&Upgraded {
+            implementation: new_implementation.address(),
+        });
+        proxy.init(alice, |_| {
+            assert_eq!(
+                Erc1967Utils::get_implementation(),
+                new_implementation.address()
+            );
+        });
+    }
+
+    #[motsu::test]
+    fn upgrade_reverts_for_unsupported_uuid(
+        proxy: Contract<UupsMock>,
+        implementation: Contract<UupsMock>,
+        bad_implementation: Contract<BadUuidMock>,
+        alice: Address,
+    ) {
+        init_implementation(&implementation, alice);
+        init_as_proxy(&proxy, implementation.address(), alice);
+
+        let err = proxy
+            .sender(alice)
+            .uups
+            .upgrade_to_and_call(
+                bad_implementation.address(),
+                Bytes::from(vec![]),
+            )
+            .motsu_unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnsupportedProxiableUuid(super::UUPSUnsupportedProxiableUUID {
+                slot
+            }) if slot == B256::ZERO
+        ));
+    }
+
+    #[motsu::test]
+    fn upgrade_reverts_for_delegated_implementation(
+        proxy: Contract<UupsMock>,
+        implementation: Contract<UupsMock>,
+        other_proxy: Contract<UupsMock>,
+        alice: Address,
+    ) {
+        init_implementation(&implementation, alice);
+        init_as_proxy(&proxy, implementation.address(), alice);
+        init_as_proxy(&other_proxy, implementation.address(), alice);
+
+        // A proxy is not a valid implementation, since its `proxiableUUID`
+        // reverts.
+        let err = proxy
+            .sender(alice)
+            .uups
+            .upgrade_to_and_call(other_proxy.address(), Bytes::from(vec![]))
+            .motsu_unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Erc1967(utils::Error::InvalidImplementation(
+                utils::ERC1967InvalidImplementation { implementation }
+            )) if implementation == other_proxy.address()
+        ));
+    }
+}
@@ -3,10 +3,10 @@
 use alloc::vec::Vec;
 
 use alloy_primitives::{Address, U256};
-use stylus_sdk::msg;
 
-use crate::token::erc1155::{
-    self, ERC1155MissingApprovalForAll, Erc1155, IErc1155,
+use crate::{
+    token::erc1155::{self, ERC1155MissingApprovalForAll, Erc1155, IErc1155},
+    utils::context,
 };
 
 /// Extension of [`Erc1155`] that allows token holders to destroy both their
@@ -94,7 +94,7 @@ impl Erc1155 {
         &self,
         account: Address,
     ) -> Result<(), erc1155::Error> {
-        let sender = msg::sender();
+        let sender = context::msg_sender();
         if account != sender && !self.is_approved_for_all(account, sender) {
             return Err(erc1155::Error::MissingApprovalForAll(
                 ERC1155MissingApprovalForAll {
@@ -111,15 +111,54 @@ impl Erc1155 {
 mod tests {
 
     use alloy_primitives::{Address, U256};
+    use alloy_sol_types::SolCall;
     use motsu::prelude::Contract;
+    use stylus_sdk::prelude::*;
 
     use super::IErc1155Burnable;
-    use crate::token::erc1155::{
-        tests::{random_token_ids, random_values},
-        ERC1155InsufficientBalance, ERC1155InvalidSender,
-        ERC1155MissingApprovalForAll, Erc1155, Error, IErc1155,
+    use crate::{
+        metatx::erc2771_context::{relay, Erc2771Context},
+        token::erc1155::{
+            tests::{random_token_ids, random_values},
+            ERC1155InsufficientBalance, ERC1155InvalidSender,
+            ERC1155MissingApprovalForAll, Erc1155, Error, IErc1155,
+        },
     };
 
+    mod abi {
+        #![allow(missing_docs)]
+
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function burn(address account, uint256 id, uint256 value) external;
+        }
+    }
+
+    /// Burnable token receiving meta-transactions through
+    /// [`Erc2771Context`].
+    #[storage]
+    struct RelayedBurnableMock {
+        #[borrow]
+        erc2771: Erc2771Context,
+        erc1155: Erc1155,
+    }
+
+    unsafe impl TopLevelStorage for RelayedBurnableMock {}
+
+    #[public]
+    #[inherit(Erc2771Context)]
+    impl RelayedBurnableMock {
+        fn burn(
+            &mut self,
+            account: Address,
+            id: U256,
+            value: U256,
+        ) -> Result<(), Error> {
+            self.erc1155.burn(account, id, value)
+        }
+    }
+
     fn init(
         contract: &mut Erc1155,
         receiver: Address,
@@ -157,6 +196,51 @@ mod tests {
         assert_eq!(U256::ZERO, balance);
     }
 
+    #[motsu::test]
+    fn relayed_burn_authorizes_relayed_sender(
+        contract: Contract<RelayedBurnableMock>,
+        alice: Address,
+        bob: Address,
+        forwarder: Address,
+    ) {
+        let (token_ids, values) = contract.init(alice, |mock| {
+            mock.erc2771._set_trusted_forwarder(forwarder);
+            mock.erc1155
+                .set_approval_for_all(forwarder, true)
+                .expect("should approve the forwarder");
+            init(&mut mock.erc1155, alice, 1)
+        });
+        let call = abi::burnCall {
+            account: alice,
+            id: token_ids[0],
+            value: values[0],
+        }
+        .abi_encode();
+
+        let err = contract
+            .init(forwarder, |_| relay::<RelayedBurnableMock>(&call, bob))
+            .expect_err("should not burn tokens of another account");
+        let expected: Vec<u8> =
+            Error::MissingApprovalForAll(ERC1155MissingApprovalForAll {
+                operator: bob,
+                owner: alice,
+            })
+            .into();
+        assert_eq!(expected, err);
+        assert_eq!(
+            values[0],
+            contract.sender(alice).erc1155.balance_of(alice, token_ids[0])
+        );
+
+        contract
+            .init(forwarder, |_| relay::<RelayedBurnableMock>(&call, alice))
+            .expect("should burn tokens of the relayed sender");
+        assert_eq!(
+            U256::ZERO,
+            contract.sender(alice).erc1155.balance_of(alice, token_ids[0])
+        );
+    }
+
     #[motsu::test]
     fn burns_with_approval(
         contract: Contract<Erc1155>,
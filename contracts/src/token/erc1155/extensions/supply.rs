@@ -18,7 +18,6 @@ use alloy_primitives::{Address, FixedBytes, U256};
 use openzeppelin_stylus_proc::interface_id;
 use stylus_sdk::{
     abi::Bytes,
-    msg,
     prelude::*,
     storage::{StorageMap, StorageU256},
 };
@@ -26,6 +25,7 @@ use stylus_sdk::{
 use crate::{
     token::erc1155::{self, Erc1155, IErc1155},
     utils::{
+        context,
         introspection::erc165::{Erc165, IErc165},
         math::storage::{AddAssignChecked, SubAssignUnchecked},
     },
@@ -297,7 +297,7 @@ impl Erc1155Supply {
 
         if !to.is_zero() {
             self.erc1155._check_on_erc1155_received(
-                msg::sender(),
+                context::msg_sender(),
                 from,
                 to,
                 erc1155::Erc1155ReceiverData::new(ids, values),
@@ -385,21 +385,83 @@ impl IErc165 for Erc1155Supply {
 
 #[cfg(all(test, feature = "std"))]
 mod tests {
-    use alloy_primitives::{Address, U256};
+    use alloy_primitives::{fixed_bytes, Address, FixedBytes, U256};
+    use alloy_sol_types::SolCall;
     use motsu::prelude::Contract;
-    use stylus_sdk::prelude::TopLevelStorage;
+    use stylus_sdk::{abi::Bytes, prelude::*, storage::StorageAddress};
 
     use super::{Erc1155Supply, IErc1155Supply};
     use crate::{
+        metatx::erc2771_context::{relay, Erc2771Context},
         token::erc1155::{
             tests::{random_token_ids, random_values},
-            ERC1155InvalidReceiver, ERC1155InvalidSender, Error, IErc1155,
+            ERC1155InvalidReceiver, ERC1155InvalidSender,
+            ERC1155MissingApprovalForAll, Error, IErc1155,
         },
         utils::introspection::erc165::IErc165,
     };
 
     unsafe impl TopLevelStorage for Erc1155Supply {}
 
+    mod abi {
+        #![allow(missing_docs)]
+
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function safeTransferFrom(address from, address to, uint256 id, uint256 value, bytes data) external;
+        }
+    }
+
+    /// Token receiving meta-transactions through [`Erc2771Context`].
+    #[storage]
+    struct RelayedSupplyMock {
+        #[borrow]
+        erc2771: Erc2771Context,
+        supply: Erc1155Supply,
+    }
+
+    unsafe impl TopLevelStorage for RelayedSupplyMock {}
+
+    #[public]
+    #[inherit(Erc2771Context)]
+    impl RelayedSupplyMock {
+        fn safe_transfer_from(
+            &mut self,
+            from: Address,
+            to: Address,
+            id: U256,
+            value: U256,
+            data: Bytes,
+        ) -> Result<(), Error> {
+            self.supply.safe_transfer_from(from, to, id, value, data)
+        }
+    }
+
+    /// Receiver recording the operator of the last received transfer.
+    #[storage]
+    struct ReceiverMock {
+        operator: StorageAddress,
+    }
+
+    unsafe impl TopLevelStorage for ReceiverMock {}
+
+    #[public]
+    impl ReceiverMock {
+        #[selector(name = "onERC1155Received")]
+        fn on_erc1155_received(
+            &mut self,
+            operator: Address,
+            _from: Address,
+            _id: U256,
+            _value: U256,
+            _data: Bytes,
+        ) -> FixedBytes<4> {
+            self.operator.set(operator);
+            fixed_bytes!("f23a6e61")
+        }
+    }
+
     fn init(
         contract: &mut Erc1155Supply,
         receiver: Address,
@@ -419,6 +481,54 @@ mod tests {
         (token_ids, values)
     }
 
+    #[motsu::test]
+    fn relayed_transfer_authorizes_relayed_sender(
+        contract: Contract<RelayedSupplyMock>,
+        receiver: Contract<ReceiverMock>,
+        alice: Address,
+        bob: Address,
+        forwarder: Address,
+    ) {
+        let (token_ids, values) = contract.init(alice, |mock| {
+            mock.erc2771._set_trusted_forwarder(forwarder);
+            mock.supply
+                .set_approval_for_all(forwarder, true)
+                .expect("should approve the forwarder");
+            init(&mut mock.supply, alice, 1)
+        });
+        let call = abi::safeTransferFromCall {
+            from: alice,
+            to: receiver.address(),
+            id: token_ids[0],
+            value: values[0],
+            data: vec![].into(),
+        }
+        .abi_encode();
+
+        let err = contract
+            .init(forwarder, |_| relay::<RelayedSupplyMock>(&call, bob))
+            .expect_err("should not transfer tokens of another account");
+        let expected: Vec<u8> =
+            Error::MissingApprovalForAll(ERC1155MissingApprovalForAll {
+                operator: bob,
+                owner: alice,
+            })
+            .into();
+        assert_eq!(expected, err);
+
+        contract
+            .init(forwarder, |_| relay::<RelayedSupplyMock>(&call, alice))
+            .expect("should transfer tokens of the relayed sender");
+        assert_eq!(
+            values[0],
+            contract
+                .sender(alice)
+                .supply
+                .balance_of(receiver.address(), token_ids[0])
+        );
+        assert_eq!(alice, receiver.sender(alice).operator.get());
+    }
+
     #[motsu::test]
     fn before_mint(contract: Contract<Erc1155Supply>, alice: Address) {
         let token_id = random_token_ids(1)[0];
@@ -6,12 +6,13 @@ use openzeppelin_stylus_proc::interface_id;
 use stylus_sdk::{
     abi::Bytes,
     call::{self, Call, MethodError},
-    evm, function_selector, msg,
+    evm, function_selector,
     prelude::*,
     storage::{StorageBool, StorageMap, StorageU256},
 };
 
 use crate::utils::{
+    context,
     introspection::erc165::{Erc165, IErc165},
     math::storage::{AddAssignChecked, SubAssignUnchecked},
 };
@@ -386,7 +387,7 @@ impl IErc1155 for Erc1155 {
         operator: Address,
         approved: bool,
     ) -> Result<(), Self::Error> {
-        self._set_approval_for_all(msg::sender(), operator, approved)
+        self._set_approval_for_all(context::msg_sender(), operator, approved)
     }
 
     fn is_approved_for_all(&self, account: Address, operator: Address) -> bool {
@@ -466,7 +467,7 @@ impl Erc1155 {
     ) -> Result<(), Error> {
         Self::require_equal_arrays_length(&ids, &values)?;
 
-        let operator = msg::sender();
+        let operator = context::msg_sender();
 
         for (&token_id, &value) in ids.iter().zip(values.iter()) {
             self.do_update(from, to, token_id, value)?;
@@ -530,7 +531,7 @@ impl Erc1155 {
 
         if !to.is_zero() {
             self._check_on_erc1155_received(
-                msg::sender(),
+                context::msg_sender(),
                 from,
                 to,
                 Erc1155ReceiverData::new(ids, values),
@@ -1038,7 +1039,7 @@ impl Erc1155 {
     /// * [`Error::MissingApprovalForAll`] -  If the `from` is not the caller
     ///   (`msg::sender()`), and the caller does not have the right to approve.
     fn authorize_transfer(&self, from: Address) -> Result<(), Error> {
-        let sender = msg::sender();
+        let sender = context::msg_sender();
         if from != sender && !self.is_approved_for_all(from, sender) {
             return Err(Error::MissingApprovalForAll(
                 ERC1155MissingApprovalForAll { operator: sender, owner: from },
@@ -1,9 +1,11 @@
 //! Optional Burnable extension of the ERC-20 standard.
 
 use alloy_primitives::{Address, U256};
-use stylus_sdk::msg;
 
-use crate::token::erc20::{self, Erc20};
+use crate::{
+    token::erc20::{self, Erc20},
+    utils::context,
+};
 
 /// Extension of [`Erc20`] that allows token holders to destroy both
 /// their own tokens and those that they have an allowance for,
@@ -60,7 +62,7 @@ impl IErc20Burnable for Erc20 {
     type Error = erc20::Error;
 
     fn burn(&mut self, value: U256) -> Result<(), Self::Error> {
-        self._burn(msg::sender(), value)
+        self._burn(context::msg_sender(), value)
     }
 
     fn burn_from(
@@ -68,7 +70,7 @@ impl IErc20Burnable for Erc20 {
         account: Address,
         value: U256,
     ) -> Result<(), Self::Error> {
-        self._spend_allowance(account, msg::sender(), value)?;
+        self._spend_allowance(account, context::msg_sender(), value)?;
         self._burn(account, value)
     }
 }
@@ -5,6 +5,7 @@ pub mod erc4626;
 pub mod flash_mint;
 pub mod metadata;
 pub mod permit;
+pub mod votes;
 pub mod wrapper;
 
 pub use burnable::IErc20Burnable;
@@ -13,4 +14,5 @@ pub use erc4626::{Erc4626, IErc4626};
 pub use flash_mint::{Erc20FlashMint, IErc3156FlashLender};
 pub use metadata::{Erc20Metadata, IErc20Metadata};
 pub use permit::Erc20Permit;
+pub use votes::Erc20Votes;
 pub use wrapper::{Erc20Wrapper, IErc20Wrapper};
@@ -14,32 +14,36 @@
 
 use alloc::{vec, vec::Vec};
 
-use alloy_primitives::{keccak256, Address, B256, U256};
-use alloy_sol_types::SolType;
+use alloy_primitives::{Address, B256, U256};
+use openzeppelin_stylus_proc::Eip712Struct;
 use stylus_sdk::{block, call::MethodError, prelude::*};
 
 use crate::{
     token::erc20::{self, Erc20},
     utils::{
-        cryptography::{ecdsa, eip712::IEip712},
+        cryptography::{
+            ecdsa,
+            eip712::{Eip712Domain, IEip712Domain},
+        },
         nonces::Nonces,
     },
 };
 
-const PERMIT_TYPEHASH: [u8; 32] =
-    keccak_const::Keccak256::new()
-        .update(b"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)")
-        .finalize();
+/// Permit message signed by the owner of the tokens.
+#[derive(Eip712Struct)]
+struct Permit {
+    owner: Address,
+    spender: Address,
+    value: U256,
+    nonce: U256,
+    deadline: U256,
+}
 
 pub use sol::*;
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod sol {
     use alloy_sol_macro::sol;
 
-    pub(crate) type StructHashTuple = sol! {
-        tuple(bytes32, address, address, uint256, uint256, uint256)
-    };
-
     sol! {
         /// Indicates an error related to the fact that
         /// permit deadline has expired.
@@ -76,18 +80,18 @@ impl MethodError for Error {
 
 /// State of an [`Erc20Permit`] Contract.
 #[storage]
-pub struct Erc20Permit<T: IEip712 + StorageType> {
-    /// Contract implementing [`IEip712`] trait.
-    pub(crate) eip712: T,
+pub struct Erc20Permit<T: IEip712Domain + StorageType> {
+    /// Contract implementing [`IEip712Domain`] trait.
+    pub eip712: T,
 }
 
 /// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
 /// calling other contracts and not `&mut (impl TopLevelStorage +
 /// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
-unsafe impl<T: IEip712 + StorageType> TopLevelStorage for Erc20Permit<T> {}
+unsafe impl<T: IEip712Domain + StorageType> TopLevelStorage for Erc20Permit<T> {}
 
 #[public]
-impl<T: IEip712 + StorageType> Erc20Permit<T> {
+impl<T: IEip712Domain + StorageType> Erc20Permit<T> {
     /// Returns the domain separator used in the encoding of the signature for
     /// [`Self::permit`], as defined by EIP712.
     ///
@@ -99,9 +103,23 @@ impl<T: IEip712 + StorageType> Erc20Permit<T> {
     pub fn domain_separator(&self) -> B256 {
         self.eip712.domain_separator_v4()
     }
+
+    /// Returns the fields and values that describe the domain separator used
+    /// in the encoding of the signature for [`Self::permit`], as defined by
+    /// [ERC-5267].
+    ///
+    /// [ERC-5267]: https://eips.ethereum.org/EIPS/eip-5267
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn eip712_domain(&self) -> Eip712Domain {
+        self.eip712.eip712_domain()
+    }
 }
 
-impl<T: IEip712 + StorageType> Erc20Permit<T> {
+impl<T: IEip712Domain + StorageType> Erc20Permit<T> {
     /// Sets `value` as the allowance of `spender` over `owner`'s tokens,
     /// given `owner`'s signed approval.
     ///
@@ -151,14 +169,14 @@ impl<T: IEip712 + StorageType> Erc20Permit<T> {
             return Err(ERC2612ExpiredSignature { deadline }.into());
         }
 
-        let struct_hash = keccak256(StructHashTuple::abi_encode(&(
-            PERMIT_TYPEHASH,
+        let struct_hash = Permit {
             owner,
             spender,
             value,
-            nonces.use_nonce(owner),
+            nonce: nonces.use_nonce(owner),
             deadline,
-        )));
+        }
+        .struct_hash();
 
         let hash: B256 = self.eip712.hash_typed_data_v4(struct_hash);
 
@@ -0,0 +1,986 @@
+//! Extension of ERC-20 to support Compound-like voting and delegation.
+//!
+//! This extension keeps a history (checkpoints) of each account's vote power.
+//! Vote power can be delegated either by calling the
+//! [`IVotes::delegate`] function directly, or by providing a signature to be
+//! used with [`Erc20Votes::delegate_by_sig`]. Voting power can be queried
+//! through the public accessors [`IVotes::get_votes`] and
+//! [`IVotes::get_past_votes`].
+//!
+//! The ERC-20, [`IVotes`] and [`IErc6372`] functions, as well as
+//! [`Erc20Votes::delegate_by_sig`] and [`Erc20Votes::nonces`], are exposed by
+//! inheriting [`Erc20Votes`].
+//!
+//! By default, token balance does not account for voting power. This makes
+//! transfers cheaper. The downside is that it requires users to delegate to
+//! themselves in order to activate checkpoints and have their voting power
+//! tracked.
+//!
+//! This extension uses [`Erc20`] as a member and overrides
+//! [`Erc20::_update`], so all token movements (transfers, mints and burns)
+//! have to go through [`Erc20Votes`] for the vote checkpoints to be kept
+//! consistent with balances. Delegation and checkpointing are handled by the
+//! [`Votes`] core.
+//!
+//! NOTE: This contract does not provide interface compatibility with
+//! Compound's COMP token.
+//!
+//! NOTE: Supply is capped at `U208::MAX` (2^208 - 1), since the checkpoints
+//! store values in 208 bits.
+use alloc::{string::String, vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{
+    aliases::{U208, U48},
+    Address, FixedBytes, B256, U256,
+};
+pub use sol::*;
+use stylus_sdk::{call::MethodError, prelude::*};
+
+use crate::{
+    governance::utils::votes::{self, IErc6372, IVotes, Votes},
+    token::erc20::{
+        self, ERC20InvalidReceiver, ERC20InvalidSender, Erc20, IErc20,
+    },
+    utils::{
+        context,
+        cryptography::eip712::IEip712Domain,
+        introspection::erc165::{Erc165, IErc165},
+    },
+};
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Total supply cap has been exceeded, introducing a risk of votes
+        /// overflowing.
+        ///
+        /// * `increased_supply` - Total supply after the operation.
+        /// * `cap` - Maximum supply supported by the checkpoints.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ERC20ExceededSafeSupply(uint256 increased_supply, uint256 cap);
+    }
+}
+
+/// An [`Erc20Votes`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Error type from [`Erc20`] contract [`erc20::Error`].
+    Erc20(erc20::Error),
+    /// Error type from [`Votes`] contract [`votes::Error`].
+    Votes(votes::Error),
+    /// Total supply cap has been exceeded, introducing a risk of votes
+    /// overflowing.
+    ExceededSafeSupply(ERC20ExceededSafeSupply),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of an [`Erc20Votes`] token.
+#[storage]
+pub struct Erc20Votes<T: IEip712Domain + StorageType> {
+    /// [`Erc20`] contract.
+    pub erc20: Erc20,
+    /// [`Votes`] contract tracking delegation and voting units.
+    pub votes: Votes<T>,
+}
+
+impl<T: IEip712Domain + StorageType> Deref for Erc20Votes<T> {
+    type Target = Erc20;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc20
+    }
+}
+
+impl<T: IEip712Domain + StorageType> DerefMut for Erc20Votes<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc20
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl<T: IEip712Domain + StorageType> TopLevelStorage for Erc20Votes<T> {}
+
+#[public]
+impl<T: IEip712Domain + StorageType> Erc20Votes<T> {
+    /// Re-export of [`IErc20::total_supply`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn total_supply(&self) -> U256 {
+        <Self as IErc20>::total_supply(self)
+    }
+
+    /// Re-export of [`IErc20::balance_of`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get balance from.
+    pub fn balance_of(&self, account: Address) -> U256 {
+        <Self as IErc20>::balance_of(self, account)
+    }
+
+    /// Re-export of [`IErc20::transfer`], moving the delegated votes of the
+    /// sender and the recipient.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - Number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientBalance`] - If the caller doesn't have a
+    ///   balance of at least `value`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn transfer(
+        &mut self,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Error> {
+        <Self as IErc20>::transfer(self, to, value)
+    }
+
+    /// Re-export of [`IErc20::allowance`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account that owns the tokens.
+    /// * `spender` - Account that will spend the tokens.
+    pub fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        <Self as IErc20>::allowance(self, owner, spender)
+    }
+
+    /// Re-export of [`IErc20::approve`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `spender` - Account that will spend the tokens.
+    /// * `value` - The number of tokens being allowed to transfer by `spender`.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidSpender`] - If the `spender` address is
+    ///   `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Approval`].
+    pub fn approve(
+        &mut self,
+        spender: Address,
+        value: U256,
+    ) -> Result<bool, Error> {
+        <Self as IErc20>::approve(self, spender, value)
+    }
+
+    /// Re-export of [`IErc20::transfer_from`], moving the delegated votes of
+    /// `from` and `to`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account to transfer tokens from.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - Number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidSender`] - If the `from` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientAllowance`] - If not enough allowance is
+    ///   available.
+    /// * [`erc20::Error::InsufficientBalance`] - If the `from` address doesn't
+    ///   have enough tokens.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Error> {
+        <Self as IErc20>::transfer_from(self, from, to, value)
+    }
+
+    /// Re-export of [`IErc6372::clock`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    pub fn clock(&self) -> U48 {
+        <Self as IErc6372>::clock(self)
+    }
+
+    /// Re-export of [`IErc6372::clock_mode`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[selector(name = "CLOCK_MODE")]
+    pub fn clock_mode(&self) -> String {
+        <Self as IErc6372>::clock_mode(self)
+    }
+
+    /// Re-export of [`IVotes::get_votes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the votes of.
+    pub fn get_votes(&self, account: Address) -> U256 {
+        <Self as IVotes>::get_votes(self, account)
+    }
+
+    /// Re-export of [`IVotes::get_past_votes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the votes of.
+    /// * `timepoint` - Timepoint to get the votes at.
+    ///
+    /// # Errors
+    ///
+    /// * [`votes::Error::FutureLookup`] - If `timepoint` is not in the past.
+    pub fn get_past_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Error> {
+        <Self as IVotes>::get_past_votes(self, account, timepoint)
+    }
+
+    /// Re-export of [`IVotes::get_past_total_supply`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `timepoint` - Timepoint to get the total supply at.
+    ///
+    /// # Errors
+    ///
+    /// * [`votes::Error::FutureLookup`] - If `timepoint` is not in the past.
+    pub fn get_past_total_supply(
+        &self,
+        timepoint: U256,
+    ) -> Result<U256, Error> {
+        <Self as IVotes>::get_past_total_supply(self, timepoint)
+    }
+
+    /// Re-export of [`IVotes::delegates`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the delegate of.
+    pub fn delegates(&self, account: Address) -> Address {
+        <Self as IVotes>::delegates(self, account)
+    }
+
+    /// Re-export of [`IVotes::delegate`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `delegatee` - Account to delegate votes to.
+    ///
+    /// # Errors
+    ///
+    /// * [`votes::Error::Checkpoints`] - If a checkpoint could not be pushed.
+    ///
+    /// # Events
+    ///
+    /// * [`votes::DelegateChanged`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn delegate(&mut self, delegatee: Address) -> Result<(), Error> {
+        <Self as IVotes>::delegate(self, delegatee)
+    }
+
+    /// Delegates votes from signer to `delegatee`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `delegatee` - Account to delegate votes to.
+    /// * `nonce` - Nonce of the signer used in the signed message.
+    /// * `expiry` - Expiration time of the signature.
+    /// * `v` - v value from the signer's signature.
+    /// * `r` - r value from the signer's signature.
+    /// * `s` - s value from the signer's signature.
+    ///
+    /// # Errors
+    ///
+    /// * [`votes::Error::ExpiredSignature`] - If the `expiry` is in the past.
+    /// * [`votes::Error::ECDSA`] - If the signature is invalid.
+    /// * [`votes::Error::Nonces`] - If the `nonce` is not the next valid nonce
+    ///   of the signer.
+    ///
+    /// # Events
+    ///
+    /// * [`votes::DelegateChanged`].
+    /// * [`votes::DelegateVotesChanged`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn delegate_by_sig(
+        &mut self,
+        delegatee: Address,
+        nonce: U256,
+        expiry: U256,
+        v: u8,
+        r: B256,
+        s: B256,
+    ) -> Result<(), Error> {
+        let signer =
+            self.votes._recover_delegator(delegatee, nonce, expiry, v, r, s)?;
+        self._delegate(signer, delegatee)
+    }
+
+    /// Returns the next unused nonce of `owner`, used by
+    /// [`Self::delegate_by_sig`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - Account to get the nonce of.
+    #[must_use]
+    pub fn nonces(&self, owner: Address) -> U256 {
+        self.votes.nonces.nonces(owner)
+    }
+
+    /// Returns the number of checkpoints for `account`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the number of checkpoints of.
+    ///
+    /// # Panics
+    ///
+    /// * If the number of checkpoints exceeds `u32::MAX`.
+    #[must_use]
+    pub fn num_checkpoints(&self, account: Address) -> u32 {
+        self.votes.num_checkpoints(account)
+    }
+
+    /// Returns the `pos`-th checkpoint for `account` as a (key, value) pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the checkpoint of.
+    /// * `pos` - Index of the checkpoint.
+    ///
+    /// # Panics
+    ///
+    /// * If `pos` exceeds the number of checkpoints of `account`.
+    #[must_use]
+    pub fn checkpoints(&self, account: Address, pos: u32) -> (U48, U208) {
+        self.votes.checkpoints(account, pos)
+    }
+}
+
+impl<T: IEip712Domain + StorageType> IErc20 for Erc20Votes<T> {
+    type Error = Error;
+
+    fn total_supply(&self) -> U256 {
+        self.erc20.total_supply()
+    }
+
+    fn balance_of(&self, account: Address) -> U256 {
+        self.erc20.balance_of(account)
+    }
+
+    fn transfer(
+        &mut self,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Self::Error> {
+        let from = context::msg_sender();
+        self._transfer(from, to, value)?;
+        Ok(true)
+    }
+
+    fn allowance(&self, owner: Address, spender: Address) -> U256 {
+        self.erc20.allowance(owner, spender)
+    }
+
+    fn approve(
+        &mut self,
+        spender: Address,
+        value: U256,
+    ) -> Result<bool, Self::Error> {
+        Ok(self.erc20.approve(spender, value)?)
+    }
+
+    fn transfer_from(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<bool, Self::Error> {
+        let spender = context::msg_sender();
+        self.erc20._spend_allowance(from, spender, value)?;
+        self._transfer(from, to, value)?;
+        Ok(true)
+    }
+}
+
+impl<T: IEip712Domain + StorageType> IErc6372 for Erc20Votes<T> {
+    fn clock(&self) -> U48 {
+        self.votes.clock()
+    }
+
+    fn clock_mode(&self) -> String {
+        self.votes.clock_mode()
+    }
+}
+
+impl<T: IEip712Domain + StorageType> IVotes for Erc20Votes<T> {
+    type Error = Error;
+
+    fn get_votes(&self, account: Address) -> U256 {
+        self.votes.get_votes(account)
+    }
+
+    fn get_past_votes(
+        &self,
+        account: Address,
+        timepoint: U256,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.votes.get_past_votes(account, timepoint)?)
+    }
+
+    fn get_past_total_supply(
+        &self,
+        timepoint: U256,
+    ) -> Result<U256, Self::Error> {
+        Ok(self.votes.get_past_total_supply(timepoint)?)
+    }
+
+    fn delegates(&self, account: Address) -> Address {
+        self.votes.delegates(account)
+    }
+
+    fn delegate(&mut self, delegatee: Address) -> Result<(), Self::Error> {
+        self._delegate(context::msg_sender(), delegatee)
+    }
+}
+
+impl<T: IEip712Domain + StorageType> Erc20Votes<T> {
+    /// Maximum token supply. Defaults to `U208::MAX` (2^208 - 1).
+    ///
+    /// This maximum is enforced in [`Self::_update`]. Increasing this value
+    /// will not remove the underlying limitation, and will cause
+    /// [`Self::_update`] to fail because of a math overflow in
+    /// [`Votes::_transfer_voting_units`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn _max_supply(&self) -> U256 {
+        U256::from(U208::MAX)
+    }
+
+    /// Creates a `value` amount of tokens and assigns them to `account`,
+    /// by transferring it from `Address::ZERO`.
+    ///
+    /// Re-export of [`Erc20::_mint`] that goes through [`Self::_update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account to mint tokens to.
+    /// * `value` - Amount of tokens to mint.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidReceiver`] - If the `account` address is
+    ///   `Address::ZERO`.
+    /// * [`Error::ExceededSafeSupply`] - If the total supply exceeds
+    ///   [`Self::_max_supply`].
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _mint(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if account.is_zero() {
+            return Err(erc20::Error::InvalidReceiver(ERC20InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+        self._update(Address::ZERO, account, value)
+    }
+
+    /// Destroys a `value` amount of tokens from `account`, lowering the total
+    /// supply.
+    ///
+    /// Re-export of [`Erc20::_burn`] that goes through [`Self::_update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account to burn tokens from.
+    /// * `value` - Amount of tokens to burn.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidSender`] - If the `account` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientBalance`] - If the `account` doesn't have
+    ///   enough tokens.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _burn(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if account.is_zero() {
+            return Err(erc20::Error::InvalidSender(ERC20InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        self._update(account, Address::ZERO, value)
+    }
+
+    /// Extended version of [`Erc20::_update`] that moves voting power when
+    /// tokens are transferred.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Owner's address.
+    /// * `to` - Recipient's address.
+    /// * `value` - Amount to be transferred.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InsufficientBalance`] - If the `from` address doesn't
+    ///   have enough tokens.
+    /// * [`Error::ExceededSafeSupply`] - If the total supply exceeds
+    ///   [`Self::_max_supply`].
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _update(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        self.erc20._update(from, to, value)?;
+
+        if from.is_zero() {
+            let supply = self.erc20.total_supply();
+            let cap = self._max_supply();
+            if supply > cap {
+                return Err(ERC20ExceededSafeSupply {
+                    increased_supply: supply,
+                    cap,
+                }
+                .into());
+            }
+        }
+
+        Ok(self.votes._transfer_voting_units(from, to, value)?)
+    }
+
+    /// Internal implementation of transferring tokens between two accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account to transfer tokens from.
+    /// * `to` - Account to transfer tokens to.
+    /// * `value` - The number of tokens to transfer.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc20::Error::InvalidSender`] - If the `from` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InvalidReceiver`] - If the `to` address is
+    ///   `Address::ZERO`.
+    /// * [`erc20::Error::InsufficientBalance`] - If the `from` address doesn't
+    ///   have enough tokens.
+    ///
+    /// # Events
+    ///
+    /// * [`erc20::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        value: U256,
+    ) -> Result<(), Error> {
+        if from.is_zero() {
+            return Err(erc20::Error::InvalidSender(ERC20InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        if to.is_zero() {
+            return Err(erc20::Error::InvalidReceiver(ERC20InvalidReceiver {
+                receiver: Address::ZERO,
+            })
+            .into());
+        }
+
+        self._update(from, to, value)
+    }
+
+    /// Delegates all of `account`'s voting units to `delegatee`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account whose voting units are delegated.
+    /// * `delegatee` - Account to delegate votes to.
+    ///
+    /// # Errors
+    ///
+    /// * [`votes::Error::Checkpoints`] - If a checkpoint could not be pushed.
+    ///
+    /// # Events
+    ///
+    /// * [`votes::DelegateChanged`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _delegate(
+        &mut self,
+        account: Address,
+        delegatee: Address,
+    ) -> Result<(), Error> {
+        let units = self._get_voting_units(account);
+        Ok(self.votes._delegate(account, delegatee, units)?)
+    }
+
+    /// Returns the voting units of an `account`, i.e. its token balance.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `account` - Account to get the voting units of.
+    fn _get_voting_units(&self, account: Address) -> U256 {
+        self.erc20.balance_of(account)
+    }
+}
+
+impl<T: IEip712Domain + StorageType> IErc165 for Erc20Votes<T> {
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        <Self as IVotes>::INTERFACE_ID == u32::from_be_bytes(*interface_id)
+            || <Self as IErc6372>::INTERFACE_ID
+                == u32::from_be_bytes(*interface_id)
+            || <Self as IErc20>::INTERFACE_ID
+                == u32::from_be_bytes(*interface_id)
+            || Erc165::supports_interface(interface_id)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{uint, Address, U256};
+    use alloy_sol_types::SolCall;
+    use motsu::prelude::*;
+    use stylus_sdk::prelude::*;
+
+    use super::{Erc20Votes, Error};
+    use crate::{
+        governance::utils::votes::{
+            self, DelegateChanged, DelegateVotesChanged, IVotes,
+        },
+        metatx::erc2771_context::{relay, Erc2771Context},
+        token::erc20::{self, IErc20, Transfer},
+        utils::{
+            cryptography::eip712::IEip712, introspection::erc165::IErc165,
+        },
+    };
+
+    mod abi {
+        #![allow(missing_docs)]
+
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function transfer(address to, uint256 value) external returns (bool);
+        }
+    }
+
+    #[storage]
+    struct Eip712;
+
+    impl IEip712 for Eip712 {
+        const NAME: &'static str = "ERC-20 Votes";
+        const VERSION: &'static str = "1";
+    }
+
+    /// Votes token receiving meta-transactions through [`Erc2771Context`].
+    #[storage]
+    struct RelayedVotesMock {
+        #[borrow]
+        erc2771: Erc2771Context,
+        votes: Erc20Votes<Eip712>,
+    }
+
+    unsafe impl TopLevelStorage for RelayedVotesMock {}
+
+    #[public]
+    #[inherit(Erc2771Context)]
+    impl RelayedVotesMock {
+        fn transfer(
+            &mut self,
+            to: Address,
+            value: U256,
+        ) -> Result<bool, Error> {
+            self.votes.transfer(to, value)
+        }
+    }
+
+    #[motsu::test]
+    fn delegate_moves_voting_units(
+        contract: Contract<Erc20Votes<Eip712>>,
+        alice: Address,
+    ) {
+        let value = uint!(10_U256);
+        contract.sender(alice)._mint(alice, value).motsu_unwrap();
+
+        // Undelegated tokens carry no voting power.
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+
+        assert_eq!(alice, contract.sender(alice).delegates(alice));
+        assert_eq!(value, contract.sender(alice).get_votes(alice));
+        assert_eq!(1, contract.sender(alice).num_checkpoints(alice));
+
+        contract.assert_emitted(&DelegateChanged {
+            delegator: alice,
+            from_delegate: Address::ZERO,
+            to_delegate: alice,
+        });
+        contract.assert_emitted(&DelegateVotesChanged {
+            delegate: alice,
+            previous_votes: U256::ZERO,
+            new_votes: value,
+        });
+    }
+
+    #[motsu::test]
+    fn redelegate_moves_voting_units(
+        contract: Contract<Erc20Votes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let value = uint!(10_U256);
+        contract.sender(alice)._mint(alice, value).motsu_unwrap();
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+
+        contract.sender(alice).delegate(bob).motsu_unwrap();
+
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+        assert_eq!(value, contract.sender(alice).get_votes(bob));
+        contract.assert_emitted(&DelegateChanged {
+            delegator: alice,
+            from_delegate: alice,
+            to_delegate: bob,
+        });
+    }
+
+    #[motsu::test]
+    fn transfer_moves_delegated_votes(
+        contract: Contract<Erc20Votes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let value = uint!(10_U256);
+        let one = uint!(1_U256);
+        contract.sender(alice)._mint(alice, value).motsu_unwrap();
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+        contract.sender(bob).delegate(bob).motsu_unwrap();
+
+        contract.sender(alice).transfer(bob, one).motsu_unwrap();
+
+        assert_eq!(value - one, contract.sender(alice).get_votes(alice));
+        assert_eq!(one, contract.sender(alice).get_votes(bob));
+        assert_eq!(one, contract.sender(alice).balance_of(bob));
+        contract.assert_emitted(&Transfer { from: alice, to: bob, value: one });
+    }
+
+    #[motsu::test]
+    fn relayed_transfer_moves_delegated_votes_of_relayed_sender(
+        contract: Contract<RelayedVotesMock>,
+        alice: Address,
+        bob: Address,
+        forwarder: Address,
+    ) {
+        let value = uint!(10_U256);
+        let one = uint!(1_U256);
+        contract.init(alice, |mock| {
+            mock.erc2771._set_trusted_forwarder(forwarder);
+            mock.votes._mint(alice, value).motsu_unwrap();
+        });
+        contract.sender(alice).votes.delegate(alice).motsu_unwrap();
+        contract.sender(bob).votes.delegate(bob).motsu_unwrap();
+
+        let call = abi::transferCall { to: bob, value: one }.abi_encode();
+        contract
+            .init(forwarder, |_| relay::<RelayedVotesMock>(&call, alice))
+            .expect("should route the relayed call");
+
+        let votes = &contract.sender(alice).votes;
+        assert_eq!(value - one, votes.balance_of(alice));
+        assert_eq!(one, votes.balance_of(bob));
+        assert_eq!(U256::ZERO, votes.balance_of(forwarder));
+        assert_eq!(value - one, votes.get_votes(alice));
+        assert_eq!(one, votes.get_votes(bob));
+        contract.assert_emitted(&Transfer { from: alice, to: bob, value: one });
+    }
+
+    #[motsu::test]
+    fn transfer_from_moves_delegated_votes(
+        contract: Contract<Erc20Votes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let value = uint!(10_U256);
+        contract.sender(alice)._mint(alice, value).motsu_unwrap();
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+        contract.sender(alice).approve(bob, value).motsu_unwrap();
+
+        contract.sender(bob).transfer_from(alice, bob, value).motsu_unwrap();
+
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(bob));
+        assert_eq!(value, contract.sender(alice).balance_of(bob));
+    }
+
+    #[motsu::test]
+    fn burn_removes_delegated_votes(
+        contract: Contract<Erc20Votes<Eip712>>,
+        alice: Address,
+    ) {
+        let value = uint!(10_U256);
+        let one = uint!(1_U256);
+        contract.sender(alice)._mint(alice, value).motsu_unwrap();
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+
+        contract.sender(alice)._burn(alice, one).motsu_unwrap();
+
+        assert_eq!(value - one, contract.sender(alice).get_votes(alice));
+        assert_eq!(value - one, contract.sender(alice).total_supply());
+    }
+
+    #[motsu::test]
+    fn mint_errors_exceeded_safe_supply(
+        contract: Contract<Erc20Votes<Eip712>>,
+        alice: Address,
+    ) {
+        let cap = contract.sender(alice)._max_supply();
+        let err = contract
+            .sender(alice)
+            ._mint(alice, cap + uint!(1_U256))
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::ExceededSafeSupply(_)));
+    }
+
+    #[motsu::test]
+    fn transfer_errors_insufficient_balance(
+        contract: Contract<Erc20Votes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            .transfer(bob, uint!(1_U256))
+            .motsu_unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Erc20(erc20::Error::InsufficientBalance(_))
+        ));
+    }
+
+    #[motsu::test]
+    fn get_past_votes_errors_future_lookup(
+        contract: Contract<Erc20Votes<Eip712>>,
+        alice: Address,
+    ) {
+        let clock = U256::from(contract.sender(alice).clock());
+        let err = contract
+            .sender(alice)
+            .get_past_votes(alice, clock)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::Votes(votes::Error::FutureLookup(_))));
+
+        let err = contract
+            .sender(alice)
+            .get_past_total_supply(clock)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::Votes(votes::Error::FutureLookup(_))));
+    }
+
+    #[motsu::test]
+    fn clock_mode_is_block_number(
+        contract: Contract<Erc20Votes<Eip712>>,
+        alice: Address,
+    ) {
+        assert_eq!(
+            "mode=blocknumber&from=default",
+            contract.sender(alice).clock_mode()
+        );
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc20Votes::<Eip712>::supports_interface(
+            <Erc20Votes<Eip712> as IVotes>::INTERFACE_ID.into()
+        ));
+        assert!(Erc20Votes::<Eip712>::supports_interface(
+            <Erc20Votes<Eip712> as IErc20>::INTERFACE_ID.into()
+        ));
+        assert!(Erc20Votes::<Eip712>::supports_interface(
+            <Erc20Votes<Eip712> as IErc165>::INTERFACE_ID.into()
+        ));
+
+        let fake_interface_id = 0x12345678u32;
+        assert!(!Erc20Votes::<Eip712>::supports_interface(
+            fake_interface_id.into()
+        ));
+    }
+}
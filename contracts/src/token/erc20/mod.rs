@@ -10,12 +10,13 @@ use alloy_primitives::{Address, FixedBytes, U256};
 use openzeppelin_stylus_proc::interface_id;
 use stylus_sdk::{
     call::MethodError,
-    evm, msg,
+    evm,
     prelude::*,
     storage::{StorageMap, StorageU256},
 };
 
 use crate::utils::{
+    context,
     introspection::erc165::{Erc165, IErc165},
     math::storage::{AddAssignChecked, AddAssignUnchecked, SubAssignUnchecked},
 };
@@ -284,7 +285,7 @@ impl IErc20 for Erc20 {
         to: Address,
         value: U256,
     ) -> Result<bool, Self::Error> {
-        let from = msg::sender();
+        let from = context::msg_sender();
         self._transfer(from, to, value)?;
         Ok(true)
     }
@@ -298,7 +299,7 @@ impl IErc20 for Erc20 {
         spender: Address,
         value: U256,
     ) -> Result<bool, Self::Error> {
-        let owner = msg::sender();
+        let owner = context::msg_sender();
         self._approve(owner, spender, value, true)
     }
 
@@ -308,7 +309,7 @@ impl IErc20 for Erc20 {
         to: Address,
         value: U256,
     ) -> Result<bool, Self::Error> {
-        let spender = msg::sender();
+        let spender = context::msg_sender();
         self._spend_allowance(from, spender, value)?;
         self._transfer(from, to, value)?;
         Ok(true)
@@ -589,6 +590,7 @@ mod tests {
     use stylus_sdk::prelude::*;
 
     use super::{Approval, Erc20, Error, IErc165, IErc20, Transfer};
+    use crate::utils::context;
 
     unsafe impl TopLevelStorage for Erc20 {}
 
@@ -780,6 +782,29 @@ mod tests {
         contract.assert_emitted(&Transfer { from: alice, to: bob, value: one });
     }
 
+    #[motsu::test]
+    fn transfer_from_relayed_sender(
+        contract: Contract<Erc20>,
+        alice: Address,
+        bob: Address,
+        forwarder: Address,
+    ) {
+        let one = uint!(1_U256);
+        contract
+            .sender(alice)
+            ._mint(alice, one)
+            .motsu_expect("should mint tokens");
+
+        contract.init(forwarder, |erc20| {
+            context::with_relayed_sender(alice, || erc20.transfer(bob, one))
+                .expect("should transfer tokens of the relayed sender");
+        });
+
+        assert_eq!(U256::ZERO, contract.sender(alice).balance_of(alice));
+        assert_eq!(one, contract.sender(alice).balance_of(bob));
+        contract.assert_emitted(&Transfer { from: alice, to: bob, value: one });
+    }
+
     #[motsu::test]
     fn transfer_errors_insufficient_balance(
         contract: Contract<Erc20>,
@@ -1,9 +1,11 @@
 //! Optional Burnable extension of the ERC-721 standard.
 
 use alloy_primitives::{Address, U256};
-use stylus_sdk::msg;
 
-use crate::token::erc721::{self, Erc721};
+use crate::{
+    token::erc721::{self, Erc721},
+    utils::context,
+};
 
 /// An [`Erc721`] token that can be burned (destroyed).
 pub trait IErc721Burnable {
@@ -39,7 +41,7 @@ impl IErc721Burnable for Erc721 {
         //
         // Therefore, it is not needed to verify that the return value is not 0
         // here.
-        self._update(Address::ZERO, token_id, msg::sender())?;
+        self._update(Address::ZERO, token_id, context::msg_sender())?;
         Ok(())
     }
 }
@@ -47,16 +49,91 @@ impl IErc721Burnable for Erc721 {
 #[cfg(all(test, feature = "std"))]
 mod tests {
     use alloy_primitives::{uint, Address, U256};
+    use alloy_sol_types::SolCall;
     use motsu::prelude::Contract;
+    use stylus_sdk::prelude::*;
 
     use super::IErc721Burnable;
-    use crate::token::erc721::{
-        ERC721InsufficientApproval, ERC721NonexistentToken, Erc721, Error,
-        IErc721,
+    use crate::{
+        metatx::erc2771_context::{relay, Erc2771Context},
+        token::erc721::{
+            ERC721InsufficientApproval, ERC721NonexistentToken, Erc721, Error,
+            IErc721,
+        },
     };
 
     const TOKEN_ID: U256 = uint!(1_U256);
 
+    mod abi {
+        #![allow(missing_docs)]
+
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function burn(uint256 tokenId) external;
+        }
+    }
+
+    /// Burnable token receiving meta-transactions through
+    /// [`Erc2771Context`].
+    #[storage]
+    struct RelayedBurnableMock {
+        #[borrow]
+        erc2771: Erc2771Context,
+        erc721: Erc721,
+    }
+
+    unsafe impl TopLevelStorage for RelayedBurnableMock {}
+
+    #[public]
+    #[inherit(Erc2771Context)]
+    impl RelayedBurnableMock {
+        fn burn(&mut self, token_id: U256) -> Result<(), Error> {
+            self.erc721.burn(token_id)
+        }
+    }
+
+    #[motsu::test]
+    fn relayed_burn_authorizes_relayed_sender(
+        contract: Contract<RelayedBurnableMock>,
+        alice: Address,
+        bob: Address,
+        forwarder: Address,
+    ) {
+        contract.init(alice, |mock| {
+            mock.erc2771._set_trusted_forwarder(forwarder);
+            mock.erc721._mint(alice, TOKEN_ID).expect("should mint a token");
+            mock.erc721
+                .set_approval_for_all(forwarder, true)
+                .expect("should approve the forwarder");
+        });
+        let call = abi::burnCall { tokenId: TOKEN_ID }.abi_encode();
+
+        let err = contract
+            .init(forwarder, |_| relay::<RelayedBurnableMock>(&call, bob))
+            .expect_err("should not burn a token of another account");
+        let expected: Vec<u8> =
+            Error::InsufficientApproval(ERC721InsufficientApproval {
+                operator: bob,
+                token_id: TOKEN_ID,
+            })
+            .into();
+        assert_eq!(expected, err);
+        assert_eq!(
+            alice,
+            contract
+                .sender(alice)
+                .erc721
+                .owner_of(TOKEN_ID)
+                .expect("should return the owner")
+        );
+
+        contract
+            .init(forwarder, |_| relay::<RelayedBurnableMock>(&call, alice))
+            .expect("should burn the token of the relayed sender");
+        assert!(contract.sender(alice).erc721.owner_of(TOKEN_ID).is_err());
+    }
+
     #[motsu::test]
     fn burns(contract: Contract<Erc721>, alice: Address) {
         let one = uint!(1_U256);
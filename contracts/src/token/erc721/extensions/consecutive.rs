@@ -31,7 +31,7 @@ use alloy_primitives::{uint, Address, FixedBytes, U256};
 use stylus_sdk::{
     abi::Bytes,
     call::MethodError,
-    evm, msg,
+    evm,
     prelude::*,
     stylus_proc::{public, SolidityError},
 };
@@ -43,6 +43,7 @@ use crate::{
         Erc721, IErc721, Transfer,
     },
     utils::{
+        context,
         introspection::erc165::{Erc165, IErc165},
         math::storage::{AddAssignUnchecked, SubAssignUnchecked},
         structs::{
@@ -200,7 +201,7 @@ impl IErc721 for Erc721Consecutive {
     ) -> Result<(), Error> {
         self.transfer_from(from, to, token_id)?;
         Ok(self.erc721._check_on_erc721_received(
-            msg::sender(),
+            context::msg_sender(),
             from,
             to,
             token_id,
@@ -224,7 +225,8 @@ impl IErc721 for Erc721Consecutive {
         // Setting an "auth" argument enables the `_is_authorized` check which
         // verifies that the token exists (`!from.is_zero()`). Therefore, it is
         // not needed to verify that the return value is not 0 here.
-        let previous_owner = self._update(to, token_id, msg::sender())?;
+        let previous_owner =
+            self._update(to, token_id, context::msg_sender())?;
         if previous_owner != from {
             return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
                 sender: from,
@@ -237,7 +239,7 @@ impl IErc721 for Erc721Consecutive {
     }
 
     fn approve(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
-        self._approve(to, token_id, msg::sender(), true)
+        self._approve(to, token_id, context::msg_sender(), true)
     }
 
     fn set_approval_for_all(
@@ -557,7 +559,7 @@ impl Erc721Consecutive {
     ) -> Result<(), Error> {
         self._mint(to, token_id)?;
         Ok(self.erc721._check_on_erc721_received(
-            msg::sender(),
+            context::msg_sender(),
             Address::ZERO,
             to,
             token_id,
@@ -688,7 +690,7 @@ impl Erc721Consecutive {
     ) -> Result<(), Error> {
         self._transfer(from, to, token_id)?;
         Ok(self.erc721._check_on_erc721_received(
-            msg::sender(),
+            context::msg_sender(),
             from,
             to,
             token_id,
@@ -784,19 +786,22 @@ impl IErc165 for Erc721Consecutive {
 
 #[cfg(all(test, feature = "std"))]
 mod tests {
-    use alloy_primitives::{uint, Address, U256};
+    use alloy_primitives::{fixed_bytes, uint, Address, FixedBytes, U256};
+    use alloy_sol_types::SolCall;
     use motsu::prelude::Contract;
+    use stylus_sdk::{abi::Bytes, prelude::*, storage::StorageAddress};
 
     use crate::{
+        metatx::erc2771_context::{relay, Erc2771Context},
         token::{
             erc721,
             erc721::{
                 extensions::consecutive::{
                     ERC721ExceededMaxBatchMint, Erc721Consecutive, Error, U96,
                 },
-                ERC721IncorrectOwner, ERC721InvalidApprover,
-                ERC721InvalidReceiver, ERC721InvalidSender,
-                ERC721NonexistentToken, IErc721,
+                ERC721IncorrectOwner, ERC721InsufficientApproval,
+                ERC721InvalidApprover, ERC721InvalidReceiver,
+                ERC721InvalidSender, ERC721NonexistentToken, IErc721,
             },
         },
         utils::introspection::erc165::IErc165,
@@ -807,6 +812,117 @@ mod tests {
     const TOKEN_ID: U256 = uint!(1_U256);
     const NON_CONSECUTIVE_TOKEN_ID: U256 = uint!(10001_U256);
 
+    mod abi {
+        #![allow(missing_docs)]
+
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function transferFrom(address from, address to, uint256 tokenId) external;
+            function safeTransferFrom(address from, address to, uint256 tokenId) external;
+            function approve(address to, uint256 tokenId) external;
+            function safeMint(address to, uint256 tokenId) external;
+            function safeTransfer(address from, address to, uint256 tokenId) external;
+        }
+    }
+
+    /// Token receiving meta-transactions through [`Erc2771Context`].
+    #[storage]
+    struct RelayedConsecutiveMock {
+        #[borrow]
+        erc2771: Erc2771Context,
+        erc721: Erc721Consecutive,
+    }
+
+    unsafe impl TopLevelStorage for RelayedConsecutiveMock {}
+
+    #[public]
+    #[inherit(Erc2771Context)]
+    impl RelayedConsecutiveMock {
+        fn transfer_from(
+            &mut self,
+            from: Address,
+            to: Address,
+            token_id: U256,
+        ) -> Result<(), Error> {
+            self.erc721.transfer_from(from, to, token_id)
+        }
+
+        fn safe_transfer_from(
+            &mut self,
+            from: Address,
+            to: Address,
+            token_id: U256,
+        ) -> Result<(), Error> {
+            self.erc721.safe_transfer_from(from, to, token_id)
+        }
+
+        fn approve(
+            &mut self,
+            to: Address,
+            token_id: U256,
+        ) -> Result<(), Error> {
+            self.erc721.approve(to, token_id)
+        }
+
+        fn safe_mint(
+            &mut self,
+            to: Address,
+            token_id: U256,
+        ) -> Result<(), Error> {
+            self.erc721._safe_mint(to, token_id, &vec![].into())
+        }
+
+        fn safe_transfer(
+            &mut self,
+            from: Address,
+            to: Address,
+            token_id: U256,
+        ) -> Result<(), Error> {
+            self.erc721._safe_transfer(from, to, token_id, &vec![].into())
+        }
+    }
+
+    /// Receiver recording the operator of the last received token.
+    #[storage]
+    struct ReceiverMock {
+        operator: StorageAddress,
+    }
+
+    unsafe impl TopLevelStorage for ReceiverMock {}
+
+    #[public]
+    impl ReceiverMock {
+        #[selector(name = "onERC721Received")]
+        fn on_erc721_received(
+            &mut self,
+            operator: Address,
+            _from: Address,
+            _token_id: U256,
+            _data: Bytes,
+        ) -> FixedBytes<4> {
+            self.operator.set(operator);
+            fixed_bytes!("150b7a02")
+        }
+    }
+
+    /// Mints [`TOKEN_ID`] to `owner` and trusts `forwarder`, which is also
+    /// approved to manage the tokens of `owner`, so that only a correctly
+    /// resolved sender makes unauthorized relayed calls revert.
+    fn init_relayed(
+        contract: &Contract<RelayedConsecutiveMock>,
+        owner: Address,
+        forwarder: Address,
+    ) {
+        contract.init(owner, |mock| {
+            mock.erc2771._set_trusted_forwarder(forwarder);
+            mint_consecutive(&mut mock.erc721, vec![owner], vec![uint!(2_U96)]);
+            mock.erc721
+                .set_approval_for_all(forwarder, true)
+                .expect("should approve the forwarder");
+        });
+    }
+
     fn mint_consecutive(
         contract: &mut Erc721Consecutive,
         receivers: Vec<Address>,
@@ -821,6 +937,119 @@ mod tests {
         }
     }
 
+    #[motsu::test]
+    fn relayed_transfer_from_authorizes_relayed_sender(
+        contract: Contract<RelayedConsecutiveMock>,
+        alice: Address,
+        bob: Address,
+        forwarder: Address,
+    ) {
+        init_relayed(&contract, alice, forwarder);
+        let call =
+            abi::transferFromCall { from: alice, to: bob, tokenId: TOKEN_ID }
+                .abi_encode();
+
+        let err = contract
+            .init(forwarder, |_| relay::<RelayedConsecutiveMock>(&call, bob))
+            .expect_err("should not transfer a token of another account");
+        let expected: Vec<u8> = Error::Erc721(
+            erc721::Error::InsufficientApproval(ERC721InsufficientApproval {
+                operator: bob,
+                token_id: TOKEN_ID,
+            }),
+        )
+        .into();
+        assert_eq!(expected, err);
+
+        contract
+            .init(forwarder, |_| relay::<RelayedConsecutiveMock>(&call, alice))
+            .expect("should transfer the token of the relayed sender");
+        assert_eq!(
+            bob,
+            contract
+                .sender(alice)
+                .erc721
+                .owner_of(TOKEN_ID)
+                .expect("should return the owner")
+        );
+    }
+
+    #[motsu::test]
+    fn relayed_approve_authorizes_relayed_sender(
+        contract: Contract<RelayedConsecutiveMock>,
+        alice: Address,
+        bob: Address,
+        forwarder: Address,
+    ) {
+        init_relayed(&contract, alice, forwarder);
+        let call = abi::approveCall { to: bob, tokenId: TOKEN_ID }.abi_encode();
+
+        let err = contract
+            .init(forwarder, |_| relay::<RelayedConsecutiveMock>(&call, bob))
+            .expect_err("should not approve a token of another account");
+        let expected: Vec<u8> = Error::Erc721(erc721::Error::InvalidApprover(
+            ERC721InvalidApprover { approver: bob },
+        ))
+        .into();
+        assert_eq!(expected, err);
+
+        contract
+            .init(forwarder, |_| relay::<RelayedConsecutiveMock>(&call, alice))
+            .expect("should approve the token of the relayed sender");
+        assert_eq!(
+            bob,
+            contract
+                .sender(alice)
+                .erc721
+                .get_approved(TOKEN_ID)
+                .expect("should return the approved account")
+        );
+    }
+
+    #[motsu::test]
+    fn relayed_safe_transfers_pass_relayed_sender_as_operator(
+        contract: Contract<RelayedConsecutiveMock>,
+        receiver: Contract<ReceiverMock>,
+        alice: Address,
+        forwarder: Address,
+    ) {
+        init_relayed(&contract, alice, forwarder);
+
+        let call = abi::safeTransferFromCall {
+            from: alice,
+            to: receiver.address(),
+            tokenId: TOKEN_ID,
+        }
+        .abi_encode();
+        contract
+            .init(forwarder, |_| relay::<RelayedConsecutiveMock>(&call, alice))
+            .expect("should transfer the token of the relayed sender");
+        assert_eq!(alice, receiver.sender(alice).operator.get());
+
+        receiver.sender(alice).operator.set(Address::ZERO);
+        let call = abi::safeMintCall {
+            to: receiver.address(),
+            tokenId: NON_CONSECUTIVE_TOKEN_ID,
+        }
+        .abi_encode();
+        contract
+            .init(forwarder, |_| relay::<RelayedConsecutiveMock>(&call, alice))
+            .expect("should mint a token");
+        assert_eq!(alice, receiver.sender(alice).operator.get());
+
+        receiver.sender(alice).operator.set(Address::ZERO);
+        let call = abi::safeTransferCall {
+            from: alice,
+            to: receiver.address(),
+            tokenId: U256::ZERO,
+        }
+        .abi_encode();
+        contract
+            .init(forwarder, |_| relay::<RelayedConsecutiveMock>(&call, alice))
+            .expect("should transfer a token");
+        assert_eq!(alice, receiver.sender(alice).operator.get());
+    }
+
     #[motsu::test]
     fn mints(contract: Contract<Erc721Consecutive>, alice: Address) {
         let initial_balance = contract
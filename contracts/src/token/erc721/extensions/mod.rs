@@ -4,6 +4,7 @@ pub mod consecutive;
 pub mod enumerable;
 pub mod metadata;
 pub mod uri_storage;
+pub mod votes;
 pub mod wrapper;
 
 pub use burnable::IErc721Burnable;
@@ -11,4 +12,5 @@ pub use consecutive::Erc721Consecutive;
 pub use enumerable::{Erc721Enumerable, IErc721Enumerable};
 pub use metadata::{Erc721Metadata, IErc721Metadata};
 pub use uri_storage::Erc721UriStorage;
+pub use votes::{Erc721ConsecutiveVotes, Erc721Votes};
 pub use wrapper::Erc721Wrapper;
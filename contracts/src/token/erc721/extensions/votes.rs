@@ -0,0 +1,1187 @@
+//! Extension of ERC-721 to support voting and delegation as implemented by
+//! [`Votes`], where each individual NFT counts as 1 vote unit.
+//!
+//! Tokens do not count as votes until they are delegated, because votes must
+//! be tracked which incurs an additional cost on every transfer. Token holders
+//! can either delegate to a trusted representative who will decide how to
+//! make use of the votes in governance decisions, or they can delegate to
+//! themselves to be their own representative.
+//!
+//! Two flavours are provided:
+//!
+//! * [`Erc721Votes`], which uses [`Erc721`] as a member and overrides
+//!   [`Erc721::_update`].
+//! * [`Erc721ConsecutiveVotes`], which uses [`Erc721Consecutive`] as a member
+//!   and overrides both [`Erc721Consecutive::_update`] and
+//!   [`Erc721Consecutive::_mint_consecutive`], so that batch mints credit one
+//!   voting unit per minted token.
+//!
+//! In both cases, all token movements (transfers, mints and burns) have to go
+//! through the votes contract for the vote checkpoints to be kept consistent
+//! with balances.
+use alloc::{string::String, vec, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use alloy_primitives::{
+    aliases::{U208, U48, U96},
+    Address, FixedBytes, B256, U256,
+};
+use stylus_sdk::{abi::Bytes, call::MethodError, prelude::*};
+
+use crate::{
+    governance::utils::votes::{self, IErc6372, IVotes, Votes},
+    token::erc721::{
+        self,
+        extensions::{consecutive, Erc721Consecutive},
+        ERC721IncorrectOwner, ERC721InvalidReceiver, ERC721InvalidSender,
+        ERC721NonexistentToken, Erc721, IErc721,
+    },
+    utils::{
+        context,
+        cryptography::eip712::IEip712Domain,
+        introspection::erc165::{Erc165, IErc165},
+    },
+};
+
+/// An [`Erc721Votes`] or [`Erc721ConsecutiveVotes`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Error type from [`Erc721`] contract [`erc721::Error`].
+    Erc721(erc721::Error),
+    /// Error type from [`Erc721Consecutive`] contract [`consecutive::Error`].
+    Consecutive(consecutive::Error),
+    /// Error type from [`Votes`] contract [`votes::Error`].
+    Votes(votes::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// Inner token of a votes extension, i.e. [`Erc721`] for [`Erc721Votes`] and
+/// [`Erc721Consecutive`] for [`Erc721ConsecutiveVotes`].
+///
+/// The logic shared by both flavours is implemented once in [`base`], on top
+/// of this trait.
+trait Erc721Token {
+    /// Returns the underlying [`Erc721`] contract.
+    fn erc721_mut(&mut self) -> &mut Erc721;
+
+    /// Returns the token balance of `account`.
+    fn balance(&self, account: Address) -> U256;
+
+    /// Calls the inner token's `_update` function.
+    fn update_token(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Error>;
+}
+
+impl Erc721Token for Erc721 {
+    fn erc721_mut(&mut self) -> &mut Erc721 {
+        self
+    }
+
+    fn balance(&self, account: Address) -> U256 {
+        self.balances.get(account)
+    }
+
+    fn update_token(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Error> {
+        Ok(self._update(to, token_id, auth)?)
+    }
+}
+
+impl Erc721Token for Erc721Consecutive {
+    fn erc721_mut(&mut self) -> &mut Erc721 {
+        &mut self.erc721
+    }
+
+    fn balance(&self, account: Address) -> U256 {
+        self.erc721.balances.get(account)
+    }
+
+    fn update_token(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Error> {
+        Ok(self._update(to, token_id, auth)?)
+    }
+}
+
+/// Votes logic shared by [`Erc721Votes`] and [`Erc721ConsecutiveVotes`],
+/// generic over the inner token.
+///
+/// See the functions of the same name on [`Erc721Votes`] for documentation.
+mod base {
+    use alloy_primitives::{uint, Address, U256};
+    use stylus_sdk::{abi::Bytes, storage::StorageType};
+
+    use super::{
+        erc721, ERC721IncorrectOwner, ERC721InvalidReceiver,
+        ERC721InvalidSender, ERC721NonexistentToken, Erc721Token, Error,
+    };
+    use crate::{
+        governance::utils::votes::Votes,
+        utils::cryptography::eip712::IEip712Domain,
+    };
+
+    pub(super) fn _update<I: Erc721Token, T: IEip712Domain + StorageType>(
+        token: &mut I,
+        votes: &mut Votes<T>,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Error> {
+        let previous_owner = token.update_token(to, token_id, auth)?;
+        votes._transfer_voting_units(previous_owner, to, uint!(1_U256))?;
+        Ok(previous_owner)
+    }
+
+    pub(super) fn transfer_from<
+        I: Erc721Token,
+        T: IEip712Domain + StorageType,
+    >(
+        token: &mut I,
+        votes: &mut Votes<T>,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        sender: Address,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        // Setting an "auth" argument enables the `_is_authorized` check which
+        // verifies that the token exists (`from != 0`). Therefore, it is
+        // not needed to verify that the return value is not 0 here.
+        let previous_owner = _update(token, votes, to, token_id, sender)?;
+        if previous_owner != from {
+            return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    pub(super) fn safe_transfer_from<
+        I: Erc721Token,
+        T: IEip712Domain + StorageType,
+    >(
+        token: &mut I,
+        votes: &mut Votes<T>,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+        sender: Address,
+    ) -> Result<(), Error> {
+        transfer_from(token, votes, from, to, token_id, sender)?;
+        Ok(token
+            .erc721_mut()
+            ._check_on_erc721_received(sender, from, to, token_id, data)?)
+    }
+
+    pub(super) fn _mint<I: Erc721Token, T: IEip712Domain + StorageType>(
+        token: &mut I,
+        votes: &mut Votes<T>,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        let previous_owner =
+            _update(token, votes, to, token_id, Address::ZERO)?;
+        if !previous_owner.is_zero() {
+            return Err(erc721::Error::InvalidSender(ERC721InvalidSender {
+                sender: Address::ZERO,
+            })
+            .into());
+        }
+        Ok(())
+    }
+
+    pub(super) fn _burn<I: Erc721Token, T: IEip712Domain + StorageType>(
+        token: &mut I,
+        votes: &mut Votes<T>,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        let previous_owner =
+            _update(token, votes, Address::ZERO, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(erc721::Error::NonexistentToken(
+                ERC721NonexistentToken { token_id },
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    pub(super) fn _transfer<I: Erc721Token, T: IEip712Domain + StorageType>(
+        token: &mut I,
+        votes: &mut Votes<T>,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        if to.is_zero() {
+            return Err(erc721::Error::InvalidReceiver(
+                ERC721InvalidReceiver { receiver: Address::ZERO },
+            )
+            .into());
+        }
+
+        let previous_owner =
+            _update(token, votes, to, token_id, Address::ZERO)?;
+        if previous_owner.is_zero() {
+            return Err(erc721::Error::NonexistentToken(
+                ERC721NonexistentToken { token_id },
+            )
+            .into());
+        } else if previous_owner != from {
+            return Err(erc721::Error::IncorrectOwner(ERC721IncorrectOwner {
+                sender: from,
+                token_id,
+                owner: previous_owner,
+            })
+            .into());
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn _delegate<I: Erc721Token, T: IEip712Domain + StorageType>(
+        token: &I,
+        votes: &mut Votes<T>,
+        account: Address,
+        delegatee: Address,
+    ) -> Result<(), Error> {
+        let units = token.balance(account);
+        Ok(votes._delegate(account, delegatee, units)?)
+    }
+}
+
+/// Implements the [`IErc6372`], [`IVotes`] and [`IErc165`] traits, and the
+/// signature delegation and checkpoint getters, for a votes extension with a
+/// `votes` field.
+macro_rules! impl_votes {
+    ($ty:ident) => {
+        impl<T: IEip712Domain + StorageType> IErc6372 for $ty<T> {
+            fn clock(&self) -> U48 {
+                self.votes.clock()
+            }
+
+            fn clock_mode(&self) -> String {
+                self.votes.clock_mode()
+            }
+        }
+
+        impl<T: IEip712Domain + StorageType> IVotes for $ty<T> {
+            type Error = Error;
+
+            fn get_votes(&self, account: Address) -> U256 {
+                self.votes.get_votes(account)
+            }
+
+            fn get_past_votes(
+                &self,
+                account: Address,
+                timepoint: U256,
+            ) -> Result<U256, Self::Error> {
+                Ok(self.votes.get_past_votes(account, timepoint)?)
+            }
+
+            fn get_past_total_supply(
+                &self,
+                timepoint: U256,
+            ) -> Result<U256, Self::Error> {
+                Ok(self.votes.get_past_total_supply(timepoint)?)
+            }
+
+            fn delegates(&self, account: Address) -> Address {
+                self.votes.delegates(account)
+            }
+
+            fn delegate(
+                &mut self,
+                delegatee: Address,
+            ) -> Result<(), Self::Error> {
+                self._delegate(context::msg_sender(), delegatee)
+            }
+        }
+
+        impl<T: IEip712Domain + StorageType> $ty<T> {
+            /// Delegates votes from signer to `delegatee`.
+            ///
+            /// # Arguments
+            ///
+            /// * `&mut self` - Write access to the contract's state.
+            /// * `delegatee` - Account to delegate votes to.
+            /// * `nonce` - Nonce of the signer used in the signed message.
+            /// * `expiry` - Expiration time of the signature.
+            /// * `v` - v value from the signer's signature.
+            /// * `r` - r value from the signer's signature.
+            /// * `s` - s value from the signer's signature.
+            ///
+            /// # Errors
+            ///
+            /// * [`votes::Error::ExpiredSignature`] - If the `expiry` is in the
+            ///   past.
+            /// * [`votes::Error::ECDSA`] - If the signature is invalid.
+            /// * [`votes::Error::Nonces`] - If the `nonce` is not the next
+            ///   valid nonce of the signer.
+            ///
+            /// # Events
+            ///
+            /// * [`votes::DelegateChanged`].
+            /// * [`votes::DelegateVotesChanged`].
+            #[allow(clippy::too_many_arguments)]
+            pub fn delegate_by_sig(
+                &mut self,
+                delegatee: Address,
+                nonce: U256,
+                expiry: U256,
+                v: u8,
+                r: B256,
+                s: B256,
+            ) -> Result<(), Error> {
+                let signer = self
+                    .votes
+                    ._recover_delegator(delegatee, nonce, expiry, v, r, s)?;
+                self._delegate(signer, delegatee)
+            }
+
+            /// Returns the number of checkpoints for `account`.
+            ///
+            /// # Arguments
+            ///
+            /// * `&self` - Read access to the contract's state.
+            /// * `account` - Account to get the number of checkpoints of.
+            ///
+            /// # Panics
+            ///
+            /// * If the number of checkpoints exceeds `u32::MAX`.
+            #[must_use]
+            pub fn num_checkpoints(&self, account: Address) -> u32 {
+                self.votes.num_checkpoints(account)
+            }
+
+            /// Returns the `pos`-th checkpoint for `account` as a (key,
+            /// value) pair.
+            ///
+            /// # Arguments
+            ///
+            /// * `&self` - Read access to the contract's state.
+            /// * `account` - Account to get the checkpoint of.
+            /// * `pos` - Index of the checkpoint.
+            ///
+            /// # Panics
+            ///
+            /// * If `pos` exceeds the number of checkpoints of `account`.
+            #[must_use]
+            pub fn checkpoints(
+                &self,
+                account: Address,
+                pos: u32,
+            ) -> (U48, U208) {
+                self.votes.checkpoints(account, pos)
+            }
+        }
+
+        impl<T: IEip712Domain + StorageType> IErc165 for $ty<T> {
+            fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+                <Self as IVotes>::INTERFACE_ID
+                    == u32::from_be_bytes(*interface_id)
+                    || <Self as IErc6372>::INTERFACE_ID
+                        == u32::from_be_bytes(*interface_id)
+                    || <Self as IErc721>::INTERFACE_ID
+                        == u32::from_be_bytes(*interface_id)
+                    || Erc165::supports_interface(interface_id)
+            }
+        }
+    };
+}
+
+/// Implements [`IErc721`] for a votes extension, routing transfers through
+/// [`base`] so that voting units move along with tokens.
+///
+/// Must be invoked with the name of the field holding the inner token.
+macro_rules! impl_erc721 {
+    ($ty:ident, $token:ident) => {
+        #[public]
+        impl<T: IEip712Domain + StorageType> IErc721 for $ty<T> {
+            type Error = Error;
+
+            fn balance_of(&self, owner: Address) -> Result<U256, Error> {
+                Ok(self.$token.balance_of(owner)?)
+            }
+
+            fn owner_of(&self, token_id: U256) -> Result<Address, Error> {
+                Ok(self.$token.owner_of(token_id)?)
+            }
+
+            fn safe_transfer_from(
+                &mut self,
+                from: Address,
+                to: Address,
+                token_id: U256,
+            ) -> Result<(), Error> {
+                self.safe_transfer_from_with_data(
+                    from,
+                    to,
+                    token_id,
+                    vec![].into(),
+                )
+            }
+
+            #[selector(name = "safeTransferFrom")]
+            fn safe_transfer_from_with_data(
+                &mut self,
+                from: Address,
+                to: Address,
+                token_id: U256,
+                data: Bytes,
+            ) -> Result<(), Error> {
+                base::safe_transfer_from(
+                    &mut self.$token,
+                    &mut self.votes,
+                    from,
+                    to,
+                    token_id,
+                    &data,
+                    context::msg_sender(),
+                )
+            }
+
+            fn transfer_from(
+                &mut self,
+                from: Address,
+                to: Address,
+                token_id: U256,
+            ) -> Result<(), Error> {
+                base::transfer_from(
+                    &mut self.$token,
+                    &mut self.votes,
+                    from,
+                    to,
+                    token_id,
+                    context::msg_sender(),
+                )
+            }
+
+            fn approve(
+                &mut self,
+                to: Address,
+                token_id: U256,
+            ) -> Result<(), Error> {
+                Ok(self.$token.approve(to, token_id)?)
+            }
+
+            fn set_approval_for_all(
+                &mut self,
+                operator: Address,
+                approved: bool,
+            ) -> Result<(), Error> {
+                Ok(self.$token.set_approval_for_all(operator, approved)?)
+            }
+
+            fn get_approved(&self, token_id: U256) -> Result<Address, Error> {
+                Ok(self.$token.get_approved(token_id)?)
+            }
+
+            fn is_approved_for_all(
+                &self,
+                owner: Address,
+                operator: Address,
+            ) -> bool {
+                self.$token.is_approved_for_all(owner, operator)
+            }
+        }
+    };
+}
+
+/// State of an [`Erc721Votes`] token.
+#[storage]
+pub struct Erc721Votes<T: IEip712Domain + StorageType> {
+    /// [`Erc721`] contract.
+    pub erc721: Erc721,
+    /// [`Votes`] contract tracking delegation and voting units.
+    pub votes: Votes<T>,
+}
+
+impl<T: IEip712Domain + StorageType> Deref for Erc721Votes<T> {
+    type Target = Erc721;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721
+    }
+}
+
+impl<T: IEip712Domain + StorageType> DerefMut for Erc721Votes<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl<T: IEip712Domain + StorageType> TopLevelStorage for Erc721Votes<T> {}
+
+// ************** ERC-721 External **************
+
+impl_erc721!(Erc721Votes, erc721);
+
+impl_votes!(Erc721Votes);
+
+impl<T: IEip712Domain + StorageType> Erc721Votes<T> {
+    /// Extended version of [`Erc721::_update`] that moves one voting unit
+    /// when a token is transferred, minted or burned.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `auth` - Account used for authorization of the update.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If token does not exist and
+    ///   `auth` is not `Address::ZERO`.
+    /// * [`erc721::Error::InsufficientApproval`] - If `auth` is not
+    ///   `Address::ZERO` and `auth` does not have a right to approve this
+    ///   token.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Error> {
+        base::_update(&mut self.erc721, &mut self.votes, to, token_id, auth)
+    }
+
+    /// Mints `token_id` and transfers it to `to`.
+    ///
+    /// Re-export of [`Erc721::_mint`] that goes through [`Self::_update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidSender`] - If `token_id` already exists.
+    /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _mint(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
+        base::_mint(&mut self.erc721, &mut self.votes, to, token_id)
+    }
+
+    /// Mints `token_id`, transfers it to `to`, and checks for `to`'s
+    /// acceptance.
+    ///
+    /// Re-export of [`Erc721::_safe_mint`] that goes through
+    /// [`Self::_update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `data` - Additional data with no specified format, sent in the call to
+    ///   [`Erc721::_check_on_erc721_received`].
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidSender`] - If `token_id` already exists.
+    /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`, or
+    ///   [`erc721::IERC721Receiver::on_erc_721_received`] hasn't returned its
+    ///   interface id or returned with error.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _safe_mint(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        self._mint(to, token_id)?;
+        Ok(self.erc721._check_on_erc721_received(
+            context::msg_sender(),
+            Address::ZERO,
+            to,
+            token_id,
+            data,
+        )?)
+    }
+
+    /// Destroys `token_id`.
+    ///
+    /// Re-export of [`Erc721::_burn`] that goes through [`Self::_update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If token does not exist.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _burn(&mut self, token_id: U256) -> Result<(), Error> {
+        base::_burn(&mut self.erc721, &mut self.votes, token_id)
+    }
+
+    /// Transfers `token_id` from `from` to `to`.
+    ///
+    /// Re-export of [`Erc721::_transfer`] that goes through
+    /// [`Self::_update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account of the sender.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    /// * [`erc721::Error::NonexistentToken`] - If `token_id` does not exist.
+    /// * [`erc721::Error::IncorrectOwner`] - If the previous owner is not
+    ///   `from`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        base::_transfer(&mut self.erc721, &mut self.votes, from, to, token_id)
+    }
+
+    /// Safely transfers `token_id` token from `from` to `to`, checking that
+    /// contract recipients are aware of the [`Erc721`] standard to prevent
+    /// tokens from being forever locked.
+    ///
+    /// Re-export of [`Erc721::_safe_transfer`] that goes through
+    /// [`Self::_update`].
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `from` - Account of the sender.
+    /// * `to` - Account of the recipient.
+    /// * `token_id` - Token id as a number.
+    /// * `data` - Additional data with no specified format, sent in the call to
+    ///   [`Erc721::_check_on_erc721_received`].
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    /// * [`erc721::Error::NonexistentToken`] - If `token_id` does not exist.
+    /// * [`erc721::Error::IncorrectOwner`] - If the previous owner is not
+    ///   `from`.
+    ///
+    /// # Events
+    ///
+    /// * [`erc721::Transfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _safe_transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+        data: &Bytes,
+    ) -> Result<(), Error> {
+        self._transfer(from, to, token_id)?;
+        Ok(self.erc721._check_on_erc721_received(
+            context::msg_sender(),
+            from,
+            to,
+            token_id,
+            data,
+        )?)
+    }
+
+    /// Delegates all of `account`'s voting units to `delegatee`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `account` - Account whose voting units are delegated.
+    /// * `delegatee` - Account to delegate votes to.
+    ///
+    /// # Errors
+    ///
+    /// * [`votes::Error::Checkpoints`] - If a checkpoint could not be pushed.
+    ///
+    /// # Events
+    ///
+    /// * [`votes::DelegateChanged`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _delegate(
+        &mut self,
+        account: Address,
+        delegatee: Address,
+    ) -> Result<(), Error> {
+        base::_delegate(&self.erc721, &mut self.votes, account, delegatee)
+    }
+}
+
+/// State of an [`Erc721ConsecutiveVotes`] token.
+#[storage]
+pub struct Erc721ConsecutiveVotes<T: IEip712Domain + StorageType> {
+    /// [`Erc721Consecutive`] contract.
+    pub erc721_consecutive: Erc721Consecutive,
+    /// [`Votes`] contract tracking delegation and voting units.
+    pub votes: Votes<T>,
+}
+
+impl<T: IEip712Domain + StorageType> Deref for Erc721ConsecutiveVotes<T> {
+    type Target = Erc721Consecutive;
+
+    fn deref(&self) -> &Self::Target {
+        &self.erc721_consecutive
+    }
+}
+
+impl<T: IEip712Domain + StorageType> DerefMut for Erc721ConsecutiveVotes<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.erc721_consecutive
+    }
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl<T: IEip712Domain + StorageType> TopLevelStorage
+    for Erc721ConsecutiveVotes<T>
+{
+}
+
+// ************** ERC-721 External **************
+
+impl_erc721!(Erc721ConsecutiveVotes, erc721_consecutive);
+
+impl_votes!(Erc721ConsecutiveVotes);
+
+impl<T: IEip712Domain + StorageType> Erc721ConsecutiveVotes<T> {
+    /// Extended version of [`Erc721Consecutive::_mint_consecutive`] that
+    /// credits `batch_size` voting units to `to`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `to` - Account of the recipient.
+    /// * `batch_size` - Number of tokens to mint.
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    /// * [`consecutive::Error::ExceededMaxBatchMint`] - If `batch_size` exceeds
+    ///   `max_batch_size` of the contract.
+    ///
+    /// # Events
+    ///
+    /// * [`consecutive::ConsecutiveTransfer`].
+    /// * [`votes::DelegateVotesChanged`].
+    pub fn _mint_consecutive(
+        &mut self,
+        to: Address,
+        batch_size: U96,
+    ) -> Result<U96, Error> {
+        let next = self.erc721_consecutive._mint_consecutive(to, batch_size)?;
+        if batch_size > U96::ZERO {
+            self.votes._transfer_voting_units(
+                Address::ZERO,
+                to,
+                U256::from(batch_size),
+            )?;
+        }
+        Ok(next)
+    }
+
+    /// Extended version of [`Erc721Consecutive::_update`] that moves one
+    /// voting unit when a token is transferred, minted or burned.
+    ///
+    /// See [`Erc721Votes::_update`].
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If token does not exist and
+    ///   `auth` is not `Address::ZERO`.
+    /// * [`erc721::Error::InsufficientApproval`] - If `auth` is not
+    ///   `Address::ZERO` and `auth` does not have a right to approve this
+    ///   token.
+    pub fn _update(
+        &mut self,
+        to: Address,
+        token_id: U256,
+        auth: Address,
+    ) -> Result<Address, Error> {
+        base::_update(
+            &mut self.erc721_consecutive,
+            &mut self.votes,
+            to,
+            token_id,
+            auth,
+        )
+    }
+
+    /// Mints `token_id` and transfers it to `to`.
+    ///
+    /// See [`Erc721Votes::_mint`].
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidSender`] - If `token_id` already exists.
+    /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    pub fn _mint(&mut self, to: Address, token_id: U256) -> Result<(), Error> {
+        base::_mint(&mut self.erc721_consecutive, &mut self.votes, to, token_id)
+    }
+
+    /// Destroys `token_id`.
+    ///
+    /// See [`Erc721Votes::_burn`].
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::NonexistentToken`] - If token does not exist.
+    pub fn _burn(&mut self, token_id: U256) -> Result<(), Error> {
+        base::_burn(&mut self.erc721_consecutive, &mut self.votes, token_id)
+    }
+
+    /// Transfers `token_id` from `from` to `to`.
+    ///
+    /// See [`Erc721Votes::_transfer`].
+    ///
+    /// # Errors
+    ///
+    /// * [`erc721::Error::InvalidReceiver`] - If `to` is `Address::ZERO`.
+    /// * [`erc721::Error::NonexistentToken`] - If `token_id` does not exist.
+    /// * [`erc721::Error::IncorrectOwner`] - If the previous owner is not
+    ///   `from`.
+    pub fn _transfer(
+        &mut self,
+        from: Address,
+        to: Address,
+        token_id: U256,
+    ) -> Result<(), Error> {
+        base::_transfer(
+            &mut self.erc721_consecutive,
+            &mut self.votes,
+            from,
+            to,
+            token_id,
+        )
+    }
+
+    /// Delegates all of `account`'s voting units to `delegatee`.
+    ///
+    /// See [`Erc721Votes::_delegate`].
+    ///
+    /// # Errors
+    ///
+    /// * [`votes::Error::Checkpoints`] - If a checkpoint could not be pushed.
+    pub fn _delegate(
+        &mut self,
+        account: Address,
+        delegatee: Address,
+    ) -> Result<(), Error> {
+        base::_delegate(
+            &self.erc721_consecutive,
+            &mut self.votes,
+            account,
+            delegatee,
+        )
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{aliases::U96, uint, Address, U256};
+    use motsu::prelude::*;
+    use stylus_sdk::prelude::*;
+
+    use super::{Erc721ConsecutiveVotes, Erc721Votes, Error};
+    use crate::{
+        governance::utils::votes::{
+            DelegateChanged, DelegateVotesChanged, IErc6372, IVotes,
+        },
+        token::erc721::{self, IErc721},
+        utils::{
+            cryptography::eip712::IEip712, introspection::erc165::IErc165,
+        },
+    };
+
+    const TOKEN_ID: U256 = uint!(1_U256);
+
+    #[storage]
+    struct Eip712;
+
+    impl IEip712 for Eip712 {
+        const NAME: &'static str = "ERC-721 Votes";
+        const VERSION: &'static str = "1";
+    }
+
+    #[motsu::test]
+    fn delegate_counts_each_token_as_one_vote(
+        contract: Contract<Erc721Votes<Eip712>>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).motsu_unwrap();
+        contract
+            .sender(alice)
+            ._mint(alice, TOKEN_ID + uint!(1_U256))
+            .motsu_unwrap();
+
+        // Undelegated tokens carry no voting power.
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+
+        assert_eq!(uint!(2_U256), contract.sender(alice).get_votes(alice));
+        contract.assert_emitted(&DelegateChanged {
+            delegator: alice,
+            from_delegate: Address::ZERO,
+            to_delegate: alice,
+        });
+        contract.assert_emitted(&DelegateVotesChanged {
+            delegate: alice,
+            previous_votes: U256::ZERO,
+            new_votes: uint!(2_U256),
+        });
+    }
+
+    #[motsu::test]
+    fn transfer_from_moves_delegated_votes(
+        contract: Contract<Erc721Votes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).motsu_unwrap();
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+        contract.sender(bob).delegate(bob).motsu_unwrap();
+
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, TOKEN_ID)
+            .motsu_unwrap();
+
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+        assert_eq!(uint!(1_U256), contract.sender(alice).get_votes(bob));
+        assert_eq!(
+            bob,
+            contract.sender(alice).owner_of(TOKEN_ID).motsu_unwrap()
+        );
+    }
+
+    #[motsu::test]
+    fn safe_transfer_from_moves_delegated_votes(
+        contract: Contract<Erc721Votes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).motsu_unwrap();
+        contract.sender(bob).delegate(bob).motsu_unwrap();
+
+        contract
+            .sender(alice)
+            .safe_transfer_from(alice, bob, TOKEN_ID)
+            .motsu_unwrap();
+
+        assert_eq!(uint!(1_U256), contract.sender(alice).get_votes(bob));
+    }
+
+    #[motsu::test]
+    fn burn_removes_delegated_votes(
+        contract: Contract<Erc721Votes<Eip712>>,
+        alice: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).motsu_unwrap();
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+
+        contract.sender(alice)._burn(TOKEN_ID).motsu_unwrap();
+
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+        assert_eq!(
+            U256::ZERO,
+            contract.sender(alice).votes._get_total_supply()
+        );
+    }
+
+    #[motsu::test]
+    fn transfer_from_errors_incorrect_owner(
+        contract: Contract<Erc721Votes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice)._mint(alice, TOKEN_ID).motsu_unwrap();
+
+        let err = contract
+            .sender(alice)
+            .transfer_from(bob, alice, TOKEN_ID)
+            .motsu_unwrap_err();
+        assert!(matches!(err, Error::Erc721(erc721::Error::IncorrectOwner(_))));
+    }
+
+    #[motsu::test]
+    fn mint_consecutive_credits_voting_units(
+        contract: Contract<Erc721ConsecutiveVotes<Eip712>>,
+        alice: Address,
+    ) {
+        let batch_size = uint!(10_U96);
+        contract.init(alice, |contract| {
+            contract.erc721_consecutive.max_batch_size.set(uint!(5000_U96));
+        });
+
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+        contract
+            .sender(alice)
+            ._mint_consecutive(alice, batch_size)
+            .motsu_unwrap();
+
+        assert_eq!(
+            U256::from(batch_size),
+            contract.sender(alice).get_votes(alice)
+        );
+        assert_eq!(
+            U256::from(batch_size),
+            contract.sender(alice).votes._get_total_supply()
+        );
+        contract.assert_emitted(&DelegateVotesChanged {
+            delegate: alice,
+            previous_votes: U256::ZERO,
+            new_votes: U256::from(batch_size),
+        });
+    }
+
+    #[motsu::test]
+    fn consecutive_transfer_and_burn_move_voting_units(
+        contract: Contract<Erc721ConsecutiveVotes<Eip712>>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let batch_size = uint!(3_U96);
+        contract.init(alice, |contract| {
+            contract.erc721_consecutive.max_batch_size.set(uint!(5000_U96));
+        });
+
+        contract
+            .sender(alice)
+            ._mint_consecutive(alice, batch_size)
+            .motsu_unwrap();
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+        contract.sender(bob).delegate(bob).motsu_unwrap();
+        assert_eq!(
+            U256::from(batch_size),
+            contract.sender(alice).get_votes(alice)
+        );
+
+        contract
+            .sender(alice)
+            .transfer_from(alice, bob, U256::ZERO)
+            .motsu_unwrap();
+        contract.sender(alice)._burn(uint!(1_U256)).motsu_unwrap();
+
+        assert_eq!(uint!(1_U256), contract.sender(alice).get_votes(alice));
+        assert_eq!(uint!(1_U256), contract.sender(alice).get_votes(bob));
+        assert_eq!(
+            uint!(2_U256),
+            contract.sender(alice).votes._get_total_supply()
+        );
+    }
+
+    #[motsu::test]
+    fn mint_consecutive_of_empty_batch_is_noop(
+        contract: Contract<Erc721ConsecutiveVotes<Eip712>>,
+        alice: Address,
+    ) {
+        contract.sender(alice).delegate(alice).motsu_unwrap();
+        contract
+            .sender(alice)
+            ._mint_consecutive(alice, U96::ZERO)
+            .motsu_unwrap();
+
+        assert_eq!(U256::ZERO, contract.sender(alice).get_votes(alice));
+        assert_eq!(0, contract.sender(alice).num_checkpoints(alice));
+    }
+
+    #[motsu::test]
+    fn clock_mode_is_block_number(
+        contract: Contract<Erc721Votes<Eip712>>,
+        alice: Address,
+    ) {
+        assert_eq!(
+            "mode=blocknumber&from=default",
+            contract.sender(alice).clock_mode()
+        );
+    }
+
+    #[motsu::test]
+    fn supports_interface() {
+        assert!(Erc721Votes::<Eip712>::supports_interface(
+            <Erc721Votes<Eip712> as IVotes>::INTERFACE_ID.into()
+        ));
+        assert!(Erc721Votes::<Eip712>::supports_interface(
+            <Erc721Votes<Eip712> as IErc721>::INTERFACE_ID.into()
+        ));
+        assert!(Erc721ConsecutiveVotes::<Eip712>::supports_interface(
+            <Erc721ConsecutiveVotes<Eip712> as IErc6372>::INTERFACE_ID.into()
+        ));
+        assert!(Erc721ConsecutiveVotes::<Eip712>::supports_interface(
+            <Erc721ConsecutiveVotes<Eip712> as IErc165>::INTERFACE_ID.into()
+        ));
+
+        let fake_interface_id = 0x12345678u32;
+        assert!(!Erc721Votes::<Eip712>::supports_interface(
+            fake_interface_id.into()
+        ));
+    }
+}
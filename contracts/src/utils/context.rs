@@ -0,0 +1,159 @@
+//! Provides information about the current execution context, including the
+//! sender of the call and its calldata.
+//!
+//! While these are generally available via [`msg::sender`], they should not
+//! be accessed in such a direct manner, since when dealing with
+//! meta-transactions the account sending and paying for execution may not be
+//! the actual sender (as far as an application is concerned).
+//!
+//! The sender of a meta-transaction is resolved by the entrypoint of the
+//! contract, e.g. [`crate::metatx::erc2771_context::router_entrypoint`], which
+//! strips it from the calldata before routing the call. Contracts using the
+//! default `#[entrypoint]` never see a context suffix, so [`msg_sender`] is
+//! the same as [`msg::sender`] for them.
+use alloy_primitives::{b256, Address, B256};
+use stylus_sdk::msg;
+
+use crate::utils::storage_slot::TransientSlot;
+
+/// Transient storage slot holding the sender resolved by the entrypoint of a
+/// relayed call, or [`Address::ZERO`] if the call is not relayed.
+///
+/// Equal to the [ERC-7201] slot of the `openzeppelin.transient.Context`
+/// namespace.
+///
+/// [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+pub(crate) const RELAYED_SENDER_SLOT: B256 =
+    b256!("2084aac9bf10badbe83d40ae5987634fc8d9f3bccedaa701133c0ef25e1f5400");
+
+/// Length of the context suffix of a relayed call, i.e. the address of the
+/// original sender.
+pub const RELAYED_SUFFIX_LENGTH: usize = Address::len_bytes();
+
+/// Returns the sender of the current call.
+///
+/// This is the original sender when the call is relayed by a trusted
+/// forwarder, and [`msg::sender`] otherwise.
+#[must_use]
+pub fn msg_sender() -> Address {
+    let relayed = TransientSlot::get_address(RELAYED_SENDER_SLOT);
+    if relayed.is_zero() {
+        msg::sender()
+    } else {
+        relayed
+    }
+}
+
+/// Returns `calldata` without the context suffix of the current call.
+///
+/// # Arguments
+///
+/// * `calldata` - The calldata of the current call, as received by the
+///   entrypoint of the contract.
+#[must_use]
+pub fn msg_data(calldata: &[u8]) -> &[u8] {
+    let length = calldata.len().saturating_sub(context_suffix_length());
+    &calldata[..length]
+}
+
+/// Returns the length of the context suffix appended to the calldata of the
+/// current call.
+#[must_use]
+pub fn context_suffix_length() -> usize {
+    if TransientSlot::get_address(RELAYED_SENDER_SLOT).is_zero() {
+        0
+    } else {
+        RELAYED_SUFFIX_LENGTH
+    }
+}
+
+/// Runs `f` with `sender` as the sender resolved for the current call, then
+/// restores the previous one.
+///
+/// Passing [`Address::ZERO`] marks the call as not relayed.
+///
+/// # Arguments
+///
+/// * `sender` - The original sender of the call.
+/// * `f` - The logic executed on behalf of `sender`.
+pub(crate) fn with_relayed_sender<T>(
+    sender: Address,
+    f: impl FnOnce() -> T,
+) -> T {
+    let previous = TransientSlot::get_address(RELAYED_SENDER_SLOT);
+    TransientSlot::set_address(RELAYED_SENDER_SLOT, sender);
+    let result = f();
+    TransientSlot::set_address(RELAYED_SENDER_SLOT, previous);
+    result
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{keccak256, Address, B256, U256};
+    use motsu::prelude::*;
+    use stylus_sdk::prelude::*;
+
+    use super::*;
+
+    #[storage]
+    struct ContextMock;
+
+    unsafe impl TopLevelStorage for ContextMock {}
+
+    #[public]
+    impl ContextMock {
+        #[allow(clippy::unused_self)]
+        fn msg_sender(&self) -> Address {
+            msg_sender()
+        }
+    }
+
+    #[test]
+    fn relayed_sender_slot_matches_erc7201() {
+        let location =
+            U256::from_be_bytes(keccak256("openzeppelin.transient.Context").0)
+                - U256::from(1);
+        let mut slot = keccak256(B256::from(location));
+        slot[31] = 0;
+        assert_eq!(RELAYED_SENDER_SLOT, slot);
+    }
+
+    #[motsu::test]
+    fn msg_sender_defaults_to_caller(
+        contract: Contract<ContextMock>,
+        alice: Address,
+    ) {
+        assert_eq!(contract.sender(alice).msg_sender(), alice);
+    }
+
+    #[motsu::test]
+    fn msg_sender_returns_relayed_sender(
+        contract: Contract<ContextMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.init(alice, |mock| {
+            with_relayed_sender(bob, || {
+                assert_eq!(mock.msg_sender(), bob);
+                assert_eq!(context_suffix_length(), RELAYED_SUFFIX_LENGTH);
+            });
+            assert_eq!(mock.msg_sender(), alice);
+            assert_eq!(context_suffix_length(), 0);
+        });
+    }
+
+    #[motsu::test]
+    fn msg_data_strips_context_suffix(
+        contract: Contract<ContextMock>,
+        alice: Address,
+        bob: Address,
+    ) {
+        let calldata = [&[0xde, 0xad, 0xbe, 0xef], bob.as_slice()].concat();
+        contract.init(alice, |_| {
+            assert_eq!(msg_data(&calldata), calldata.as_slice());
+            with_relayed_sender(bob, || {
+                assert_eq!(msg_data(&calldata), &[0xde, 0xad, 0xbe, 0xef]);
+            });
+        });
+    }
+}
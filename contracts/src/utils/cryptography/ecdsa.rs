@@ -17,6 +17,14 @@ use crate::utils::cryptography::ecdsa;
 pub const ECRECOVER_ADDR: Address =
     address!("0000000000000000000000000000000000000001");
 
+/// Length of a signature in its `r || s || v` form.
+pub const SIGNATURE_LENGTH: usize = 65;
+
+/// Length of a signature in its [EIP-2098] compact `r || vs` form.
+///
+/// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+pub const COMPACT_SIGNATURE_LENGTH: usize = 64;
+
 /// Upper range for `s` value from the signature.
 pub const SIGNATURE_S_UPPER_BOUND: U256 = uint!(
     0x7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF5D576E7357A4501DDFE92F46681B20A0_U256
@@ -39,6 +47,13 @@ mod sol {
         #[derive(Debug)]
         #[allow(missing_docs)]
         error ECDSAInvalidSignatureS(bytes32 s);
+
+        /// The signature has an invalid length.
+        ///
+        /// * `length` - Length of the signature.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ECDSAInvalidSignatureLength(uint256 length);
     }
 
     sol! {
@@ -64,6 +79,8 @@ pub enum Error {
     InvalidSignature(ECDSAInvalidSignature),
     /// The signature has an `S` value that is in the upper half order.
     InvalidSignatureS(ECDSAInvalidSignatureS),
+    /// The signature has an invalid length.
+    InvalidSignatureLength(ECDSAInvalidSignatureLength),
 }
 
 impl MethodError for ecdsa::Error {
@@ -86,7 +103,8 @@ impl MethodError for ecdsa::Error {
 ///
 /// * [`Error::InvalidSignatureS`] - If the `s` value is grater than
 ///   [`SIGNATURE_S_UPPER_BOUND`].
-/// * [`Error::InvalidSignature`] - If the recovered address is `Address::ZERO`.
+/// * [`Error::InvalidSignature`] - If `v` is neither 27 nor 28, or if the
+///   recovered address is `Address::ZERO`.
 ///
 /// # Panics
 ///
@@ -103,6 +121,111 @@ pub fn recover(
     _recover(storage, hash, v, r, s)
 }
 
+/// Returns the address that signed a hashed message (`hash`) with
+/// `signature`.
+///
+/// Accepts both 65-byte `r || s || v` signatures and 64-byte [EIP-2098]
+/// compact `r || vs` signatures.
+///
+/// # Arguments
+///
+/// * `storage` - Write access to storage.
+/// * `hash` - Hash of the message.
+/// * `signature` - Signature of `hash`.
+///
+/// # Errors
+///
+/// * [`Error::InvalidSignatureLength`] - If `signature` is neither 65 nor 64
+///   bytes long.
+/// * [`Error::InvalidSignatureS`] - If the `s` value is greater than
+///   [`SIGNATURE_S_UPPER_BOUND`].
+/// * [`Error::InvalidSignature`] - If `v` is neither 27 nor 28, or if the
+///   recovered address is `Address::ZERO`.
+///
+/// # Panics
+///
+/// * If the `ecrecover` precompile fails to execute.
+///
+/// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+pub fn recover_from_bytes(
+    storage: &mut impl TopLevelStorage,
+    hash: B256,
+    signature: &[u8],
+) -> Result<Address, Error> {
+    let (v, r, s) = parse_signature(signature)?;
+    recover(storage, hash, v, r, s)
+}
+
+/// Returns the address that signed a hashed message (`hash`) with
+/// `signature`, without ever panicking.
+///
+/// Same as [`recover_from_bytes`], except that a failure of the `ecrecover`
+/// precompile is reported as [`Error::InvalidSignature`].
+///
+/// # Arguments
+///
+/// * `storage` - Write access to storage.
+/// * `hash` - Hash of the message.
+/// * `signature` - Signature of `hash`.
+///
+/// # Errors
+///
+/// * [`Error::InvalidSignatureLength`] - If `signature` is neither 65 nor 64
+///   bytes long.
+/// * [`Error::InvalidSignatureS`] - If the `s` value is greater than
+///   [`SIGNATURE_S_UPPER_BOUND`].
+/// * [`Error::InvalidSignature`] - If `v` is neither 27 nor 28, if the
+///   `ecrecover` precompile fails, or if the recovered address is
+///   `Address::ZERO`.
+pub fn try_recover(
+    storage: &mut impl TopLevelStorage,
+    hash: B256,
+    signature: &[u8],
+) -> Result<Address, Error> {
+    let (v, r, s) = parse_signature(signature)?;
+    check_if_malleable(&s)?;
+    check_v(v)?;
+
+    let recovered = ecrecover(storage, hash, v, r, s)
+        .map_err(|_| ECDSAInvalidSignature {})?;
+    decode_signer(&recovered)
+}
+
+/// Splits `signature` into its `v`, `r` and `s` values.
+///
+/// # Arguments
+///
+/// * `signature` - A 65-byte `r || s || v` signature, or a 64-byte [EIP-2098]
+///   compact `r || vs` signature.
+///
+/// # Errors
+///
+/// * [`Error::InvalidSignatureLength`] - If `signature` is neither 65 nor 64
+///   bytes long.
+///
+/// [EIP-2098]: https://eips.ethereum.org/EIPS/eip-2098
+fn parse_signature(signature: &[u8]) -> Result<(u8, B256, B256), Error> {
+    match signature.len() {
+        SIGNATURE_LENGTH => {
+            let r = B256::from_slice(&signature[..32]);
+            let s = B256::from_slice(&signature[32..64]);
+            Ok((signature[64], r, s))
+        }
+        COMPACT_SIGNATURE_LENGTH => {
+            let r = B256::from_slice(&signature[..32]);
+            let mut s = B256::from_slice(&signature[32..]);
+            // The highest bit of `vs` is the parity of `v`.
+            let v = 27 + (s[0] >> 7);
+            s[0] &= 0x7f;
+            Ok((v, r, s))
+        }
+        length => {
+            Err(ECDSAInvalidSignatureLength { length: U256::from(length) }
+                .into())
+        }
+    }
+}
+
 /// Calls `ecrecover` EVM precompile.
 ///
 /// The `ecrecover` EVM precompile allows for malleable (non-unique) signatures:
@@ -119,7 +242,8 @@ pub fn recover(
 ///
 /// # Errors
 ///
-/// * [`Error::InvalidSignature`] - If the recovered address is `Address::ZERO`.
+/// * [`Error::InvalidSignature`] - If `v` is neither 27 nor 28, or if the
+///   recovered address is `Address::ZERO`.
 ///
 /// # Panics
 ///
@@ -131,28 +255,80 @@ fn _recover(
     r: B256,
     s: B256,
 ) -> Result<Address, Error> {
+    check_v(v)?;
+
+    let recovered = ecrecover(storage, hash, v, r, s)
+        .expect("should call `ecrecover` precompile");
+    decode_signer(&recovered)
+}
+
+/// Calls the `ecrecover` EVM precompile, returning its raw output.
+///
+/// # Arguments
+///
+/// * `storage` - Write access to storage.
+/// * `hash` - Hash of the message.
+/// * `v` - `v` value from the signature.
+/// * `r` - `r` value from the signature.
+/// * `s` - `s` value from the signature.
+///
+/// # Errors
+///
+/// * If the `ecrecover` precompile fails to execute.
+fn ecrecover(
+    storage: &mut impl TopLevelStorage,
+    hash: B256,
+    v: u8,
+    r: B256,
+    s: B256,
+) -> Result<Vec<u8>, call::Error> {
     let calldata = encode_calldata(hash, v, r, s);
+    call::static_call(Call::new_in(storage), ECRECOVER_ADDR, &calldata)
+}
 
-    if v == 0 || v == 1 {
-        // `ecrecover` panics for these values
-        // but following the Solidity tests
-        // https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/test/utils/cryptography/ECDSA.test.js
-        // it should return `ECDSAInvalidSignature` error.
+/// Decodes the signer from the output of the `ecrecover` EVM precompile.
+///
+/// # Arguments
+///
+/// * `recovered` - Output of the `ecrecover` precompile.
+///
+/// # Errors
+///
+/// * [`Error::InvalidSignature`] - If the output is empty, or if the recovered
+///   address is `Address::ZERO`.
+fn decode_signer(recovered: &[u8]) -> Result<Address, Error> {
+    // `ecrecover` returns no data for signatures it can't recover.
+    if recovered.len() != 32 {
         return Err(ECDSAInvalidSignature {}.into());
     }
 
-    let recovered =
-        call::static_call(Call::new_in(storage), ECRECOVER_ADDR, &calldata)
-            .expect("should call `ecrecover` precompile");
-
     let recovered = Address::from_slice(&recovered[12..]);
-
     if recovered.is_zero() {
         return Err(ECDSAInvalidSignature {}.into());
     }
     Ok(recovered)
 }
 
+/// Validates the `v` value of a signature.
+///
+/// # Arguments
+///
+/// * `v` - `v` value from the signature.
+///
+/// # Errors
+///
+/// * [`Error::InvalidSignature`] - If `v` is neither 27 nor 28.
+fn check_v(v: u8) -> Result<(), Error> {
+    // `ecrecover` returns no data for other values but, following the
+    // Solidity tests
+    // https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/test/utils/cryptography/ECDSA.test.js
+    // it should return `ECDSAInvalidSignature` error.
+    if v != 27 && v != 28 {
+        return Err(ECDSAInvalidSignature {}.into());
+    }
+    Ok(())
+}
+
 /// Encodes call data for `ecrecover` EVM precompile.
 ///
 /// # Arguments
@@ -242,6 +418,72 @@ mod tests {
         ));
     }
 
+    fn signature() -> Vec<u8> {
+        [R.as_slice(), S.as_slice(), &[V]].concat()
+    }
+
+    #[test]
+    fn parses_signature() {
+        let (v, r, s) =
+            parse_signature(&signature()).expect("should parse signature");
+        assert_eq!((v, r, s), (V, R, S));
+    }
+
+    #[test]
+    fn parses_compact_signature() {
+        let mut vs = S;
+        // `V` is 28, so the parity bit is set.
+        vs[0] |= 0x80;
+        let compact = [R.as_slice(), vs.as_slice()].concat();
+        let (v, r, s) =
+            parse_signature(&compact).expect("should parse compact signature");
+        assert_eq!((v, r, s), (V, R, S));
+
+        let compact = [R.as_slice(), S.as_slice()].concat();
+        let (v, _, s) =
+            parse_signature(&compact).expect("should parse compact signature");
+        assert_eq!((v, s), (27, S));
+    }
+
+    #[test]
+    fn rejects_invalid_signature_length() {
+        for length in [0, 63, 66, 128] {
+            let err = parse_signature(&vec![0x42; length])
+                .expect_err("should return ECDSAInvalidSignatureLength");
+
+            assert!(matches!(err,
+                    Error::InvalidSignatureLength(ECDSAInvalidSignatureLength {
+                        length: l
+                    }) if l == U256::from(length)
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_v() {
+        for v in [0, 1, 26, 29, 255] {
+            let err =
+                check_v(v).expect_err("should return ECDSAInvalidSignature");
+            assert!(matches!(err, Error::InvalidSignature(_)));
+        }
+        assert!(check_v(27).is_ok());
+        assert!(check_v(28).is_ok());
+    }
+
+    #[test]
+    fn decodes_signer() {
+        let signer = address!("000000000000000000000000000000000000dEaD");
+        let recovered = B256::left_padding_from(signer.as_slice());
+        assert_eq!(decode_signer(recovered.as_slice()).ok(), Some(signer));
+
+        let err = decode_signer(&[]).expect_err("should reject empty output");
+        assert!(matches!(err, Error::InvalidSignature(_)));
+
+        let err = decode_signer(B256::ZERO.as_slice())
+            .expect_err("should reject zero address");
+        assert!(matches!(err, Error::InvalidSignature(_)));
+    }
+
     #[test]
     fn validates_s() {
         let valid_s = SIGNATURE_S_UPPER_BOUND - uint!(1_U256);
@@ -10,11 +10,17 @@
 //!
 //! [`eth_signTypedDataV4`]: https://docs.metamask.io/guide/signing-data.html
 
-use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
 
-use alloy_primitives::{keccak256, Address, B256, U256};
+use alloy_primitives::{
+    fixed_bytes, keccak256, Address, FixedBytes, B256, U256,
+};
 use alloy_sol_types::{sol, SolType};
-use stylus_sdk::{block, contract};
+use stylus_sdk::{
+    block, contract,
+    prelude::*,
+    storage::{StorageAddress, StorageB256, StorageString, StorageU256},
+};
 
 /// Keccak-256 hash of the EIP-712 domain separator type string.
 const TYPE_HASH: [u8; 32] =
@@ -22,11 +28,14 @@ const TYPE_HASH: [u8; 32] =
         .update(b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")
         .finalize();
 
-/// Field for the domain separator.
-const FIELDS: [u8; 1] = [0x0f];
+/// Fields of the domain separator, as defined in [ERC-5267]: `name`,
+/// `version`, `chainId` and `verifyingContract`.
+///
+/// [ERC-5267]: https://eips.ethereum.org/EIPS/eip-5267
+const FIELDS: FixedBytes<1> = fixed_bytes!("0f");
 
 /// Salt for the domain separator.
-const SALT: [u8; 32] = [0u8; 32];
+const SALT: B256 = B256::ZERO;
 
 /// Prefix for ERC-191 version with `0x01`.
 const TYPED_DATA_PREFIX: [u8; 2] = [0x19, 0x01];
@@ -57,21 +66,25 @@ pub fn to_typed_data_hash(
     keccak256(preimage)
 }
 
-/// EIP-712 Contract interface.
-pub trait IEip712 {
-    /// Immutable name of EIP-712 instance.
-    const NAME: &'static str;
-    /// Hashed name of EIP-712 instance.
-    const HASHED_NAME: [u8; 32] =
-        keccak_const::Keccak256::new().update(Self::NAME.as_bytes()).finalize();
-
-    /// Immutable version of EIP-712 instance.
-    const VERSION: &'static str;
-    /// Hashed version of EIP-712 instance.
-    const HASHED_VERSION: [u8; 32] = keccak_const::Keccak256::new()
-        .update(Self::VERSION.as_bytes())
-        .finalize();
+/// The fields and values describing an EIP-712 domain, as returned by the
+/// [ERC-5267] `eip712Domain` function: `(fields, name, version, chainId,
+/// verifyingContract, salt, extensions)`.
+///
+/// [ERC-5267]: https://eips.ethereum.org/EIPS/eip-5267
+pub type Eip712Domain =
+    (FixedBytes<1>, String, String, U256, Address, B256, Vec<U256>);
 
+/// EIP-712 domain of a contract hashing typed data.
+///
+/// Implemented for every [`IEip712`] contract, whose name and version are
+/// constants, and by [`Eip712`], whose name and version are set at runtime.
+///
+/// The domain separator is rebuilt on each call, unless the implementation
+/// provides a [cached one] computed for the current chain id and contract
+/// address.
+///
+/// [cached one]: Self::cached_domain_separator
+pub trait IEip712Domain {
     /// Returns chain id.
     #[must_use]
     fn chain_id() -> U256 {
@@ -84,19 +97,47 @@ pub trait IEip712 {
         contract::address()
     }
 
+    /// Returns the name of the EIP-712 domain.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn name(&self) -> String;
+
+    /// Returns the hashed name of the EIP-712 domain.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn hashed_name(&self) -> B256;
+
+    /// Returns the version of the EIP-712 domain.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn version(&self) -> String;
+
+    /// Returns the hashed version of the EIP-712 domain.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn hashed_version(&self) -> B256;
+
     /// Returns the fields and values that describe the domain separator used by
-    /// this contract for EIP-712 signature.
+    /// this contract for EIP-712 signature, as defined in [ERC-5267].
+    ///
+    /// [ERC-5267]: https://eips.ethereum.org/EIPS/eip-5267
     ///
     /// # Arguments
     ///
     /// * `&self` - Read access to the contract's state.
-    fn eip712_domain(
-        &self,
-    ) -> ([u8; 1], String, String, U256, Address, [u8; 32], Vec<U256>) {
+    fn eip712_domain(&self) -> Eip712Domain {
         (
             FIELDS,
-            Self::NAME.to_owned(),
-            Self::VERSION.to_owned(),
+            self.name(),
+            self.version(),
             Self::chain_id(),
             Self::contract_address(),
             SALT,
@@ -104,16 +145,49 @@ pub trait IEip712 {
         )
     }
 
+    /// Returns a previously computed domain separator, along with the chain id
+    /// and contract address it was computed for.
+    ///
+    /// Defaults to `None`. Implementations with storage can override it to
+    /// avoid rebuilding the domain separator on each call.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn cached_domain_separator(&self) -> Option<(U256, Address, B256)> {
+        None
+    }
+
     /// Returns the domain separator for the current chain.
     ///
+    /// The cached domain separator is returned if it was computed for the
+    /// current chain id and contract address, otherwise it is rebuilt.
+    ///
     /// # Arguments
     ///
     /// * `&self` - Read access to the contract's state.
     fn domain_separator_v4(&self) -> B256 {
+        match self.cached_domain_separator() {
+            Some((chain_id, this, domain_separator))
+                if chain_id == Self::chain_id()
+                    && this == Self::contract_address() =>
+            {
+                domain_separator
+            }
+            _ => self.build_domain_separator(),
+        }
+    }
+
+    /// Computes the domain separator for the current chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    fn build_domain_separator(&self) -> B256 {
         let encoded = DomainSeparatorTuple::abi_encode(&(
             TYPE_HASH,
-            Self::HASHED_NAME,
-            Self::HASHED_VERSION,
+            self.hashed_name(),
+            self.hashed_version(),
             Self::chain_id(),
             Self::contract_address(),
         ));
@@ -135,44 +209,239 @@ pub trait IEip712 {
     }
 }
 
+/// EIP-712 Contract interface.
+///
+/// Implements [`IEip712Domain`] with a name and a version known at compile
+/// time. Such contracts have no storage to cache the domain separator in, so
+/// it is rebuilt on each call. Use [`Eip712`] to cache it.
+pub trait IEip712 {
+    /// Immutable name of EIP-712 instance.
+    const NAME: &'static str;
+    /// Hashed name of EIP-712 instance.
+    const HASHED_NAME: [u8; 32] =
+        keccak_const::Keccak256::new().update(Self::NAME.as_bytes()).finalize();
+
+    /// Immutable version of EIP-712 instance.
+    const VERSION: &'static str;
+    /// Hashed version of EIP-712 instance.
+    const HASHED_VERSION: [u8; 32] = keccak_const::Keccak256::new()
+        .update(Self::VERSION.as_bytes())
+        .finalize();
+}
+
+impl<T: IEip712> IEip712Domain for T {
+    fn name(&self) -> String {
+        T::NAME.to_owned()
+    }
+
+    fn hashed_name(&self) -> B256 {
+        T::HASHED_NAME.into()
+    }
+
+    fn version(&self) -> String {
+        T::VERSION.to_owned()
+    }
+
+    fn hashed_version(&self) -> B256 {
+        T::HASHED_VERSION.into()
+    }
+}
+
+/// State of an [`Eip712`] contract.
+///
+/// Implements [`IEip712Domain`] with a name and a version set at runtime, e.g.
+/// when the contract is initialized, and caches the domain separator in
+/// storage.
+#[storage]
+pub struct Eip712 {
+    /// Name of the EIP-712 domain.
+    pub(crate) name: StorageString,
+    /// Hashed name of the EIP-712 domain.
+    pub(crate) hashed_name: StorageB256,
+    /// Version of the EIP-712 domain.
+    pub(crate) version: StorageString,
+    /// Hashed version of the EIP-712 domain.
+    pub(crate) hashed_version: StorageB256,
+    /// Chain id of the cached domain separator.
+    pub(crate) cached_chain_id: StorageU256,
+    /// Contract address of the cached domain separator.
+    pub(crate) cached_this: StorageAddress,
+    /// Cached domain separator.
+    pub(crate) cached_domain_separator: StorageB256,
+}
+
+impl Eip712 {
+    /// Sets the `name` and `version` of the EIP-712 domain, and caches its
+    /// domain separator for the current chain.
+    ///
+    /// If the chain id changes, e.g. after a fork, the domain separator is
+    /// rebuilt on each call, until the domain is set again.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `name` - The user readable name of the signing domain, i.e. the name
+    ///   of the dApp or the protocol.
+    /// * `version` - The current major version of the signing domain.
+    pub fn _set_domain(&mut self, name: &str, version: &str) {
+        self.name.set_str(name);
+        self.hashed_name.set(keccak256(name));
+        self.version.set_str(version);
+        self.hashed_version.set(keccak256(version));
+
+        self.cached_chain_id.set(Self::chain_id());
+        self.cached_this.set(Self::contract_address());
+        self.cached_domain_separator.set(self.build_domain_separator());
+    }
+}
+
+impl IEip712Domain for Eip712 {
+    fn name(&self) -> String {
+        self.name.get_string()
+    }
+
+    fn hashed_name(&self) -> B256 {
+        self.hashed_name.get()
+    }
+
+    fn version(&self) -> String {
+        self.version.get_string()
+    }
+
+    fn hashed_version(&self) -> B256 {
+        self.hashed_version.get()
+    }
+
+    fn cached_domain_separator(&self) -> Option<(U256, Address, B256)> {
+        let domain_separator = self.cached_domain_separator.get();
+        if domain_separator.is_zero() {
+            return None;
+        }
+
+        Some((
+            self.cached_chain_id.get(),
+            self.cached_this.get(),
+            domain_separator,
+        ))
+    }
+}
+
 #[cfg(all(test, feature = "std"))]
 mod tests {
-    use alloy_primitives::{address, b256, uint, Address, U256};
-
-    use super::{to_typed_data_hash, IEip712, FIELDS, SALT};
+    use alloy_primitives::{b256, keccak256, Address, U256};
+    use motsu::prelude::*;
+    use stylus_sdk::prelude::*;
 
-    const CHAIN_ID: U256 = uint!(42161_U256);
+    use super::{
+        to_typed_data_hash, Eip712, IEip712, IEip712Domain, FIELDS, SALT,
+    };
 
-    const CONTRACT_ADDRESS: Address =
-        address!("000000000000000000000000000000000000dEaD");
+    const CHAIN_ID: u64 = 42161;
 
-    #[derive(Default)]
     struct TestEIP712;
 
     impl IEip712 for TestEIP712 {
         const NAME: &'static str = "A Name";
         const VERSION: &'static str = "1";
+    }
 
-        fn chain_id() -> U256 {
-            CHAIN_ID
-        }
+    #[motsu::test]
+    fn domain_test(contract: Contract<Eip712Mock>, alice: Address) {
+        VMContext::current().set_chain_id(CHAIN_ID);
 
-        fn contract_address() -> Address {
-            CONTRACT_ADDRESS
-        }
+        let domain = contract.init(alice, |_| TestEIP712.eip712_domain());
+        assert_eq!(FIELDS, domain.0);
+        assert_eq!(TestEIP712::NAME, domain.1);
+        assert_eq!(TestEIP712::VERSION, domain.2);
+        assert_eq!(U256::from(CHAIN_ID), domain.3);
+        assert_eq!(contract.address(), domain.4);
+        assert_eq!(SALT, domain.5);
+        assert_eq!(Vec::<U256>::new(), domain.6);
     }
 
     #[test]
-    fn domain_test() {
-        let contract = TestEIP712::default();
-        let domain = contract.eip712_domain();
+    fn domain_separator_is_not_cached_by_default() {
+        let contract = TestEIP712;
+        assert_eq!(None, contract.cached_domain_separator());
+    }
+
+    #[storage]
+    struct Eip712Mock {
+        eip712: Eip712,
+    }
+
+    #[public]
+    impl Eip712Mock {}
+
+    unsafe impl TopLevelStorage for Eip712Mock {}
+
+    #[motsu::test]
+    fn sets_domain(contract: Contract<Eip712Mock>, alice: Address) {
+        contract.sender(alice).eip712._set_domain("A Name", "1");
+
+        let domain = contract.sender(alice).eip712.eip712_domain();
         assert_eq!(FIELDS, domain.0);
-        assert_eq!(TestEIP712::NAME, domain.1);
-        assert_eq!(TestEIP712::VERSION, domain.2);
-        assert_eq!(CHAIN_ID, domain.3);
-        assert_eq!(CONTRACT_ADDRESS, domain.4);
+        assert_eq!("A Name", domain.1);
+        assert_eq!("1", domain.2);
+        assert_eq!(Eip712::chain_id(), domain.3);
+        assert_eq!(contract.address(), domain.4);
         assert_eq!(SALT, domain.5);
         assert_eq!(Vec::<U256>::new(), domain.6);
+
+        assert_eq!(
+            keccak256("A Name"),
+            contract.sender(alice).eip712.hashed_name()
+        );
+        assert_eq!(
+            keccak256("1"),
+            contract.sender(alice).eip712.hashed_version()
+        );
+    }
+
+    #[motsu::test]
+    fn caches_domain_separator(contract: Contract<Eip712Mock>, alice: Address) {
+        assert_eq!(
+            None,
+            contract.sender(alice).eip712.cached_domain_separator()
+        );
+
+        contract.sender(alice).eip712._set_domain("A Name", "1");
+
+        let domain_separator =
+            contract.sender(alice).eip712.build_domain_separator();
+        assert_eq!(
+            Some((Eip712::chain_id(), contract.address(), domain_separator)),
+            contract.sender(alice).eip712.cached_domain_separator()
+        );
+        assert_eq!(
+            domain_separator,
+            contract.sender(alice).eip712.domain_separator_v4()
+        );
+    }
+
+    #[motsu::test]
+    fn rebuilds_domain_separator_on_chain_id_change(
+        contract: Contract<Eip712Mock>,
+        alice: Address,
+    ) {
+        contract.sender(alice).eip712._set_domain("A Name", "1");
+        let domain_separator =
+            contract.sender(alice).eip712.domain_separator_v4();
+
+        VMContext::current().set_chain_id(1);
+
+        assert_ne!(
+            domain_separator,
+            contract.sender(alice).eip712.domain_separator_v4()
+        );
+        assert_eq!(
+            contract.sender(alice).eip712.build_domain_separator(),
+            contract.sender(alice).eip712.domain_separator_v4()
+        );
+        assert_eq!(
+            U256::from(1),
+            contract.sender(alice).eip712.eip712_domain().3
+        );
     }
 
     #[test]
@@ -0,0 +1,150 @@
+//! Signature message hash utilities for producing digests to be consumed by
+//! [`super::ecdsa`] recovery or signing.
+//!
+//! The library provides methods for generating a hash of a message that
+//! conforms to the [ERC-191] signed data standard, that is version `0x45`
+//! (`personal_sign` messages) and version `0x00` (data with intended
+//! validator). Typed data, i.e. version `0x01`, is covered by
+//! [`super::eip712::to_typed_data_hash`].
+//!
+//! [ERC-191]: https://eips.ethereum.org/EIPS/eip-191
+use alloc::string::ToString;
+
+use alloy_primitives::{Address, B256};
+use openzeppelin_crypto::{
+    hash::{BuildHasher, Hasher},
+    keccak::KeccakBuilder,
+};
+
+/// Prefix for ERC-191 version with `0x45` (`personal_sign` messages).
+const ETH_SIGNED_MESSAGE_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+/// Prefix for ERC-191 version with `0x00` (data with intended validator).
+const INTENDED_VALIDATOR_PREFIX: [u8; 2] = [0x19, 0x00];
+
+/// Returns the keccak256 digest of an ERC-191 signed data with version `0x45`
+/// (`personal_sign` messages).
+///
+/// The digest is calculated by prefixing a bytes32 `message_hash` with
+/// `"\x19Ethereum Signed Message:\n32"` and hashing the result. It corresponds
+/// with the hash signed when using the [eth_sign] JSON-RPC method.
+///
+/// NOTE: The `message_hash` parameter is intended to be the result of hashing
+/// a raw message with keccak256, although any bytes32 value can be safely
+/// used because the final digest will be re-hashed.
+///
+/// # Arguments
+///
+/// * `message_hash` - Hash of the signed message.
+///
+/// [eth_sign]: https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_sign
+#[must_use]
+pub fn to_eth_signed_message_hash(message_hash: &[u8; 32]) -> B256 {
+    let mut hasher = KeccakBuilder.build_hasher();
+    hasher.update(ETH_SIGNED_MESSAGE_PREFIX);
+    hasher.update(b"32");
+    hasher.update(message_hash);
+    B256::from(hasher.finalize())
+}
+
+/// Returns the keccak256 digest of an ERC-191 signed data with version `0x45`
+/// (`personal_sign` messages).
+///
+/// The digest is calculated by prefixing an arbitrary `message` with
+/// `"\x19Ethereum Signed Message:\n" + len(message)` and hashing the result.
+/// The length is encoded in decimal. It corresponds with the hash signed when
+/// using the [eth_sign] JSON-RPC method.
+///
+/// # Arguments
+///
+/// * `message` - The signed message.
+///
+/// [eth_sign]: https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_sign
+#[must_use]
+pub fn to_eth_signed_message_hash_bytes(message: &[u8]) -> B256 {
+    let mut hasher = KeccakBuilder.build_hasher();
+    hasher.update(ETH_SIGNED_MESSAGE_PREFIX);
+    hasher.update(message.len().to_string());
+    hasher.update(message);
+    B256::from(hasher.finalize())
+}
+
+/// Returns the keccak256 digest of an ERC-191 signed data with version `0x00`
+/// (data with intended validator).
+///
+/// The digest is calculated by prefixing an arbitrary `data` with `"\x19\x00"`
+/// and the intended `validator` address, and hashing the result.
+///
+/// # Arguments
+///
+/// * `validator` - Address of the contract expected to validate the signature.
+/// * `data` - The signed data.
+#[must_use]
+pub fn to_data_with_intended_validator_hash(
+    validator: Address,
+    data: &[u8],
+) -> B256 {
+    let mut hasher = KeccakBuilder.build_hasher();
+    hasher.update(INTENDED_VALIDATOR_PREFIX);
+    hasher.update(validator);
+    hasher.update(data);
+    B256::from(hasher.finalize())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{
+        address, b256, eip191_hash_message, keccak256, Address, B256,
+    };
+
+    use super::*;
+
+    const MESSAGE_HASH: B256 = b256!(
+        "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+    );
+
+    const VALIDATOR: Address =
+        address!("000000000000000000000000000000000000dEaD");
+
+    #[test]
+    fn eth_signed_message_hash_of_bytes32() {
+        let expected = keccak256(
+            [b"\x19Ethereum Signed Message:\n32", MESSAGE_HASH.as_slice()]
+                .concat(),
+        );
+        assert_eq!(to_eth_signed_message_hash(&MESSAGE_HASH), expected);
+        assert_eq!(
+            to_eth_signed_message_hash(&MESSAGE_HASH),
+            eip191_hash_message(MESSAGE_HASH)
+        );
+    }
+
+    #[test]
+    fn eth_signed_message_hash_of_bytes() {
+        for message in [&b""[..], b"stylus", &[0x42; 1234]] {
+            assert_eq!(
+                to_eth_signed_message_hash_bytes(message),
+                eip191_hash_message(message)
+            );
+        }
+    }
+
+    #[test]
+    fn eth_signed_message_hash_of_bytes32_matches_bytes() {
+        assert_eq!(
+            to_eth_signed_message_hash(&MESSAGE_HASH),
+            to_eth_signed_message_hash_bytes(MESSAGE_HASH.as_slice())
+        );
+    }
+
+    #[test]
+    fn data_with_intended_validator_hash() {
+        let data = b"stylus";
+        let expected =
+            keccak256([&[0x19, 0x00], VALIDATOR.as_slice(), data].concat());
+        assert_eq!(
+            to_data_with_intended_validator_hash(VALIDATOR, data),
+            expected
+        );
+    }
+}
@@ -1,3 +1,8 @@
 //! Smart Contracts with cryptography.
 pub mod ecdsa;
 pub mod eip712;
+pub mod message_hash_utils;
+pub mod p256;
+pub mod signature_checker;
+pub mod signer;
+pub mod webauthn;
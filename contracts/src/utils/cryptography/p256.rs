@@ -0,0 +1,264 @@
+//! Verification of [P-256] (secp256r1) signatures.
+//!
+//! P-256 is the curve used by passkeys, secure enclaves and most hardware
+//! security modules. Signatures are verified with the [RIP-7212] precompile
+//! when the chain provides it, and with the pure-Rust implementation of
+//! [`openzeppelin_crypto::p256`] otherwise.
+//!
+//! Unlike ECDSA over secp256k1, P-256 signatures can't be used to recover
+//! the signer, so the public key has to be provided.
+//!
+//! [P-256]: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-186.pdf
+//! [RIP-7212]: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+use alloy_primitives::{address, b256, uint, Address, B256, U256};
+use openzeppelin_crypto::p256;
+use stylus_sdk::{
+    call::{self, Call},
+    prelude::*,
+};
+
+/// Address of the [RIP-7212] `P256VERIFY` precompile.
+///
+/// [RIP-7212]: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+pub const P256_VERIFY_ADDR: Address =
+    address!("0000000000000000000000000000000000000100");
+
+/// Order of the P-256 curve.
+pub const N: U256 = uint!(
+    0xFFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551_U256
+);
+
+/// Upper range for `s` value from the signature, i.e. half of [`N`].
+pub const SIGNATURE_S_UPPER_BOUND: U256 = uint!(
+    0x7FFFFFFF800000007FFFFFFFFFFFFFFFDE737D56D38BCF4279DCE5617E3192A8_U256
+);
+
+/// Valid signature `(hash, r, s, qx, qy)` used to check whether the
+/// [RIP-7212] precompile is available.
+///
+/// [RIP-7212]: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+pub(crate) const PROBE: [B256; 5] = [
+    b256!("74f2bab0f7b496db35967b365a4bedc0f6378888dea671ec307ee99e677fe21d"),
+    b256!("30845bd5eb740e50bf436053524db8f979037ccf8963d1be542c97d440e6ef10"),
+    b256!("06ff7f96f373719d953a66ccba30b238952ee800dfedbd59b63db6b53d3e81f3"),
+    b256!("8300b1b782b2006949895287fd2c9431f2ab7813e45f2fae2c1fb845a3e37567"),
+    b256!("bccc4d1cd06e7efb369461ea177162b29255d02470e88aadcfd9c80105352b52"),
+];
+
+/// Verifies a P-256 signature of `hash` by the public key `(qx, qy)`.
+///
+/// Uses the [RIP-7212] precompile if it's available, and falls back to
+/// [`verify_rust`] otherwise.
+///
+/// Returns `false` for malleable signatures, i.e. if `s` is greater than
+/// [`SIGNATURE_S_UPPER_BOUND`].
+///
+/// # Arguments
+///
+/// * `storage` - Write access to storage.
+/// * `hash` - Hash of the signed message.
+/// * `r` - The `r` value of the signature.
+/// * `s` - The `s` value of the signature.
+/// * `qx` - The `x` coordinate of the public key.
+/// * `qy` - The `y` coordinate of the public key.
+///
+/// [RIP-7212]: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+pub fn verify(
+    storage: &mut impl TopLevelStorage,
+    hash: B256,
+    r: B256,
+    s: B256,
+    qx: B256,
+    qy: B256,
+) -> bool {
+    try_verify_native(storage, hash, r, s, qx, qy)
+        .unwrap_or_else(|| verify_rust(hash, r, s, qx, qy))
+}
+
+/// Verifies a P-256 signature of `hash` by the public key `(qx, qy)` with the
+/// [RIP-7212] precompile.
+///
+/// Returns `false` if the precompile is not available.
+///
+/// # Arguments
+///
+/// * `storage` - Write access to storage.
+/// * `hash` - Hash of the signed message.
+/// * `r` - The `r` value of the signature.
+/// * `s` - The `s` value of the signature.
+/// * `qx` - The `x` coordinate of the public key.
+/// * `qy` - The `y` coordinate of the public key.
+///
+/// [RIP-7212]: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+pub fn verify_native(
+    storage: &mut impl TopLevelStorage,
+    hash: B256,
+    r: B256,
+    s: B256,
+    qx: B256,
+    qy: B256,
+) -> bool {
+    try_verify_native(storage, hash, r, s, qx, qy).unwrap_or(false)
+}
+
+/// Verifies a P-256 signature of `hash` by the public key `(qx, qy)` without
+/// calling the [RIP-7212] precompile.
+///
+/// # Arguments
+///
+/// * `hash` - Hash of the signed message.
+/// * `r` - The `r` value of the signature.
+/// * `s` - The `s` value of the signature.
+/// * `qx` - The `x` coordinate of the public key.
+/// * `qy` - The `y` coordinate of the public key.
+///
+/// [RIP-7212]: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+#[must_use]
+pub fn verify_rust(hash: B256, r: B256, s: B256, qx: B256, qy: B256) -> bool {
+    p256::verify(&hash, &r, &s, &qx, &qy)
+}
+
+/// Returns `true` if `(qx, qy)` is a valid P-256 public key.
+///
+/// # Arguments
+///
+/// * `qx` - The `x` coordinate of the public key.
+/// * `qy` - The `y` coordinate of the public key.
+#[must_use]
+pub fn is_valid_public_key(qx: B256, qy: B256) -> bool {
+    p256::is_valid_public_key(&qx, &qy)
+}
+
+/// Tries to verify a signature with the [RIP-7212] precompile.
+///
+/// Returns `None` if the precompile is not available. Invalid signatures are
+/// rejected before calling it.
+///
+/// [RIP-7212]: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+fn try_verify_native(
+    storage: &mut impl TopLevelStorage,
+    hash: B256,
+    r: B256,
+    s: B256,
+    qx: B256,
+    qy: B256,
+) -> Option<bool> {
+    if !is_proper_signature(r, s) || !is_valid_public_key(qx, qy) {
+        return Some(false);
+    }
+
+    if rip7212(storage, hash, r, s, qx, qy) {
+        return Some(true);
+    }
+
+    // The precompile returns no data both for invalid signatures and when
+    // it's missing, so a known valid signature tells them apart.
+    let [hash, r, s, qx, qy] = PROBE;
+    rip7212(storage, hash, r, s, qx, qy).then_some(false)
+}
+
+/// Calls the [RIP-7212] precompile, returning `true` if it accepts the
+/// signature.
+///
+/// [RIP-7212]: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+fn rip7212(
+    storage: &mut impl TopLevelStorage,
+    hash: B256,
+    r: B256,
+    s: B256,
+    qx: B256,
+    qy: B256,
+) -> bool {
+    let calldata = [hash, r, s, qx, qy].concat();
+    call::static_call(Call::new_in(storage), P256_VERIFY_ADDR, &calldata)
+        .is_ok_and(|output| {
+            output.len() == 32 && U256::from_be_slice(&output) == U256::from(1)
+        })
+}
+
+/// Returns `true` if `r` and `s` are in range, and `s` is in the lower half
+/// of the order of the curve.
+fn is_proper_signature(r: B256, s: B256) -> bool {
+    let r = U256::from_be_bytes(r.0);
+    let s = U256::from_be_bytes(s.0);
+    !r.is_zero() && r < N && !s.is_zero() && s <= SIGNATURE_S_UPPER_BOUND
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{Address, B256, U256};
+    use motsu::prelude::*;
+    use stylus_sdk::prelude::*;
+
+    use super::*;
+
+    #[storage]
+    struct P256Mock;
+
+    unsafe impl TopLevelStorage for P256Mock {}
+
+    #[public]
+    impl P256Mock {
+        fn verify(
+            &mut self,
+            hash: B256,
+            r: B256,
+            s: B256,
+            qx: B256,
+            qy: B256,
+        ) -> bool {
+            super::verify(self, hash, r, s, qx, qy)
+        }
+    }
+
+    #[test]
+    fn upper_bound_is_half_of_the_order() {
+        assert_eq!(SIGNATURE_S_UPPER_BOUND, N / U256::from(2));
+    }
+
+    #[test]
+    fn verify_rust_accepts_probe() {
+        let [hash, r, s, qx, qy] = PROBE;
+        assert!(verify_rust(hash, r, s, qx, qy));
+        assert!(is_valid_public_key(qx, qy));
+    }
+
+    #[test]
+    fn verify_rust_rejects_wrong_hash() {
+        let [_, r, s, qx, qy] = PROBE;
+        assert!(!verify_rust(B256::repeat_byte(0x11), r, s, qx, qy));
+    }
+
+    // These signatures are rejected before calling the RIP-7212 precompile.
+    #[motsu::test]
+    fn rejects_improper_signature(
+        contract: Contract<P256Mock>,
+        alice: Address,
+    ) {
+        let [hash, r, s, qx, qy] = PROBE;
+        let high_s = B256::from(N - U256::from_be_bytes(s.0));
+        let n = B256::from(N);
+
+        for (r, s) in [(B256::ZERO, s), (n, s), (r, B256::ZERO), (r, high_s)] {
+            assert!(!contract.sender(alice).verify(hash, r, s, qx, qy));
+        }
+    }
+
+    #[motsu::test]
+    fn rejects_invalid_public_key(
+        contract: Contract<P256Mock>,
+        alice: Address,
+    ) {
+        let [hash, r, s, qx, _] = PROBE;
+
+        assert!(!is_valid_public_key(B256::ZERO, B256::ZERO));
+        assert!(!contract.sender(alice).verify(hash, r, s, qx, qx));
+        assert!(!contract.sender(alice).verify(
+            hash,
+            r,
+            s,
+            B256::ZERO,
+            B256::ZERO
+        ));
+    }
+}
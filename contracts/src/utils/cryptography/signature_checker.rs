@@ -0,0 +1,281 @@
+//! Signature verification helper for both EOAs and smart contract wallets.
+//!
+//! Can be used instead of [`ecdsa::recover`] to seamlessly support both ECDSA
+//! signatures from externally owned accounts (EOAs) as well as [ERC-1271]
+//! signatures from smart contract wallets like Argent and Safe Wallet
+//! (previously Gnosis Safe).
+//!
+//! NOTE: Unlike ECDSA signatures, contract signatures are revocable, and the
+//! outcome of this function can thus change through time. It could return
+//! `true` at block N and `false` at block N+1 (or the opposite).
+//!
+//! [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+use alloy_primitives::{fixed_bytes, Address, FixedBytes, B256};
+use openzeppelin_stylus_proc::interface_id;
+use stylus_sdk::{abi::Bytes, prelude::*};
+
+use crate::utils::cryptography::ecdsa;
+
+/// Value returned by [`IErc1271::is_valid_signature`] when the signature is
+/// valid.
+///
+/// Equal to the selector of `isValidSignature(bytes32,bytes)`.
+pub const ERC1271_MAGIC_VALUE: FixedBytes<4> = fixed_bytes!("1626ba7e");
+
+mod interface {
+    #![allow(missing_docs)]
+    #![cfg_attr(coverage_nightly, coverage(off))]
+    use alloc::vec;
+
+    use stylus_sdk::prelude::sol_interface;
+    sol_interface! {
+        interface IErc1271 {
+            function isValidSignature(bytes32 hash, bytes signature) external view returns (bytes4 magicValue);
+        }
+    }
+}
+
+/// Interface of the [ERC-1271] standard signature validation method for
+/// contracts.
+///
+/// [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+#[interface_id]
+pub trait IErc1271 {
+    /// Returns [`ERC1271_MAGIC_VALUE`] if the provided signature is valid for
+    /// the provided `hash`, and any other value otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `hash` - Hash of the data to be signed.
+    /// * `signature` - Signature byte array associated with `hash`.
+    fn is_valid_signature(&self, hash: B256, signature: Bytes)
+        -> FixedBytes<4>;
+}
+
+/// Checks if a signature is valid for a given `signer` and data `hash`.
+///
+/// If the signer is a smart contract, the signature is validated against that
+/// smart contract using [ERC-1271], otherwise it's validated using
+/// [`ecdsa::try_recover`].
+///
+/// NOTE: Unlike ECDSA signatures, contract signatures are revocable, and the
+/// outcome of this function can thus change through time. It could return
+/// `true` at block N and `false` at block N+1 (or the opposite).
+///
+/// # Arguments
+///
+/// * `storage` - Write access to storage.
+/// * `signer` - Address that should have signed `hash`.
+/// * `hash` - Hash of the signed data.
+/// * `signature` - Signature of `hash`.
+///
+/// [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+pub fn is_valid_signature_now(
+    storage: &mut impl TopLevelStorage,
+    signer: Address,
+    hash: B256,
+    signature: &[u8],
+) -> bool {
+    if ecdsa::try_recover(storage, hash, signature).is_ok_and(|a| a == signer) {
+        return true;
+    }
+
+    is_valid_erc1271_signature_now(storage, signer, hash, signature)
+}
+
+/// Checks if a signature is valid for a given `signer` and data `hash`. The
+/// signature is validated against the `signer` smart contract using
+/// [ERC-1271].
+///
+/// NOTE: Unlike ECDSA signatures, contract signatures are revocable, and the
+/// outcome of this function can thus change through time. It could return
+/// `true` at block N and `false` at block N+1 (or the opposite).
+///
+/// # Arguments
+///
+/// * `storage` - Read access to storage.
+/// * `signer` - Address of the smart contract that should have signed `hash`.
+/// * `hash` - Hash of the signed data.
+/// * `signature` - Signature of `hash`.
+///
+/// [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+pub fn is_valid_erc1271_signature_now(
+    storage: &impl TopLevelStorage,
+    signer: Address,
+    hash: B256,
+    signature: &[u8],
+) -> bool {
+    interface::IErc1271::new(signer)
+        .is_valid_signature(storage, hash, signature.to_vec().into())
+        .is_ok_and(|magic_value| magic_value == ERC1271_MAGIC_VALUE)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{keccak256, Address, FixedBytes, B256};
+    use motsu::prelude::*;
+    use stylus_sdk::{
+        abi::{Bytes, Router},
+        prelude::*,
+        storage::{StorageAddress, StorageB256, StorageBool},
+        ArbResult,
+    };
+
+    use super::*;
+
+    const HASH: B256 = B256::repeat_byte(0x11);
+
+    #[storage]
+    struct WalletMock {
+        hash: StorageB256,
+        invalid_magic_value: StorageBool,
+    }
+
+    unsafe impl TopLevelStorage for WalletMock {}
+
+    #[public]
+    impl IErc1271 for WalletMock {
+        fn is_valid_signature(
+            &self,
+            hash: B256,
+            _signature: Bytes,
+        ) -> FixedBytes<4> {
+            if hash != self.hash.get() {
+                return FixedBytes::ZERO;
+            }
+            if self.invalid_magic_value.get() {
+                return FixedBytes::from([0xde, 0xad, 0xbe, 0xef]);
+            }
+            ERC1271_MAGIC_VALUE
+        }
+    }
+
+    /// Stands in for the `ecrecover` precompile, which isn't available in
+    /// tests, returning `recovered` for any input.
+    #[storage]
+    struct EcrecoverMock {
+        recovered: StorageAddress,
+    }
+
+    unsafe impl TopLevelStorage for EcrecoverMock {}
+
+    impl Router<Self> for EcrecoverMock {
+        type Storage = Self;
+
+        fn route(mock: &mut Self, _: u32, _: &[u8]) -> Option<ArbResult> {
+            Some(Ok(mock.recovered.get().into_word().to_vec()))
+        }
+
+        fn receive(_: &mut Self) -> Option<Result<(), Vec<u8>>> {
+            None
+        }
+
+        fn fallback(_: &mut Self, _: &[u8]) -> Option<ArbResult> {
+            None
+        }
+    }
+
+    #[storage]
+    struct CheckerMock;
+
+    unsafe impl TopLevelStorage for CheckerMock {}
+
+    #[public]
+    impl CheckerMock {
+        fn is_valid(
+            &mut self,
+            signer: Address,
+            hash: B256,
+            signature: Bytes,
+        ) -> bool {
+            is_valid_signature_now(self, signer, hash, &signature)
+        }
+    }
+
+    // Signatures that must fail ECDSA recovery before calling the `ecrecover`
+    // precompile, except for 64-byte ones, which are read as EIP-2098 compact
+    // signatures.
+    fn signature(len: usize) -> Bytes {
+        // `v` is neither 27 nor 28, so ECDSA recovery fails early.
+        let mut signature = vec![0x42; len];
+        if let Some(v) = signature.last_mut() {
+            *v = 0;
+        }
+        Bytes::from(signature)
+    }
+
+    #[test]
+    fn magic_value_is_is_valid_signature_selector() {
+        let selector = keccak256("isValidSignature(bytes32,bytes)");
+        assert_eq!(ERC1271_MAGIC_VALUE, selector[..4]);
+    }
+
+    #[motsu::test]
+    fn accepts_valid_erc1271_signature(
+        checker: Contract<CheckerMock>,
+        wallet: Contract<WalletMock>,
+        alice: Address,
+    ) {
+        wallet.sender(alice).hash.set(HASH);
+
+        for len in [0, 65, 100] {
+            assert!(checker.sender(alice).is_valid(
+                wallet.address(),
+                HASH,
+                signature(len)
+            ));
+        }
+    }
+
+    #[motsu::test]
+    fn accepts_erc1271_signature_of_compact_length(
+        checker: Contract<CheckerMock>,
+        wallet: Contract<WalletMock>,
+        alice: Address,
+    ) {
+        wallet.sender(alice).hash.set(HASH);
+        // A 64-byte signature is first recovered as an EIP-2098 signature,
+        // which yields an account other than the wallet.
+        let ecrecover =
+            Contract::<EcrecoverMock>::new_at(ecdsa::ECRECOVER_ADDR);
+        ecrecover.sender(alice).recovered.set(alice);
+
+        assert!(checker.sender(alice).is_valid(
+            wallet.address(),
+            HASH,
+            signature(64)
+        ));
+    }
+
+    #[motsu::test]
+    fn rejects_erc1271_signature_of_other_hash(
+        checker: Contract<CheckerMock>,
+        wallet: Contract<WalletMock>,
+        alice: Address,
+    ) {
+        wallet.sender(alice).hash.set(HASH);
+
+        assert!(!checker.sender(alice).is_valid(
+            wallet.address(),
+            B256::repeat_byte(0x22),
+            signature(65)
+        ));
+    }
+
+    #[motsu::test]
+    fn rejects_invalid_erc1271_magic_value(
+        checker: Contract<CheckerMock>,
+        wallet: Contract<WalletMock>,
+        alice: Address,
+    ) {
+        wallet.sender(alice).hash.set(HASH);
+        wallet.sender(alice).invalid_magic_value.set(true);
+
+        assert!(!checker.sender(alice).is_valid(
+            wallet.address(),
+            HASH,
+            signature(65)
+        ));
+    }
+}
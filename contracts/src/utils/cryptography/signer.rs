@@ -0,0 +1,363 @@
+//! Signature validation abstraction, used by smart accounts to decouple their
+//! logic from the signing scheme of their owner.
+//!
+//! * [`SignerEcdsa`] - Validates secp256k1 signatures of an address, like
+//!   externally owned accounts (EOAs).
+//! * [`SignerP256`] - Validates P-256 signatures of a public key, like passkeys
+//!   and secure enclaves.
+//! * [`SignerErc1271`] - Delegates validation to an [ERC-1271] contract, like
+//!   another smart account.
+//!
+//! Each signer is a storage component that must be initialized with
+//! `_set_signer` before it validates any signature. Uninitialized signers
+//! reject every signature.
+//!
+//! [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{Address, B256};
+use stylus_sdk::{
+    prelude::*,
+    storage::{StorageAddress, StorageB256},
+};
+
+use crate::utils::cryptography::{ecdsa, p256, signature_checker};
+
+/// Abstract signature validation.
+///
+/// Implementors validate signatures of a given hash in the signing scheme of
+/// their choice.
+pub trait AbstractSigner {
+    /// Returns `true` if `signature` is a valid signature of `hash` by this
+    /// signer.
+    ///
+    /// WARNING: Implementing a signature validation algorithm is a
+    /// security-sensitive operation as it involves cryptographic verification.
+    /// It is important to review and test thoroughly before deployment.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `hash` - Hash of the signed data.
+    /// * `signature` - Signature of `hash`.
+    fn raw_signature_validation(
+        &mut self,
+        hash: B256,
+        signature: &[u8],
+    ) -> bool;
+}
+
+/// State of a [`SignerEcdsa`] contract.
+#[storage]
+pub struct SignerEcdsa {
+    /// Address of the signer.
+    pub(crate) signer: StorageAddress,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for SignerEcdsa {}
+
+impl SignerEcdsa {
+    /// Returns the address of the signer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn signer(&self) -> Address {
+        self.signer.get()
+    }
+
+    /// Sets the address of the signer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `signer` - Address of the new signer.
+    pub fn _set_signer(&mut self, signer: Address) {
+        self.signer.set(signer);
+    }
+}
+
+impl AbstractSigner for SignerEcdsa {
+    fn raw_signature_validation(
+        &mut self,
+        hash: B256,
+        signature: &[u8],
+    ) -> bool {
+        let signer = self.signer();
+        !signer.is_zero()
+            && ecdsa::try_recover(self, hash, signature)
+                .is_ok_and(|recovered| recovered == signer)
+    }
+}
+
+/// State of a [`SignerP256`] contract.
+///
+/// Signatures are expected as the 64-byte concatenation of `r` and `s`.
+#[storage]
+pub struct SignerP256 {
+    /// The `x` coordinate of the public key of the signer.
+    pub(crate) qx: StorageB256,
+    /// The `y` coordinate of the public key of the signer.
+    pub(crate) qy: StorageB256,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for SignerP256 {}
+
+impl SignerP256 {
+    /// Returns the public key `(qx, qy)` of the signer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn signer(&self) -> (B256, B256) {
+        (self.qx.get(), self.qy.get())
+    }
+
+    /// Sets the public key of the signer.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `qx` - The `x` coordinate of the public key.
+    /// * `qy` - The `y` coordinate of the public key.
+    pub fn _set_signer(&mut self, qx: B256, qy: B256) {
+        self.qx.set(qx);
+        self.qy.set(qy);
+    }
+}
+
+impl AbstractSigner for SignerP256 {
+    fn raw_signature_validation(
+        &mut self,
+        hash: B256,
+        signature: &[u8],
+    ) -> bool {
+        if signature.len() != 64 {
+            return false;
+        }
+
+        let r = B256::from_slice(&signature[..32]);
+        let s = B256::from_slice(&signature[32..]);
+        let (qx, qy) = self.signer();
+        p256::verify(self, hash, r, s, qx, qy)
+    }
+}
+
+/// State of a [`SignerErc1271`] contract.
+#[storage]
+pub struct SignerErc1271 {
+    /// Address of the [ERC-1271] contract validating signatures.
+    ///
+    /// [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+    pub(crate) signer: StorageAddress,
+}
+
+/// NOTE: Implementation of [`TopLevelStorage`] to be able use `&mut self` when
+/// calling other contracts and not `&mut (impl TopLevelStorage +
+/// BorrowMut<Self>)`. Should be fixed in the future by the Stylus team.
+unsafe impl TopLevelStorage for SignerErc1271 {}
+
+impl SignerErc1271 {
+    /// Returns the address of the signer contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn signer(&self) -> Address {
+        self.signer.get()
+    }
+
+    /// Sets the address of the signer contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `signer` - Address of the new signer contract.
+    pub fn _set_signer(&mut self, signer: Address) {
+        self.signer.set(signer);
+    }
+}
+
+impl AbstractSigner for SignerErc1271 {
+    fn raw_signature_validation(
+        &mut self,
+        hash: B256,
+        signature: &[u8],
+    ) -> bool {
+        let signer = self.signer();
+        !signer.is_zero()
+            && signature_checker::is_valid_erc1271_signature_now(
+                self, signer, hash, signature,
+            )
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{Address, FixedBytes, B256};
+    use motsu::prelude::*;
+    use stylus_sdk::{
+        abi::{Bytes, Router},
+        prelude::*,
+        storage::StorageB256,
+        ArbResult,
+    };
+
+    use super::*;
+    use crate::utils::cryptography::{
+        p256::{P256_VERIFY_ADDR, PROBE},
+        signature_checker::{IErc1271, ERC1271_MAGIC_VALUE},
+    };
+
+    const HASH: B256 = B256::repeat_byte(0x11);
+
+    #[storage]
+    struct WalletMock {
+        hash: StorageB256,
+    }
+
+    unsafe impl TopLevelStorage for WalletMock {}
+
+    #[public]
+    impl IErc1271 for WalletMock {
+        fn is_valid_signature(
+            &self,
+            hash: B256,
+            _signature: Bytes,
+        ) -> FixedBytes<4> {
+            if hash == self.hash.get() {
+                ERC1271_MAGIC_VALUE
+            } else {
+                FixedBytes::ZERO
+            }
+        }
+    }
+
+    /// Stands in for a missing RIP-7212 precompile: like an account without
+    /// code, it returns no data for any call, so that [`p256::verify`] falls
+    /// back to [`p256::verify_rust`].
+    #[storage]
+    struct MissingPrecompileMock;
+
+    unsafe impl TopLevelStorage for MissingPrecompileMock {}
+
+    impl Router<Self> for MissingPrecompileMock {
+        type Storage = Self;
+
+        fn route(_: &mut Self, _: u32, _: &[u8]) -> Option<ArbResult> {
+            Some(Ok(vec![]))
+        }
+
+        fn receive(_: &mut Self) -> Option<Result<(), Vec<u8>>> {
+            None
+        }
+
+        fn fallback(_: &mut Self, _: &[u8]) -> Option<ArbResult> {
+            None
+        }
+    }
+
+    #[storage]
+    struct SignersMock {
+        ecdsa: SignerEcdsa,
+        p256: SignerP256,
+        erc1271: SignerErc1271,
+    }
+
+    unsafe impl TopLevelStorage for SignersMock {}
+
+    #[public]
+    impl SignersMock {}
+
+    #[motsu::test]
+    fn ecdsa_signer_rejects_invalid_signatures(
+        contract: Contract<SignersMock>,
+        alice: Address,
+    ) {
+        contract.init(alice, |mock| {
+            // Uninitialized signer.
+            assert!(!mock.ecdsa.raw_signature_validation(HASH, &[0; 65]));
+
+            mock.ecdsa._set_signer(alice);
+            assert_eq!(mock.ecdsa.signer(), alice);
+            // The `ecrecover` precompile isn't available in tests, so
+            // signatures must fail before calling it.
+            assert!(!mock.ecdsa.raw_signature_validation(HASH, &[0; 65]));
+            assert!(!mock.ecdsa.raw_signature_validation(HASH, &[0; 10]));
+        });
+    }
+
+    #[motsu::test]
+    fn p256_signer_rejects_invalid_signatures(
+        contract: Contract<SignersMock>,
+        alice: Address,
+    ) {
+        let qx = B256::repeat_byte(0x01);
+        let qy = B256::repeat_byte(0x02);
+
+        contract.init(alice, |mock| {
+            mock.p256._set_signer(qx, qy);
+            assert_eq!(mock.p256.signer(), (qx, qy));
+
+            // Invalid length.
+            assert!(!mock.p256.raw_signature_validation(HASH, &[0x01; 65]));
+            // `r` and `s` out of range.
+            assert!(!mock.p256.raw_signature_validation(HASH, &[0xff; 64]));
+            // Invalid public key.
+            assert!(!mock.p256.raw_signature_validation(HASH, &[0x01; 64]));
+        });
+    }
+
+    #[motsu::test]
+    fn p256_signer_validates_signatures(
+        contract: Contract<SignersMock>,
+        alice: Address,
+    ) {
+        let _precompile =
+            Contract::<MissingPrecompileMock>::new_at(P256_VERIFY_ADDR);
+        let [hash, r, s, qx, qy] = PROBE;
+        let signature = [r, s].concat();
+
+        contract.init(alice, |mock| {
+            // Uninitialized signer.
+            assert!(!mock.p256.raw_signature_validation(hash, &signature));
+
+            mock.p256._set_signer(qx, qy);
+            assert!(mock.p256.raw_signature_validation(hash, &signature));
+            assert!(!mock.p256.raw_signature_validation(HASH, &signature));
+            assert!(!mock
+                .p256
+                .raw_signature_validation(hash, &[s, r].concat()));
+        });
+    }
+
+    #[motsu::test]
+    fn erc1271_signer_validates_signatures(
+        contract: Contract<SignersMock>,
+        wallet: Contract<WalletMock>,
+        alice: Address,
+    ) {
+        wallet.sender(alice).hash.set(HASH);
+
+        contract.init(alice, |mock| {
+            // Uninitialized signer.
+            assert!(!mock.erc1271.raw_signature_validation(HASH, &[]));
+
+            mock.erc1271._set_signer(wallet.address());
+            assert_eq!(mock.erc1271.signer(), wallet.address());
+            assert!(mock.erc1271.raw_signature_validation(HASH, &[]));
+            assert!(!mock
+                .erc1271
+                .raw_signature_validation(B256::repeat_byte(0x22), &[]));
+        });
+    }
+}
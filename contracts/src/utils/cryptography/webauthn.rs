@@ -0,0 +1,338 @@
+//! Verification of [WebAuthn] assertions, as produced by passkeys.
+//!
+//! An assertion is a [P-256] signature by the authenticator over
+//! `sha256(authenticatorData || sha256(clientDataJSON))`. Besides the
+//! signature, [`verify`] checks that:
+//!
+//! 1. The `type` field of `clientDataJSON` is `"webauthn.get"`.
+//! 2. The `challenge` field of `clientDataJSON` is the base64url encoding of
+//!    the expected challenge.
+//! 3. The User Present (UP) flag of `authenticatorData` is set, as well as the
+//!    User Verified (UV) flag if required.
+//! 4. The Backup State (BS) flag is only set along with the Backup Eligibility
+//!    (BE) flag.
+//!
+//! Other fields, like the origin or the relying party ID, are not checked.
+//! Locating the `type` and `challenge` fields is left to the caller, who
+//! provides their offsets in `clientDataJSON` instead of parsing it on-chain.
+//!
+//! [WebAuthn]: https://www.w3.org/TR/webauthn-2/#sctn-verifying-assertion
+//! [P-256]: super::p256
+use alloc::{string::String, vec::Vec};
+
+use alloy_primitives::B256;
+use openzeppelin_crypto::{
+    hash::{BuildHasher, Hasher},
+    sha256::Sha256Builder,
+};
+use stylus_sdk::prelude::*;
+
+use crate::utils::cryptography::p256;
+
+/// User Present (UP) flag of the authenticator data.
+pub const AUTH_DATA_FLAGS_UP: u8 = 0x01;
+
+/// User Verified (UV) flag of the authenticator data.
+pub const AUTH_DATA_FLAGS_UV: u8 = 0x04;
+
+/// Backup Eligibility (BE) flag of the authenticator data.
+pub const AUTH_DATA_FLAGS_BE: u8 = 0x08;
+
+/// Backup State (BS) flag of the authenticator data.
+pub const AUTH_DATA_FLAGS_BS: u8 = 0x10;
+
+/// Minimum length of the authenticator data, i.e. the `rpIdHash` (32 bytes),
+/// the flags (1 byte) and the `signCount` (4 bytes).
+pub const MIN_AUTH_DATA_LENGTH: usize = 37;
+
+/// Offset of the flags in the authenticator data.
+const AUTH_DATA_FLAGS_OFFSET: usize = 32;
+
+/// Expected `type` field of `clientDataJSON`.
+const EXPECTED_TYPE: &str = "\"type\":\"webauthn.get\"";
+
+/// Alphabet of the base64url encoding.
+const BASE64_URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// A [WebAuthn] assertion.
+///
+/// [WebAuthn]: https://www.w3.org/TR/webauthn-2/#authenticatorassertionresponse
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WebAuthnAuth {
+    /// The `r` value of the P-256 signature.
+    pub r: B256,
+    /// The `s` value of the P-256 signature.
+    pub s: B256,
+    /// Offset of `"challenge":"..."` in `client_data_json`.
+    pub challenge_index: usize,
+    /// Offset of `"type":"..."` in `client_data_json`.
+    pub type_index: usize,
+    /// The authenticator data, as returned by the authenticator.
+    pub authenticator_data: Vec<u8>,
+    /// The client data JSON, as serialized by the client.
+    pub client_data_json: String,
+}
+
+/// Verifies a [WebAuthn] assertion of `challenge` by the P-256 public key
+/// `(qx, qy)`.
+///
+/// Returns `false` if any of the checks described in the [module
+/// documentation][self] fails.
+///
+/// # Arguments
+///
+/// * `storage` - Write access to storage.
+/// * `challenge` - The challenge the assertion is expected to sign.
+/// * `auth` - The assertion.
+/// * `qx` - The `x` coordinate of the public key.
+/// * `qy` - The `y` coordinate of the public key.
+/// * `require_uv` - Whether the User Verified (UV) flag must be set.
+///
+/// [WebAuthn]: https://www.w3.org/TR/webauthn-2/#sctn-verifying-assertion
+pub fn verify(
+    storage: &mut impl TopLevelStorage,
+    challenge: &[u8],
+    auth: &WebAuthnAuth,
+    qx: B256,
+    qy: B256,
+    require_uv: bool,
+) -> bool {
+    validate_expected_type(&auth.client_data_json, auth.type_index)
+        && validate_challenge(
+            &auth.client_data_json,
+            auth.challenge_index,
+            challenge,
+        )
+        && validate_flags(&auth.authenticator_data, require_uv)
+        && p256::verify(storage, digest(auth), auth.r, auth.s, qx, qy)
+}
+
+/// Returns `true` if the `type` field at `type_index` of `client_data_json`
+/// is `"webauthn.get"`.
+fn validate_expected_type(client_data_json: &str, type_index: usize) -> bool {
+    client_data_json
+        .as_bytes()
+        .get(type_index..)
+        .is_some_and(|json| json.starts_with(EXPECTED_TYPE.as_bytes()))
+}
+
+/// Returns `true` if the `challenge` field at `challenge_index` of
+/// `client_data_json` is the base64url encoding of `challenge`.
+fn validate_challenge(
+    client_data_json: &str,
+    challenge_index: usize,
+    challenge: &[u8],
+) -> bool {
+    let expected =
+        ["\"challenge\":\"", &base64_url_encode(challenge), "\""].concat();
+    client_data_json
+        .as_bytes()
+        .get(challenge_index..)
+        .is_some_and(|json| json.starts_with(expected.as_bytes()))
+}
+
+/// Returns `true` if the flags of `authenticator_data` are valid.
+fn validate_flags(authenticator_data: &[u8], require_uv: bool) -> bool {
+    if authenticator_data.len() < MIN_AUTH_DATA_LENGTH {
+        return false;
+    }
+
+    let flags = authenticator_data[AUTH_DATA_FLAGS_OFFSET];
+    let up = flags & AUTH_DATA_FLAGS_UP != 0;
+    let uv = flags & AUTH_DATA_FLAGS_UV != 0;
+    let be = flags & AUTH_DATA_FLAGS_BE != 0;
+    let bs = flags & AUTH_DATA_FLAGS_BS != 0;
+
+    up && (uv || !require_uv) && (be || !bs)
+}
+
+/// Returns the digest signed by the authenticator, i.e.
+/// `sha256(authenticatorData || sha256(clientDataJSON))`.
+fn digest(auth: &WebAuthnAuth) -> B256 {
+    let mut hasher = Sha256Builder.build_hasher();
+    hasher.update(&auth.client_data_json);
+    let client_data_hash = hasher.finalize();
+
+    let mut hasher = Sha256Builder.build_hasher();
+    hasher.update(&auth.authenticator_data);
+    hasher.update(client_data_hash);
+    B256::from(hasher.finalize())
+}
+
+/// Encodes `data` in base64url, without padding.
+fn base64_url_encode(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or_default(),
+            chunk.get(2).copied().unwrap_or_default(),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        // A chunk of `k` bytes is encoded with `k + 1` characters.
+        for i in 0..=chunk.len() {
+            let index = (n >> (18 - 6 * i)) & 0x3f;
+            encoded.push(char::from(BASE64_URL_ALPHABET[index as usize]));
+        }
+    }
+    encoded
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{b256, hex, Address, B256};
+    use motsu::prelude::*;
+    use stylus_sdk::prelude::*;
+
+    use super::*;
+
+    const CHALLENGE: B256 = b256!(
+        "f631058a3ba1116acce12396fad0a125b5041c43f8e15723709f81aa8d5f4ccf"
+    );
+    const QX: B256 = b256!(
+        "471c3e758c4904285bba7e53118ed0f524adeb0757d25bd2f8e7b0d76dfa714c"
+    );
+    const QY: B256 = b256!(
+        "dd520f7aca8a8b917acc37f51de8f0c9bbe3ad858382e702dc25a12d09f7a858"
+    );
+
+    fn auth() -> WebAuthnAuth {
+        WebAuthnAuth {
+            r: b256!("1b03ebc11c0f022df4bdfdf9506bc7184e5b40204e6e8d16a11951e178665997"),
+            s: b256!("4015f3889c244bd8fc4284f1545c1e082f011d3ee1a702a57503f2ff89de7879"),
+            challenge_index: 23,
+            type_index: 1,
+            authenticator_data: hex!("49960de5880e8c687434170f6476605b8fe4aeb9a28632c7995cf3ba831d97630500000001").to_vec(),
+            client_data_json: r#"{"type":"webauthn.get","challenge":"9jEFijuhEWrM4SOW-tChJbUEHEP44VcjcJ-Bqo1fTM8","origin":"http://localhost:3005","crossOrigin":false}"#.into(),
+        }
+    }
+
+    fn with_flags(flags: u8) -> Vec<u8> {
+        let mut authenticator_data = auth().authenticator_data;
+        authenticator_data[AUTH_DATA_FLAGS_OFFSET] = flags;
+        authenticator_data
+    }
+
+    #[storage]
+    struct WebAuthnMock;
+
+    unsafe impl TopLevelStorage for WebAuthnMock {}
+
+    #[public]
+    impl WebAuthnMock {}
+
+    impl WebAuthnMock {
+        fn verify(&mut self, challenge: &[u8], auth: &WebAuthnAuth) -> bool {
+            super::verify(self, challenge, auth, QX, QY, false)
+        }
+    }
+
+    #[test]
+    fn encodes_base64_url() {
+        assert_eq!(base64_url_encode(b""), "");
+        assert_eq!(base64_url_encode(b"f"), "Zg");
+        assert_eq!(base64_url_encode(b"fo"), "Zm8");
+        assert_eq!(base64_url_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_url_encode(b"foob"), "Zm9vYg");
+        assert_eq!(base64_url_encode(&[0xfb, 0xff]), "-_8");
+        assert_eq!(
+            base64_url_encode(CHALLENGE.as_slice()),
+            "9jEFijuhEWrM4SOW-tChJbUEHEP44VcjcJ-Bqo1fTM8"
+        );
+    }
+
+    // The RIP-7212 precompile isn't available in tests, so the signature is
+    // checked against the pure-Rust implementation instead.
+    #[test]
+    fn signs_expected_digest() {
+        let auth = auth();
+        assert_eq!(
+            digest(&auth),
+            b256!("a41ec3e49b95cb1eea8cd4cc7700e6a7478cd7966985eefd7fc8ee3d1af3999a")
+        );
+        assert!(p256::verify_rust(digest(&auth), auth.r, auth.s, QX, QY));
+    }
+
+    #[test]
+    fn validates_assertion_fields() {
+        let auth = auth();
+        assert!(validate_expected_type(
+            &auth.client_data_json,
+            auth.type_index
+        ));
+        assert!(validate_challenge(
+            &auth.client_data_json,
+            auth.challenge_index,
+            CHALLENGE.as_slice()
+        ));
+        assert!(validate_flags(&auth.authenticator_data, false));
+    }
+
+    #[test]
+    fn validates_type() {
+        let json = auth().client_data_json;
+        assert!(!validate_expected_type(&json, 0));
+        assert!(!validate_expected_type(&json, json.len() + 1));
+
+        let json = json.replace("webauthn.get", "webauthn.create");
+        assert!(!validate_expected_type(&json, 1));
+    }
+
+    #[test]
+    fn validates_challenge() {
+        let json = auth().client_data_json;
+        assert!(!validate_challenge(&json, 23, &CHALLENGE[..31]));
+        assert!(!validate_challenge(&json, 23, B256::ZERO.as_slice()));
+        assert!(!validate_challenge(&json, 22, CHALLENGE.as_slice()));
+        assert!(!validate_challenge(
+            &json,
+            json.len() + 1,
+            CHALLENGE.as_slice()
+        ));
+    }
+
+    #[test]
+    fn validates_flags() {
+        let up = AUTH_DATA_FLAGS_UP;
+        let uv = AUTH_DATA_FLAGS_UV;
+        let be = AUTH_DATA_FLAGS_BE;
+        let bs = AUTH_DATA_FLAGS_BS;
+
+        assert!(validate_flags(&with_flags(up), false));
+        assert!(!validate_flags(&with_flags(up), true));
+        assert!(validate_flags(&with_flags(up | uv), true));
+        assert!(!validate_flags(&with_flags(uv), false));
+        assert!(validate_flags(&with_flags(up | be), false));
+        assert!(validate_flags(&with_flags(up | be | bs), false));
+        assert!(!validate_flags(&with_flags(up | bs), false));
+
+        let mut short = with_flags(up);
+        short.truncate(MIN_AUTH_DATA_LENGTH - 1);
+        assert!(!validate_flags(&short, false));
+    }
+
+    #[motsu::test]
+    fn rejects_invalid_assertion(
+        contract: Contract<WebAuthnMock>,
+        alice: Address,
+    ) {
+        contract.init(alice, |mock| {
+            let auth = auth();
+            assert!(!mock.verify(B256::ZERO.as_slice(), &auth));
+
+            let invalid = WebAuthnAuth { type_index: 0, ..auth.clone() };
+            assert!(!mock.verify(CHALLENGE.as_slice(), &invalid));
+
+            let invalid = WebAuthnAuth {
+                authenticator_data: with_flags(AUTH_DATA_FLAGS_UV),
+                ..auth.clone()
+            };
+            assert!(!mock.verify(CHALLENGE.as_slice(), &invalid));
+
+            // Rejected as malleable before calling the precompile.
+            let invalid = WebAuthnAuth { s: B256::repeat_byte(0xff), ..auth };
+            assert!(!mock.verify(CHALLENGE.as_slice(), &invalid));
+        });
+    }
+}
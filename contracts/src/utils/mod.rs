@@ -1,13 +1,23 @@
 //! Common Smart Contracts utilities.
+pub mod context;
 pub mod cryptography;
 pub mod introspection;
 pub mod math;
 pub mod metadata;
+pub mod multicall;
 pub mod nonces;
+pub mod nonces_keyed;
 pub mod pausable;
+pub mod reentrancy_guard;
+pub mod reentrancy_guard_transient;
 pub mod reentrant_call_handler;
+pub mod storage_slot;
 pub mod structs;
+pub mod types;
 
 pub use metadata::Metadata;
+pub use multicall::Multicall;
 pub use pausable::Pausable;
+pub use reentrancy_guard::ReentrancyGuard;
+pub use reentrancy_guard_transient::ReentrancyGuardTransient;
 pub use reentrant_call_handler::ReentrantCallHandler;
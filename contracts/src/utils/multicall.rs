@@ -0,0 +1,224 @@
+//! Provides a function to batch together multiple calls in a single external
+//! call.
+//!
+//! Each call is delegated to the contract itself, so that it runs with the
+//! same `msg::sender` as the batch. Delegated calls re-enter the contract, so
+//! it must be compiled with the `reentrant` feature of the Stylus SDK.
+//!
+//! When the batch is relayed by an [ERC-2771] trusted forwarder, the sender
+//! resolved by [`crate::utils::context::msg_sender`] is appended to each call,
+//! so that every call resolves the same sender as the batch. Contracts
+//! resolving the sender from another calldata suffix can call
+//! [`Multicall::multicall_with_context`] with that suffix instead.
+//!
+//! NOTE: Since it's not payable, [`Multicall::multicall`] can't be used to
+//! reuse `msg::value` across several calls.
+//!
+//! ```ignore
+//! #[entrypoint]
+//! #[storage]
+//! struct Token {
+//!     #[borrow]
+//!     erc20: Erc20,
+//!     #[borrow]
+//!     multicall: Multicall,
+//! }
+//!
+//! #[public]
+//! #[inherit(Erc20, Multicall)]
+//! impl Token {}
+//! ```
+//!
+//! [ERC-2771]: https://eips.ethereum.org/EIPS/eip-2771
+use alloc::{vec, vec::Vec};
+
+use stylus_sdk::{
+    abi::Bytes,
+    call::{self, MethodError, RawCall},
+    contract, msg,
+    prelude::*,
+};
+
+use crate::utils::{context, ReentrantCallHandler};
+
+/// A [`Multicall`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// One of the batched calls reverted. Contains its revert data as is.
+    FailedCall(call::Error),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of a [`Multicall`] contract.
+#[storage]
+pub struct Multicall {}
+
+#[public]
+impl Multicall {
+    /// Receives and executes a batch of function calls on this contract,
+    /// returning the result of each call.
+    ///
+    /// If the batch is relayed by a trusted forwarder, the original sender is
+    /// appended to each call.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `data` - Calldata of each call, executed in order.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If any of the calls reverts. The revert data
+    ///   of the first failing call is returned unchanged.
+    pub fn multicall(&mut self, data: Vec<Bytes>) -> Result<Vec<Bytes>, Error> {
+        let sender = context::msg_sender();
+        let context: &[u8] =
+            if sender == msg::sender() { &[] } else { sender.as_slice() };
+        self.multicall_with_context(data, context)
+    }
+}
+
+impl Multicall {
+    /// Executes a batch of function calls on this contract, appending
+    /// `context` to the calldata of each call.
+    ///
+    /// Contracts extracting the sender from a calldata suffix, like
+    /// [ERC-2771], should pass that suffix as `context` when the batch is
+    /// relayed, so that each call resolves the same sender as the batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `data` - Calldata of each call, executed in order.
+    /// * `context` - Bytes appended to the calldata of each call.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::FailedCall`] - If any of the calls reverts. The revert data
+    ///   of the first failing call is returned unchanged.
+    ///
+    /// [ERC-2771]: https://eips.ethereum.org/EIPS/eip-2771
+    #[allow(clippy::unused_self)]
+    pub fn multicall_with_context(
+        &mut self,
+        data: Vec<Bytes>,
+        context: &[u8],
+    ) -> Result<Vec<Bytes>, Error> {
+        let this = contract::address();
+        data.into_iter()
+            .map(|call| {
+                let calldata = [call.as_slice(), context].concat();
+                RawCall::new_delegate()
+                    .call_with_reentrant_handling(this, &calldata)
+                    .map(Bytes::from)
+                    .map_err(|e| Error::FailedCall(call::Error::Revert(e)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{Address, U256};
+    use alloy_sol_types::SolCall;
+    use motsu::prelude::*;
+    use stylus_sdk::{abi::Bytes, prelude::*, storage::StorageU256};
+
+    use super::Multicall;
+
+    mod abi {
+        #![allow(missing_docs)]
+
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function increment(uint256 amount) external returns (uint256);
+            function fail(uint8 code) external;
+        }
+    }
+
+    #[storage]
+    struct MulticallMock {
+        #[borrow]
+        multicall: Multicall,
+        counter: StorageU256,
+    }
+
+    unsafe impl TopLevelStorage for MulticallMock {}
+
+    #[public]
+    #[inherit(Multicall)]
+    impl MulticallMock {
+        fn increment(&mut self, amount: U256) -> U256 {
+            let counter = self.counter.get() + amount;
+            self.counter.set(counter);
+            counter
+        }
+
+        fn fail(&mut self, code: u8) -> Result<(), Vec<u8>> {
+            self.counter.set(U256::MAX);
+            Err(vec![code])
+        }
+    }
+
+    fn increment(amount: u64) -> Bytes {
+        abi::incrementCall { amount: U256::from(amount) }.abi_encode().into()
+    }
+
+    fn fail(code: u8) -> Bytes {
+        abi::failCall { code }.abi_encode().into()
+    }
+
+    #[motsu::test]
+    fn multicall_returns_nothing_for_empty_batch(
+        contract: Contract<MulticallMock>,
+        alice: Address,
+    ) {
+        let results =
+            contract.sender(alice).multicall.multicall(vec![]).motsu_unwrap();
+
+        assert!(results.is_empty());
+    }
+
+    #[motsu::test]
+    fn multicall_executes_calls_in_order(
+        contract: Contract<MulticallMock>,
+        alice: Address,
+    ) {
+        let results = contract
+            .sender(alice)
+            .multicall
+            .multicall(vec![increment(1), increment(2), increment(3)])
+            .motsu_unwrap();
+
+        let expected: Vec<Bytes> = [1u64, 3, 6]
+            .into_iter()
+            .map(|counter| {
+                abi::incrementCall::abi_encode_returns(&(U256::from(counter),))
+                    .into()
+            })
+            .collect();
+        assert_eq!(results, expected);
+        assert_eq!(contract.sender(alice).counter.get(), U256::from(6));
+    }
+
+    #[motsu::test]
+    fn multicall_bubbles_up_first_revert(
+        contract: Contract<MulticallMock>,
+        alice: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            .multicall
+            .multicall(vec![increment(1), fail(7), fail(8), increment(2)])
+            .motsu_unwrap_err();
+
+        assert_eq!(Vec::<u8>::from(err), vec![7]);
+        assert_eq!(contract.sender(alice).counter.get(), U256::ZERO);
+    }
+}
@@ -0,0 +1,271 @@
+//! Implementation of keyed nonce tracking for addresses.
+//!
+//! Follows the [ERC-4337] semantics: a `U256` nonce is made of a 192-bit key
+//! and a 64-bit sequence. Each key has its own sequence, so operations signed
+//! with different keys can be consumed in any order. The zero key uses the
+//! sequential nonces of [`Nonces`].
+//!
+//! Nonces will only increment.
+//!
+//! [ERC-4337]: https://eips.ethereum.org/EIPS/eip-4337
+
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{
+    aliases::{U192, U64},
+    uint, Address, U256,
+};
+use stylus_sdk::{
+    prelude::*,
+    storage::{StorageMap, StorageU64},
+};
+
+use crate::utils::{
+    math::storage::AddAssignChecked,
+    nonces::{Error, InvalidAccountNonce, Nonces},
+};
+
+const ONE: U64 = uint!(1_U64);
+
+/// State of a [`NoncesKeyed`] Contract.
+#[storage]
+pub struct NoncesKeyed {
+    /// [`Nonces`] contract, used for the zero key.
+    pub nonces: Nonces,
+    /// Mapping from address and key to the sequence of its nonce.
+    pub(crate) keyed_nonces: StorageMap<Address, StorageMap<U192, StorageU64>>,
+}
+
+#[public]
+impl NoncesKeyed {
+    /// Returns the next unused nonce for the given account and key.
+    ///
+    /// The returned nonce includes the `key`, i.e. it is `key << 64 |
+    /// sequence`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    /// * `owner` - The address for which to return the nonce.
+    /// * `key` - The key of the nonce.
+    #[must_use]
+    pub fn nonces(&self, owner: Address, key: U192) -> U256 {
+        if key.is_zero() {
+            return self.nonces.nonces(owner);
+        }
+
+        pack(key, self.keyed_nonces.get(owner).get(key))
+    }
+}
+
+impl NoncesKeyed {
+    /// Consumes the next unused nonce for the given `owner` and `key`.
+    ///
+    /// Returns the consumed nonce, including the `key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - The address for which to consume the nonce.
+    /// * `key` - The key of the nonce.
+    ///
+    /// # Panics
+    ///
+    /// * If the sequence for the given `owner` and `key` exceeds `U64::MAX`.
+    /// * If the nonce for the given `owner` exceeds `U256::MAX` with the zero
+    ///   key.
+    pub fn use_nonce(&mut self, owner: Address, key: U192) -> U256 {
+        if key.is_zero() {
+            return self.nonces.use_nonce(owner);
+        }
+
+        let mut owner_nonces = self.keyed_nonces.setter(owner);
+        let mut sequence = owner_nonces.setter(key);
+        let nonce = pack(key, sequence.get());
+        sequence.add_assign_checked(ONE, "nonce should not exceed `U64::MAX`");
+
+        nonce
+    }
+
+    /// Same as [`Self::use_nonce`] but checking that `key_and_nonce` is the
+    /// next valid nonce for the owner. The key is the 192 most significant
+    /// bits of `key_and_nonce`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    /// * `owner` - The address for which to consume the nonce.
+    /// * `key_and_nonce` - The nonce to consume, including its key.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::InvalidAccountNonce`] - Returns an error if `key_and_nonce`
+    ///   is not the next valid nonce for the owner.
+    ///
+    /// # Panics
+    ///
+    /// * If the sequence for the given `owner` and key exceeds `U64::MAX`.
+    /// * If the nonce for the given `owner` exceeds `U256::MAX` with the zero
+    ///   key.
+    pub fn use_checked_nonce(
+        &mut self,
+        owner: Address,
+        key_and_nonce: U256,
+    ) -> Result<(), Error> {
+        let (key, _) = unpack(key_and_nonce);
+        if key.is_zero() {
+            return self.nonces.use_checked_nonce(owner, key_and_nonce);
+        }
+
+        let current_nonce = self.use_nonce(owner, key);
+
+        if key_and_nonce != current_nonce {
+            return Err(Error::InvalidAccountNonce(InvalidAccountNonce {
+                account: owner,
+                current_nonce,
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+/// Packs a `key` and a `sequence` into a nonce.
+fn pack(key: U192, sequence: U64) -> U256 {
+    (U256::from(key) << 64) | U256::from(sequence)
+}
+
+/// Unpacks a nonce into its key and sequence.
+fn unpack(key_and_nonce: U256) -> (U192, U64) {
+    (U192::from(key_and_nonce >> 64), U64::from(key_and_nonce.as_limbs()[0]))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{aliases::U192, uint, Address, U256};
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::TopLevelStorage;
+
+    use super::{pack, unpack, NoncesKeyed};
+    use crate::utils::nonces::{Error, InvalidAccountNonce};
+
+    unsafe impl TopLevelStorage for NoncesKeyed {}
+
+    const ONE: U256 = uint!(1_U256);
+    const KEY: U192 = uint!(42_U192);
+
+    #[test]
+    fn packs_and_unpacks_nonces() {
+        let nonce = pack(U192::MAX, uint!(7_U64));
+        assert_eq!(nonce, (U256::MAX << 64) | uint!(7_U256));
+        assert_eq!(unpack(nonce), (U192::MAX, uint!(7_U64)));
+    }
+
+    #[motsu::test]
+    fn initiate_nonces(contract: Contract<NoncesKeyed>, alice: Address) {
+        assert_eq!(
+            contract.sender(alice).nonces(alice, U192::ZERO),
+            U256::ZERO
+        );
+        assert_eq!(
+            contract.sender(alice).nonces(alice, KEY),
+            U256::from(KEY) << 64
+        );
+    }
+
+    #[motsu::test]
+    fn use_nonce_with_zero_key(
+        contract: Contract<NoncesKeyed>,
+        alice: Address,
+    ) {
+        let nonce = contract.sender(alice).use_nonce(alice, U192::ZERO);
+        assert_eq!(nonce, U256::ZERO);
+
+        assert_eq!(contract.sender(alice).nonces(alice, U192::ZERO), ONE);
+        assert_eq!(contract.sender(alice).nonces.nonces(alice), ONE);
+    }
+
+    #[motsu::test]
+    fn use_nonce_with_key(contract: Contract<NoncesKeyed>, alice: Address) {
+        let first = contract.sender(alice).use_nonce(alice, KEY);
+        assert_eq!(first, U256::from(KEY) << 64);
+
+        let second = contract.sender(alice).use_nonce(alice, KEY);
+        assert_eq!(second, first + ONE);
+
+        assert_eq!(
+            contract.sender(alice).nonces(alice, KEY),
+            first + uint!(2_U256)
+        );
+    }
+
+    #[motsu::test]
+    fn keys_are_independent(
+        contract: Contract<NoncesKeyed>,
+        alice: Address,
+        bob: Address,
+    ) {
+        contract.sender(alice).use_nonce(alice, KEY);
+
+        let other_key = KEY + U192::from(1);
+        assert_eq!(
+            contract.sender(alice).nonces(alice, other_key),
+            U256::from(other_key) << 64
+        );
+        assert_eq!(
+            contract.sender(alice).nonces(alice, U192::ZERO),
+            U256::ZERO
+        );
+        assert_eq!(
+            contract.sender(alice).nonces(bob, KEY),
+            U256::from(KEY) << 64
+        );
+    }
+
+    #[motsu::test]
+    fn use_checked_nonce(contract: Contract<NoncesKeyed>, alice: Address) {
+        contract
+            .sender(alice)
+            .use_checked_nonce(alice, U256::ZERO)
+            .expect("should consume nonce with zero key");
+        assert_eq!(contract.sender(alice).nonces(alice, U192::ZERO), ONE);
+
+        let nonce = U256::from(KEY) << 64;
+        contract
+            .sender(alice)
+            .use_checked_nonce(alice, nonce)
+            .expect("should consume keyed nonce");
+        assert_eq!(contract.sender(alice).nonces(alice, KEY), nonce + ONE);
+    }
+
+    #[motsu::test]
+    fn use_checked_nonce_invalid_nonce(
+        contract: Contract<NoncesKeyed>,
+        alice: Address,
+    ) {
+        let err = contract
+            .sender(alice)
+            .use_checked_nonce(alice, ONE)
+            .expect_err("should reject invalid nonce with zero key");
+        assert!(matches!(
+            err,
+            Error::InvalidAccountNonce(InvalidAccountNonce {
+                account,
+                current_nonce,
+            }) if account == alice && current_nonce.is_zero()
+        ));
+
+        let nonce = U256::from(KEY) << 64;
+        let err = contract
+            .sender(alice)
+            .use_checked_nonce(alice, nonce + ONE)
+            .expect_err("should reject invalid keyed nonce");
+        assert!(matches!(
+            err,
+            Error::InvalidAccountNonce(InvalidAccountNonce {
+                account,
+                current_nonce,
+            }) if account == alice && current_nonce == nonce
+        ));
+    }
+}
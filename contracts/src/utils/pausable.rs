@@ -17,9 +17,9 @@
 use alloc::{vec, vec::Vec};
 
 pub use sol::*;
-use stylus_sdk::{
-    call::MethodError, evm, msg, prelude::*, storage::StorageBool,
-};
+use stylus_sdk::{call::MethodError, evm, prelude::*, storage::StorageBool};
+
+use crate::utils::context;
 
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod sol {
@@ -101,7 +101,7 @@ impl Pausable {
     pub fn pause(&mut self) -> Result<(), Error> {
         self.when_not_paused()?;
         self.paused.set(true);
-        evm::log(Paused { account: msg::sender() });
+        evm::log(Paused { account: context::msg_sender() });
         Ok(())
     }
 
@@ -117,7 +117,7 @@ impl Pausable {
     pub fn unpause(&mut self) -> Result<(), Error> {
         self.when_paused()?;
         self.paused.set(false);
-        evm::log(Unpaused { account: msg::sender() });
+        evm::log(Unpaused { account: context::msg_sender() });
         Ok(())
     }
 
@@ -159,13 +159,51 @@ impl Pausable {
 #[cfg(all(test, feature = "std"))]
 mod tests {
     use alloy_primitives::Address;
+    use alloy_sol_types::SolCall;
     use motsu::prelude::Contract;
-    use stylus_sdk::prelude::TopLevelStorage;
+    use stylus_sdk::prelude::*;
 
-    use crate::utils::pausable::{Error, Pausable};
+    use crate::{
+        metatx::erc2771_context::{relay, Erc2771Context},
+        utils::pausable::{Error, Pausable, Paused, Unpaused},
+    };
 
     unsafe impl TopLevelStorage for Pausable {}
 
+    mod abi {
+        #![allow(missing_docs)]
+
+        use alloy_sol_macro::sol;
+
+        sol! {
+            function pause() external;
+            function unpause() external;
+        }
+    }
+
+    /// Pausable contract receiving meta-transactions through
+    /// [`Erc2771Context`].
+    #[storage]
+    struct RelayedPausableMock {
+        #[borrow]
+        erc2771: Erc2771Context,
+        pausable: Pausable,
+    }
+
+    unsafe impl TopLevelStorage for RelayedPausableMock {}
+
+    #[public]
+    #[inherit(Erc2771Context)]
+    impl RelayedPausableMock {
+        fn pause(&mut self) -> Result<(), Error> {
+            self.pausable.pause()
+        }
+
+        fn unpause(&mut self) -> Result<(), Error> {
+            self.pausable.unpause()
+        }
+    }
+
     fn construct_paused(contract: &mut Pausable) {
         contract.paused.set(true);
     }
@@ -271,4 +309,27 @@ mod tests {
         assert!(matches!(result, Err(Error::ExpectedPause(_))));
         assert!(!contract.sender(alice).paused());
     }
+
+    #[motsu::test]
+    fn relayed_pause_and_unpause_log_relayed_sender(
+        contract: Contract<RelayedPausableMock>,
+        alice: Address,
+        forwarder: Address,
+    ) {
+        contract.init(alice, |mock| {
+            mock.erc2771._set_trusted_forwarder(forwarder);
+        });
+
+        let call = abi::pauseCall {}.abi_encode();
+        contract
+            .init(forwarder, |_| relay::<RelayedPausableMock>(&call, alice))
+            .expect("should pause");
+        contract.assert_emitted(&Paused { account: alice });
+
+        let call = abi::unpauseCall {}.abi_encode();
+        contract
+            .init(forwarder, |_| relay::<RelayedPausableMock>(&call, alice))
+            .expect("should unpause");
+        contract.assert_emitted(&Unpaused { account: alice });
+    }
 }
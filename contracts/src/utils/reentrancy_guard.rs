@@ -0,0 +1,334 @@
+//! Contract module that helps prevent reentrant calls to a function.
+//!
+//! Wrapping a function in [`ReentrancyGuard::non_reentrant`] makes sure there
+//! are no nested (reentrant) calls to it, or to any other function wrapped the
+//! same way.
+//!
+//! Note that Stylus contracts can only be re-entered when compiled with the
+//! `reentrant` feature, since otherwise reentrant calls are rejected by the
+//! SDK.
+//!
+//! Note that because there is a single guard, functions wrapped in
+//! [`ReentrancyGuard::non_reentrant`] may not call one another. This can be
+//! worked around by making those functions private, and then adding external
+//! non-reentrant entry points to them.
+//!
+//! The state of [`ReentrancyGuard`] is stored in the [ERC-7201] namespace
+//! `openzeppelin.storage.ReentrancyGuard`, so that it doesn't take up any slot
+//! of the contract's `#[storage]` layout. See
+//! [`super::reentrancy_guard_transient::ReentrancyGuardTransient`] for a
+//! variant using transient storage.
+//!
+//! ```ignore
+//! #[entrypoint]
+//! #[storage]
+//! struct Vault {
+//!     #[borrow]
+//!     reentrancy_guard: ReentrancyGuard,
+//!     balances: StorageMap<Address, StorageU256>,
+//! }
+//!
+//! #[public]
+//! impl Vault {
+//!     fn withdraw(&mut self) -> Result<(), Vec<u8>> {
+//!         ReentrancyGuard::non_reentrant(self, |vault| vault._withdraw())
+//!     }
+//! }
+//! ```
+//!
+//! [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+use alloc::{vec, vec::Vec};
+use core::borrow::BorrowMut;
+
+use alloy_primitives::{uint, U256};
+use openzeppelin_stylus_proc::storage_location;
+pub use sol::*;
+use stylus_sdk::{call::MethodError, prelude::*, storage::StorageU256};
+
+/// The guard is not entered.
+const NOT_ENTERED: U256 = uint!(1_U256);
+/// The guard is entered.
+const ENTERED: U256 = uint!(2_U256);
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod sol {
+    use alloy_sol_macro::sol;
+
+    sol! {
+        /// Unauthorized reentrant call.
+        #[derive(Debug)]
+        #[allow(missing_docs)]
+        error ReentrancyGuardReentrantCall();
+    }
+}
+
+/// A [`ReentrancyGuard`] error.
+#[derive(SolidityError, Debug)]
+pub enum Error {
+    /// Unauthorized reentrant call.
+    ReentrantCall(ReentrancyGuardReentrantCall),
+}
+
+impl MethodError for Error {
+    fn encode(self) -> alloc::vec::Vec<u8> {
+        self.into()
+    }
+}
+
+/// State of a [`ReentrancyGuard`] contract.
+#[storage_location(erc7201 = "openzeppelin.storage.ReentrancyGuard")]
+pub struct ReentrancyGuard {
+    /// Whether a non-reentrant function is being executed.
+    ///
+    /// Booleans are more expensive than `uint256` or any type that takes up a
+    /// full word, because each write operation emits an extra `SLOAD` to
+    /// first read the slot's contents. We also never reset it to zero, so
+    /// that only the first call pays for the zero to non-zero write.
+    pub(crate) status: StorageU256,
+}
+
+impl ReentrancyGuard {
+    /// Runs `f`, preventing the contract from calling itself, directly or
+    /// indirectly, until it returns.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - Write access to the contract's state.
+    /// * `f` - The guarded logic.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ReentrantCall`] - If the guard is already entered.
+    /// * If `f` returns an error, it is returned, and the guard is exited.
+    pub fn non_reentrant<S, T, E>(
+        storage: &mut S,
+        f: impl FnOnce(&mut S) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        S: BorrowMut<Self>,
+        E: From<Error>,
+    {
+        storage.borrow_mut().non_reentrant_before()?;
+        let result = f(storage);
+        storage.borrow_mut().non_reentrant_after();
+        result
+    }
+
+    /// Enters the guard.
+    ///
+    /// Must be paired with a call to [`ReentrancyGuard::non_reentrant_after`]
+    /// once the guarded logic returns. Prefer
+    /// [`ReentrancyGuard::non_reentrant`], which does it automatically.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ReentrantCall`] - If the guard is already entered.
+    pub fn non_reentrant_before(&mut self) -> Result<(), Error> {
+        if self.reentrancy_guard_entered() {
+            return Err(Error::ReentrantCall(ReentrancyGuardReentrantCall {}));
+        }
+        self.status.set(ENTERED);
+        Ok(())
+    }
+
+    /// Exits the guard.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    pub fn non_reentrant_after(&mut self) {
+        self.status.set(NOT_ENTERED);
+    }
+
+    /// Returns `true` if the guard is currently entered, i.e. there is a
+    /// non-reentrant function in the call stack.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn reentrancy_guard_entered(&self) -> bool {
+        self.status.get() == ENTERED
+    }
+}
+
+/// Generates the tests shared by the reentrancy guard variants, run against a
+/// mock contract guarded by `$guard`.
+#[cfg(all(test, feature = "std", feature = "reentrant"))]
+macro_rules! reentrancy_guard_tests {
+    ($guard:ident) => {
+        use alloy_primitives::{Address, U256};
+        use motsu::prelude::*;
+        use stylus_sdk::{
+            call::Call, contract, prelude::*, storage::StorageU256,
+        };
+        use $crate::utils::reentrancy_guard::{
+            Error, ReentrancyGuardReentrantCall,
+        };
+
+        mod interface {
+            #![allow(missing_docs)]
+
+            use alloc::vec;
+
+            use stylus_sdk::prelude::sol_interface;
+            sol_interface! {
+                interface IReentrancyMock {
+                    function countThisRecursive(uint256 n) external;
+                }
+            }
+        }
+
+        #[storage]
+        struct ReentrancyMock {
+            #[borrow]
+            guard: $guard,
+            counter: StorageU256,
+        }
+
+        unsafe impl TopLevelStorage for ReentrancyMock {}
+
+        #[public]
+        impl ReentrancyMock {
+            fn count(&mut self) -> Result<(), Vec<u8>> {
+                $guard::non_reentrant(self, |mock| {
+                    mock.increment();
+                    Ok(())
+                })
+            }
+
+            fn count_local_recursive(
+                &mut self,
+                n: U256,
+            ) -> Result<(), Vec<u8>> {
+                $guard::non_reentrant(self, |mock| {
+                    if n > U256::ZERO {
+                        mock.increment();
+                        mock.count_local_recursive(n - U256::from(1))?;
+                    }
+                    Ok(())
+                })
+            }
+
+            fn count_this_recursive(&mut self, n: U256) -> Result<(), Vec<u8>> {
+                $guard::non_reentrant(self, |mock| {
+                    if n > U256::ZERO {
+                        mock.increment();
+                        interface::IReentrancyMock::new(contract::address())
+                            .count_this_recursive(
+                                Call::new_in(mock),
+                                n - U256::from(1),
+                            )?;
+                    }
+                    Ok(())
+                })
+            }
+
+            fn guarded_check_entered(&mut self) -> Result<bool, Vec<u8>> {
+                $guard::non_reentrant(self, |mock| {
+                    Ok(mock.guard.reentrancy_guard_entered())
+                })
+            }
+
+            fn count_and_fail_handled(&mut self) -> bool {
+                let result = $guard::non_reentrant(self, |mock| {
+                    mock.increment();
+                    Err::<(), _>(Vec::<u8>::new())
+                });
+                result.is_err()
+            }
+        }
+
+        impl ReentrancyMock {
+            fn increment(&mut self) {
+                let counter = self.counter.get();
+                self.counter.set(counter + U256::from(1));
+            }
+        }
+
+        fn reentrant_call_error() -> Vec<u8> {
+            Error::ReentrantCall(ReentrancyGuardReentrantCall {}).into()
+        }
+
+        #[motsu::test]
+        fn allows_non_reentrant_calls(
+            contract: Contract<ReentrancyMock>,
+            alice: Address,
+        ) {
+            assert!(!contract.sender(alice).guard.reentrancy_guard_entered());
+
+            contract.sender(alice).count().motsu_unwrap();
+            contract.sender(alice).count().motsu_unwrap();
+
+            assert_eq!(contract.sender(alice).counter.get(), U256::from(2));
+            assert!(!contract.sender(alice).guard.reentrancy_guard_entered());
+        }
+
+        #[motsu::test]
+        fn reports_entered_guard(
+            contract: Contract<ReentrancyMock>,
+            alice: Address,
+        ) {
+            assert!(contract
+                .sender(alice)
+                .guarded_check_entered()
+                .motsu_unwrap());
+            assert!(!contract.sender(alice).guard.reentrancy_guard_entered());
+        }
+
+        #[motsu::test]
+        fn reverts_on_local_recursion(
+            contract: Contract<ReentrancyMock>,
+            alice: Address,
+        ) {
+            let err = contract
+                .sender(alice)
+                .count_local_recursive(U256::from(10))
+                .motsu_unwrap_err();
+
+            assert_eq!(err, reentrant_call_error());
+            assert_eq!(contract.sender(alice).counter.get(), U256::ZERO);
+        }
+
+        #[motsu::test]
+        fn reverts_on_reentrant_call(
+            contract: Contract<ReentrancyMock>,
+            alice: Address,
+        ) {
+            let err = contract
+                .sender(alice)
+                .count_this_recursive(U256::from(10))
+                .motsu_unwrap_err();
+
+            assert_eq!(err, reentrant_call_error());
+            assert_eq!(contract.sender(alice).counter.get(), U256::ZERO);
+            assert!(!contract.sender(alice).guard.reentrancy_guard_entered());
+        }
+
+        #[motsu::test]
+        fn exits_guard_when_guarded_logic_fails(
+            contract: Contract<ReentrancyMock>,
+            alice: Address,
+        ) {
+            assert!(contract.sender(alice).count_and_fail_handled());
+            assert!(!contract.sender(alice).guard.reentrancy_guard_entered());
+
+            contract.sender(alice).count().motsu_unwrap();
+            assert_eq!(contract.sender(alice).counter.get(), U256::from(2));
+        }
+    };
+}
+
+#[cfg(all(test, feature = "std", feature = "reentrant"))]
+pub(crate) use reentrancy_guard_tests;
+
+#[cfg(all(test, feature = "std", feature = "reentrant"))]
+mod tests {
+    use super::{reentrancy_guard_tests, ReentrancyGuard};
+
+    reentrancy_guard_tests!(ReentrancyGuard);
+}
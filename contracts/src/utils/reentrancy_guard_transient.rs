@@ -0,0 +1,127 @@
+//! Variant of [`super::reentrancy_guard::ReentrancyGuard`] that uses
+//! transient storage.
+//!
+//! The guard flag is kept in [EIP-1153] transient storage, at the slot of the
+//! [ERC-7201] namespace `openzeppelin.storage.ReentrancyGuard`. It is cheaper
+//! than persistent storage, and is discarded at the end of the transaction.
+//!
+//! NOTE: This variant only works on chains where transient storage is
+//! available to Stylus programs.
+//!
+//! ```ignore
+//! #[entrypoint]
+//! #[storage]
+//! struct Vault {
+//!     #[borrow]
+//!     reentrancy_guard: ReentrancyGuardTransient,
+//!     balances: StorageMap<Address, StorageU256>,
+//! }
+//!
+//! #[public]
+//! impl Vault {
+//!     fn withdraw(&mut self) -> Result<(), Vec<u8>> {
+//!         ReentrancyGuardTransient::non_reentrant(self, |vault| {
+//!             vault._withdraw()
+//!         })
+//!     }
+//! }
+//! ```
+//!
+//! [EIP-1153]: https://eips.ethereum.org/EIPS/eip-1153
+//! [ERC-7201]: https://eips.ethereum.org/EIPS/eip-7201
+use alloc::{vec, vec::Vec};
+use core::borrow::BorrowMut;
+
+use openzeppelin_stylus_proc::storage_location;
+use stylus_sdk::prelude::*;
+
+pub use crate::utils::reentrancy_guard::{Error, ReentrancyGuardReentrantCall};
+use crate::utils::storage_slot::TransientSlot;
+
+/// State of a [`ReentrancyGuardTransient`] contract.
+///
+/// It has no persistent state, and takes up no slot of the contract's
+/// `#[storage]` layout.
+#[storage_location(erc7201 = "openzeppelin.storage.ReentrancyGuard")]
+pub struct ReentrancyGuardTransient {}
+
+// The guard flag lives in transient storage, so the methods don't access
+// `self`. They still take it, to match the API of `ReentrancyGuard`.
+#[allow(clippy::unused_self)]
+impl ReentrancyGuardTransient {
+    /// Runs `f`, preventing the contract from calling itself, directly or
+    /// indirectly, until it returns.
+    ///
+    /// # Arguments
+    ///
+    /// * `storage` - Write access to the contract's state.
+    /// * `f` - The guarded logic.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ReentrantCall`] - If the guard is already entered.
+    /// * If `f` returns an error, it is returned, and the guard is exited.
+    pub fn non_reentrant<S, T, E>(
+        storage: &mut S,
+        f: impl FnOnce(&mut S) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        S: BorrowMut<Self>,
+        E: From<Error>,
+    {
+        storage.borrow_mut().non_reentrant_before()?;
+        let result = f(storage);
+        storage.borrow_mut().non_reentrant_after();
+        result
+    }
+
+    /// Enters the guard.
+    ///
+    /// Must be paired with a call to
+    /// [`ReentrancyGuardTransient::non_reentrant_after`] once the guarded
+    /// logic returns. Prefer [`ReentrancyGuardTransient::non_reentrant`],
+    /// which does it automatically.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    ///
+    /// # Errors
+    ///
+    /// * [`Error::ReentrantCall`] - If the guard is already entered.
+    pub fn non_reentrant_before(&mut self) -> Result<(), Error> {
+        if self.reentrancy_guard_entered() {
+            return Err(Error::ReentrantCall(ReentrancyGuardReentrantCall {}));
+        }
+        TransientSlot::set_bool(Self::STORAGE_SLOT, true);
+        Ok(())
+    }
+
+    /// Exits the guard.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the contract's state.
+    pub fn non_reentrant_after(&mut self) {
+        TransientSlot::set_bool(Self::STORAGE_SLOT, false);
+    }
+
+    /// Returns `true` if the guard is currently entered, i.e. there is a
+    /// non-reentrant function in the call stack.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the contract's state.
+    #[must_use]
+    pub fn reentrancy_guard_entered(&self) -> bool {
+        TransientSlot::get_bool(Self::STORAGE_SLOT)
+    }
+}
+
+#[cfg(all(test, feature = "std", feature = "reentrant"))]
+mod tests {
+    use super::ReentrancyGuardTransient;
+    use crate::utils::reentrancy_guard::reentrancy_guard_tests;
+
+    reentrancy_guard_tests!(ReentrancyGuardTransient);
+}
@@ -0,0 +1,190 @@
+//! Helper for reading and writing primitive types to specific storage slots.
+//!
+//! Storage slots are often used to avoid storage conflict when dealing with
+//! upgradeable contracts. This module helps with reading and writing to such
+//! slots without the need for a `#[storage]` field at that location.
+//!
+//! [`TransientSlot`] provides the same helpers for [EIP-1153] transient
+//! storage, which is discarded at the end of the transaction.
+//!
+//! [EIP-1153]: https://eips.ethereum.org/EIPS/eip-1153
+//!
+//! ```ignore
+//! use alloy_primitives::{b256, B256};
+//! use openzeppelin_stylus::utils::storage_slot::StorageSlot;
+//!
+//! const IMPLEMENTATION_SLOT: B256 =
+//!     b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc");
+//!
+//! let implementation = StorageSlot::get_address(IMPLEMENTATION_SLOT);
+//! ```
+use alloy_primitives::{Address, B256, U256};
+use stylus_sdk::hostio;
+
+/// Access to arbitrary storage slots.
+pub struct StorageSlot;
+
+impl StorageSlot {
+    /// Returns the 32-byte word stored at `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The storage slot to read.
+    #[must_use]
+    pub fn get_word(slot: B256) -> B256 {
+        let mut word = B256::ZERO;
+        // SAFETY: Both pointers reference 32-byte buffers.
+        unsafe {
+            hostio::storage_load_bytes32(slot.as_ptr(), word.as_mut_ptr());
+        }
+        word
+    }
+
+    /// Stores a 32-byte word at `slot`.
+    ///
+    /// NOTE: The value is written to the storage cache, which is persisted at
+    /// the end of the call, like the values of `#[storage]` fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The storage slot to write.
+    /// * `value` - The word to store.
+    pub fn set_word(slot: B256, value: B256) {
+        // SAFETY: Both pointers reference 32-byte buffers.
+        unsafe { hostio::storage_cache_bytes32(slot.as_ptr(), value.as_ptr()) };
+    }
+
+    /// Returns the [`Address`] stored at `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The storage slot to read.
+    #[must_use]
+    pub fn get_address(slot: B256) -> Address {
+        Address::from_word(StorageSlot::get_word(slot))
+    }
+
+    /// Stores an [`Address`] at `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The storage slot to write.
+    /// * `value` - The address to store.
+    pub fn set_address(slot: B256, value: Address) {
+        StorageSlot::set_word(slot, value.into_word());
+    }
+
+    /// Returns the [`U256`] stored at `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The storage slot to read.
+    #[must_use]
+    pub fn get_uint(slot: B256) -> U256 {
+        U256::from_be_bytes(StorageSlot::get_word(slot).0)
+    }
+
+    /// Stores a [`U256`] at `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The storage slot to write.
+    /// * `value` - The integer to store.
+    pub fn set_uint(slot: B256, value: U256) {
+        StorageSlot::set_word(slot, B256::from(value));
+    }
+}
+
+/// Access to arbitrary transient storage slots, as introduced by [EIP-1153].
+///
+/// NOTE: The host only provides transient storage to WASM programs. Outside of
+/// WASM, e.g. in unit tests, it is emulated with persistent storage, which
+/// behaves the same within a single transaction.
+///
+/// [EIP-1153]: https://eips.ethereum.org/EIPS/eip-1153
+pub struct TransientSlot;
+
+#[cfg(target_arch = "wasm32")]
+#[link(wasm_import_module = "vm_hooks")]
+extern "C" {
+    fn transient_load_bytes32(key: *const u8, dest: *mut u8);
+    fn transient_store_bytes32(key: *const u8, value: *const u8);
+}
+
+impl TransientSlot {
+    /// Returns the 32-byte word stored at the transient `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The transient storage slot to read.
+    #[must_use]
+    pub fn get_word(slot: B256) -> B256 {
+        #[cfg(target_arch = "wasm32")]
+        {
+            let mut word = B256::ZERO;
+            // SAFETY: Both pointers reference 32-byte buffers.
+            unsafe {
+                transient_load_bytes32(slot.as_ptr(), word.as_mut_ptr());
+            }
+            word
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        StorageSlot::get_word(slot)
+    }
+
+    /// Stores a 32-byte word at the transient `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The transient storage slot to write.
+    /// * `value` - The word to store.
+    pub fn set_word(slot: B256, value: B256) {
+        #[cfg(target_arch = "wasm32")]
+        // SAFETY: Both pointers reference 32-byte buffers.
+        unsafe {
+            transient_store_bytes32(slot.as_ptr(), value.as_ptr());
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        StorageSlot::set_word(slot, value);
+    }
+
+    /// Returns the [`Address`] stored at the transient `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The transient storage slot to read.
+    #[must_use]
+    pub fn get_address(slot: B256) -> Address {
+        Address::from_word(TransientSlot::get_word(slot))
+    }
+
+    /// Stores an [`Address`] at the transient `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The transient storage slot to write.
+    /// * `value` - The address to store.
+    pub fn set_address(slot: B256, value: Address) {
+        TransientSlot::set_word(slot, value.into_word());
+    }
+
+    /// Returns the boolean stored at the transient `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The transient storage slot to read.
+    #[must_use]
+    pub fn get_bool(slot: B256) -> bool {
+        !TransientSlot::get_word(slot).is_zero()
+    }
+
+    /// Stores a boolean at the transient `slot`.
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - The transient storage slot to write.
+    /// * `value` - The boolean to store.
+    pub fn set_bool(slot: B256, value: bool) {
+        TransientSlot::set_word(slot, B256::with_last_byte(u8::from(value)));
+    }
+}
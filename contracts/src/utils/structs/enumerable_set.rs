@@ -0,0 +1,252 @@
+//! Storage type for managing [sets] of primitive types.
+//!
+//! Sets have the following properties:
+//!
+//! - Elements are added, removed, and checked for existence in constant time
+//!   (O(1)).
+//! - Elements are enumerated in O(n). No guarantees are made on the ordering.
+//!
+//! Supported element types are [`Address`], [`B256`] and [`U256`], see
+//! [`Element`].
+//!
+//! [sets]: https://en.wikipedia.org/wiki/Set_(abstract_data_type)
+use alloc::{vec, vec::Vec};
+
+use alloy_primitives::{Address, B256, U256};
+use stylus_sdk::{
+    prelude::*,
+    storage::{
+        SimpleStorageType, StorageAddress, StorageFixedBytes, StorageKey,
+        StorageMap, StorageU256, StorageVec,
+    },
+};
+
+/// Type that can be stored in an [`EnumerableSet`].
+pub trait Element: StorageKey + Copy {
+    /// Storage type used to keep the element inside a [`StorageVec`].
+    type StorageElement: for<'a> SimpleStorageType<'a, Wraps<'a> = Self>;
+}
+
+impl Element for Address {
+    type StorageElement = StorageAddress;
+}
+
+impl Element for B256 {
+    type StorageElement = StorageFixedBytes<32>;
+}
+
+impl Element for U256 {
+    type StorageElement = StorageU256;
+}
+
+/// State of an [`EnumerableSet`] contract.
+#[storage]
+pub struct EnumerableSet<T: Element> {
+    /// Values in the set.
+    pub(crate) values: StorageVec<T::StorageElement>,
+    /// Position is the index of the value in the `values` array plus 1.
+    /// Position 0 is used to mean a value is not in the set.
+    pub(crate) positions: StorageMap<T, StorageU256>,
+}
+
+impl<T: Element> EnumerableSet<T> {
+    /// Adds a value to the set. Returns `true` if the value was added to the
+    /// set, that is if it was not already present.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the set's state.
+    /// * `value` - The value to add to the set.
+    pub fn add(&mut self, value: T) -> bool {
+        if self.contains(value) {
+            return false;
+        }
+
+        self.values.push(value);
+        // The value is stored at `length - 1`, but we add 1 to all indexes and
+        // use 0 as a sentinel value.
+        let position = U256::from(self.values.len());
+        self.positions.setter(value).set(position);
+        true
+    }
+
+    /// Removes a value from the set. Returns `true` if the value was removed
+    /// from the set, that is if it was present.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - Write access to the set's state.
+    /// * `value` - The value to remove from the set.
+    ///
+    /// # Panics
+    ///
+    /// * If the tracked position of `value` is out of bounds of the set, which
+    ///   would indicate a corrupted storage layout.
+    pub fn remove(&mut self, value: T) -> bool {
+        let position = self.positions.get(value);
+        if position.is_zero() {
+            return false;
+        }
+
+        // To delete an element from the `values` array in O(1), we swap the
+        // element to delete with the last one in the array, and then remove
+        // the last element (sometimes called as 'swap and pop').
+        let value_index = position - U256::from(1);
+        let last_index = U256::from(self.values.len() - 1);
+
+        if value_index != last_index {
+            let last_value = self
+                .values
+                .get(last_index)
+                .expect("last index should be in bounds");
+            // Move the last value to the index where the value to delete is.
+            self.values
+                .setter(value_index)
+                .expect("value index should be in bounds")
+                .set_by_wrapped(last_value);
+            // Update the tracked position of the last value (that was just
+            // moved).
+            self.positions.setter(last_value).set(position);
+        }
+
+        // Delete the slot where the moved value was stored.
+        self.values.pop();
+        // Delete the tracked position for the deleted slot.
+        self.positions.delete(value);
+        true
+    }
+
+    /// Returns `true` if the value is in the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the set's state.
+    /// * `value` - The value to look for.
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        !self.positions.get(value).is_zero()
+    }
+
+    /// Returns the number of values in the set.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the set's state.
+    #[must_use]
+    pub fn length(&self) -> U256 {
+        U256::from(self.values.len())
+    }
+
+    /// Returns the value stored at position `index` in the set, or [`None`]
+    /// if `index` is out of bounds.
+    ///
+    /// Note that there are no guarantees on the ordering of values inside the
+    /// array, and it may change when more values are added or removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the set's state.
+    /// * `index` - Index of the value to return.
+    #[must_use]
+    pub fn at(&self, index: U256) -> Option<T> {
+        self.values.get(index)
+    }
+
+    /// Returns the entire set as a vector.
+    ///
+    /// WARNING: This operation will copy the entire storage to memory, which
+    /// can be quite expensive. This is designed to mostly be used by view
+    /// accessors that are queried without any gas fees. Developers should
+    /// keep in mind that this function has an unbounded cost, and using it as
+    /// part of a state-changing function may render the function uncallable
+    /// if the set grows to a point where copying to memory consumes too much
+    /// gas to fit in a block.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - Read access to the set's state.
+    #[must_use]
+    pub fn values(&self) -> Vec<T> {
+        (0..self.values.len())
+            .filter_map(|index| self.values.get(index))
+            .collect()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::{
+        private::proptest::{prop_assert, prop_assert_eq, proptest},
+        Address, U256,
+    };
+    use motsu::prelude::Contract;
+    use stylus_sdk::prelude::{public, TopLevelStorage};
+
+    use super::EnumerableSet;
+
+    unsafe impl TopLevelStorage for EnumerableSet<Address> {}
+
+    #[public]
+    impl EnumerableSet<Address> {}
+
+    #[motsu::test]
+    fn add_value() {
+        proptest!(|(value: Address, alice: Address)| {
+            let set = Contract::<EnumerableSet<Address>>::new();
+            let mut set = set.sender(alice);
+            prop_assert!(!set.contains(value));
+            prop_assert!(set.add(value));
+            prop_assert!(set.contains(value));
+            prop_assert_eq!(set.length(), U256::from(1));
+            prop_assert_eq!(set.at(U256::ZERO), Some(value));
+        });
+    }
+
+    #[motsu::test]
+    fn add_existing_value_returns_false() {
+        proptest!(|(value: Address, alice: Address)| {
+            let set = Contract::<EnumerableSet<Address>>::new();
+            let mut set = set.sender(alice);
+            prop_assert!(set.add(value));
+            prop_assert!(!set.add(value));
+            prop_assert_eq!(set.length(), U256::from(1));
+        });
+    }
+
+    #[motsu::test]
+    fn remove_value() {
+        proptest!(|(value: Address, alice: Address)| {
+            let set = Contract::<EnumerableSet<Address>>::new();
+            let mut set = set.sender(alice);
+            prop_assert!(!set.remove(value));
+            set.add(value);
+            prop_assert!(set.remove(value));
+            prop_assert!(!set.contains(value));
+            prop_assert_eq!(set.length(), U256::ZERO);
+            prop_assert_eq!(set.at(U256::ZERO), None);
+        });
+    }
+
+    #[motsu::test]
+    fn remove_swaps_last_value(
+        set: Contract<EnumerableSet<Address>>,
+        alice: Address,
+    ) {
+        let values: Vec<Address> =
+            (1..=3).map(Address::with_last_byte).collect();
+        let mut set = set.sender(alice);
+        for value in &values {
+            set.add(*value);
+        }
+
+        assert!(set.remove(values[0]));
+
+        assert_eq!(set.values(), vec![values[2], values[1]]);
+        assert_eq!(set.at(U256::ZERO), Some(values[2]));
+        assert!(set.contains(values[2]));
+
+        // The moved value is still removable from its new position.
+        assert!(set.remove(values[2]));
+        assert_eq!(set.values(), vec![values[1]]);
+    }
+}
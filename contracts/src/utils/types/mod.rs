@@ -0,0 +1,2 @@
+//! Value types shared by other contracts.
+pub mod time;
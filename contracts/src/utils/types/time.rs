@@ -0,0 +1,171 @@
+//! Helpers for manipulating time-related objects.
+//!
+//! Timepoints are stored as [`U48`] timestamps, and durations as `u32`
+//! seconds. A [`Delay`] is a duration that can be updated, with the new value
+//! only taking effect at a given timepoint, so that a delay can't be reduced
+//! to bypass a pending operation.
+use alloy_primitives::aliases::{U112, U48};
+use stylus_sdk::block;
+
+/// Returns the current timestamp as a [`U48`].
+#[must_use]
+pub fn timestamp() -> U48 {
+    U48::from(block::timestamp())
+}
+
+/// A `u32` duration that can be updated with an effect timepoint.
+///
+/// Packed into a [`U112`] as `effect (48) | value_before (32) |
+/// value_after (32)`, so that it fits in the same storage slot as other
+/// values. A zero `effect` means there is no pending update.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Delay(U112);
+
+impl Delay {
+    /// Wraps a duration into a [`Delay`] with no pending update.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - Duration in seconds.
+    #[must_use]
+    pub fn new(duration: u32) -> Self {
+        Self::pack(0, duration, U48::ZERO)
+    }
+
+    /// Returns the current value plus the pending value and effect
+    /// timepoint, if they are still pending.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The delay.
+    #[must_use]
+    pub fn get_full(self) -> (u32, u32, U48) {
+        self.get_full_at(timestamp())
+    }
+
+    /// Returns the current value of the delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The delay.
+    #[must_use]
+    pub fn get(self) -> u32 {
+        self.get_full().0
+    }
+
+    /// Updates the delay to `new_value`, and returns the updated delay along
+    /// with the timepoint at which the update takes effect.
+    ///
+    /// The update is scheduled so that the delay can't be reduced faster
+    /// than the difference between the current and the new value, with a
+    /// minimum of `min_setback`.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The delay.
+    /// * `new_value` - The new duration in seconds.
+    /// * `min_setback` - Minimum time before the update takes effect.
+    #[must_use]
+    pub fn with_update(self, new_value: u32, min_setback: u32) -> (Self, U48) {
+        let value = self.get();
+        let setback = min_setback.max(value.saturating_sub(new_value));
+        let effect = timestamp() + U48::from(setback);
+        (Self::pack(value, new_value, effect), effect)
+    }
+
+    /// Splits the delay into its components: `(value_before, value_after,
+    /// effect)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `self` - The delay.
+    #[must_use]
+    pub fn unpack(self) -> (u32, u32, U48) {
+        let raw = self.0;
+        let value_after: u32 = (raw & U112::from(u32::MAX)).to();
+        let value_before: u32 = ((raw >> 32_usize) & U112::from(u32::MAX)).to();
+        let effect: U48 = (raw >> 64_usize).to();
+        (value_before, value_after, effect)
+    }
+
+    /// Packs the components into a delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_before` - Value before the effect timepoint.
+    /// * `value_after` - Value after the effect timepoint.
+    /// * `effect` - Timepoint at which `value_after` takes effect.
+    #[must_use]
+    pub fn pack(value_before: u32, value_after: u32, effect: U48) -> Self {
+        Self(
+            (U112::from(effect) << 64)
+                | (U112::from(value_before) << 32)
+                | U112::from(value_after),
+        )
+    }
+
+    /// Returns the value at a given `timepoint` plus the pending value and
+    /// effect timepoint, if they are still pending.
+    fn get_full_at(self, timepoint: U48) -> (u32, u32, U48) {
+        let (value_before, value_after, effect) = self.unpack();
+        if effect <= timepoint {
+            (value_after, 0, U48::ZERO)
+        } else {
+            (value_before, value_after, effect)
+        }
+    }
+}
+
+impl From<U112> for Delay {
+    fn from(value: U112) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Delay> for U112 {
+    fn from(value: Delay) -> Self {
+        value.0
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloy_primitives::aliases::U48;
+
+    use super::{timestamp, Delay};
+
+    #[test]
+    fn packs_and_unpacks() {
+        let effect = U48::from(1_700_000_000);
+        let delay = Delay::pack(10, 20, effect);
+        assert_eq!(delay.unpack(), (10, 20, effect));
+    }
+
+    #[test]
+    fn new_delay_applies_immediately() {
+        let delay = Delay::new(10);
+        assert_eq!(delay.get(), 10);
+        assert_eq!(delay.get_full(), (10, 0, U48::ZERO));
+    }
+
+    #[test]
+    fn increase_waits_for_min_setback() {
+        let (delay, effect) = Delay::new(10).with_update(20, 5);
+        assert_eq!(effect, timestamp() + U48::from(5));
+        assert_eq!(delay.get_full(), (10, 20, effect));
+    }
+
+    #[test]
+    fn decrease_waits_for_difference() {
+        let (delay, effect) = Delay::new(100).with_update(20, 5);
+        assert_eq!(effect, timestamp() + U48::from(80));
+        assert_eq!(delay.get(), 100);
+    }
+
+    #[test]
+    fn update_without_setback_applies_at_current_timepoint() {
+        let (delay, effect) = Delay::new(10).with_update(20, 0);
+        assert_eq!(effect, timestamp());
+        assert_eq!(delay.get(), 20);
+    }
+}
@@ -0,0 +1,229 @@
+//! Tests for the [`Eip712Struct`] derive macro, comparing its output against
+//! [`alloy_sol_types`].
+#![allow(missing_docs)]
+extern crate alloc;
+// Links the host implementations of the Stylus hostio functions.
+extern crate motsu;
+
+use alloc::{string::String, vec, vec::Vec};
+
+use alloy_primitives::{
+    address, b256, keccak256, Address, Bytes, FixedBytes, I256, U256,
+};
+use alloy_sol_types::SolStruct;
+use openzeppelin_stylus_proc::Eip712Struct;
+
+#[derive(Eip712Struct)]
+struct Person {
+    name: String,
+    wallet: Address,
+}
+
+#[derive(Eip712Struct)]
+struct Mail {
+    from: Person,
+    to: Person,
+    contents: String,
+}
+
+#[derive(Eip712Struct)]
+struct Asset {
+    token: Address,
+    amount: U256,
+}
+
+#[derive(Eip712Struct)]
+struct Order {
+    maker: Person,
+    mails: Vec<Mail>,
+    assets: [Asset; 2],
+    taker: Person,
+}
+
+#[derive(Eip712Struct)]
+struct AllTypes {
+    flag: bool,
+    small: u8,
+    big: U256,
+    negative: i32,
+    signed: I256,
+    tag: FixedBytes<4>,
+    data: Bytes,
+    text: String,
+    owner: Address,
+    values: Vec<U256>,
+    pair: [u64; 2],
+    snake_case: u16,
+}
+
+mod sol_types {
+    alloy_sol_types::sol! {
+        struct AllTypes {
+            bool flag;
+            uint8 small;
+            uint256 big;
+            int32 negative;
+            int256 signed;
+            bytes4 tag;
+            bytes data;
+            string text;
+            address owner;
+            uint256[] values;
+            uint64[2] pair;
+            uint16 snakeCase;
+        }
+
+        struct Person {
+            string name;
+            address wallet;
+        }
+
+        struct Mail {
+            Person from;
+            Person to;
+            string contents;
+        }
+
+        struct Asset {
+            address token;
+            uint256 amount;
+        }
+
+        struct Order {
+            Person maker;
+            Mail[] mails;
+            Asset[2] assets;
+            Person taker;
+        }
+    }
+}
+
+#[derive(Eip712Struct)]
+struct Keyword {
+    r#type: u8,
+}
+
+fn mail() -> Mail {
+    Mail {
+        from: Person {
+            name: "Cow".into(),
+            wallet: address!("CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+        },
+        to: Person {
+            name: "Bob".into(),
+            wallet: address!("bBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+        },
+        contents: "Hello, Bob!".into(),
+    }
+}
+
+#[test]
+fn encodes_type() {
+    assert_eq!(Person::encode_type(), "Person(string name,address wallet)");
+    assert_eq!(
+            Mail::encode_type(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+    assert_eq!(
+            Order::encode_type(),
+            "Order(Person maker,Mail[] mails,Asset[2] assets,Person taker)Asset(address token,uint256 amount)Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+    assert_eq!(Order::TYPE_HASH, keccak256(Order::encode_type()));
+    assert_eq!(Keyword::encode_type(), "Keyword(uint8 type)");
+    assert_eq!(
+        Keyword { r#type: 1 }.struct_hash(),
+        keccak256([Keyword::TYPE_HASH.as_slice(), &[0; 31], &[1]].concat())
+    );
+}
+
+#[test]
+fn hashes_struct() {
+    assert_eq!(
+        Mail::TYPE_HASH,
+        b256!(
+            "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+        )
+    );
+    assert_eq!(
+        mail().struct_hash(),
+        b256!(
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        )
+    );
+}
+
+#[test]
+fn hashes_struct_like_sol_struct() {
+    let value = AllTypes {
+        flag: true,
+        small: 7,
+        big: U256::MAX,
+        negative: -42,
+        signed: I256::MINUS_ONE,
+        tag: FixedBytes::repeat_byte(0xab),
+        data: Bytes::from(vec![0xde, 0xad, 0xbe, 0xef]),
+        text: "text".into(),
+        owner: address!("000000000000000000000000000000000000dEaD"),
+        values: vec![U256::from(1), U256::from(2)],
+        pair: [3, 4],
+        snake_case: 5,
+    };
+    let expected = sol_types::AllTypes {
+        flag: value.flag,
+        small: value.small,
+        big: value.big,
+        negative: value.negative,
+        signed: value.signed,
+        tag: value.tag,
+        data: value.data.clone(),
+        text: value.text.clone(),
+        owner: value.owner,
+        values: value.values.clone(),
+        pair: value.pair,
+        snakeCase: value.snake_case,
+    };
+
+    assert_eq!(
+        AllTypes::encode_type(),
+        sol_types::AllTypes::eip712_encode_type()
+    );
+    assert_eq!(AllTypes::TYPE_HASH, expected.eip712_type_hash());
+    assert_eq!(value.struct_hash(), expected.eip712_hash_struct());
+}
+
+#[test]
+fn hashes_nested_struct_like_sol_struct() {
+    let person =
+        |name: &str, wallet: Address| Person { name: name.into(), wallet };
+    let asset = |amount: u64| Asset {
+        token: address!("000000000000000000000000000000000000dEaD"),
+        amount: U256::from(amount),
+    };
+    let value = Order {
+        maker: person("Alice", Address::repeat_byte(0xaa)),
+        mails: vec![mail(), mail()],
+        assets: [asset(1), asset(2)],
+        taker: person("Bob", Address::repeat_byte(0xbb)),
+    };
+
+    let sol_person = |p: &Person| sol_types::Person {
+        name: p.name.clone(),
+        wallet: p.wallet,
+    };
+    let sol_mail = |m: &Mail| sol_types::Mail {
+        from: sol_person(&m.from),
+        to: sol_person(&m.to),
+        contents: m.contents.clone(),
+    };
+    let sol_asset =
+        |a: &Asset| sol_types::Asset { token: a.token, amount: a.amount };
+    let expected = sol_types::Order {
+        maker: sol_person(&value.maker),
+        mails: value.mails.iter().map(sol_mail).collect(),
+        assets: [sol_asset(&value.assets[0]), sol_asset(&value.assets[1])],
+        taker: sol_person(&value.taker),
+    };
+
+    assert_eq!(Order::encode_type(), sol_types::Order::eip712_encode_type());
+    assert_eq!(value.struct_hash(), expected.eip712_hash_struct());
+}
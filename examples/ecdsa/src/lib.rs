@@ -5,7 +5,7 @@ use alloc::vec::Vec;
 
 use alloy_primitives::{Address, B256};
 use openzeppelin_stylus::utils::cryptography::ecdsa;
-use stylus_sdk::prelude::*;
+use stylus_sdk::{abi::Bytes, prelude::*};
 
 #[entrypoint]
 #[storage]
@@ -22,4 +22,20 @@ impl ECDSAExample {
     ) -> Result<Address, ecdsa::Error> {
         ecdsa::recover(self, hash, v, r, s)
     }
+
+    fn recover_from_bytes(
+        &mut self,
+        hash: B256,
+        signature: Bytes,
+    ) -> Result<Address, ecdsa::Error> {
+        ecdsa::recover_from_bytes(self, hash, &signature)
+    }
+
+    fn try_recover(
+        &mut self,
+        hash: B256,
+        signature: Bytes,
+    ) -> Result<Address, ecdsa::Error> {
+        ecdsa::try_recover(self, hash, &signature)
+    }
 }
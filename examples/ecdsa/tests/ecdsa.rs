@@ -176,3 +176,91 @@ async fn error_when_higher_s(alice: Account) -> Result<()> {
 
     Ok(())
 }
+
+#[e2e::test]
+async fn recovers_from_bytes(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.address()?;
+    let contract = ECDSA::new(contract_addr, &alice.wallet);
+
+    let signature = [R.as_slice(), S.as_slice(), &[V]].concat();
+
+    let ECDSA::recoverFromBytesReturn { recovered } = contract
+        .recoverFromBytes(HASH, signature.clone().into())
+        .call()
+        .await?;
+    assert_eq!(ADDRESS, recovered);
+
+    let ECDSA::tryRecoverReturn { recovered } =
+        contract.tryRecover(HASH, signature.into()).call().await?;
+    assert_eq!(ADDRESS, recovered);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn recovers_from_compact_bytes(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.address()?;
+    let contract = ECDSA::new(contract_addr, &alice.wallet);
+
+    // `V` is 28, so the highest bit of `vs` is set.
+    let mut vs = S;
+    vs[0] |= 0x80;
+    let signature = [R.as_slice(), vs.as_slice()].concat();
+
+    let ECDSA::recoverFromBytesReturn { recovered } = contract
+        .recoverFromBytes(HASH, signature.clone().into())
+        .call()
+        .await?;
+    assert_eq!(ADDRESS, recovered);
+
+    let ECDSA::tryRecoverReturn { recovered } =
+        contract.tryRecover(HASH, signature.into()).call().await?;
+    assert_eq!(ADDRESS, recovered);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn error_when_invalid_signature_length(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.address()?;
+    let contract = ECDSA::new(contract_addr, &alice.wallet);
+
+    let signature = [R.as_slice(), S.as_slice(), &[V, V]].concat();
+
+    let err = contract
+        .recoverFromBytes(HASH, signature.clone().into())
+        .call()
+        .await
+        .expect_err("should return `ECDSAInvalidSignatureLength`");
+    assert!(err.reverted_with(ECDSA::ECDSAInvalidSignatureLength {
+        length: uint!(66_U256)
+    }));
+
+    let err = contract
+        .tryRecover(HASH, signature.into())
+        .call()
+        .await
+        .expect_err("should return `ECDSAInvalidSignatureLength`");
+    assert!(err.reverted_with(ECDSA::ECDSAInvalidSignatureLength {
+        length: uint!(66_U256)
+    }));
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn try_recover_rejects_invalid_v(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.address()?;
+    let contract = ECDSA::new(contract_addr, &alice.wallet);
+
+    let signature = [R.as_slice(), S.as_slice(), &[29]].concat();
+
+    let err = contract
+        .tryRecover(HASH, signature.into())
+        .call()
+        .await
+        .expect_err("should return `ECDSAInvalidSignature`");
+    assert!(err.reverted_with(ECDSA::ECDSAInvalidSignature {}));
+
+    Ok(())
+}
@@ -17,6 +17,7 @@ sol!(
         function permit(address owner, address spender, uint256 value, uint256 deadline, uint8 v, bytes32 r, bytes32 s) external;
         function nonces(address owner) external view returns (uint256 nonce);
         function DOMAIN_SEPARATOR() external view returns (bytes32 domainSeparator);
+        function eip712Domain() external view returns (bytes1 fields, string memory name, string memory version, uint256 chainId, address verifyingContract, bytes32 salt, uint256[] memory extensions);
 
         error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
         error ERC20InvalidSender(address sender);
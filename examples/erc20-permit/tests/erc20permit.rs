@@ -2,7 +2,8 @@
 
 use abi::Erc20Permit;
 use alloy::{
-    primitives::{keccak256, Address, B256, U256},
+    primitives::{fixed_bytes, keccak256, Address, B256, U256},
+    providers::Provider,
     sol,
     sol_types::SolType,
 };
@@ -122,6 +123,33 @@ async fn error_when_expired_deadline_for_permit(
     Ok(())
 }
 
+#[e2e::test]
+async fn eip712_domain_works(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.address()?;
+    let contract = Erc20Permit::new(contract_addr, &alice.wallet);
+    let chain_id = alice.wallet.get_chain_id().await?;
+
+    let Erc20Permit::eip712DomainReturn {
+        fields,
+        name,
+        version,
+        chainId,
+        verifyingContract,
+        salt,
+        extensions,
+    } = contract.eip712Domain().call().await?;
+
+    assert_eq!(fixed_bytes!("0f"), fields);
+    assert_eq!("ERC-20 Permit Example", name);
+    assert_eq!("1", version);
+    assert_eq!(U256::from(chain_id), chainId);
+    assert_eq!(contract_addr, verifyingContract);
+    assert_eq!(B256::ZERO, salt);
+    assert!(extensions.is_empty());
+
+    Ok(())
+}
+
 #[e2e::test]
 async fn permit_works(alice: Account, bob: Account) -> Result<()> {
     let contract_addr = alice.as_deployer().deploy().await?.address()?;
@@ -0,0 +1,51 @@
+#![cfg_attr(not(test), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use alloy_primitives::{Address, FixedBytes, U256};
+use openzeppelin_stylus::{
+    token::erc20::extensions::{votes, Erc20Votes},
+    utils::{cryptography::eip712::IEip712, introspection::erc165::IErc165},
+};
+use stylus_sdk::prelude::*;
+
+#[entrypoint]
+#[storage]
+struct Erc20VotesExample {
+    #[borrow]
+    erc20_votes: Erc20Votes<Eip712>,
+}
+
+#[storage]
+struct Eip712;
+
+impl IEip712 for Eip712 {
+    const NAME: &'static str = "ERC-20 Votes Example";
+    const VERSION: &'static str = "1";
+}
+
+#[public]
+#[inherit(Erc20Votes<Eip712>)]
+impl Erc20VotesExample {
+    // Add token minting feature.
+    fn mint(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), votes::Error> {
+        self.erc20_votes._mint(account, value)
+    }
+
+    fn burn(
+        &mut self,
+        account: Address,
+        value: U256,
+    ) -> Result<(), votes::Error> {
+        self.erc20_votes._burn(account, value)
+    }
+
+    fn supports_interface(interface_id: FixedBytes<4>) -> bool {
+        Erc20Votes::<Eip712>::supports_interface(interface_id)
+    }
+}
@@ -0,0 +1,50 @@
+#![allow(dead_code)]
+#![allow(clippy::too_many_arguments)]
+use alloy::sol;
+
+sol!(
+    #[sol(rpc)]
+    contract Erc20Votes {
+        function totalSupply() external view returns (uint256 totalSupply);
+        function balanceOf(address account) external view returns (uint256 balance);
+        function transfer(address recipient, uint256 amount) external returns (bool);
+        function allowance(address owner, address spender) external view returns (uint256 allowance);
+        function approve(address spender, uint256 amount) external returns (bool);
+        function transferFrom(address sender, address recipient, uint256 amount) external returns (bool);
+
+        function mint(address account, uint256 amount) external;
+        function burn(address account, uint256 amount) external;
+
+        function clock() external view returns (uint48 clock);
+        function CLOCK_MODE() external view returns (string memory mode);
+        function getVotes(address account) external view returns (uint256 votes);
+        function getPastVotes(address account, uint256 timepoint) external view returns (uint256 votes);
+        #[derive(Debug)]
+        function getPastTotalSupply(uint256 timepoint) external view returns (uint256 totalSupply);
+        function delegates(address account) external view returns (address delegatee);
+        function delegate(address delegatee) external;
+        function delegateBySig(address delegatee, uint256 nonce, uint256 expiry, uint8 v, bytes32 r, bytes32 s) external;
+        function nonces(address owner) external view returns (uint256 nonce);
+        function numCheckpoints(address account) external view returns (uint32 checkpoints);
+
+        error ERC20InsufficientBalance(address sender, uint256 balance, uint256 needed);
+        error ERC20InvalidSender(address sender);
+        error ERC20InvalidReceiver(address receiver);
+        error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed);
+        error ERC20InvalidSpender(address spender);
+
+        error ERC5805FutureLookup(uint256 timepoint, uint48 clock);
+        error VotesExpiredSignature(uint256 expiry);
+        error ERC20ExceededSafeSupply(uint256 increased_supply, uint256 cap);
+        error InvalidAccountNonce(address account, uint256 current_nonce);
+
+        #[derive(Debug, PartialEq)]
+        event Transfer(address indexed from, address indexed to, uint256 value);
+        #[derive(Debug, PartialEq)]
+        event Approval(address indexed owner, address indexed spender, uint256 value);
+        #[derive(Debug, PartialEq)]
+        event DelegateChanged(address indexed delegator, address indexed fromDelegate, address indexed toDelegate);
+        #[derive(Debug, PartialEq)]
+        event DelegateVotesChanged(address indexed delegate, uint256 previousVotes, uint256 newVotes);
+    }
+);
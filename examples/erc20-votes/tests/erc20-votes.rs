@@ -0,0 +1,246 @@
+#![cfg(feature = "e2e")]
+
+use abi::Erc20Votes;
+use alloy::{
+    primitives::{keccak256, Address, B256, U256},
+    providers::Provider,
+    sol,
+    sol_types::SolType,
+};
+use alloy_primitives::uint;
+use e2e::{receipt, send, watch, Account, EventExt, ReceiptExt, Revert};
+use eyre::Result;
+
+mod abi;
+
+// Saturday, 1 January 2000 00:00:00
+const EXPIRED_DEADLINE: U256 = uint!(946_684_800_U256);
+
+// Wednesday, 1 January 3000 00:00:00
+const FAIR_DEADLINE: U256 = uint!(32_503_680_000_U256);
+
+const DELEGATION_TYPEHASH: [u8; 32] = keccak_const::Keccak256::new()
+    .update(b"Delegation(address delegatee,uint256 nonce,uint256 expiry)")
+    .finalize();
+
+type DelegationStructHashTuple = sol! {
+    tuple(bytes32, address, uint256, uint256)
+};
+
+const EIP712_DOMAIN_TYPEHASH: [u8; 32] =
+    keccak_const::Keccak256::new()
+        .update(b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)")
+        .finalize();
+
+type DomainSeparatorTuple = sol! {
+    tuple(bytes32, bytes32, bytes32, uint256, address)
+};
+
+fn domain_separator(contract_addr: Address, chain_id: u64) -> B256 {
+    keccak256(DomainSeparatorTuple::abi_encode(&(
+        EIP712_DOMAIN_TYPEHASH,
+        keccak256(b"ERC-20 Votes Example"),
+        keccak256(b"1"),
+        U256::from(chain_id),
+        contract_addr,
+    )))
+}
+
+fn delegation_hash(
+    contract_addr: Address,
+    chain_id: u64,
+    delegatee: Address,
+    nonce: U256,
+    expiry: U256,
+) -> B256 {
+    let struct_hash = keccak256(DelegationStructHashTuple::abi_encode(&(
+        DELEGATION_TYPEHASH,
+        delegatee,
+        nonce,
+        expiry,
+    )));
+    let typed_data_hash =
+        openzeppelin_stylus::utils::cryptography::eip712::to_typed_data_hash(
+            &domain_separator(contract_addr, chain_id),
+            &struct_hash,
+        );
+    B256::from_slice(typed_data_hash.as_slice())
+}
+
+// I was unable to find a function in alloy that converts `v` into [non-eip155
+// value], so I implemented the logic manually.
+//
+// [non-eip155 value]: https://eips.ethereum.org/EIPS/eip-155
+fn to_non_eip155_v(v: bool) -> u8 {
+    v as u8 + 27
+}
+
+// ============================================================================
+// Integration Tests: ERC-20 Votes Extension
+// ============================================================================
+
+#[e2e::test]
+async fn delegates_and_tracks_votes(
+    alice: Account,
+    bob: Account,
+) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.address()?;
+    let contract = Erc20Votes::new(contract_addr, &alice.wallet);
+    let alice_addr = alice.address();
+    let bob_addr = bob.address();
+
+    let balance = uint!(10_U256);
+    watch!(contract.mint(alice_addr, balance))?;
+
+    let Erc20Votes::getVotesReturn { votes } =
+        contract.getVotes(alice_addr).call().await?;
+    assert_eq!(U256::ZERO, votes);
+
+    let receipt = receipt!(contract.delegate(alice_addr))?;
+    assert!(receipt.emits(Erc20Votes::DelegateChanged {
+        delegator: alice_addr,
+        fromDelegate: Address::ZERO,
+        toDelegate: alice_addr,
+    }));
+    assert!(receipt.emits(Erc20Votes::DelegateVotesChanged {
+        delegate: alice_addr,
+        previousVotes: U256::ZERO,
+        newVotes: balance,
+    }));
+
+    let one = uint!(1_U256);
+    watch!(contract.transfer(bob_addr, one))?;
+
+    let Erc20Votes::getVotesReturn { votes } =
+        contract.getVotes(alice_addr).call().await?;
+    assert_eq!(balance - one, votes);
+
+    let Erc20Votes::delegatesReturn { delegatee } =
+        contract.delegates(bob_addr).call().await?;
+    assert_eq!(Address::ZERO, delegatee);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn reads_past_votes(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.address()?;
+    let contract = Erc20Votes::new(contract_addr, &alice.wallet);
+    let alice_addr = alice.address();
+
+    let balance = uint!(10_U256);
+    watch!(contract.mint(alice_addr, balance))?;
+    let receipt = receipt!(contract.delegate(alice_addr))?;
+    let timepoint = U256::from(receipt.block_number.expect("block number"));
+
+    // Move the clock forward.
+    watch!(contract.mint(alice_addr, balance))?;
+
+    let Erc20Votes::getPastVotesReturn { votes } =
+        contract.getPastVotes(alice_addr, timepoint).call().await?;
+    assert_eq!(balance, votes);
+
+    let Erc20Votes::getPastTotalSupplyReturn { totalSupply } =
+        contract.getPastTotalSupply(timepoint).call().await?;
+    assert_eq!(balance + balance, totalSupply);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn error_when_looking_up_future_votes(alice: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.address()?;
+    let contract = Erc20Votes::new(contract_addr, &alice.wallet);
+
+    let timepoint = U256::from(u64::MAX);
+    let err = contract
+        .getPastTotalSupply(timepoint)
+        .call()
+        .await
+        .expect_err("should return `ERC5805FutureLookup`");
+    assert!(err.reverted_with(Erc20Votes::ERC5805FutureLookup {
+        timepoint,
+        clock: contract.clock().call().await?.clock,
+    }));
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn delegates_by_sig(alice: Account, bob: Account) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.address()?;
+    let contract = Erc20Votes::new(contract_addr, &bob.wallet);
+    let alice_addr = alice.address();
+
+    let balance = uint!(10_U256);
+    watch!(contract.mint(alice_addr, balance))?;
+
+    let chain_id = alice.wallet.get_chain_id().await?;
+    let hash = delegation_hash(
+        contract_addr,
+        chain_id,
+        alice_addr,
+        U256::ZERO,
+        FAIR_DEADLINE,
+    );
+    let signature = alice.sign_hash(&hash).await;
+
+    let receipt = receipt!(contract.delegateBySig(
+        alice_addr,
+        U256::ZERO,
+        FAIR_DEADLINE,
+        to_non_eip155_v(signature.v()),
+        signature.r().into(),
+        signature.s().into()
+    ))?;
+    assert!(receipt.emits(Erc20Votes::DelegateChanged {
+        delegator: alice_addr,
+        fromDelegate: Address::ZERO,
+        toDelegate: alice_addr,
+    }));
+
+    let Erc20Votes::getVotesReturn { votes } =
+        contract.getVotes(alice_addr).call().await?;
+    assert_eq!(balance, votes);
+
+    let Erc20Votes::noncesReturn { nonce } =
+        contract.nonces(alice_addr).call().await?;
+    assert_eq!(uint!(1_U256), nonce);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn error_when_delegating_by_expired_sig(
+    alice: Account,
+    bob: Account,
+) -> Result<()> {
+    let contract_addr = alice.as_deployer().deploy().await?.address()?;
+    let contract = Erc20Votes::new(contract_addr, &bob.wallet);
+    let alice_addr = alice.address();
+
+    let chain_id = alice.wallet.get_chain_id().await?;
+    let hash = delegation_hash(
+        contract_addr,
+        chain_id,
+        alice_addr,
+        U256::ZERO,
+        EXPIRED_DEADLINE,
+    );
+    let signature = alice.sign_hash(&hash).await;
+
+    let err = send!(contract.delegateBySig(
+        alice_addr,
+        U256::ZERO,
+        EXPIRED_DEADLINE,
+        to_non_eip155_v(signature.v()),
+        signature.r().into(),
+        signature.s().into()
+    ))
+    .expect_err("should return `VotesExpiredSignature`");
+    assert!(err.reverted_with(Erc20Votes::VotesExpiredSignature {
+        expiry: EXPIRED_DEADLINE
+    }));
+
+    Ok(())
+}
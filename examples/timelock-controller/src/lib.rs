@@ -0,0 +1,23 @@
+#![cfg_attr(not(test), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openzeppelin_stylus::governance::TimelockController;
+use stylus_sdk::prelude::*;
+
+#[entrypoint]
+#[storage]
+struct TimelockControllerExample {
+    #[borrow]
+    timelock: TimelockController,
+}
+
+#[public]
+#[inherit(TimelockController)]
+impl TimelockControllerExample {
+    #[receive]
+    fn receive(&mut self) -> Result<(), Vec<u8>> {
+        Ok(())
+    }
+}
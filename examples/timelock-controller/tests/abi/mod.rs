@@ -0,0 +1,50 @@
+#![allow(dead_code)]
+use alloy::sol;
+
+sol!(
+    #[sol(rpc)]
+    contract TimelockController {
+        function hasRole(bytes32 role, address account) public view virtual returns (bool hasRole);
+        function getRoleAdmin(bytes32 role) public view virtual returns (bytes32 role);
+        function grantRole(bytes32 role, address account) public virtual;
+        function revokeRole(bytes32 role, address account) public virtual;
+        function renounceRole(bytes32 role, address callerConfirmation) public virtual;
+
+        function isOperation(bytes32 id) external view returns (bool);
+        function isOperationPending(bytes32 id) external view returns (bool);
+        function isOperationReady(bytes32 id) external view returns (bool);
+        function isOperationDone(bytes32 id) external view returns (bool);
+        function getTimestamp(bytes32 id) external view returns (uint256);
+        function getOperationState(bytes32 id) external view returns (uint8);
+        function getMinDelay() external view returns (uint256);
+        function hashOperation(address target, uint256 value, bytes calldata data, bytes32 predecessor, bytes32 salt) external pure returns (bytes32);
+        function hashOperationBatch(address[] calldata targets, uint256[] calldata values, bytes[] calldata payloads, bytes32 predecessor, bytes32 salt) external pure returns (bytes32);
+        function schedule(address target, uint256 value, bytes calldata data, bytes32 predecessor, bytes32 salt, uint256 delay) external;
+        function scheduleBatch(address[] calldata targets, uint256[] calldata values, bytes[] calldata payloads, bytes32 predecessor, bytes32 salt, uint256 delay) external;
+        function cancel(bytes32 id) external;
+        function execute(address target, uint256 value, bytes calldata payload, bytes32 predecessor, bytes32 salt) external payable;
+        function executeBatch(address[] calldata targets, uint256[] calldata values, bytes[] calldata payloads, bytes32 predecessor, bytes32 salt) external payable;
+        function updateDelay(uint256 newDelay) external;
+        function onERC721Received(address operator, address from, uint256 tokenId, bytes calldata data) external returns (bytes4);
+
+        error AccessControlUnauthorizedAccount(address account, bytes32 neededRole);
+        error TimelockInvalidOperationLength(uint256 targets, uint256 payloads, uint256 values);
+        error TimelockInsufficientDelay(uint256 delay, uint256 minDelay);
+        error TimelockUnexpectedOperationState(bytes32 operationId, bytes32 expectedStates);
+        error TimelockUnexecutedPredecessor(bytes32 predecessorId);
+        error TimelockUnauthorizedCaller(address caller);
+
+        #[derive(Debug, PartialEq)]
+        event RoleGranted(bytes32 indexed role, address indexed account, address indexed sender);
+        #[derive(Debug, PartialEq)]
+        event CallScheduled(bytes32 indexed id, uint256 indexed index, address target, uint256 value, bytes data, bytes32 predecessor, uint256 delay);
+        #[derive(Debug, PartialEq)]
+        event CallExecuted(bytes32 indexed id, uint256 indexed index, address target, uint256 value, bytes data);
+        #[derive(Debug, PartialEq)]
+        event CallSalt(bytes32 indexed id, bytes32 salt);
+        #[derive(Debug, PartialEq)]
+        event Cancelled(bytes32 indexed id);
+        #[derive(Debug, PartialEq)]
+        event MinDelayChange(uint256 oldDuration, uint256 newDuration);
+    }
+);
@@ -0,0 +1,327 @@
+#![cfg(feature = "e2e")]
+
+use abi::TimelockController;
+use alloy::{
+    primitives::{Address, Bytes, B256, U256},
+    sol,
+};
+use e2e::{receipt, send, watch, Account, EventExt, ReceiptExt, Revert};
+use eyre::Result;
+use openzeppelin_stylus::governance::timelock_controller::{
+    CANCELLER_ROLE, EXECUTOR_ROLE, PROPOSER_ROLE,
+};
+
+use crate::TimelockControllerExample::constructorCall;
+
+mod abi;
+
+sol!("src/constructor.sol");
+
+const MIN_DELAY: u64 = 60;
+const SALT: B256 = B256::repeat_byte(1);
+
+fn ctr(
+    min_delay: U256,
+    proposers: Vec<Address>,
+    executors: Vec<Address>,
+    admin: Address,
+) -> constructorCall {
+    constructorCall { minDelay: min_delay, proposers, executors, admin }
+}
+
+// ============================================================================
+// Integration Tests: TimelockController
+// ============================================================================
+
+#[e2e::test]
+async fn constructs(alice: Account, bob: Account) -> Result<()> {
+    let alice_addr = alice.address();
+    let bob_addr = bob.address();
+    let receipt = alice
+        .as_deployer()
+        .with_constructor(ctr(
+            U256::from(MIN_DELAY),
+            vec![alice_addr],
+            vec![bob_addr],
+            Address::ZERO,
+        ))
+        .deploy()
+        .await?;
+    let contract = TimelockController::new(receipt.address()?, &alice.wallet);
+
+    assert!(receipt.emits(TimelockController::MinDelayChange {
+        oldDuration: U256::ZERO,
+        newDuration: U256::from(MIN_DELAY),
+    }));
+
+    let TimelockController::getMinDelayReturn { _0: min_delay } =
+        contract.getMinDelay().call().await?;
+    assert_eq!(U256::from(MIN_DELAY), min_delay);
+
+    let TimelockController::hasRoleReturn { hasRole } =
+        contract.hasRole(PROPOSER_ROLE.into(), alice_addr).call().await?;
+    assert!(hasRole);
+    let TimelockController::hasRoleReturn { hasRole } =
+        contract.hasRole(CANCELLER_ROLE.into(), alice_addr).call().await?;
+    assert!(hasRole);
+    let TimelockController::hasRoleReturn { hasRole } =
+        contract.hasRole(EXECUTOR_ROLE.into(), bob_addr).call().await?;
+    assert!(hasRole);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn schedules_operation(alice: Account, bob: Account) -> Result<()> {
+    let alice_addr = alice.address();
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(ctr(
+            U256::from(MIN_DELAY),
+            vec![alice_addr],
+            vec![alice_addr],
+            Address::ZERO,
+        ))
+        .deploy()
+        .await?
+        .address()?;
+    let contract = TimelockController::new(contract_addr, &alice.wallet);
+
+    let target = bob.address();
+    let data = Bytes::new();
+    let TimelockController::hashOperationReturn { _0: id } = contract
+        .hashOperation(target, U256::ZERO, data.clone(), B256::ZERO, SALT)
+        .call()
+        .await?;
+
+    let receipt = receipt!(contract.schedule(
+        target,
+        U256::ZERO,
+        data.clone(),
+        B256::ZERO,
+        SALT,
+        U256::from(MIN_DELAY)
+    ))?;
+    assert!(receipt.emits(TimelockController::CallScheduled {
+        id,
+        index: U256::ZERO,
+        target,
+        value: U256::ZERO,
+        data,
+        predecessor: B256::ZERO,
+        delay: U256::from(MIN_DELAY),
+    }));
+    assert!(receipt.emits(TimelockController::CallSalt { id, salt: SALT }));
+
+    let TimelockController::isOperationPendingReturn { _0: pending } =
+        contract.isOperationPending(id).call().await?;
+    assert!(pending);
+    let TimelockController::isOperationReadyReturn { _0: ready } =
+        contract.isOperationReady(id).call().await?;
+    assert!(!ready);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn schedule_reverts_when_delay_is_insufficient(
+    alice: Account,
+    bob: Account,
+) -> Result<()> {
+    let alice_addr = alice.address();
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(ctr(
+            U256::from(MIN_DELAY),
+            vec![alice_addr],
+            vec![alice_addr],
+            Address::ZERO,
+        ))
+        .deploy()
+        .await?
+        .address()?;
+    let contract = TimelockController::new(contract_addr, &alice.wallet);
+
+    let err = send!(contract.schedule(
+        bob.address(),
+        U256::ZERO,
+        Bytes::new(),
+        B256::ZERO,
+        SALT,
+        U256::ZERO
+    ))
+    .expect_err("should not schedule below the minimum delay");
+    assert!(err.reverted_with(TimelockController::TimelockInsufficientDelay {
+        delay: U256::ZERO,
+        minDelay: U256::from(MIN_DELAY),
+    }));
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn schedule_reverts_when_not_proposer(
+    alice: Account,
+    bob: Account,
+) -> Result<()> {
+    let alice_addr = alice.address();
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(ctr(
+            U256::from(MIN_DELAY),
+            vec![alice_addr],
+            vec![alice_addr],
+            Address::ZERO,
+        ))
+        .deploy()
+        .await?
+        .address()?;
+    let contract = TimelockController::new(contract_addr, &bob.wallet);
+
+    let err = send!(contract.schedule(
+        alice_addr,
+        U256::ZERO,
+        Bytes::new(),
+        B256::ZERO,
+        SALT,
+        U256::from(MIN_DELAY)
+    ))
+    .expect_err("should not schedule without the proposer role");
+    assert!(err.reverted_with(
+        TimelockController::AccessControlUnauthorizedAccount {
+            account: bob.address(),
+            neededRole: PROPOSER_ROLE.into(),
+        }
+    ));
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn executes_ready_operation(alice: Account, bob: Account) -> Result<()> {
+    let alice_addr = alice.address();
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(ctr(
+            U256::ZERO,
+            vec![alice_addr],
+            vec![alice_addr],
+            Address::ZERO,
+        ))
+        .deploy()
+        .await?
+        .address()?;
+    let contract = TimelockController::new(contract_addr, &alice.wallet);
+
+    let target = bob.address();
+    let data = Bytes::new();
+    let TimelockController::hashOperationReturn { _0: id } = contract
+        .hashOperation(target, U256::ZERO, data.clone(), B256::ZERO, SALT)
+        .call()
+        .await?;
+
+    watch!(contract.schedule(
+        target,
+        U256::ZERO,
+        data.clone(),
+        B256::ZERO,
+        SALT,
+        U256::ZERO
+    ))?;
+
+    let receipt = receipt!(contract.execute(
+        target,
+        U256::ZERO,
+        data.clone(),
+        B256::ZERO,
+        SALT
+    ))?;
+    assert!(receipt.emits(TimelockController::CallExecuted {
+        id,
+        index: U256::ZERO,
+        target,
+        value: U256::ZERO,
+        data: data.clone(),
+    }));
+
+    let TimelockController::isOperationDoneReturn { _0: done } =
+        contract.isOperationDone(id).call().await?;
+    assert!(done);
+
+    let err =
+        send!(contract.execute(target, U256::ZERO, data, B256::ZERO, SALT))
+            .expect_err("should not execute twice");
+    assert!(err.reverted_with(
+        TimelockController::TimelockUnexpectedOperationState {
+            operationId: id,
+            expectedStates: B256::from(U256::from(1) << 2),
+        }
+    ));
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn cancels_pending_operation(alice: Account, bob: Account) -> Result<()> {
+    let alice_addr = alice.address();
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(ctr(
+            U256::from(MIN_DELAY),
+            vec![alice_addr],
+            vec![alice_addr],
+            Address::ZERO,
+        ))
+        .deploy()
+        .await?
+        .address()?;
+    let contract = TimelockController::new(contract_addr, &alice.wallet);
+
+    let target = bob.address();
+    let TimelockController::hashOperationReturn { _0: id } = contract
+        .hashOperation(target, U256::ZERO, Bytes::new(), B256::ZERO, SALT)
+        .call()
+        .await?;
+    watch!(contract.schedule(
+        target,
+        U256::ZERO,
+        Bytes::new(),
+        B256::ZERO,
+        SALT,
+        U256::from(MIN_DELAY)
+    ))?;
+
+    let receipt = receipt!(contract.cancel(id))?;
+    assert!(receipt.emits(TimelockController::Cancelled { id }));
+
+    let TimelockController::isOperationReturn { _0: is_operation } =
+        contract.isOperation(id).call().await?;
+    assert!(!is_operation);
+
+    Ok(())
+}
+
+#[e2e::test]
+async fn update_delay_reverts_when_not_timelock(alice: Account) -> Result<()> {
+    let alice_addr = alice.address();
+    let contract_addr = alice
+        .as_deployer()
+        .with_constructor(ctr(
+            U256::from(MIN_DELAY),
+            vec![alice_addr],
+            vec![alice_addr],
+            alice_addr,
+        ))
+        .deploy()
+        .await?
+        .address()?;
+    let contract = TimelockController::new(contract_addr, &alice.wallet);
+
+    let err = send!(contract.updateDelay(U256::ZERO))
+        .expect_err("should only be callable by the timelock");
+    assert!(err.reverted_with(
+        TimelockController::TimelockUnauthorizedCaller { caller: alice_addr }
+    ));
+
+    Ok(())
+}
@@ -140,4 +140,14 @@ pub trait BigInteger:
 
     /// Convert bigint to little-endian bytes.
     fn into_bytes_le(self) -> alloc::vec::Vec<u8>;
+
+    /// Create bigint from big-endian bytes.
+    ///
+    /// # Panics
+    ///
+    /// * If the number of bytes is not equal to `Self::BYTES`.
+    fn from_bytes_be(bytes: &[u8]) -> Self;
+
+    /// Convert bigint to big-endian bytes.
+    fn into_bytes_be(self) -> alloc::vec::Vec<u8>;
 }
@@ -679,6 +679,16 @@ impl<const N: usize> BigInteger for Uint<N> {
     fn into_bytes_le(self) -> Vec<u8> {
         self.limbs.iter().flat_map(|&limb| limb.to_le_bytes()).collect()
     }
+
+    fn from_bytes_be(bytes: &[u8]) -> Self {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::ct_from_le_slice(&bytes)
+    }
+
+    fn into_bytes_be(self) -> Vec<u8> {
+        self.limbs.iter().rev().flat_map(|&limb| limb.to_be_bytes()).collect()
+    }
 }
 
 impl<const N: usize> BitIteratorBE for Uint<N> {
@@ -964,6 +974,20 @@ mod test {
         assert_eq!(num.num_bits(), 64 + 64 + 64 + 2);
     }
 
+    #[test]
+    fn bytes_be_roundtrip() {
+        let num: Uint<4> = from_hex!(
+            "0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20"
+        );
+        let bytes: Vec<u8> = (1..=32).collect();
+        assert_eq!(num.into_bytes_be(), bytes);
+        assert_eq!(Uint::<4>::from_bytes_be(&bytes), num);
+
+        let mut reversed = bytes.clone();
+        reversed.reverse();
+        assert_eq!(num.into_bytes_le(), reversed);
+    }
+
     #[test]
     fn ct_rem() {
         let dividend = from_num!("43129923721897334698312931");
@@ -0,0 +1,6 @@
+//! This module contains the [Short Weierstrass] configurations of some popular
+//! curves.
+//!
+//! [Short Weierstrass]: https://www.hyperelliptic.org/EFD/g1p/auto-shortw.html
+pub mod secp256k1;
+pub mod secp256r1;
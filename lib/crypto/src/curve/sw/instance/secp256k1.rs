@@ -0,0 +1,306 @@
+//! The [secp256k1] curve, used by Ethereum and Bitcoin signatures.
+//!
+//! It is defined over the prime field of order
+//! `p = 2^256 - 2^32 - 977`, by the equation `y² = x³ + 7`, and has a prime
+//! order subgroup of order `n` with cofactor `1`.
+//!
+//! Scalar multiplication uses the [GLV] endomorphism `(x, y) -> (β * x, y)`,
+//! which is equivalent to multiplying by `λ`. A scalar `k` is split into two
+//! half-length scalars `k1` and `k2` such that `k = k1 + k2 * λ`, which
+//! halves the number of point doublings.
+//!
+//! [secp256k1]: https://www.secg.org/sec2-v2.pdf
+//! [GLV]: https://www.iacr.org/archive/crypto2001/21390189.pdf
+use num_traits::Zero;
+
+use crate::{
+    arithmetic::uint::{Uint, U256},
+    bits::BitIteratorBE,
+    curve::{
+        sw,
+        sw::{sw_double_scalar_mul_affine, SWCurveConfig},
+        AffineRepr, CurveConfig, CurveGroup,
+    },
+    field::{
+        fp::{Fp256, FpParams, LIMBS_256},
+        group::AdditiveGroup,
+        prime::PrimeField,
+    },
+    fp_from_hex, fp_from_num, from_hex,
+};
+
+/// Base field of the [secp256k1] curve.
+///
+/// [secp256k1]: self
+pub type Fq = Fp256<FqParam>;
+
+/// Parameters of the [`Fq`] field.
+pub struct FqParam;
+
+impl FpParams<LIMBS_256> for FqParam {
+    const GENERATOR: Fq = fp_from_num!("3");
+    const MODULUS: U256 = from_hex!(
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f"
+    );
+}
+
+/// Scalar field of the [secp256k1] curve, i.e. integers modulo the order `n`
+/// of its prime order subgroup.
+///
+/// [secp256k1]: self
+pub type Fr = Fp256<FrParam>;
+
+/// Parameters of the [`Fr`] field.
+pub struct FrParam;
+
+impl FpParams<LIMBS_256> for FrParam {
+    const GENERATOR: Fr = fp_from_num!("7");
+    const MODULUS: U256 = from_hex!(
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+    );
+}
+
+/// Affine point of the [secp256k1] curve.
+///
+/// [secp256k1]: self
+pub type Affine = sw::Affine<Secp256k1Config>;
+
+/// Projective point of the [secp256k1] curve.
+///
+/// [secp256k1]: self
+pub type Projective = sw::Projective<Secp256k1Config>;
+
+/// Cube root of unity in [`Fq`], such that `(β * x, y) = λ * (x, y)`.
+const BETA: Fq = fp_from_hex!(
+    "7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee"
+);
+
+/// Cube root of unity in [`Fr`], such that `(β * x, y) = λ * (x, y)`.
+const LAMBDA: Fr = fp_from_hex!(
+    "5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72"
+);
+
+/// `-b1`, where `(a1, b1)` and `(a2, b2)` are the short lattice basis used to
+/// split scalars.
+const MINUS_B1: Fr = fp_from_hex!("e4437ed6010e88286f547fa90abfe4c3");
+
+/// `-b2 mod n`, where `b2 = a1`.
+const MINUS_B2: Fr = fp_from_hex!(
+    "fffffffffffffffffffffffffffffffe8a280ac50774346dd765cda83db1562c"
+);
+
+/// `round(2^384 * b2 / n)`.
+const G1: U256 = from_hex!(
+    "3086d221a7d46bcde86c90e49284eb153daa8a1471e8ca7fe893209a45dbb031"
+);
+
+/// `round(2^384 * -b1 / n)`.
+const G2: U256 = from_hex!(
+    "e4437ed6010e88286f547fa90abfe4c4221208ac9df506c61571b4ae8ac47f71"
+);
+
+/// Half of the order of the [`Fr`] field.
+const HALF_N: U256 = from_hex!(
+    "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0"
+);
+
+/// Configuration of the [secp256k1] curve.
+///
+/// [secp256k1]: self
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secp256k1Config;
+
+impl CurveConfig for Secp256k1Config {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    const COFACTOR: &'static [u64] = &[0x1];
+    const COFACTOR_INV: Fr = Fr::ONE;
+}
+
+impl SWCurveConfig for Secp256k1Config {
+    const COEFF_A: Fq = Fq::ZERO;
+    const COEFF_B: Fq = fp_from_num!("7");
+    const GENERATOR: Affine = Affine::new_unchecked(
+        fp_from_hex!(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        ),
+        fp_from_hex!(
+            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+        ),
+    );
+
+    fn mul_projective(
+        base: &Projective,
+        scalar: impl BitIteratorBE,
+    ) -> Projective {
+        glv_mul(&base.into_affine(), reduce(scalar))
+    }
+
+    fn mul_affine(base: &Affine, scalar: impl BitIteratorBE) -> Projective {
+        glv_mul(base, reduce(scalar))
+    }
+}
+
+/// Multiplies `base` by `scalar` with the [GLV] endomorphism.
+///
+/// [GLV]: https://www.iacr.org/archive/crypto2001/21390189.pdf
+fn glv_mul(base: &Affine, scalar: Fr) -> Projective {
+    if base.is_zero() {
+        return Projective::zero();
+    }
+
+    let ((k1_neg, k1), (k2_neg, k2)) = decompose(scalar);
+    let p1 = if k1_neg { -*base } else { *base };
+    let p2 = Affine::new_unchecked(base.x * BETA, base.y);
+    let p2 = if k2_neg { -p2 } else { p2 };
+
+    sw_double_scalar_mul_affine(&p1, k1, &p2, k2)
+}
+
+/// Splits `k` into `k1` and `k2` such that `k = k1 + k2 * λ`.
+///
+/// Both halves are returned as a sign and an absolute value of at most 128
+/// bits.
+fn decompose(k: Fr) -> ((bool, U256), (bool, U256)) {
+    let k_int = k.into_bigint();
+    let c1 = Fr::from_bigint(mul_shift_384(&k_int, &G1));
+    let c2 = Fr::from_bigint(mul_shift_384(&k_int, &G2));
+
+    let k2 = c1 * MINUS_B1 + c2 * MINUS_B2;
+    let k1 = k - k2 * LAMBDA;
+
+    (split_sign(k1), split_sign(k2))
+}
+
+/// Returns `round(a * b / 2^384)`.
+fn mul_shift_384(a: &U256, b: &U256) -> U256 {
+    let (_, high) = a.ct_widening_mul(b);
+    let limbs = high.into_limbs();
+    let shifted = Uint::new([limbs[2], limbs[3], 0, 0]);
+    // Round to the nearest integer with the most significant dropped bit.
+    shifted.ct_add(&Uint::from_u64(limbs[1] >> 63))
+}
+
+/// Returns whether `k` is in the upper half of the [`Fr`] field, i.e. is a
+/// negative number, and its absolute value.
+fn split_sign(k: Fr) -> (bool, U256) {
+    let k_int = k.into_bigint();
+    if k_int.ct_gt(&HALF_N) {
+        (true, (-k).into_bigint())
+    } else {
+        (false, k_int)
+    }
+}
+
+/// Reduces an arbitrary length `scalar` modulo the order of the curve.
+fn reduce(scalar: impl BitIteratorBE) -> Fr {
+    scalar.bit_be_trimmed_iter().fold(Fr::ZERO, |acc, bit| {
+        let acc = acc.double();
+        if bit {
+            acc + Fr::ONE
+        } else {
+            acc
+        }
+    })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use num_traits::Zero;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{
+        curve::{sw::sw_double_and_add_affine, PrimeGroup},
+        from_num,
+    };
+
+    #[test]
+    fn generator_is_on_curve() {
+        let generator = Affine::generator();
+        assert!(generator.is_on_curve());
+        assert!(generator.is_in_correct_subgroup_assuming_on_curve());
+    }
+
+    #[test]
+    fn endomorphism_is_multiplication_by_lambda() {
+        let generator = Affine::generator();
+        let expected = Affine::new_unchecked(generator.x * BETA, generator.y);
+        let lambda_g =
+            sw_double_and_add_affine(&generator, LAMBDA.into_bigint());
+        assert_eq!(lambda_g, expected);
+    }
+
+    #[test]
+    fn half_n_is_half_of_the_order() {
+        let mut n = Fr::MODULUS;
+        n.div2_assign();
+        assert_eq!(HALF_N, n);
+    }
+
+    #[test]
+    fn scalar_mul() {
+        // Test vectors for `k * G`.
+        let expected = [
+            (
+                1u32,
+                fp_from_hex!("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798"),
+                fp_from_hex!("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8"),
+            ),
+            (
+                2,
+                fp_from_hex!("C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5"),
+                fp_from_hex!("1AE168FEA63DC339A3C58419466CEAEEF7F632653266D0E1236431A950CFE52A"),
+            ),
+            (
+                3,
+                fp_from_hex!("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+                fp_from_hex!("388F7B0F632DE8140FE337E62A37F3566500A99934C2231B6CB9FD7584B8E672"),
+            ),
+        ];
+
+        for (k, x, y) in expected {
+            let point = Affine::generator().mul_bigint(k).into_affine();
+            assert!(point.is_on_curve());
+            assert_eq!(point.x, x);
+            assert_eq!(point.y, y);
+        }
+    }
+
+    #[test]
+    fn scalar_mul_by_order() {
+        let generator = Projective::generator();
+        assert!(generator.mul_bigint(Fr::MODULUS).is_zero());
+
+        let n_minus_one = Fr::MODULUS.ct_wrapping_sub(&from_num!("1"));
+        assert_eq!(generator.mul_bigint(n_minus_one), -generator);
+        assert!(Projective::zero().mul_bigint(n_minus_one).is_zero());
+    }
+
+    proptest! {
+        #[test]
+        fn decompose_splits_scalar(limbs: [u64; 4]) {
+            let k = Fr::from_bigint(U256::new(limbs));
+            let ((k1_neg, k1), (k2_neg, k2)) = decompose(k);
+
+            prop_assert!(k1.ct_num_bits() <= 128);
+            prop_assert!(k2.ct_num_bits() <= 128);
+
+            let k1 = if k1_neg { -Fr::from_bigint(k1) } else { Fr::from_bigint(k1) };
+            let k2 = if k2_neg { -Fr::from_bigint(k2) } else { Fr::from_bigint(k2) };
+            prop_assert_eq!(k1 + k2 * LAMBDA, k);
+        }
+
+        #[test]
+        fn glv_mul_matches_double_and_add(limbs: [u64; 4]) {
+            let k = U256::new(limbs);
+            let generator = Affine::generator();
+
+            prop_assert_eq!(
+                generator.mul_bigint(k),
+                sw_double_and_add_affine(&generator, k)
+            );
+        }
+    }
+}
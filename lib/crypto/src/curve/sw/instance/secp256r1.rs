@@ -0,0 +1,144 @@
+//! The [secp256r1] curve, also known as NIST P-256.
+//!
+//! It is defined over the prime field of order
+//! `p = 2^256 - 2^224 + 2^192 + 2^96 - 1`, by the equation
+//! `y² = x³ - 3x + b`, and has a prime order subgroup of order `n` with
+//! cofactor `1`.
+//!
+//! [secp256r1]: https://www.secg.org/sec2-v2.pdf
+use crate::{
+    arithmetic::uint::U256,
+    curve::{sw, sw::SWCurveConfig, CurveConfig},
+    field::fp::{Fp256, FpParams, LIMBS_256},
+    fp_from_hex, fp_from_num, from_hex,
+};
+
+/// Base field of the [secp256r1] curve.
+///
+/// [secp256r1]: self
+pub type Fq = Fp256<FqParam>;
+
+/// Parameters of the [`Fq`] field.
+pub struct FqParam;
+
+impl FpParams<LIMBS_256> for FqParam {
+    const GENERATOR: Fq = fp_from_num!("6");
+    const MODULUS: U256 = from_hex!(
+        "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff"
+    );
+}
+
+/// Scalar field of the [secp256r1] curve, i.e. integers modulo the order `n`
+/// of its prime order subgroup.
+///
+/// [secp256r1]: self
+pub type Fr = Fp256<FrParam>;
+
+/// Parameters of the [`Fr`] field.
+pub struct FrParam;
+
+impl FpParams<LIMBS_256> for FrParam {
+    const GENERATOR: Fr = fp_from_num!("7");
+    const MODULUS: U256 = from_hex!(
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551"
+    );
+}
+
+/// Affine point of the [secp256r1] curve.
+///
+/// [secp256r1]: self
+pub type Affine = sw::Affine<Secp256r1Config>;
+
+/// Projective point of the [secp256r1] curve.
+///
+/// [secp256r1]: self
+pub type Projective = sw::Projective<Secp256r1Config>;
+
+/// Configuration of the [secp256r1] curve.
+///
+/// [secp256r1]: self
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secp256r1Config;
+
+impl CurveConfig for Secp256r1Config {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    const COFACTOR: &'static [u64] = &[0x1];
+    const COFACTOR_INV: Fr = Fr::ONE;
+}
+
+impl SWCurveConfig for Secp256r1Config {
+    const COEFF_A: Fq = fp_from_hex!(
+        "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc"
+    );
+    const COEFF_B: Fq = fp_from_hex!(
+        "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"
+    );
+    const GENERATOR: Affine = Affine::new_unchecked(
+        fp_from_hex!(
+            "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"
+        ),
+        fp_from_hex!(
+            "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"
+        ),
+    );
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use num_traits::Zero;
+
+    use super::*;
+    use crate::{
+        curve::{AffineRepr, CurveGroup, PrimeGroup},
+        field::{group::AdditiveGroup, prime::PrimeField},
+    };
+
+    #[test]
+    fn generator_is_on_curve() {
+        let generator = Affine::generator();
+        assert!(generator.is_on_curve());
+        assert!(generator.is_in_correct_subgroup_assuming_on_curve());
+    }
+
+    #[test]
+    fn generator_has_order_n() {
+        let generator = Projective::generator();
+        assert!(generator.mul_bigint(Fr::MODULUS).is_zero());
+        let n_minus_one = Fr::MODULUS.ct_wrapping_sub(&crate::from_num!("1"));
+        assert_eq!(generator.mul_bigint(n_minus_one), -generator);
+    }
+
+    #[test]
+    fn scalar_mul() {
+        // Test vectors for `k * G`.
+        let expected = [
+            (
+                1u32,
+                fp_from_hex!("6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296"),
+                fp_from_hex!("4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5"),
+            ),
+            (
+                2,
+                fp_from_hex!("7CF27B188D034F7E8A52380304B51AC3C08969E277F21B35A60B48FC47669978"),
+                fp_from_hex!("07775510DB8ED040293D9AC69F7430DBBA7DADE63CE982299E04B79D227873D1"),
+            ),
+            (
+                3,
+                fp_from_hex!("5ECBE4D1A6330A44C8F7EF951D4BF165E6C6B721EFADA985FB41661BC6E7FD6C"),
+                fp_from_hex!("8734640C4998FF7E374B06CE1A64A2ECD82AB036384FB83D9A79B127A27D5032"),
+            ),
+        ];
+
+        for (k, x, y) in expected {
+            let point = Affine::generator().mul_bigint(k).into_affine();
+            assert!(point.is_on_curve());
+            assert_eq!(point.x, x);
+            assert_eq!(point.y, y);
+        }
+
+        let g = Projective::generator();
+        assert_eq!(g.double(), g + Affine::generator());
+    }
+}
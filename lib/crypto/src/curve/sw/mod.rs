@@ -11,9 +11,11 @@ pub use affine::*;
 mod projective;
 pub use projective::*;
 
+pub mod instance;
+
 use crate::{
     bits::BitIteratorBE,
-    curve::AffineRepr,
+    curve::{AffineRepr, CurveGroup},
     field::{group::AdditiveGroup, prime::PrimeField},
 };
 
@@ -135,6 +137,40 @@ pub fn sw_double_and_add_projective<P: SWCurveConfig>(
     res
 }
 
+/// Computes `a * k + b * l` with [Shamir's trick], i.e. doubling only once
+/// per bit of the longest scalar.
+///
+/// [Shamir's trick]: https://cr.yp.to/bib/2003/-shamir.pdf
+#[inline(always)]
+pub fn sw_double_scalar_mul_affine<P: SWCurveConfig>(
+    a: &Affine<P>,
+    k: impl BitIteratorBE,
+    b: &Affine<P>,
+    l: impl BitIteratorBE,
+) -> Projective<P> {
+    let a_plus_b = (Projective::from(*a) + b).into_affine();
+    let k_bits: alloc::vec::Vec<bool> = k.bit_be_trimmed_iter().collect();
+    let l_bits: alloc::vec::Vec<bool> = l.bit_be_trimmed_iter().collect();
+    let len = k_bits.len().max(l_bits.len());
+    let k_bits =
+        core::iter::repeat(false).take(len - k_bits.len()).chain(k_bits);
+    let l_bits =
+        core::iter::repeat(false).take(len - l_bits.len()).chain(l_bits);
+
+    let mut res = Projective::zero();
+    for (k_bit, l_bit) in k_bits.zip(l_bits) {
+        res.double_in_place();
+        match (k_bit, l_bit) {
+            (true, true) => res += &a_plus_b,
+            (true, false) => res += a,
+            (false, true) => res += b,
+            (false, false) => {}
+        }
+    }
+
+    res
+}
+
 #[cfg(all(test, feature = "std"))]
 mod test {
     use num_traits::Zero;
@@ -0,0 +1,348 @@
+//! This module deals with verification of [ECDSA] signatures over the
+//! [secp256k1] curve, and recovery of their signer.
+//!
+//! Unlike the `ecrecover` EVM precompile, these functions don't need a host,
+//! so they can be used off-chain and in unit tests.
+//!
+//! [ECDSA]: https://www.secg.org/sec1-v2.pdf
+//! [secp256k1]: crate::curve::sw::instance::secp256k1
+use crate::{
+    arithmetic::{uint::U256, BigInteger},
+    curve::{
+        sw::{
+            instance::secp256k1::{
+                Affine, Fq, Fr, Projective, Secp256k1Config,
+            },
+            SWCurveConfig,
+        },
+        AffineRepr, CurveGroup, PrimeGroup,
+    },
+    field::{prime::PrimeField, Field},
+    hash::{BuildHasher, Hasher},
+    keccak::KeccakBuilder,
+};
+
+type Bytes32 = [u8; 32];
+
+/// Half of the order of the [`Fr`] field, i.e. the largest `s` value
+/// accepted by [`verify`] and [`recover`].
+const HALF_N: U256 = crate::from_hex!(
+    "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0"
+);
+
+/// `(p + 1) / 4`, used to compute square roots in [`Fq`], since
+/// `p ≡ 3 mod 4`.
+const SQRT_EXP: U256 = crate::from_hex!(
+    "3fffffffffffffffffffffffffffffffffffffffffffffffffffffffbfffff0c"
+);
+
+/// Verifies a secp256k1 signature of `hash` by the public key `(qx, qy)`.
+///
+/// Returns `false` if the signature is malleable, i.e. if `s` is greater
+/// than `n / 2`, where `n` is the order of the curve.
+///
+/// # Arguments
+///
+/// * `hash` - Hash of the signed message.
+/// * `r` - The `r` value of the signature.
+/// * `s` - The `s` value of the signature.
+/// * `qx` - The `x` coordinate of the public key.
+/// * `qy` - The `y` coordinate of the public key.
+#[must_use]
+pub fn verify(
+    hash: &Bytes32,
+    r: &Bytes32,
+    s: &Bytes32,
+    qx: &Bytes32,
+    qy: &Bytes32,
+) -> bool {
+    let Some((r, s)) = scalars(r, s) else {
+        return false;
+    };
+    let Some(q) = public_key(qx, qy) else {
+        return false;
+    };
+
+    // `s` is not zero, so it's invertible.
+    let Some(w) = s.inverse() else {
+        return false;
+    };
+    let u1 = message(hash) * w;
+    let u2 = r * w;
+
+    let point = (Projective::generator() * u1 + q.mul_bigint(u2.into_bigint()))
+        .into_affine();
+    if point.is_zero() {
+        return false;
+    }
+
+    Fr::from_bigint(reduce(point.x.into_bigint())) == r
+}
+
+/// Recovers the public key `(qx, qy)` that signed `hash`.
+///
+/// Returns `None` if the signature is invalid or malleable, i.e. if `s` is
+/// greater than `n / 2`, where `n` is the order of the curve.
+///
+/// # Arguments
+///
+/// * `hash` - Hash of the signed message.
+/// * `v` - The recovery identifier of the signature, either `27` or `28`.
+/// * `r` - The `r` value of the signature.
+/// * `s` - The `s` value of the signature.
+#[must_use]
+pub fn recover_public_key(
+    hash: &Bytes32,
+    v: u8,
+    r: &Bytes32,
+    s: &Bytes32,
+) -> Option<(Bytes32, Bytes32)> {
+    let y_is_odd = match v {
+        27 => false,
+        28 => true,
+        _ => return None,
+    };
+    let (r, s) = scalars(r, s)?;
+
+    // `r` is lower than `n`, which is lower than `p`.
+    let x = Fq::from_bigint(r.into_bigint());
+    let y = sqrt(Secp256k1Config::add_b(x.square() * x))?;
+    let y = if y.into_bigint().ct_is_odd() == y_is_odd { y } else { -y };
+    let point = Affine::new_unchecked(x, y);
+
+    // `Q = r⁻¹ * (s * R - e * G)`.
+    let r_inv = r.inverse()?;
+    let q = (point.mul_bigint((s * r_inv).into_bigint())
+        - Projective::generator() * (message(hash) * r_inv))
+        .into_affine();
+    if q.is_zero() {
+        return None;
+    }
+
+    Some((to_bytes(q.x.into_bigint()), to_bytes(q.y.into_bigint())))
+}
+
+/// Recovers the address of the account that signed `hash`.
+///
+/// The address is the last 20 bytes of the keccak256 hash of the public key
+/// returned by [`recover_public_key`].
+///
+/// # Arguments
+///
+/// * `hash` - Hash of the signed message.
+/// * `v` - The recovery identifier of the signature, either `27` or `28`.
+/// * `r` - The `r` value of the signature.
+/// * `s` - The `s` value of the signature.
+#[must_use]
+pub fn recover(
+    hash: &Bytes32,
+    v: u8,
+    r: &Bytes32,
+    s: &Bytes32,
+) -> Option<[u8; 20]> {
+    let (qx, qy) = recover_public_key(hash, v, r, s)?;
+    let mut hasher = KeccakBuilder.build_hasher();
+    hasher.update(qx);
+    hasher.update(qy);
+    let hash = hasher.finalize();
+
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    Some(address)
+}
+
+/// Decodes `r` and `s`, returning `None` if they are out of range or if `s`
+/// is in the upper half of the order of the curve.
+fn scalars(r: &Bytes32, s: &Bytes32) -> Option<(Fr, Fr)> {
+    let r = U256::from_bytes_be(r);
+    let s = U256::from_bytes_be(s);
+    if r.ct_is_zero() || r.ct_ge(&Fr::MODULUS) {
+        return None;
+    }
+    if s.ct_is_zero() || s.ct_gt(&HALF_N) {
+        return None;
+    }
+
+    Some((Fr::from_bigint(r), Fr::from_bigint(s)))
+}
+
+/// Decodes the public key `(qx, qy)`, returning `None` if it's not a valid
+/// point of the curve.
+fn public_key(qx: &Bytes32, qy: &Bytes32) -> Option<Affine> {
+    let x = U256::from_bytes_be(qx);
+    let y = U256::from_bytes_be(qy);
+    if x.ct_ge(&Fq::MODULUS) || y.ct_ge(&Fq::MODULUS) {
+        return None;
+    }
+
+    let point = Affine::new_unchecked(Fq::from_bigint(x), Fq::from_bigint(y));
+    // The point at infinity can't be encoded, since `(0, 0)` is not on the
+    // curve.
+    point.is_on_curve().then_some(point)
+}
+
+/// Converts `hash` to a scalar.
+fn message(hash: &Bytes32) -> Fr {
+    Fr::from_bigint(reduce(U256::from_bytes_be(hash)))
+}
+
+/// Returns the square root of `a`, if any.
+fn sqrt(a: Fq) -> Option<Fq> {
+    let root = a.pow(SQRT_EXP);
+    (root.square() == a).then_some(root)
+}
+
+/// Reduces `value` modulo the order of the curve.
+///
+/// Valid for any 256-bit `value`, since the order is greater than `2^255`.
+fn reduce(value: U256) -> U256 {
+    if value.ct_ge(&Fr::MODULUS) {
+        value.ct_wrapping_sub(&Fr::MODULUS)
+    } else {
+        value
+    }
+}
+
+/// Encodes `value` as big-endian bytes.
+fn to_bytes(value: U256) -> Bytes32 {
+    value.into_bytes_be().try_into().expect("should be 32 bytes long")
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use hex_literal::hex;
+    use num_traits::Zero;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const HASH: Bytes32 = hex!(
+        "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2"
+    );
+    const V: u8 = 28;
+    const R: Bytes32 = hex!(
+        "65e72b1cf8e189569963750e10ccb88fe89389daeeb8b735277d59cd6885ee82"
+    );
+    const S: Bytes32 = hex!(
+        "3eb5a6982b540f185703492dab77b863a88ce01f27e21ade8b2879c10fc9e653"
+    );
+    const ADDRESS: [u8; 20] = hex!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
+    /// Private key of [`ADDRESS`].
+    const PRIVATE_KEY: Bytes32 = hex!(
+        "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+    );
+
+    /// Returns the public key of `private_key`.
+    fn public_key_of(private_key: Fr) -> (Bytes32, Bytes32) {
+        let q = (Projective::generator() * private_key).into_affine();
+        (to_bytes(q.x.into_bigint()), to_bytes(q.y.into_bigint()))
+    }
+
+    /// Signs `hash` with `private_key` and `nonce`, returning a low-s
+    /// `(v, r, s)` signature.
+    fn sign(hash: &Bytes32, private_key: Fr, nonce: Fr) -> (u8, Fr, Fr) {
+        let point = (Projective::generator() * nonce).into_affine();
+        let r = Fr::from_bigint(reduce(point.x.into_bigint()));
+        let s = nonce.inverse().unwrap() * (message(hash) + r * private_key);
+
+        let y_is_odd = point.y.into_bigint().ct_is_odd();
+        if s.into_bigint().ct_gt(&HALF_N) {
+            (if y_is_odd { 27 } else { 28 }, r, -s)
+        } else {
+            (if y_is_odd { 28 } else { 27 }, r, s)
+        }
+    }
+
+    fn negate(value: &Bytes32) -> Bytes32 {
+        to_bytes(Fr::MODULUS.ct_wrapping_sub(&U256::from_bytes_be(value)))
+    }
+
+    #[test]
+    fn recovers_signer() {
+        assert_eq!(recover(&HASH, V, &R, &S), Some(ADDRESS));
+
+        let private_key = Fr::from_bigint(U256::from_bytes_be(&PRIVATE_KEY));
+        assert_eq!(
+            recover_public_key(&HASH, V, &R, &S),
+            Some(public_key_of(private_key))
+        );
+    }
+
+    #[test]
+    fn verifies_signature() {
+        let private_key = Fr::from_bigint(U256::from_bytes_be(&PRIVATE_KEY));
+        let (qx, qy) = public_key_of(private_key);
+        assert!(verify(&HASH, &R, &S, &qx, &qy));
+
+        let mut hash = HASH;
+        hash[0] ^= 1;
+        assert!(!verify(&hash, &R, &S, &qx, &qy));
+        assert!(!verify(&HASH, &R, &S, &qy, &qx));
+    }
+
+    #[test]
+    fn recovers_other_signer_with_other_v() {
+        let recovered = recover(&HASH, 27, &R, &S);
+        assert!(recovered.is_some());
+        assert_ne!(recovered, Some(ADDRESS));
+    }
+
+    #[test]
+    fn rejects_invalid_v() {
+        for v in [0, 1, 26, 29, u8::MAX] {
+            assert_eq!(recover(&HASH, v, &R, &S), None);
+        }
+    }
+
+    #[test]
+    fn rejects_malleable_signature() {
+        let high_s = negate(&S);
+        assert_eq!(recover(&HASH, 27, &R, &high_s), None);
+
+        let private_key = Fr::from_bigint(U256::from_bytes_be(&PRIVATE_KEY));
+        let (qx, qy) = public_key_of(private_key);
+        assert!(!verify(&HASH, &R, &high_s, &qx, &qy));
+    }
+
+    #[test]
+    fn rejects_out_of_range_r_and_s() {
+        let zero = [0u8; 32];
+        let n = to_bytes(Fr::MODULUS);
+
+        for invalid in [zero, n, [0xff; 32]] {
+            assert_eq!(recover(&HASH, V, &invalid, &S), None);
+            assert_eq!(recover(&HASH, V, &R, &invalid), None);
+        }
+    }
+
+    #[test]
+    fn rejects_r_not_on_curve() {
+        // `x³ + 7` has no square root for `x = 5`.
+        let mut r = [0u8; 32];
+        r[31] = 5;
+        assert_eq!(recover(&HASH, V, &r, &S), None);
+    }
+
+    proptest! {
+        #[test]
+        fn recovers_random_signatures(
+            hash: Bytes32,
+            private_key: [u64; 4],
+            nonce: [u64; 4],
+        ) {
+            let private_key = Fr::from_bigint(U256::new(private_key));
+            let nonce = Fr::from_bigint(U256::new(nonce));
+            prop_assume!(!private_key.is_zero() && !nonce.is_zero());
+
+            let (v, r, s) = sign(&hash, private_key, nonce);
+            prop_assume!(!r.is_zero() && !s.is_zero());
+            let r = to_bytes(r.into_bigint());
+            let s = to_bytes(s.into_bigint());
+
+            let (qx, qy) = public_key_of(private_key);
+            prop_assert!(verify(&hash, &r, &s, &qx, &qy));
+            prop_assert_eq!(recover_public_key(&hash, v, &r, &s), Some((qx, qy)));
+        }
+    }
+}
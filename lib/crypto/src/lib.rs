@@ -34,10 +34,13 @@ mod const_helpers;
 pub mod hash;
 pub mod keccak;
 pub mod merkle;
+pub mod p256;
 pub mod poseidon2;
+pub mod sha256;
 
 pub use keccak::KeccakBuilder;
 
 pub mod curve;
+pub mod ecdsa;
 #[cfg(all(test, feature = "std"))]
 mod test_helpers;
@@ -0,0 +1,298 @@
+//! This module deals with verification of [P-256] (secp256r1) signatures.
+//!
+//! P-256 is the curve used by passkeys, secure enclaves and most hardware
+//! security modules. Signatures are verified with the pure-Rust
+//! [`crate::curve::sw::instance::secp256r1`] curve arithmetic, so [`verify`]
+//! works on any chain, regardless of the availability of a [RIP-7212]
+//! precompile.
+//!
+//! [P-256]: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-186.pdf
+//! [RIP-7212]: https://github.com/ethereum/RIPs/blob/master/RIPS/rip-7212.md
+use crate::{
+    arithmetic::{uint::U256, BigInteger},
+    curve::{
+        sw::{
+            instance::secp256r1::{Affine, Fq, Fr, Secp256r1Config},
+            sw_double_scalar_mul_affine, SWCurveConfig,
+        },
+        AffineRepr, CurveGroup,
+    },
+    field::{prime::PrimeField, Field},
+};
+
+type Bytes32 = [u8; 32];
+
+/// Half of the order of the [`Fr`] field, i.e. the largest `s` value
+/// accepted by [`verify`].
+const HALF_N: U256 = crate::from_hex!(
+    "7fffffff800000007fffffffffffffffde737d56d38bcf4279dce5617e3192a8"
+);
+
+/// Verifies a P-256 signature of `hash` by the public key `(qx, qy)`.
+///
+/// Returns `false` if the signature is malleable, i.e. if `s` is greater
+/// than `n / 2`, where `n` is the order of the curve. Signers can always
+/// produce such a signature by negating `s` modulo `n`.
+///
+/// # Arguments
+///
+/// * `hash` - Hash of the signed message.
+/// * `r` - The `r` value of the signature.
+/// * `s` - The `s` value of the signature.
+/// * `qx` - The `x` coordinate of the public key.
+/// * `qy` - The `y` coordinate of the public key.
+#[must_use]
+pub fn verify(
+    hash: &Bytes32,
+    r: &Bytes32,
+    s: &Bytes32,
+    qx: &Bytes32,
+    qy: &Bytes32,
+) -> bool {
+    let s = U256::from_bytes_be(s);
+    if s.ct_gt(&HALF_N) {
+        return false;
+    }
+
+    verify_unchecked_malleability(hash, r, &s, qx, qy)
+}
+
+/// Returns `true` if `(qx, qy)` is a valid P-256 public key, i.e. a point of
+/// the curve other than the point at infinity, with both coordinates lower
+/// than the order of the base field.
+///
+/// # Arguments
+///
+/// * `qx` - The `x` coordinate of the public key.
+/// * `qy` - The `y` coordinate of the public key.
+#[must_use]
+pub fn is_valid_public_key(qx: &Bytes32, qy: &Bytes32) -> bool {
+    public_key(qx, qy).is_some()
+}
+
+/// Verifies a P-256 signature without rejecting malleable signatures.
+fn verify_unchecked_malleability(
+    hash: &Bytes32,
+    r: &Bytes32,
+    s: &U256,
+    qx: &Bytes32,
+    qy: &Bytes32,
+) -> bool {
+    let r = U256::from_bytes_be(r);
+    if r.ct_is_zero() || r.ct_ge(&Fr::MODULUS) {
+        return false;
+    }
+    if s.ct_is_zero() || s.ct_ge(&Fr::MODULUS) {
+        return false;
+    }
+
+    let Some(q) = public_key(qx, qy) else {
+        return false;
+    };
+
+    // `s` is not zero, so it's invertible.
+    let Some(w) = Fr::from_bigint(*s).inverse() else {
+        return false;
+    };
+    let u1 = Fr::from_bigint(reduce(U256::from_bytes_be(hash))) * w;
+    let u2 = Fr::from_bigint(r) * w;
+
+    let point = sw_double_scalar_mul_affine(
+        &Secp256r1Config::GENERATOR,
+        u1.into_bigint(),
+        &q,
+        u2.into_bigint(),
+    )
+    .into_affine();
+    if point.is_zero() {
+        return false;
+    }
+
+    reduce(point.x.into_bigint()) == r
+}
+
+/// Decodes the public key `(qx, qy)`, returning `None` if it's not a valid
+/// point of the curve.
+fn public_key(qx: &Bytes32, qy: &Bytes32) -> Option<Affine> {
+    let x = U256::from_bytes_be(qx);
+    let y = U256::from_bytes_be(qy);
+    if x.ct_ge(&Fq::MODULUS) || y.ct_ge(&Fq::MODULUS) {
+        return None;
+    }
+
+    let point = Affine::new_unchecked(Fq::from_bigint(x), Fq::from_bigint(y));
+    // The point at infinity can't be encoded, since `(0, 0)` is not on the
+    // curve.
+    point.is_on_curve().then_some(point)
+}
+
+/// Reduces `value` modulo the order of the curve.
+///
+/// Valid for any 256-bit `value`, since the order is greater than `2^255`.
+fn reduce(value: U256) -> U256 {
+    if value.ct_ge(&Fr::MODULUS) {
+        value.ct_wrapping_sub(&Fr::MODULUS)
+    } else {
+        value
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    /// Decodes a hex string into 32 bytes.
+    fn b32(hex: &str) -> Bytes32 {
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        bytes.try_into().unwrap()
+    }
+
+    /// `(hash, r, s, qx, qy)` of valid, low-s signatures.
+    const VECTORS: [[&str; 5]; 6] = [
+        [
+            "74f2bab0f7b496db35967b365a4bedc0f6378888dea671ec307ee99e677fe21d",
+            "30845bd5eb740e50bf436053524db8f979037ccf8963d1be542c97d440e6ef10",
+            "06ff7f96f373719d953a66ccba30b238952ee800dfedbd59b63db6b53d3e81f3",
+            "8300b1b782b2006949895287fd2c9431f2ab7813e45f2fae2c1fb845a3e37567",
+            "bccc4d1cd06e7efb369461ea177162b29255d02470e88aadcfd9c80105352b52",
+        ],
+        [
+            "b526aef1a341cfe6e5c377ed4c222888eeb81f913a107110a867e009c1758f24",
+            "c7894da147cef87bdc953f90f5634fa5f334d49884347eb8b5e3870da0c5b341",
+            "2e7529b9cae740b2a82f5846661a14e06a58a3d0e5678a700398414d837b4ba9",
+            "6576c92b3f40159cb3c280e8b99d681001a1513bea2c561418ff5fb5cf8eb620",
+            "44bf2af9cfc9223268728a6e3577ee94253106251f0a3413170a17a93ee6cbdb",
+        ],
+        [
+            "84768ddee659efeafdeb972b55143141bc23b6e333c70e8b68d29774ab09a548",
+            "957dfd3bf8bc4da2ce4b9f6dc153a5693570b6cb52041e538c176ab8dcfb4311",
+            "724e309538887e6d06281b3cc48278b0edeabb257d1366b3e224bc2828ad59fa",
+            "27c9417f4c4284331d5f5b7e8d60971ecbf0083fecd3654d24af84503ad75202",
+            "48826e8272bfa68cc67b6fc907090462c96f52100cb907e73a75e1bd44ed14a2",
+        ],
+        [
+            "fb29a8d5309d7c35b180dbd78c63a455a5d1fb45149a3264c08f1aff43524beb",
+            "9221a094f0cf10bbf55c4788ebf22e2a53a0d38563e598f84b2d13b5908b0e41",
+            "3c1a5c353491642f039c6f5861273e04f51f209e5704f332eb3b160435079f50",
+            "839ca8fa0119b13598fad08f3b2f6a405fd07861af4b1f5de3e6b881c66052cb",
+            "5d3602a1791564a2c265529175f61d8e88476a773b8d83e32e7e0a27f39b5112",
+        ],
+        // Hash greater than the order of the curve.
+        [
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "4053b47b4adbbe42a0d9da013b8775f35faa2d579e12487467a90df34cc26d61",
+            "1ff99700e5c712e6eea18227ee0e9211c15a49e60818bad723825010746c9d36",
+            "24e00d56c52d7d37fcc840902b4f36139e147e7d53064eac411acf16c95cd8fe",
+            "26f2587a1f704c30771c2198b1498042236c9ec954f6a57ef031e7212d660704",
+        ],
+        // Zero hash.
+        [
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "3d373a390403451ea47a1c0a7afdc994f4f70ad5ec8f97fa767c4678fa69ed90",
+            "4fab8e72d81c5cea919d7831d56755a796778b12162f7d56d3751f1737a140d9",
+            "2d4c9904523fedf909cd21ecd947a95b226ff553e208f67fe08cb4627a5293a7",
+            "a2c3b022d2fbb45c6e80a418542ddaa24be1a9d9f2c627e57a8fea3e610af98a",
+        ],
+    ];
+
+    fn vectors() -> impl Iterator<Item = [Bytes32; 5]> {
+        VECTORS.iter().map(|vector| vector.map(b32))
+    }
+
+    /// Returns `n - value`.
+    fn negate(value: &Bytes32) -> Bytes32 {
+        Fr::MODULUS
+            .ct_wrapping_sub(&U256::from_bytes_be(value))
+            .into_bytes_be()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn half_n_is_half_of_the_order() {
+        let mut n = Fr::MODULUS;
+        n.div2_assign();
+        assert_eq!(HALF_N, n);
+    }
+
+    #[test]
+    fn accepts_valid_signatures() {
+        for [hash, r, s, qx, qy] in vectors() {
+            assert!(verify(&hash, &r, &s, &qx, &qy));
+            assert!(is_valid_public_key(&qx, &qy));
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_hash() {
+        for [mut hash, r, s, qx, qy] in vectors() {
+            hash[31] ^= 1;
+            assert!(!verify(&hash, &r, &s, &qx, &qy));
+        }
+    }
+
+    #[test]
+    fn rejects_signature_of_other_public_key() {
+        let [hash, r, s, ..] = vectors().next().unwrap();
+        let [_, _, _, qx, qy] = vectors().nth(1).unwrap();
+        assert!(!verify(&hash, &r, &s, &qx, &qy));
+    }
+
+    #[test]
+    fn rejects_tampered_signature() {
+        for [hash, mut r, mut s, qx, qy] in vectors() {
+            r[0] ^= 1;
+            assert!(!verify(&hash, &r, &s, &qx, &qy));
+            r[0] ^= 1;
+            s[31] ^= 1;
+            assert!(!verify(&hash, &r, &s, &qx, &qy));
+        }
+    }
+
+    #[test]
+    fn rejects_malleable_signature() {
+        for [hash, r, s, qx, qy] in vectors() {
+            let high_s = negate(&s);
+            assert!(!verify(&hash, &r, &high_s, &qx, &qy));
+            // The malleable signature is otherwise valid.
+            assert!(verify_unchecked_malleability(
+                &hash,
+                &r,
+                &U256::from_bytes_be(&high_s),
+                &qx,
+                &qy
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_r_and_s() {
+        let [hash, r, s, qx, qy] = vectors().next().unwrap();
+        let zero = [0u8; 32];
+        let n: Bytes32 = Fr::MODULUS.into_bytes_be().try_into().unwrap();
+        let max = [0xff; 32];
+
+        for invalid in [zero, n, max] {
+            assert!(!verify(&hash, &invalid, &s, &qx, &qy));
+            assert!(!verify(&hash, &r, &invalid, &qx, &qy));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_public_key() {
+        let [hash, r, s, qx, mut qy] = vectors().next().unwrap();
+        let zero = [0u8; 32];
+        let p: Bytes32 = Fq::MODULUS.into_bytes_be().try_into().unwrap();
+
+        assert!(!is_valid_public_key(&zero, &zero));
+        assert!(!verify(&hash, &r, &s, &zero, &zero));
+        assert!(!is_valid_public_key(&p, &qy));
+
+        qy[31] ^= 1;
+        assert!(!is_valid_public_key(&qx, &qy));
+        assert!(!verify(&hash, &r, &s, &qx, &qy));
+    }
+}
@@ -0,0 +1,78 @@
+//! An interface to the [SHA-256] hashing algorithm, used by passkeys and most
+//! P-256 signatures.
+//!
+//! [SHA-256]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+use sha2::Digest;
+
+use crate::hash::{BuildHasher, Hasher};
+
+/// A [`Hasher`] builder that instantiates a [`Sha256`] hasher.
+pub struct Sha256Builder;
+
+impl BuildHasher for Sha256Builder {
+    type Hasher = Sha256;
+
+    #[inline]
+    fn build_hasher(&self) -> Self::Hasher {
+        Sha256(sha2::Sha256::new())
+    }
+}
+
+/// A [`Hasher`] computing [SHA-256] digests.
+///
+/// [SHA-256]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+pub struct Sha256(sha2::Sha256);
+
+impl Hasher for Sha256 {
+    type Output = [u8; 32];
+
+    fn update(&mut self, input: impl AsRef<[u8]>) {
+        self.0.update(input.as_ref());
+    }
+
+    fn finalize(self) -> Self::Output {
+        self.0.finalize().into()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use hex_literal::hex;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn sha256(data: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256Builder.build_hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    #[test]
+    fn matches_test_vectors() {
+        // Test vectors from FIPS 180-4 examples.
+        assert_eq!(
+            sha256(b""),
+            hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+        assert_eq!(
+            sha256(b"abc"),
+            hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            hex!("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn sequential_updates_match_concatenated(data1: Vec<u8>, data2: Vec<u8>) {
+            let mut hasher = Sha256Builder.build_hasher();
+            hasher.update(&data1);
+            hasher.update(&data2);
+
+            prop_assert_eq!(hasher.finalize(), sha256(&[data1, data2].concat()));
+        }
+    }
+}